@@ -0,0 +1,382 @@
+//! Automatic verification of contracts with the bytecode identical to one of verified contracts
+//! (e.g., contracts deployed by factories, or clones).
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context as _;
+use chrono::Utc;
+use tokio::sync::watch;
+use vise::{Counter, Metrics};
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    contract_verification_api::{DeployContractCalldata, VerificationInfo},
+    Address, MiniblockNumber,
+};
+
+use crate::verifier::{ConstructorArgs, ContractVerifier};
+
+/// Maximum number of miniblocks scanned for new deployments in a single iteration.
+const MINIBLOCKS_CHUNK_SIZE: u32 = 10_000;
+/// Number of verified contracts processed at a time when backfilling bytecode hashes.
+const BACKFILL_CHUNK_SIZE: usize = 100;
+/// Maximum number of previously deployed contracts verified in a single DB transaction
+/// after a contract with the identical bytecode is verified.
+const IDENTICAL_CONTRACTS_CHUNK_SIZE: usize = 100;
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_contract_verifier")]
+struct IdenticalContractsMetrics {
+    /// Number of contracts verified based on the bytecode identical to one of verified contracts.
+    identical_contracts_verified: Counter,
+}
+
+#[vise::register]
+static METRICS: vise::Global<IdenticalContractsMetrics> = vise::Global::new();
+
+/// Creates verification info for the contract at `address` with the bytecode identical to the verified contract.
+/// The contract is verified with its own constructor arguments taken from the deployment `calldata`.
+fn identical_contract_info(
+    verified_info: &VerificationInfo,
+    address: Address,
+    calldata: DeployContractCalldata,
+) -> VerificationInfo {
+    let constructor_args =
+        match ContractVerifier::decode_constructor_arguments_from_calldata(calldata, address) {
+            ConstructorArgs::Check(args) => args,
+            ConstructorArgs::Ignore => vec![],
+        };
+    let mut request = verified_info.request.clone();
+    request.req.contract_address = address;
+    request.req.constructor_arguments = constructor_args.into();
+    let identical_to = verified_info
+        .identical_to
+        .unwrap_or(verified_info.request.req.contract_address);
+
+    VerificationInfo {
+        request,
+        artifacts: verified_info.artifacts.clone(),
+        verified_at: Utc::now(),
+        bytecode_match: verified_info.bytecode_match,
+        identical_to: Some(identical_to),
+    }
+}
+
+/// Verifies the contract at `address` based on the verification info of a contract with the identical bytecode.
+/// Returns `false` if the contract cannot be verified (e.g., because its bytecode has changed).
+async fn verify_identical_contract(
+    storage: &mut StorageProcessor<'_>,
+    verified_info: &VerificationInfo,
+    address: Address,
+) -> anyhow::Result<bool> {
+    let Some((bytecode, calldata)) = storage
+        .contract_verification_dal()
+        .get_contract_info_for_verification(address)
+        .await
+        .with_context(|| format!("get_contract_info_for_verification({address:?})"))?
+    else {
+        return Ok(false);
+    };
+    if bytecode != verified_info.artifacts.bytecode {
+        return Ok(false);
+    }
+
+    let info = identical_contract_info(verified_info, address, calldata);
+    storage
+        .contract_verification_dal()
+        .insert_verification_info(info)
+        .await
+        .with_context(|| format!("insert_verification_info({address:?})"))?;
+    tracing::info!(
+        "Verified contract {address:?} as identical to {:?}",
+        verified_info.request.req.contract_address
+    );
+    Ok(true)
+}
+
+/// Component that verifies contracts with the bytecode identical to one of verified contracts.
+/// Scans contracts deployed in new miniblocks; the last scanned miniblock is persisted in Postgres,
+/// so that the scan is resumed after a restart. On the first start, all deployed contracts are scanned
+/// (thus verifying contracts retroactively). Additionally, once a contract is verified, previously deployed
+/// contracts with the same bytecode are verified in chunks; contracts for which this check is completed
+/// are marked in Postgres.
+#[derive(Debug)]
+pub struct IdenticalContractsVerifier {
+    connection_pool: ConnectionPool,
+    poll_interval: Duration,
+}
+
+impl IdenticalContractsVerifier {
+    pub fn new(connection_pool: ConnectionPool, poll_interval: Duration) -> Self {
+        Self {
+            connection_pool,
+            poll_interval,
+        }
+    }
+
+    /// Sets bytecode hashes for contracts verified before bytecode hashes were recorded.
+    async fn backfill_bytecode_hashes(&self) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        loop {
+            let infos = storage
+                .contract_verification_dal()
+                .get_verification_info_without_bytecode_hash(BACKFILL_CHUNK_SIZE)
+                .await
+                .context("get_verification_info_without_bytecode_hash()")?;
+            if infos.is_empty() {
+                return Ok(());
+            }
+            tracing::info!(
+                "Backfilling bytecode hashes for {} verified contracts",
+                infos.len()
+            );
+
+            for info in infos {
+                let bytecode_hash = zksync_utils::bytecode::hash_bytecode(&info.artifacts.bytecode);
+                storage
+                    .contract_verification_dal()
+                    .set_verified_bytecode_hash(info.request.req.contract_address, bytecode_hash)
+                    .await
+                    .context("set_verified_bytecode_hash()")?;
+            }
+        }
+    }
+
+    /// Verifies previously deployed contracts with the same bytecode as the verified contract. Contracts are
+    /// verified in chunks, each in a separate DB transaction; the check is marked as completed after the last chunk.
+    async fn verify_identical_contracts(
+        storage: &mut StorageProcessor<'_>,
+        verified_info: &VerificationInfo,
+    ) -> anyhow::Result<()> {
+        let verified_address = verified_info.request.req.contract_address;
+        let bytecode_hash =
+            zksync_utils::bytecode::hash_bytecode(&verified_info.artifacts.bytecode);
+        let mut after_address = Address::zero();
+        loop {
+            let mut transaction = storage
+                .start_transaction()
+                .await
+                .context("start_transaction()")?;
+            let addresses = transaction
+                .contract_verification_dal()
+                .get_unverified_contracts_with_bytecode_hash(
+                    bytecode_hash,
+                    after_address,
+                    IDENTICAL_CONTRACTS_CHUNK_SIZE,
+                )
+                .await
+                .context("get_unverified_contracts_with_bytecode_hash()")?;
+            let Some(&last_address) = addresses.last() else {
+                break;
+            };
+            after_address = last_address;
+
+            let mut verified_count = 0;
+            for address in addresses {
+                if verify_identical_contract(&mut transaction, verified_info, address).await? {
+                    verified_count += 1;
+                }
+            }
+            transaction.commit().await.context("commit()")?;
+            METRICS
+                .identical_contracts_verified
+                .inc_by(verified_count as u64);
+        }
+
+        storage
+            .contract_verification_dal()
+            .set_identical_contracts_checked(verified_address)
+            .await
+            .with_context(|| format!("set_identical_contracts_checked({verified_address:?})"))
+    }
+
+    /// Verifies previously deployed contracts with the same bytecode as newly verified contracts.
+    async fn process_verified_contracts(
+        storage: &mut StorageProcessor<'_>,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            let Some(verified_info) = storage
+                .contract_verification_dal()
+                .get_next_verification_info_with_unchecked_identical_contracts()
+                .await
+                .context("get_next_verification_info_with_unchecked_identical_contracts()")?
+            else {
+                break;
+            };
+            Self::verify_identical_contracts(storage, &verified_info).await?;
+        }
+        Ok(())
+    }
+
+    /// Verifies unverified contracts deployed in the specified miniblock range and records
+    /// the range as processed.
+    async fn process_miniblocks(
+        storage: &mut StorageProcessor<'_>,
+        from_miniblock: MiniblockNumber,
+        to_miniblock: MiniblockNumber,
+    ) -> anyhow::Result<()> {
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .context("start_transaction()")?;
+        let storage = &mut transaction;
+        let contracts = storage
+            .contract_verification_dal()
+            .get_unverified_identical_contracts(from_miniblock..=to_miniblock)
+            .await
+            .context("get_unverified_identical_contracts()")?;
+
+        let mut verified_infos = HashMap::new();
+        let mut verified_count = 0;
+        for (address, verified_address) in contracts {
+            if !verified_infos.contains_key(&verified_address) {
+                let info = storage
+                    .contract_verification_dal()
+                    .get_contract_verification_info(verified_address)
+                    .await
+                    .with_context(|| {
+                        format!("get_contract_verification_info({verified_address:?})")
+                    })?;
+                verified_infos.insert(verified_address, info);
+            }
+            let Some(verified_info) = &verified_infos[&verified_address] else {
+                continue;
+            };
+            if verify_identical_contract(storage, verified_info, address).await? {
+                verified_count += 1;
+            }
+        }
+        storage
+            .contract_verification_dal()
+            .set_last_identical_contracts_miniblock(to_miniblock)
+            .await
+            .context("set_last_identical_contracts_miniblock()")?;
+        transaction.commit().await.context("commit()")?;
+
+        METRICS
+            .identical_contracts_verified
+            .inc_by(verified_count as u64);
+        Ok(())
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.backfill_bytecode_hashes().await?;
+
+        let last_processed_miniblock = self
+            .connection_pool
+            .access_storage()
+            .await?
+            .contract_verification_dal()
+            .get_last_identical_contracts_miniblock()
+            .await
+            .context("get_last_identical_contracts_miniblock()")?;
+        let mut next_miniblock =
+            last_processed_miniblock.map_or(MiniblockNumber(0), |number| number + 1);
+        tracing::info!(
+            "Scanning for contracts with identical bytecode from miniblock #{next_miniblock}"
+        );
+        while !*stop_receiver.borrow() {
+            let mut storage = self.connection_pool.access_storage().await?;
+            Self::process_verified_contracts(&mut storage, &stop_receiver).await?;
+            let sealed_miniblock = storage
+                .blocks_dal()
+                .get_sealed_miniblock_number()
+                .await
+                .context("get_sealed_miniblock_number()")?;
+            if sealed_miniblock >= next_miniblock {
+                let to_miniblock =
+                    sealed_miniblock.min(next_miniblock + (MINIBLOCKS_CHUNK_SIZE - 1));
+                Self::process_miniblocks(&mut storage, next_miniblock, to_miniblock).await?;
+                next_miniblock = to_miniblock + 1;
+                if to_miniblock < sealed_miniblock {
+                    // Process the remaining miniblocks without waiting.
+                    continue;
+                }
+            }
+            drop(storage);
+
+            tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, identical contracts verifier is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethabi::Token;
+    use zksync_types::{
+        contract_verification_api::{
+            BytecodeMatch, CompilationArtifacts, CompilerVersions, SourceCodeData,
+            VerificationIncomingRequest, VerificationRequest,
+        },
+        H256,
+    };
+
+    use super::*;
+
+    fn create2_calldata(constructor_args: &[u8]) -> DeployContractCalldata {
+        let deployer_contract = zksync_contracts::deployer_contract();
+        let calldata = deployer_contract
+            .function("create2")
+            .unwrap()
+            .encode_input(&[
+                Token::FixedBytes(vec![0; 32]),
+                Token::FixedBytes(H256::repeat_byte(1).0.to_vec()),
+                Token::Bytes(constructor_args.to_vec()),
+            ])
+            .unwrap();
+        DeployContractCalldata::Deploy(calldata)
+    }
+
+    fn verified_info(address: Address, constructor_args: &[u8]) -> VerificationInfo {
+        let req = VerificationIncomingRequest {
+            contract_address: address,
+            source_code_data: SourceCodeData::SolSingleFile("contract Test {}".to_owned()),
+            contract_name: "Test".to_owned(),
+            compiler_versions: CompilerVersions::Solc {
+                compiler_zksolc_version: "v1.3.18".to_owned(),
+                compiler_solc_version: "0.8.23".to_owned(),
+            },
+            optimization_used: true,
+            optimizer_mode: None,
+            constructor_arguments: constructor_args.to_vec().into(),
+            is_system: false,
+        };
+        VerificationInfo {
+            request: VerificationRequest { id: 1, req },
+            artifacts: CompilationArtifacts {
+                bytecode: vec![0; 32],
+                abi: serde_json::Value::Array(vec![]),
+            },
+            verified_at: Utc::now(),
+            bytecode_match: BytecodeMatch::Partial,
+            identical_to: None,
+        }
+    }
+
+    #[test]
+    fn identical_contract_is_verified_with_own_constructor_args() {
+        let verified_address = Address::repeat_byte(1);
+        let verified_info = verified_info(verified_address, &[1; 32]);
+        let clone_address = Address::repeat_byte(2);
+
+        let info =
+            identical_contract_info(&verified_info, clone_address, create2_calldata(&[2; 32]));
+        assert_eq!(info.request.req.contract_address, clone_address);
+        assert_eq!(info.request.req.constructor_arguments.0, [2; 32]);
+        assert_eq!(info.identical_to, Some(verified_address));
+        assert_eq!(info.bytecode_match, BytecodeMatch::Partial);
+        assert_eq!(info.artifacts.bytecode, verified_info.artifacts.bytecode);
+
+        // Clones of clones should refer to the originally verified contract.
+        let other_clone_address = Address::repeat_byte(3);
+        let info =
+            identical_contract_info(&info, other_clone_address, DeployContractCalldata::Ignore);
+        assert_eq!(info.request.req.contract_address, other_clone_address);
+        assert!(info.request.req.constructor_arguments.0.is_empty());
+        assert_eq!(info.identical_to, Some(verified_address));
+    }
+}
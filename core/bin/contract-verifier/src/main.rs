@@ -10,9 +10,10 @@ use zksync_env_config::FromEnv;
 use zksync_queued_job_processor::JobProcessor;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
 
-use crate::verifier::ContractVerifier;
+use crate::{identical_contracts::IdenticalContractsVerifier, verifier::ContractVerifier};
 
 pub mod error;
+pub mod identical_contracts;
 pub mod verifier;
 pub mod zksolc_utils;
 pub mod zkvyper_utils;
@@ -176,12 +177,15 @@ async fn main() -> anyhow::Result<()> {
 
     update_compiler_versions(&pool).await;
 
+    let identical_contracts_verifier =
+        IdenticalContractsVerifier::new(pool.clone(), verifier_config.polling_interval());
     let contract_verifier = ContractVerifier::new(verifier_config, pool);
     let tasks = vec![
         // todo PLA-335: Leftovers after the prover DB split.
         // The prover connection pool is not used by the contract verifier, but we need to pass it
         // since `JobProcessor` trait requires it.
         tokio::spawn(contract_verifier.run(stop_receiver.clone(), opt.jobs_number)),
+        tokio::spawn(identical_contracts_verifier.run(stop_receiver.clone())),
         tokio::spawn(
             PrometheusExporterConfig::pull(prometheus_config.listener_port).run(stop_receiver),
         ),
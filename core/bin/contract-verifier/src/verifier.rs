@@ -17,7 +17,7 @@ use zksync_env_config::FromEnv;
 use zksync_queued_job_processor::{async_trait, JobProcessor};
 use zksync_types::{
     contract_verification_api::{
-        CompilationArtifacts, CompilerType, DeployContractCalldata, SourceCodeData,
+        BytecodeMatch, CompilationArtifacts, CompilerType, DeployContractCalldata, SourceCodeData,
         VerificationInfo, VerificationRequest,
     },
     Address,
@@ -33,8 +33,35 @@ lazy_static! {
     static ref DEPLOYER_CONTRACT: Contract = zksync_contracts::deployer_contract();
 }
 
+/// Size of a bytecode word in bytes.
+const BYTECODE_WORD_SIZE: usize = 32;
+
+/// Checks whether the deployed and compiled bytecodes are identical except for the metadata hash.
+/// The hash occupies the last non-padding word of the bytecode; the bytecode may be padded with zero words
+/// so that the number of words is odd.
+fn matches_modulo_metadata_hash(deployed: &[u8], compiled: &[u8]) -> bool {
+    if deployed.len() != compiled.len() || deployed.len() % BYTECODE_WORD_SIZE != 0 {
+        return false;
+    }
+
+    let deployed_words = deployed.chunks_exact(BYTECODE_WORD_SIZE);
+    let compiled_words = compiled.chunks_exact(BYTECODE_WORD_SIZE);
+    let mut differing_words = deployed_words.zip(compiled_words).enumerate().filter_map(
+        |(i, (deployed_word, compiled_word))| (deployed_word != compiled_word).then_some(i),
+    );
+    let Some(hash_word_idx) = differing_words.next() else {
+        return true;
+    };
+    if differing_words.next().is_some() {
+        return false;
+    }
+    // Since the other words are equal, it's sufficient to check padding in one of the bytecodes.
+    let padding = &deployed[(hash_word_idx + 1) * BYTECODE_WORD_SIZE..];
+    padding.iter().all(|&byte| byte == 0)
+}
+
 #[derive(Debug)]
-enum ConstructorArgs {
+pub(crate) enum ConstructorArgs {
     Check(Vec<u8>),
     Ignore,
 }
@@ -58,7 +85,7 @@ impl ContractVerifier {
         mut request: VerificationRequest,
         config: ContractVerifierConfig,
     ) -> Result<VerificationInfo, ContractVerifierError> {
-        let artifacts = Self::compile(request.clone(), config).await?;
+        let mut artifacts = Self::compile(request.clone(), config).await?;
 
         // Bytecode should be present because it is checked when accepting request.
         let (deployed_bytecode, creation_tx_calldata) = storage
@@ -74,9 +101,18 @@ impl ContractVerifier {
             request.req.contract_address,
         );
 
-        if artifacts.bytecode != deployed_bytecode {
+        let bytecode_match = if artifacts.bytecode == deployed_bytecode {
+            BytecodeMatch::Full
+        } else if Self::is_metadata_hash_enabled(&request)
+            && matches_modulo_metadata_hash(&deployed_bytecode, &artifacts.bytecode)
+        {
+            // Store the deployed bytecode so that artifacts are consistent with the on-chain state
+            // (e.g., when looking for contracts with the identical bytecode).
+            artifacts.bytecode = deployed_bytecode;
+            BytecodeMatch::Partial
+        } else {
             return Err(ContractVerifierError::BytecodeMismatch);
-        }
+        };
 
         match constructor_args {
             ConstructorArgs::Check(args) => {
@@ -93,9 +129,34 @@ impl ContractVerifier {
             request,
             artifacts,
             verified_at: Utc::now(),
+            bytecode_match,
+            identical_to: None,
         })
     }
 
+    /// Checks whether the compiler appends the metadata hash to the bytecode for the request.
+    /// If the hash is disabled or the compiler mode isn't known to append it (e.g., for zkvyper or Yul),
+    /// bytecodes can only match fully.
+    fn is_metadata_hash_enabled(request: &VerificationRequest) -> bool {
+        match &request.req.source_code_data {
+            // zksolc appends the keccak256 metadata hash by default.
+            SourceCodeData::SolSingleFile(_) => true,
+            SourceCodeData::StandardJsonInput(input) => {
+                let language = input.get("language").and_then(serde_json::Value::as_str);
+                let bytecode_hash = input
+                    .get("settings")
+                    .and_then(|settings| settings.get("metadata"))
+                    .and_then(|metadata| metadata.get("bytecodeHash"));
+                let is_keccak256_hash = match bytecode_hash {
+                    None => true,
+                    Some(hash) => hash.as_str() == Some("keccak256"),
+                };
+                language == Some("Solidity") && is_keccak256_hash
+            }
+            SourceCodeData::VyperMultiFile(_) | SourceCodeData::YulSingleFile(_) => false,
+        }
+    }
+
     async fn compile_zksolc(
         request: VerificationRequest,
         config: ContractVerifierConfig,
@@ -339,7 +400,7 @@ impl ContractVerifier {
         })
     }
 
-    fn decode_constructor_arguments_from_calldata(
+    pub(crate) fn decode_constructor_arguments_from_calldata(
         calldata: DeployContractCalldata,
         contract_address_to_verify: Address,
     ) -> ConstructorArgs {
@@ -419,14 +480,15 @@ impl ContractVerifier {
         storage: &mut StorageProcessor<'_>,
         request_id: usize,
         verification_result: Result<VerificationInfo, ContractVerifierError>,
-    ) {
+    ) -> anyhow::Result<()> {
         match verification_result {
             Ok(info) => {
+                // Previously deployed contracts with identical bytecode are verified by `IdenticalContractsVerifier`.
                 storage
                     .contract_verification_dal()
                     .save_verification_info(info)
                     .await
-                    .unwrap();
+                    .context("save_verification_info()")?;
                 tracing::info!("Successfully processed request with id = {}", request_id);
             }
             Err(error) => {
@@ -441,10 +503,11 @@ impl ContractVerifier {
                     .contract_verification_dal()
                     .save_verification_error(request_id, error_message, compilation_errors, None)
                     .await
-                    .unwrap();
+                    .context("save_verification_error()")?;
                 tracing::info!("Request with id = {} was failed", request_id);
             }
         }
+        Ok(())
     }
 }
 
@@ -506,7 +569,7 @@ impl JobProcessor for ContractVerifier {
 
             let job_id = job.id;
             let verification_result = Self::verify(&mut connection, job, config).await;
-            Self::process_result(&mut connection, job_id, verification_result).await;
+            Self::process_result(&mut connection, job_id, verification_result).await?;
 
             metrics::histogram!(
                 "api.contract_verifier.request_processing_time",
@@ -534,3 +597,107 @@ impl JobProcessor for ContractVerifier {
         Ok(1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::contract_verification_api::{CompilerVersions, VerificationIncomingRequest};
+
+    use super::*;
+
+    fn bytecode(words: &[u8]) -> Vec<u8> {
+        words
+            .iter()
+            .flat_map(|&word| [word; BYTECODE_WORD_SIZE])
+            .collect()
+    }
+
+    #[test]
+    fn matching_bytecodes_modulo_metadata_hash() {
+        let deployed = bytecode(&[1, 2, 3]);
+        assert!(matches_modulo_metadata_hash(&deployed, &deployed));
+        assert!(matches_modulo_metadata_hash(
+            &deployed,
+            &bytecode(&[1, 2, 4])
+        ));
+        // The metadata hash may be followed by zero padding.
+        let deployed = bytecode(&[1, 2, 3, 0, 0]);
+        assert!(matches_modulo_metadata_hash(
+            &deployed,
+            &bytecode(&[1, 2, 4, 0, 0])
+        ));
+
+        // Mismatches outside the metadata hash.
+        assert!(!matches_modulo_metadata_hash(
+            &deployed,
+            &bytecode(&[1, 5, 3, 0, 0])
+        ));
+        assert!(!matches_modulo_metadata_hash(
+            &deployed,
+            &bytecode(&[1, 5, 4, 0, 0])
+        ));
+        assert!(!matches_modulo_metadata_hash(
+            &bytecode(&[1, 2, 3, 4, 5]),
+            &bytecode(&[1, 2, 6, 4, 5])
+        ));
+        assert!(!matches_modulo_metadata_hash(
+            &deployed,
+            &bytecode(&[1, 2, 4])
+        ));
+    }
+
+    fn verification_request(source_code_data: SourceCodeData) -> VerificationRequest {
+        let req = VerificationIncomingRequest {
+            contract_address: Address::repeat_byte(1),
+            source_code_data,
+            contract_name: "Test".to_owned(),
+            compiler_versions: CompilerVersions::Solc {
+                compiler_zksolc_version: "v1.3.18".to_owned(),
+                compiler_solc_version: "0.8.23".to_owned(),
+            },
+            optimization_used: true,
+            optimizer_mode: None,
+            constructor_arguments: Default::default(),
+            is_system: false,
+        };
+        VerificationRequest { id: 1, req }
+    }
+
+    fn standard_json(input: serde_json::Value) -> SourceCodeData {
+        let serde_json::Value::Object(input) = input else {
+            unreachable!();
+        };
+        SourceCodeData::StandardJsonInput(input)
+    }
+
+    #[test]
+    fn checking_metadata_hash_for_requests() {
+        let request = verification_request(SourceCodeData::SolSingleFile(String::new()));
+        assert!(ContractVerifier::is_metadata_hash_enabled(&request));
+
+        let input = serde_json::json!({ "language": "Solidity", "sources": {}, "settings": {} });
+        let request = verification_request(standard_json(input));
+        assert!(ContractVerifier::is_metadata_hash_enabled(&request));
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": {},
+            "settings": { "metadata": { "bytecodeHash": "keccak256" } },
+        });
+        let request = verification_request(standard_json(input));
+        assert!(ContractVerifier::is_metadata_hash_enabled(&request));
+
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": {},
+            "settings": { "metadata": { "bytecodeHash": "none" } },
+        });
+        let request = verification_request(standard_json(input));
+        assert!(!ContractVerifier::is_metadata_hash_enabled(&request));
+        let input = serde_json::json!({ "language": "Yul", "sources": {}, "settings": {} });
+        let request = verification_request(standard_json(input));
+        assert!(!ContractVerifier::is_metadata_hash_enabled(&request));
+        let request = verification_request(SourceCodeData::YulSingleFile(String::new()));
+        assert!(!ContractVerifier::is_metadata_hash_enabled(&request));
+        let request = verification_request(SourceCodeData::VyperMultiFile(HashMap::new()));
+        assert!(!ContractVerifier::is_metadata_hash_enabled(&request));
+    }
+}
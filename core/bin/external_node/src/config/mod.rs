@@ -4,9 +4,17 @@ use anyhow::Context;
 use serde::Deserialize;
 use url::Url;
 use zksync_basic_types::{Address, L1ChainId, L2ChainId, MiniblockNumber};
-use zksync_core::api_server::{
-    tx_sender::TxSenderConfig,
-    web3::{state::InternalApiConfig, Namespace},
+use zksync_config::ObjectStoreConfig;
+use zksync_core::{
+    api_server::{
+        tx_sender::TxSenderConfig,
+        web3::{
+            client_limits::{ClientLimits, ClientLimitsConfig},
+            state::InternalApiConfig,
+            Namespace,
+        },
+    },
+    db_pruner::DbPrunerConfig,
 };
 use zksync_types::api::BridgeAddresses;
 use zksync_web3_decl::{
@@ -119,6 +127,28 @@ pub struct OptionalENConfig {
     /// Maximum response body size in MiBs. Default is 10 MiB.
     #[serde(default = "OptionalENConfig::default_max_response_body_size_mb")]
     pub max_response_body_size_mb: usize,
+    /// Maximum number of JSON-RPC calls per minute for a single client of the HTTP server. Clients are identified
+    /// by an API key or by their IP address. If not set, calls are not rate-limited.
+    http_requests_per_minute_limit: Option<u32>,
+    /// Maximum number of calls to expensive methods (e.g., `eth_call`) per minute for a single client
+    /// of the HTTP server. If not set, expensive methods don't have a separate budget.
+    http_expensive_requests_per_minute_limit: Option<u32>,
+    /// Patterns of expensive methods for the HTTP server, e.g. `eth_call,debug_*`. If not set, the default list is used.
+    http_expensive_methods: Option<Vec<String>>,
+    /// Name of the header containing API keys for the HTTP server. Default is `x-api-key`.
+    http_api_key_header: Option<String>,
+    /// API keys recognized by the HTTP server.
+    #[serde(default)]
+    http_api_keys: Vec<String>,
+    /// If set, only methods matching one of these patterns are allowed on the HTTP server.
+    http_method_allow_list: Option<Vec<String>>,
+    /// Methods matching one of these patterns are not allowed on the HTTP server.
+    #[serde(default)]
+    http_method_deny_list: Vec<String>,
+    /// Whether to identify HTTP clients by the `X-Forwarded-For` / `X-Real-IP` headers instead of the peer address.
+    /// Should only be enabled if the server is only reachable via a proxy that sets these headers.
+    #[serde(default)]
+    http_trust_forwarded_headers: bool,
 
     // Other API config settings
     /// Interval between polling DB for pubsub (in ms).
@@ -195,6 +225,36 @@ pub struct OptionalENConfig {
     /// 0 means that sealing is synchronous; this is mostly useful for performance comparison, testing etc.
     #[serde(default = "OptionalENConfig::default_miniblock_seal_queue_capacity")]
     pub miniblock_seal_queue_capacity: usize,
+
+    /// Whether the main node publishes L1 batch pubdata to a data availability layer (validium mode). In this mode,
+    /// commit transactions carry data availability inclusion data instead of the pubdata, so the consistency checker
+    /// doesn't check this field.
+    #[serde(default)]
+    pub validium_mode: bool,
+
+    // Pruning config
+    /// Enables pruning of historical Postgres data (transactions, events, storage logs etc.) for L1 batches
+    /// that are older than `pruning_data_retention_sec` and are executed on L1. Pruned data is no longer
+    /// served by the API.
+    #[serde(default)]
+    pub pruning_enabled: bool,
+    /// Number of L1 batches pruned in a single pruning iteration.
+    #[serde(default = "OptionalENConfig::default_pruning_chunk_size")]
+    pub pruning_chunk_size: u32,
+    /// Delay between marking L1 batches as pruned and removing their data from Postgres. Must be greater
+    /// than the TTL of the first available block cache in the API server (10 seconds); otherwise, the node
+    /// refuses to start.
+    #[serde(default = "OptionalENConfig::default_pruning_removal_delay_sec")]
+    pruning_removal_delay_sec: u64,
+    /// Minimum age of an L1 batch for it to be pruned.
+    #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
+    pruning_data_retention_sec: u64,
+
+    // Snapshot recovery config
+    /// Enables recovery from the newest snapshot available on the main node if Postgres is empty.
+    /// Requires the snapshots object store to be configured via `EN_SNAPSHOTS_OBJECT_STORE_*` variables.
+    #[serde(default)]
+    pub snapshots_recovery_enabled: bool,
 }
 
 impl OptionalENConfig {
@@ -297,6 +357,18 @@ impl OptionalENConfig {
         10
     }
 
+    const fn default_pruning_chunk_size() -> u32 {
+        10
+    }
+
+    const fn default_pruning_removal_delay_sec() -> u64 {
+        60
+    }
+
+    const fn default_pruning_data_retention_sec() -> u64 {
+        7 * 24 * 3_600 // 7 days
+    }
+
     pub fn polling_interval(&self) -> Duration {
         Duration::from_millis(self.polling_interval)
     }
@@ -335,6 +407,14 @@ impl OptionalENConfig {
         Duration::from_secs(self.merkle_tree_stalled_writes_timeout_sec)
     }
 
+    pub fn pruning_removal_delay(&self) -> Duration {
+        Duration::from_secs(self.pruning_removal_delay_sec)
+    }
+
+    pub fn pruning_data_retention(&self) -> Duration {
+        Duration::from_secs(self.pruning_data_retention_sec)
+    }
+
     pub fn api_namespaces(&self) -> Vec<Namespace> {
         self.api_namespaces
             .clone()
@@ -344,6 +424,20 @@ impl OptionalENConfig {
     pub fn max_response_body_size(&self) -> usize {
         self.max_response_body_size_mb * BYTES_IN_MEGABYTE
     }
+
+    pub fn client_limits(&self) -> ClientLimits {
+        ClientLimitsConfig {
+            requests_per_minute: self.http_requests_per_minute_limit,
+            expensive_requests_per_minute: self.http_expensive_requests_per_minute_limit,
+            expensive_methods: self.http_expensive_methods.as_deref(),
+            api_key_header: self.http_api_key_header.as_deref(),
+            api_keys: &self.http_api_keys,
+            method_allow_list: self.http_method_allow_list.as_deref(),
+            method_deny_list: &self.http_method_deny_list,
+            trust_forwarded_headers: self.http_trust_forwarded_headers,
+        }
+        .into()
+    }
 }
 
 /// This part of the external node config is required for its operation.
@@ -407,6 +501,14 @@ impl PostgresConfig {
     }
 }
 
+/// Loads the configuration of the object store with snapshots produced by the main node.
+/// Only required if snapshot recovery is enabled.
+pub(crate) fn read_snapshots_object_store_config() -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed("EN_SNAPSHOTS_OBJECT_STORE_")
+        .from_env::<ObjectStoreConfig>()
+        .context("failed loading snapshots object store config from env variables")
+}
+
 /// External Node Config contains all the configuration required for the EN operation.
 /// It is split into three parts: required, optional and remote for easier navigation.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -428,6 +530,15 @@ impl ExternalNodeConfig {
         let optional = envy::prefixed("EN_")
             .from_env::<OptionalENConfig>()
             .context("could not load external node config")?;
+        if optional.pruning_enabled {
+            let min_delay = DbPrunerConfig::MIN_REMOVAL_DELAY;
+            anyhow::ensure!(
+                optional.pruning_removal_delay() > min_delay,
+                "Pruning removal delay ({:?}) must be greater than the TTL of the first available block cache \
+                 in the API server ({min_delay:?})",
+                optional.pruning_removal_delay()
+            );
+        }
 
         let client = HttpClientBuilder::default()
             .build(required.main_node_url()?)
@@ -536,6 +647,8 @@ impl From<ExternalNodeConfig> for TxSenderConfig {
             max_allowed_l2_tx_gas_limit: u32::MAX,
             validation_computational_gas_limit: u32::MAX,
             chain_id: config.remote.l2_chain_id,
+            // Per-account limits are enforced by the main node.
+            account_limits: None,
         }
     }
 }
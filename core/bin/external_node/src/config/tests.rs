@@ -1,5 +1,7 @@
 //! Tests for EN configuration.
 
+use std::num::NonZeroU32;
+
 use super::*;
 
 #[test]
@@ -44,6 +46,9 @@ fn parsing_optional_config_from_env() {
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
+        ("EN_HTTP_REQUESTS_PER_MINUTE_LIMIT", "600"),
+        ("EN_HTTP_API_KEYS", "key1,key2"),
+        ("EN_HTTP_METHOD_DENY_LIST", "debug_*"),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -70,4 +75,11 @@ fn parsing_optional_config_from_env() {
         32 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), BYTES_IN_MEGABYTE);
+
+    let client_limits = config.client_limits();
+    assert_eq!(client_limits.requests_per_minute, NonZeroU32::new(600));
+    assert_eq!(client_limits.expensive_requests_per_minute, None);
+    assert_eq!(client_limits.api_keys.len(), 2);
+    assert_eq!(client_limits.method_deny_list, ["debug_*"]);
+    assert!(client_limits.method_allow_list.is_none());
 }
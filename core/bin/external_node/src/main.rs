@@ -5,7 +5,11 @@ use clap::Parser;
 use futures::{future::FusedFuture, FutureExt};
 use metrics::EN_METRICS;
 use prometheus_exporter::PrometheusExporterConfig;
-use tokio::{sync::watch, task, time::sleep};
+use tokio::{
+    sync::{broadcast, watch},
+    task,
+    time::sleep,
+};
 use zksync_basic_types::{Address, L2ChainId};
 use zksync_core::{
     api_server::{
@@ -16,12 +20,14 @@ use zksync_core::{
     },
     block_reverter::{BlockReverter, BlockReverterFlags, L1ExecutedBatchesRevert},
     consistency_checker::ConsistencyChecker,
+    db_pruner::{DbPruner, DbPrunerConfig},
     l1_gas_price::MainNodeGasPriceFetcher,
     metadata_calculator::{
         MetadataCalculator, MetadataCalculatorConfig, MetadataCalculatorModeConfig,
     },
     reorg_detector::ReorgDetector,
     setup_sigint_handler,
+    snapshot_recovery::SnapshotsApplier,
     state_keeper::{
         L1BatchExecutorBuilder, MainBatchExecutorBuilder, MiniblockSealer, MiniblockSealerHandle,
         ZkSyncStateKeeper,
@@ -33,6 +39,7 @@ use zksync_core::{
 };
 use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
 use zksync_health_check::CheckHealth;
+use zksync_object_store::ObjectStoreFactory;
 use zksync_state::PostgresStorageCaches;
 use zksync_storage::RocksDB;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
@@ -43,7 +50,7 @@ mod metrics;
 const RELEASE_MANIFEST: &str =
     std::include_str!("../../../../.github/release-please/manifest.json");
 
-use crate::config::ExternalNodeConfig;
+use crate::config::{read_snapshots_object_store_config, ExternalNodeConfig};
 
 /// Creates the state keeper configured to work in the external node mode.
 #[allow(clippy::too_many_arguments)]
@@ -125,10 +132,14 @@ async fn init_tasks(
     let (action_queue_sender, action_queue) = ActionQueue::new();
 
     let mut task_handles = vec![];
+    // Sealed miniblocks are pushed to subscribers of the WS API server.
+    let (sealed_miniblocks_sender, _) = broadcast::channel(128);
     let (miniblock_sealer, miniblock_sealer_handle) = MiniblockSealer::new(
         connection_pool.clone(),
         config.optional.miniblock_seal_queue_capacity,
     );
+    let miniblock_sealer =
+        miniblock_sealer.with_sealed_miniblocks_sender(sealed_miniblocks_sender.clone());
     task_handles.push(tokio::spawn(miniblock_sealer.run()));
     let pool = connection_pool.clone();
     task_handles.push(tokio::spawn(async move {
@@ -206,7 +217,8 @@ async fn init_tasks(
             .build()
             .await
             .context("failed to build connection pool for ConsistencyChecker")?,
-    );
+    )
+    .with_validium_mode(config.optional.validium_mode);
 
     let batch_status_updater = BatchStatusUpdater::new(
         &main_node_url,
@@ -275,6 +287,7 @@ async fn init_tasks(
             .with_filter_limit(config.optional.filters_limit)
             .with_batch_request_size_limit(config.optional.max_batch_request_size)
             .with_response_body_size_limit(config.optional.max_response_body_size())
+            .with_client_limits(config.optional.client_limits())
             .with_threads(config.required.threads_per_server)
             .with_tx_sender(tx_sender.clone(), vm_barrier.clone())
             .with_sync_state(sync_state.clone())
@@ -294,6 +307,7 @@ async fn init_tasks(
             .with_threads(config.required.threads_per_server)
             .with_tx_sender(tx_sender, vm_barrier)
             .with_sync_state(sync_state)
+            .with_sealed_miniblocks(sealed_miniblocks_sender)
             .enable_api_namespaces(config.optional.api_namespaces())
             .build(stop_receiver.clone())
             .await
@@ -323,6 +337,22 @@ async fn init_tasks(
     ]);
     task_handles.push(consistency_checker_handle);
 
+    if config.optional.pruning_enabled {
+        tracing::info!("Postgres pruning is enabled");
+        let pruner_config = DbPrunerConfig {
+            data_retention: config.optional.pruning_data_retention(),
+            pruned_batch_chunk_size: config.optional.pruning_chunk_size,
+            removal_delay: config.optional.pruning_removal_delay(),
+            poll_interval: Duration::from_secs(10),
+        };
+        let pruner_pool = singleton_pool_builder
+            .build()
+            .await
+            .context("failed to build a connection pool for DbPruner")?;
+        let pruner = DbPruner::new(pruner_config, pruner_pool);
+        task_handles.push(tokio::spawn(pruner.run(stop_receiver.clone())));
+    }
+
     Ok((task_handles, stop_sender, healthcheck_handle, stop_receiver))
 }
 
@@ -428,6 +458,21 @@ async fn main() -> anyhow::Result<()> {
     // Make sure that genesis is performed.
     let main_node_client = <dyn MainNodeClient>::json_rpc(&main_node_url)
         .context("Failed creating JSON-RPC client for main node")?;
+    if config.optional.snapshots_recovery_enabled {
+        let object_store_config = read_snapshots_object_store_config()?;
+        let blob_store = ObjectStoreFactory::new(object_store_config)
+            .create_store()
+            .await;
+        let applier = SnapshotsApplier::new(
+            connection_pool.clone(),
+            Box::new(main_node_client.clone()),
+            blob_store.into(),
+        );
+        applier
+            .load_snapshot()
+            .await
+            .context("Recovering from snapshot failed")?;
+    }
     perform_genesis_if_needed(
         &mut connection_pool.access_storage().await.unwrap(),
         config.remote.l2_chain_id,
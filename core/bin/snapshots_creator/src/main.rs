@@ -1,5 +1,3 @@
-mod chunking;
-
 use std::{cmp::max, time::Duration};
 
 use anyhow::Context as _;
@@ -7,19 +5,18 @@ use prometheus_exporter::PrometheusExporterConfig;
 use tokio::sync::{watch, Semaphore};
 use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
 use zksync_config::{configs::PrometheusConfig, PostgresConfig, SnapshotsCreatorConfig};
-use zksync_dal::ConnectionPool;
+use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
 use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_types::{
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        get_chunk_hashed_keys_range, SnapshotFactoryDependencies, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey,
     },
     L1BatchNumber, MiniblockNumber,
 };
 use zksync_utils::ceil_div;
 
-use crate::chunking::get_chunk_hashed_keys_range;
-
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "snapshots_creator")]
 struct SnapshotsCreatorMetrics {
@@ -41,6 +38,31 @@ struct SnapshotsCreatorMetrics {
 #[vise::register]
 pub(crate) static METRICS: vise::Global<SnapshotsCreatorMetrics> = vise::Global::new();
 
+/// Kind of the created snapshot.
+#[derive(Debug, Clone, Copy)]
+enum SnapshotKind {
+    /// Snapshot containing the entire storage state.
+    Full,
+    /// Snapshot containing only storage logs and factory deps changed after the parent snapshot.
+    Delta {
+        parent_l1_batch_number: L1BatchNumber,
+        /// First miniblock after the parent snapshot.
+        first_miniblock_number: MiniblockNumber,
+    },
+}
+
+impl SnapshotKind {
+    fn parent_l1_batch_number(self) -> Option<L1BatchNumber> {
+        match self {
+            Self::Full => None,
+            Self::Delta {
+                parent_l1_batch_number,
+                ..
+            } => Some(parent_l1_batch_number),
+        }
+    }
+}
+
 async fn maybe_enable_prometheus_metrics(
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
@@ -63,6 +85,7 @@ async fn process_storage_logs_single_chunk(
     blob_store: &dyn ObjectStore,
     pool: &ConnectionPool,
     semaphore: &Semaphore,
+    kind: SnapshotKind,
     miniblock_number: MiniblockNumber,
     l1_batch_number: L1BatchNumber,
     chunk_id: u64,
@@ -72,11 +95,24 @@ async fn process_storage_logs_single_chunk(
     let hashed_keys_range = get_chunk_hashed_keys_range(chunk_id, chunks_count);
     let latency = METRICS.storage_logs_processing_duration.start();
     let mut conn = pool.access_storage_tagged("snapshots_creator").await?;
-    let logs = conn
-        .snapshots_creator_dal()
-        .get_storage_logs_chunk(miniblock_number, hashed_keys_range)
-        .await
-        .context("Error fetching storage logs count")?;
+    let mut dal = conn.snapshots_creator_dal();
+    let logs = match kind {
+        SnapshotKind::Full => {
+            dal.get_storage_logs_chunk(miniblock_number, hashed_keys_range)
+                .await
+        }
+        SnapshotKind::Delta {
+            first_miniblock_number,
+            ..
+        } => {
+            dal.get_changed_storage_logs_chunk(
+                first_miniblock_number..=miniblock_number,
+                hashed_keys_range,
+            )
+            .await
+        }
+    };
+    let logs = logs.context("Error fetching storage logs chunk")?;
     drop(conn);
     let storage_logs_chunk = SnapshotStorageLogsChunk { storage_logs: logs };
     let key = SnapshotStorageLogsStorageKey {
@@ -105,15 +141,23 @@ async fn process_storage_logs_single_chunk(
 async fn process_factory_deps(
     blob_store: &dyn ObjectStore,
     pool: &ConnectionPool,
+    kind: SnapshotKind,
     miniblock_number: MiniblockNumber,
     l1_batch_number: L1BatchNumber,
 ) -> anyhow::Result<String> {
     let latency = METRICS.factory_deps_processing_duration.start();
     let mut conn = pool.access_storage_tagged("snapshots_creator").await?;
-    let factory_deps = conn
-        .snapshots_creator_dal()
-        .get_all_factory_deps(miniblock_number)
-        .await?;
+    let mut dal = conn.snapshots_creator_dal();
+    let factory_deps = match kind {
+        SnapshotKind::Full => dal.get_all_factory_deps(miniblock_number).await?,
+        SnapshotKind::Delta {
+            first_miniblock_number,
+            ..
+        } => {
+            dal.get_factory_deps_for_miniblocks(first_miniblock_number..=miniblock_number)
+                .await?
+        }
+    };
     let factory_deps = SnapshotFactoryDependencies { factory_deps };
     drop(conn);
     let filename = blob_store
@@ -130,6 +174,50 @@ async fn process_factory_deps(
     Ok(output_filepath)
 }
 
+/// Returns the L1 batch number of the parent snapshot if a delta snapshot should be created
+/// for `l1_batch_number`, or `None` if a full snapshot should be created.
+async fn find_parent_snapshot(
+    conn: &mut StorageProcessor<'_>,
+    l1_batch_number: L1BatchNumber,
+    max_delta_chain_length: u32,
+) -> anyhow::Result<Option<L1BatchNumber>> {
+    if max_delta_chain_length == 0 {
+        return Ok(None);
+    }
+    let all_snapshots = conn.snapshots_dal().get_all_snapshots().await?;
+    let parent_l1_batch_number = all_snapshots
+        .snapshots_l1_batch_numbers
+        .into_iter()
+        .filter(|&number| number < l1_batch_number)
+        .max();
+    let Some(parent_l1_batch_number) = parent_l1_batch_number else {
+        return Ok(None);
+    };
+
+    // Count delta snapshots in the chain ending at the parent snapshot.
+    let mut delta_chain_length = 0;
+    let mut current_l1_batch_number = parent_l1_batch_number;
+    while delta_chain_length < max_delta_chain_length {
+        let metadata = conn
+            .snapshots_dal()
+            .get_snapshot_metadata(current_l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("snapshot for L1 batch #{current_l1_batch_number} is missing")
+            })?;
+        let Some(grandparent_l1_batch_number) = metadata.parent_l1_batch_number else {
+            return Ok(Some(parent_l1_batch_number));
+        };
+        delta_chain_length += 1;
+        current_l1_batch_number = grandparent_l1_batch_number;
+    }
+    tracing::info!(
+        "Delta snapshot chain ending at L1 batch #{parent_l1_batch_number} has reached \
+         the maximum length {max_delta_chain_length}; creating a full snapshot"
+    );
+    Ok(None)
+}
+
 async fn run(
     blob_store: Box<dyn ObjectStore>,
     replica_pool: ConnectionPool,
@@ -158,6 +246,12 @@ async fn run(
         tracing::info!("Snapshot for L1 batch number {l1_batch_number} already exists, exiting",);
         return Ok(());
     }
+    let parent_l1_batch_number = find_parent_snapshot(
+        &mut master_conn,
+        l1_batch_number,
+        config.max_delta_chain_length,
+    )
+    .await?;
     drop(master_conn);
 
     let last_miniblock_number_in_batch = conn
@@ -166,10 +260,37 @@ async fn run(
         .await?
         .context("Error fetching last miniblock number")?
         .1;
-    let distinct_storage_logs_keys_count = conn
-        .snapshots_creator_dal()
-        .get_distinct_storage_logs_keys_count(l1_batch_number)
-        .await?;
+    let kind = if let Some(parent_l1_batch_number) = parent_l1_batch_number {
+        let parent_last_miniblock_number = conn
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(parent_l1_batch_number)
+            .await?
+            .context("Error fetching last miniblock number of parent snapshot")?
+            .1;
+        SnapshotKind::Delta {
+            parent_l1_batch_number,
+            first_miniblock_number: parent_last_miniblock_number + 1,
+        }
+    } else {
+        SnapshotKind::Full
+    };
+    let distinct_storage_logs_keys_count = match kind {
+        SnapshotKind::Full => {
+            conn.snapshots_creator_dal()
+                .get_distinct_storage_logs_keys_count(l1_batch_number)
+                .await?
+        }
+        SnapshotKind::Delta {
+            first_miniblock_number,
+            ..
+        } => {
+            conn.snapshots_creator_dal()
+                .get_changed_storage_logs_keys_count(
+                    first_miniblock_number..=last_miniblock_number_in_batch,
+                )
+                .await?
+        }
+    };
 
     drop(conn);
 
@@ -183,11 +304,22 @@ async fn run(
         "Creating snapshot for storage logs up to miniblock {last_miniblock_number_in_batch}, l1_batch {}",
         l1_batch_number.0
     );
+    if let SnapshotKind::Delta {
+        parent_l1_batch_number,
+        first_miniblock_number,
+    } = kind
+    {
+        tracing::info!(
+            "Snapshot is a delta one relative to snapshot for L1 batch #{parent_l1_batch_number}, \
+             covering miniblocks starting from {first_miniblock_number}"
+        );
+    }
     tracing::info!("Starting to generate {chunks_count} chunks of expected size {chunk_size}");
 
     let factory_deps_output_file = process_factory_deps(
         &*blob_store,
         &replica_pool,
+        kind,
         last_miniblock_number_in_batch,
         l1_batch_number,
     )
@@ -203,6 +335,7 @@ async fn run(
             &*blob_store,
             &replica_pool,
             &semaphore,
+            kind,
             last_miniblock_number_in_batch,
             l1_batch_number,
             chunk_id,
@@ -225,6 +358,7 @@ async fn run(
             l1_batch_number,
             &storage_logs_output_files,
             &factory_deps_output_file,
+            kind.parent_l1_batch_number(),
         )
         .await?;
 
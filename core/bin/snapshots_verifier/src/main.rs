@@ -0,0 +1,144 @@
+use std::{path::PathBuf, time::Instant};
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_core::snapshot_recovery::{
+    SnapshotVerificationReport, SnapshotVerifier, SnapshotsApplierMainNodeClient,
+};
+use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
+use zksync_merkle_tree::{PatchSet, RocksDBWrapper};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::L1BatchNumber;
+use zksync_web3_decl::jsonrpsee::http_client::HttpClientBuilder;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Integrity verifier for snapshots produced by snapshots creator",
+    long_about = None
+)]
+struct Cli {
+    /// URL of the main node JSON-RPC API. Used to fetch the snapshot header and the root hash
+    /// of the snapshot L1 batch.
+    #[arg(long = "rpc-url")]
+    rpc_url: String,
+    /// L1 batch number of the snapshot to verify. If not specified, the newest snapshot is verified.
+    #[arg(long = "l1-batch")]
+    l1_batch: Option<u32>,
+    /// Path to an empty directory used to store the recovered Merkle tree in RocksDB.
+    /// If not specified, the tree is kept in memory, which is only feasible for small snapshots.
+    #[arg(long = "tree-path")]
+    tree_path: Option<PathBuf>,
+    /// Number of storage logs chunks fetched from the object store concurrently.
+    #[arg(long, default_value_t = 10)]
+    concurrency: usize,
+}
+
+impl Cli {
+    async fn run(self) -> anyhow::Result<()> {
+        let client = HttpClientBuilder::default()
+            .build(&self.rpc_url)
+            .context("failed creating JSON-RPC client")?;
+        let header = if let Some(l1_batch) = self.l1_batch {
+            client
+                .fetch_snapshot(L1BatchNumber(l1_batch))
+                .await?
+                .with_context(|| format!("snapshot for L1 batch #{l1_batch} does not exist"))?
+        } else {
+            client
+                .fetch_newest_snapshot()
+                .await?
+                .context("main node does not have any snapshots")?
+        };
+        let l1_batch_number = header.l1_batch_number;
+
+        // Cross-check the root hash in the header with the one returned by the L1 batch API.
+        let root_hash = client
+            .fetch_l1_batch_root_hash(l1_batch_number)
+            .await?
+            .with_context(|| format!("root hash for L1 batch #{l1_batch_number} is missing"))?;
+        let header_root_hash = header.last_l1_batch_with_metadata.metadata.root_hash;
+        anyhow::ensure!(
+            root_hash == header_root_hash,
+            "root hash in snapshot header {header_root_hash:?} differs from the root hash \
+             of L1 batch #{l1_batch_number} {root_hash:?}"
+        );
+
+        // Object store mode (GCS, file-backed etc.) is determined by the config.
+        let object_store_config = SnapshotsObjectStoreConfig::from_env()
+            .context("SnapshotsObjectStoreConfig::from_env()")?;
+        let blob_store = ObjectStoreFactory::new(object_store_config.0)
+            .create_store()
+            .await;
+        let verifier = SnapshotVerifier::new(&*blob_store).with_concurrency(self.concurrency);
+
+        let started_at = Instant::now();
+        let report = if let Some(tree_path) = &self.tree_path {
+            tracing::info!(
+                "Recovering Merkle tree in RocksDB at {}",
+                tree_path.display()
+            );
+            verifier
+                .verify(&header, RocksDBWrapper::new(tree_path))
+                .await?
+        } else {
+            verifier.verify(&header, PatchSet::default()).await?
+        };
+        tracing::info!(
+            "Finished verifying snapshot for L1 batch #{l1_batch_number} in {:?}",
+            started_at.elapsed()
+        );
+        Self::print_report(&report)
+    }
+
+    fn print_report(report: &SnapshotVerificationReport) -> anyhow::Result<()> {
+        tracing::info!(
+            "Processed {} storage logs in {} chunks",
+            report.storage_log_count,
+            report.chunk_count
+        );
+        for failure in &report.chunk_failures {
+            tracing::error!(
+                "Storage logs chunk #{} is invalid: {}",
+                failure.chunk_id,
+                failure.error
+            );
+        }
+        if let Some(computed_root_hash) = report.computed_root_hash {
+            tracing::info!(
+                "Recovered root hash: {computed_root_hash:?}, expected: {:?}",
+                report.expected_root_hash
+            );
+        }
+
+        anyhow::ensure!(
+            report.is_valid(),
+            "snapshot for L1 batch #{} is invalid",
+            report.l1_batch_number
+        );
+        tracing::info!("Snapshot for L1 batch #{} is valid", report.l1_batch_number);
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let log_format = vlog::log_format_from_env();
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let sentry_url = vlog::sentry_url_from_env();
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let environment = vlog::environment_from_env();
+
+    let mut builder = vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(environment);
+    }
+    let _guard = builder.build();
+
+    Cli::parse().run().await
+}
@@ -11,8 +11,8 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, PrometheusConfig,
-        ProofDataHandlerConfig, ProverGroupConfig, WitnessGeneratorConfig,
+        DADispatcherConfig, FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig,
+        PrometheusConfig, ProofDataHandlerConfig, ProverGroupConfig, WitnessGeneratorConfig,
     },
     ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
     FetcherConfig, GasAdjusterConfig, ObjectStoreConfig, PostgresConfig, ProverConfigs,
@@ -21,7 +21,7 @@ use zksync_core::{
     genesis_init, initialize_components, is_genesis_needed, setup_sigint_handler,
     temp_config_store::TempConfigStore, Component, Components,
 };
-use zksync_env_config::FromEnv;
+use zksync_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
 use zksync_storage::RocksDB;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
 
@@ -111,6 +111,7 @@ async fn main() -> anyhow::Result<()> {
         prometheus_config: PrometheusConfig::from_env().ok(),
         proof_data_handler_config: ProofDataHandlerConfig::from_env().ok(),
         prover_group_config: ProverGroupConfig::from_env().ok(),
+        da_dispatcher_config: DADispatcherConfig::from_env().ok(),
         witness_generator_config: WitnessGeneratorConfig::from_env().ok(),
         api_config: ApiConfig::from_env().ok(),
         contracts_config: ContractsConfig::from_env().ok(),
@@ -122,6 +123,9 @@ async fn main() -> anyhow::Result<()> {
         gas_adjuster_config: GasAdjusterConfig::from_env().ok(),
         prover_configs: ProverConfigs::from_env().ok(),
         object_store_config: ObjectStoreConfig::from_env().ok(),
+        prover_object_store_config: ProverObjectStoreConfig::from_env()
+            .ok()
+            .map(|config| config.0),
     };
 
     let postgres_config = configs.postgres_config.clone().context("PostgresConfig")?;
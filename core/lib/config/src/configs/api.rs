@@ -87,8 +87,31 @@ pub struct Web3JsonRpcConfig {
     /// The value is per active connection.
     /// Note: For HTTP, rate limiting is expected to be configured on the infra level.
     pub websocket_requests_per_minute_limit: Option<u32>,
+    /// Maximum number of JSON-RPC calls per minute for a single client of the HTTP server. Clients are identified
+    /// by an API key or by their IP address. If not set, calls are not rate-limited.
+    pub http_requests_per_minute_limit: Option<u32>,
+    /// Maximum number of calls to expensive methods (e.g., `eth_call`) per minute for a single client
+    /// of the HTTP server. If not set, expensive methods don't have a separate budget.
+    pub http_expensive_requests_per_minute_limit: Option<u32>,
+    /// Patterns of expensive methods for the HTTP server, e.g. `eth_call,debug_*`. If not set, the default list is used.
+    pub http_expensive_methods: Option<Vec<String>>,
+    /// Name of the header containing API keys for the HTTP server. Default is `x-api-key`.
+    pub http_api_key_header: Option<String>,
+    /// API keys recognized by the HTTP server. Clients with a recognized key are rate-limited separately
+    /// from their IP address.
+    pub http_api_keys: Option<Vec<String>>,
+    /// If set, only methods matching one of these patterns (e.g., `eth_*,net_version`) are allowed on the HTTP server.
+    pub http_method_allow_list: Option<Vec<String>>,
+    /// Methods matching one of these patterns are not allowed on the HTTP server.
+    pub http_method_deny_list: Option<Vec<String>>,
+    /// Whether to identify HTTP clients by the `X-Forwarded-For` / `X-Real-IP` headers instead of the peer address.
+    /// Should only be enabled if the server is only reachable via a proxy that sets these headers. Default is `false`.
+    pub http_trust_forwarded_headers: Option<bool>,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
+    /// API namespaces enabled on the HTTP and WebSocket servers, e.g. `eth,net,web3,zks,en,pubsub,txpool`.
+    /// If not set, the default namespaces are enabled.
+    pub api_namespaces: Option<Vec<String>>,
 }
 
 impl Web3JsonRpcConfig {
@@ -125,7 +148,16 @@ impl Web3JsonRpcConfig {
             max_batch_request_size: Default::default(),
             max_response_body_size_mb: Default::default(),
             websocket_requests_per_minute_limit: Default::default(),
+            http_requests_per_minute_limit: None,
+            http_expensive_requests_per_minute_limit: None,
+            http_expensive_methods: None,
+            http_api_key_header: None,
+            http_api_keys: None,
+            http_method_allow_list: None,
+            http_method_deny_list: None,
+            http_trust_forwarded_headers: None,
             tree_api_url: None,
+            api_namespaces: None,
         }
     }
 
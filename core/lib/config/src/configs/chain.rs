@@ -40,6 +40,24 @@ impl NetworkConfig {
     }
 }
 
+/// Policy used to order L2 transactions in the mempool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolOrdering {
+    /// Transactions are ordered by the time they were received by the mempool.
+    #[default]
+    Fifo,
+    /// Transactions are ordered by their priority fee (`max_priority_fee_per_gas`) in descending order;
+    /// transactions with equal fees are ordered by the time they were received. Once the mempool
+    /// reaches its capacity, accounts with the lowest-paying transactions are evicted from it, and their
+    /// transactions are marked as rejected.
+    ///
+    /// The ordering only applies to transactions loaded into the mempool. Transactions are still loaded
+    /// from the database in the order they were received (in batches of `mempool.sync_batch_size`),
+    /// so a high-paying transaction may wait until older transactions are loaded.
+    PriorityFee,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Default)]
 pub struct StateKeeperConfig {
     /// The max number of slots for txs in a block before it should be sealed by the slots sealer.
@@ -93,6 +111,10 @@ pub struct StateKeeperConfig {
 
     /// Number of keys that is processed by enum_index migration in State Keeper each L1 batch.
     pub enum_index_migration_chunk_size: Option<usize>,
+
+    /// Policy used to order L2 transactions in the mempool. If not specified, FIFO ordering is used.
+    #[serde(default)]
+    pub mempool_ordering: MempoolOrdering,
 }
 
 impl StateKeeperConfig {
@@ -121,6 +143,7 @@ impl StateKeeperConfig {
             virtual_blocks_per_miniblock: 1,
             upload_witness_inputs_to_gcs: false,
             enum_index_migration_chunk_size: None,
+            mempool_ordering: MempoolOrdering::Fifo,
         }
     }
 
@@ -167,9 +190,47 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Maximum number of pending transactions from a single account.
+    #[serde(default = "MempoolConfig::default_max_pending_txs_per_account")]
+    pub max_pending_txs_per_account: usize,
+    /// Maximum difference between the nonce of a transaction and the next nonce expected
+    /// for its initiator.
+    #[serde(default = "MempoolConfig::default_max_nonce_gap")]
+    pub max_nonce_gap: u32,
+    /// Minimum increase (in percent) of `max_fee_per_gas` and `max_priority_fee_per_gas` required
+    /// to replace a pending transaction with the same initiator and nonce.
+    #[serde(default = "MempoolConfig::default_replacement_fee_bump_percent")]
+    pub replacement_fee_bump_percent: u32,
 }
 
 impl MempoolConfig {
+    /// Creates a config object suitable for use in unit tests.
+    pub fn for_tests() -> Self {
+        Self {
+            sync_interval_ms: 10,
+            sync_batch_size: 1_000,
+            capacity: 10_000_000,
+            stuck_tx_timeout: 86_400,
+            remove_stuck_txs: true,
+            delay_interval: 100,
+            max_pending_txs_per_account: Self::default_max_pending_txs_per_account(),
+            max_nonce_gap: Self::default_max_nonce_gap(),
+            replacement_fee_bump_percent: Self::default_replacement_fee_bump_percent(),
+        }
+    }
+
+    const fn default_max_pending_txs_per_account() -> usize {
+        64
+    }
+
+    const fn default_max_nonce_gap() -> u32 {
+        64
+    }
+
+    const fn default_replacement_fee_bump_percent() -> u32 {
+        10
+    }
+
     pub fn sync_interval(&self) -> Duration {
         Duration::from_millis(self.sync_interval_ms)
     }
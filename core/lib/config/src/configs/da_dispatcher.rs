@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Configuration for the data availability dispatcher, which sends L1 batch pubdata
+/// to a data availability layer in the validium mode.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DADispatcherConfig {
+    /// Interval between polling the database for batches to dispatch and for inclusion of dispatched blobs.
+    pub polling_interval_ms: u64,
+    /// Maximum number of L1 batches dispatched in a single iteration.
+    pub max_rows_to_dispatch: u32,
+    /// Directory used by the file-backed data availability client. Intended for local setups and tests.
+    pub file_client_path: Option<String>,
+}
+
+impl DADispatcherConfig {
+    pub fn polling_interval(&self) -> Duration {
+        Duration::from_millis(self.polling_interval_ms)
+    }
+}
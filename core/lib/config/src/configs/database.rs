@@ -162,6 +162,9 @@ pub struct PostgresConfig {
     /// Statement timeout in seconds for Postgres connections. Applies only to the replica
     /// connection pool used by the API servers.
     pub statement_timeout_sec: Option<u64>,
+    /// Whether prover pipeline components (witness generators, provers, proof compressor) should subscribe
+    /// to Postgres notifications about queued jobs instead of relying on polling only.
+    pub job_notifications_enabled: bool,
 }
 
 impl PostgresConfig {
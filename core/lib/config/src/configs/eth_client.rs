@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 // External uses
 use serde::Deserialize;
 
@@ -8,4 +10,32 @@ pub struct ETHClientConfig {
     pub chain_id: u64,
     /// Address of the Ethereum node API.
     pub web3_url: String,
+    /// Addresses of fallback Ethereum node APIs. Requests are sent to the fallback APIs
+    /// if the main API (or a higher-priority fallback API) is unavailable.
+    pub fallback_web3_urls: Option<Vec<String>>,
+    /// Minimum number of Ethereum node APIs that must agree on the response for quorum reads
+    /// (block numbers, logs and transaction statuses). If not set, quorum reads are disabled,
+    /// and all requests are served by a single API.
+    pub quorum: Option<usize>,
+    /// Timeout for a single request to an Ethereum node API, after which the request is retried
+    /// with the next API. If not set, 30 seconds.
+    pub provider_timeout_ms: Option<u64>,
+}
+
+impl ETHClientConfig {
+    const DEFAULT_PROVIDER_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Returns the timeout for a single request to an Ethereum node API.
+    pub fn provider_timeout(&self) -> Duration {
+        self.provider_timeout_ms
+            .map_or(Self::DEFAULT_PROVIDER_TIMEOUT, Duration::from_millis)
+    }
+
+    /// Returns addresses of all Ethereum node APIs, starting from the main one.
+    pub fn web3_urls(&self) -> impl Iterator<Item = &str> + '_ {
+        let fallback_urls = self.fallback_web3_urls.iter().flatten();
+        std::iter::once(&self.web3_url)
+            .chain(fallback_urls)
+            .map(String::as_str)
+    }
 }
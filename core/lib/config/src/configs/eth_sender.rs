@@ -35,6 +35,7 @@ impl ETHSenderConfig {
                 l1_batch_min_age_before_execute_seconds: None,
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
+                pubdata_sending_mode: PubdataSendingMode::Calldata,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -63,6 +64,17 @@ pub enum ProofLoadingMode {
     FriProofFromGcs,
 }
 
+/// Where L1 batch pubdata is published.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+pub enum PubdataSendingMode {
+    /// Pubdata is sent to L1 in the commit transaction calldata.
+    #[default]
+    Calldata,
+    /// Pubdata is dispatched to a data availability layer (validium mode); the commit transaction
+    /// only carries the data proving its inclusion on the layer.
+    DataAvailability,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct SenderConfig {
     pub aggregated_proof_sizes: Vec<usize>,
@@ -96,6 +108,9 @@ pub struct SenderConfig {
 
     /// The mode in which proofs are loaded, either from DB/GCS for FRI/Old proof.
     pub proof_loading_mode: ProofLoadingMode,
+    /// Where L1 batch pubdata is published.
+    #[serde(default)]
+    pub pubdata_sending_mode: PubdataSendingMode,
 }
 
 impl SenderConfig {
@@ -111,9 +126,29 @@ impl SenderConfig {
 
     // Don't load private key, if it's not required.
     pub fn private_key(&self) -> Option<H256> {
-        std::env::var("ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")
-            .ok()
-            .map(|pk| pk.parse().unwrap())
+        Self::load_private_key("ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")
+    }
+
+    /// Private key of the operator account used exclusively for sending commit transactions.
+    /// If not set, commit transactions are sent from the main operator account.
+    pub fn commit_operator_private_key(&self) -> Option<H256> {
+        Self::load_private_key("ETH_SENDER_SENDER_COMMIT_OPERATOR_PRIVATE_KEY")
+    }
+
+    /// Private key of the operator account used exclusively for sending proof transactions.
+    /// If not set, proof transactions are sent from the main operator account.
+    pub fn prove_operator_private_key(&self) -> Option<H256> {
+        Self::load_private_key("ETH_SENDER_SENDER_PROVE_OPERATOR_PRIVATE_KEY")
+    }
+
+    /// Private key of the operator account used exclusively for sending execute transactions.
+    /// If not set, execute transactions are sent from the main operator account.
+    pub fn execute_operator_private_key(&self) -> Option<H256> {
+        Self::load_private_key("ETH_SENDER_SENDER_EXECUTE_OPERATOR_PRIVATE_KEY")
+    }
+
+    fn load_private_key(env_var: &str) -> Option<H256> {
+        std::env::var(env_var).ok().map(|pk| pk.parse().unwrap())
     }
 }
 
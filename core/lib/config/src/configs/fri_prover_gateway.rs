@@ -2,10 +2,19 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
+use super::proof_data_handler::ProofDataHandlerAuthMode;
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct FriProverGatewayConfig {
     pub api_url: String,
     pub api_poll_duration_secs: u16,
+    /// Authentication mode used by the proof data handler API.
+    #[serde(default)]
+    pub auth_mode: ProofDataHandlerAuthMode,
+    /// Prover identity sent to the proof data handler API. Required if authentication is enabled.
+    pub prover_id: Option<String>,
+    /// Secret shared with the proof data handler API. Required if authentication is enabled.
+    pub prover_secret: Option<String>,
 
     /// Configurations for prometheus
     pub prometheus_listener_port: u16,
@@ -16,4 +16,8 @@ pub struct HouseKeeperConfig {
     pub fri_prover_stats_reporting_interval_ms: u64,
     pub fri_proof_compressor_job_retrying_interval_ms: u64,
     pub fri_proof_compressor_stats_reporting_interval_ms: u64,
+    /// Interval between removals of prover artifacts (prover jobs and witness inputs) from the object store
+    /// for L1 batches already proven on L1. If not set, artifacts are never removed.
+    #[serde(default)]
+    pub prover_artifacts_cleaning_interval_ms: Option<u64>,
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Clone, Copy)]
@@ -5,14 +7,88 @@ pub enum ObjectStoreMode {
     GCS,
     GCSWithCredentialFile,
     FileBacked,
+    /// S3-compatible storage (AWS S3, MinIO etc.). `bucket_base_url` is used as the S3 bucket name.
+    S3,
 }
 
 /// Configuration for the object store
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Deserialize, Clone, PartialEq)]
 pub struct ObjectStoreConfig {
     pub bucket_base_url: String,
     pub mode: ObjectStoreMode,
     pub file_backed_base_path: String,
     pub gcs_credential_file_path: String,
     pub max_retries: u16,
+    /// Endpoint of the S3-compatible API (e.g., `http://localhost:9000` for a local MinIO instance).
+    /// If not specified, the AWS S3 endpoint for `s3_region` is used.
+    #[serde(default)]
+    pub s3_endpoint: Option<String>,
+    /// Region used to sign S3 requests.
+    #[serde(default = "ObjectStoreConfig::default_s3_region")]
+    pub s3_region: String,
+    /// Whether to use path-style addressing (`{endpoint}/{bucket}/{key}`) instead of
+    /// virtual-hosted-style addressing (`{bucket}.{endpoint}/{key}`). Usually required for MinIO.
+    #[serde(default)]
+    pub s3_path_style: bool,
+    /// Access key ID for S3. If not specified, it is taken from the `AWS_ACCESS_KEY_ID` env variable.
+    #[serde(default)]
+    pub s3_access_key_id: Option<String>,
+    /// Secret access key for S3. If not specified, it is taken from the `AWS_SECRET_ACCESS_KEY`
+    /// env variable.
+    #[serde(default)]
+    pub s3_secret_access_key: Option<String>,
+    /// Objects larger than this size are uploaded to S3 using multipart uploads with parts of this size.
+    /// Must be at least 5 MB.
+    #[serde(default = "ObjectStoreConfig::default_s3_multipart_part_size_mb")]
+    pub s3_multipart_part_size_mb: usize,
+    /// Path to the directory used to cache objects fetched from the store on the local disk.
+    /// If not specified, caching is disabled.
+    #[serde(default)]
+    pub cache_path: Option<String>,
+    /// Maximum total size of cached objects. Once it is exceeded, least recently used objects
+    /// are evicted from the cache.
+    #[serde(default = "ObjectStoreConfig::default_cache_max_size_mb")]
+    pub cache_max_size_mb: u64,
+    /// Names of the buckets whose objects are cached, e.g. `witness_inputs,prover_jobs_fri`.
+    /// Objects in other buckets are always fetched from the store.
+    #[serde(default)]
+    pub cached_buckets: Vec<String>,
+}
+
+impl fmt::Debug for ObjectStoreConfig {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The S3 secret must not be logged.
+        let s3_secret_access_key = self.s3_secret_access_key.as_ref().map(|_| "<redacted>");
+        formatter
+            .debug_struct("ObjectStoreConfig")
+            .field("bucket_base_url", &self.bucket_base_url)
+            .field("mode", &self.mode)
+            .field("file_backed_base_path", &self.file_backed_base_path)
+            .field("gcs_credential_file_path", &self.gcs_credential_file_path)
+            .field("max_retries", &self.max_retries)
+            .field("s3_endpoint", &self.s3_endpoint)
+            .field("s3_region", &self.s3_region)
+            .field("s3_path_style", &self.s3_path_style)
+            .field("s3_access_key_id", &self.s3_access_key_id)
+            .field("s3_secret_access_key", &s3_secret_access_key)
+            .field("s3_multipart_part_size_mb", &self.s3_multipart_part_size_mb)
+            .field("cache_path", &self.cache_path)
+            .field("cache_max_size_mb", &self.cache_max_size_mb)
+            .field("cached_buckets", &self.cached_buckets)
+            .finish()
+    }
+}
+
+impl ObjectStoreConfig {
+    fn default_s3_region() -> String {
+        "us-east-1".to_owned()
+    }
+
+    const fn default_s3_multipart_part_size_mb() -> usize {
+        64
+    }
+
+    const fn default_cache_max_size_mb() -> u64 {
+        10_240
+    }
 }
@@ -1,5 +1,6 @@
-use std::time::Duration;
+use std::{collections::HashMap, fmt, num::NonZeroU32, time::Duration};
 
+use anyhow::Context as _;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
@@ -8,15 +9,111 @@ pub enum ProtocolVersionLoadingMode {
     FromEnvVar,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+/// Authentication mode for requests to the proof data handler API.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+pub enum ProofDataHandlerAuthMode {
+    /// Requests are not authenticated.
+    #[default]
+    None,
+    /// Requests are authenticated with a per-prover API key passed in the `Authorization: Bearer ...` header.
+    ApiKey,
+    /// Requests are authenticated with an HMAC-SHA256 signature keyed by the per-prover secret.
+    Hmac,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
 pub struct ProofDataHandlerConfig {
     pub http_port: u16,
     pub proof_generation_timeout_in_secs: u16,
     pub protocol_version_loading_mode: ProtocolVersionLoadingMode,
     pub fri_protocol_version_id: u16,
+    /// Authentication mode for the API.
+    #[serde(default)]
+    pub auth_mode: ProofDataHandlerAuthMode,
+    /// Credentials of provers allowed to access the API, in the `prover_id:secret` format. The secret is used
+    /// as an API key or as an HMAC key depending on the `auth_mode`.
+    #[serde(default)]
+    pub prover_credentials: Vec<String>,
+    /// Maximum number of authenticated requests per minute accepted from a single prover (or from a single
+    /// IP address if authentication is disabled). If not specified, authenticated requests are not rate-limited.
+    pub rate_limit_requests_per_minute: Option<NonZeroU32>,
+    /// Maximum number of requests per minute accepted from a single IP address before authentication
+    /// (i.e., including requests with invalid credentials). Should be set high enough to accommodate all provers
+    /// sharing an IP address. If not specified, requests are not rate-limited before authentication.
+    pub ip_rate_limit_requests_per_minute: Option<NonZeroU32>,
+    /// Maximum difference between the timestamp of an HMAC-signed request and the server time.
+    #[serde(default = "ProofDataHandlerConfig::default_max_signature_age_secs")]
+    pub max_signature_age_secs: u64,
+}
+
+impl fmt::Debug for ProofDataHandlerConfig {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Prover secrets must not be logged.
+        let prover_credentials: Vec<_> = self
+            .prover_credentials
+            .iter()
+            .map(|entry| match entry.split_once(':') {
+                Some((prover_id, _)) => format!("{prover_id}:<redacted>"),
+                None => "<redacted>".to_owned(),
+            })
+            .collect();
+        formatter
+            .debug_struct("ProofDataHandlerConfig")
+            .field("http_port", &self.http_port)
+            .field(
+                "proof_generation_timeout_in_secs",
+                &self.proof_generation_timeout_in_secs,
+            )
+            .field(
+                "protocol_version_loading_mode",
+                &self.protocol_version_loading_mode,
+            )
+            .field("fri_protocol_version_id", &self.fri_protocol_version_id)
+            .field("auth_mode", &self.auth_mode)
+            .field("prover_credentials", &prover_credentials)
+            .field(
+                "rate_limit_requests_per_minute",
+                &self.rate_limit_requests_per_minute,
+            )
+            .field(
+                "ip_rate_limit_requests_per_minute",
+                &self.ip_rate_limit_requests_per_minute,
+            )
+            .field("max_signature_age_secs", &self.max_signature_age_secs)
+            .finish()
+    }
 }
+
 impl ProofDataHandlerConfig {
+    const fn default_max_signature_age_secs() -> u64 {
+        300
+    }
+
     pub fn proof_generation_timeout(&self) -> Duration {
         Duration::from_secs(self.proof_generation_timeout_in_secs as u64)
     }
+
+    pub fn max_signature_age(&self) -> Duration {
+        Duration::from_secs(self.max_signature_age_secs)
+    }
+
+    /// Parses prover credentials into a map from prover identity to its secret.
+    pub fn prover_credentials(&self) -> anyhow::Result<HashMap<String, String>> {
+        let mut credentials = HashMap::with_capacity(self.prover_credentials.len());
+        for entry in &self.prover_credentials {
+            let (prover_id, secret) = entry
+                .split_once(':')
+                .context("prover credentials must have `prover_id:secret` format")?;
+            anyhow::ensure!(
+                !prover_id.is_empty() && !secret.is_empty(),
+                "prover identity and secret must be non-empty"
+            );
+            let prev_secret = credentials.insert(prover_id.to_owned(), secret.to_owned());
+            anyhow::ensure!(
+                prev_secret.is_none(),
+                "duplicate credentials for prover `{prover_id}`"
+            );
+        }
+        Ok(credentials)
+    }
 }
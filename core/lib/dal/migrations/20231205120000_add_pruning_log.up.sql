@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS pruning_log
+(
+    pruned_l1_batch  BIGINT    NOT NULL,
+    pruned_miniblock BIGINT    NOT NULL,
+    -- 'Soft' means that data is marked as pruned and is no longer served by the API;
+    -- 'Hard' means that data is removed from the storage.
+    prune_type       TEXT      NOT NULL CHECK (prune_type IN ('Soft', 'Hard')),
+
+    created_at       TIMESTAMP NOT NULL,
+    updated_at       TIMESTAMP NOT NULL,
+    PRIMARY KEY (prune_type, pruned_l1_batch)
+);
@@ -0,0 +1,4 @@
+ALTER TABLE initial_writes ADD CONSTRAINT initial_writes_l1_batch_number_fkey
+    FOREIGN KEY (l1_batch_number) REFERENCES l1_batches (number) ON DELETE CASCADE;
+
+DROP TABLE IF EXISTS snapshot_recovery;
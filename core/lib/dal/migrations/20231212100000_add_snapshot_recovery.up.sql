@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS snapshot_recovery
+(
+    l1_batch_number               BIGINT    NOT NULL PRIMARY KEY,
+    l1_batch_root_hash            BYTEA     NOT NULL,
+    miniblock_number              BIGINT    NOT NULL,
+    miniblock_hash                BYTEA     NOT NULL,
+    -- Element `i` is set to TRUE once the storage logs chunk with ID `i` is applied.
+    storage_logs_chunks_processed BOOLEAN[] NOT NULL,
+
+    created_at                    TIMESTAMP NOT NULL,
+    updated_at                    TIMESTAMP NOT NULL
+);
+
+-- A node recovered from a snapshot doesn't have L1 batches preceding the snapshot L1 batch,
+-- but its initial writes still refer to them.
+ALTER TABLE initial_writes DROP CONSTRAINT IF EXISTS initial_writes_l1_batch_number_fkey;
@@ -0,0 +1 @@
+ALTER TABLE snapshots DROP COLUMN IF EXISTS parent_l1_batch_number;
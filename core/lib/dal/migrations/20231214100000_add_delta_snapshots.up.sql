@@ -0,0 +1,3 @@
+-- Delta snapshots only contain storage logs and factory deps changed since the parent snapshot.
+ALTER TABLE snapshots ADD COLUMN IF NOT EXISTS parent_l1_batch_number BIGINT
+    REFERENCES snapshots (l1_batch_number);
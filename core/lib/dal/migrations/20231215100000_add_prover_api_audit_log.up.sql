@@ -0,0 +1,12 @@
+-- Audit log of proof data handler API requests: which prover fetched / submitted proofs for which L1 batch.
+CREATE TABLE IF NOT EXISTS prover_api_audit_log
+(
+    id              BIGSERIAL PRIMARY KEY,
+    prover_id       TEXT,
+    action          TEXT      NOT NULL,
+    l1_batch_number BIGINT    NOT NULL,
+    created_at      TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_prover_api_audit_log_l1_batch_number
+    ON prover_api_audit_log (l1_batch_number);
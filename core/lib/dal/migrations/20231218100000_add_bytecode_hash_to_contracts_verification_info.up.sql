@@ -0,0 +1,3 @@
+ALTER TABLE contracts_verification_info ADD COLUMN IF NOT EXISTS bytecode_hash BYTEA;
+CREATE INDEX IF NOT EXISTS contracts_verification_info_bytecode_hash_idx
+    ON contracts_verification_info (bytecode_hash);
@@ -0,0 +1,4 @@
+-- `NULL` corresponds to the main operator account; otherwise, the transaction is sent from a custom operator account
+-- used only for a specific type of aggregated operations.
+ALTER TABLE eth_txs ADD COLUMN IF NOT EXISTS from_addr BYTEA;
+CREATE INDEX IF NOT EXISTS eth_txs_from_addr_idx ON eth_txs (from_addr);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS data_availability (
+    l1_batch_number BIGINT PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    -- Identifier of the L1 batch pubdata on the data availability layer.
+    blob_id TEXT NOT NULL,
+    -- Data proving the inclusion of pubdata on the data availability layer; sent to L1 instead of the pubdata.
+    -- `NULL` if the inclusion is not confirmed yet.
+    inclusion_data BYTEA,
+    sent_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS data_availability_awaiting_inclusion_idx
+    ON data_availability (l1_batch_number) WHERE inclusion_data IS NULL;
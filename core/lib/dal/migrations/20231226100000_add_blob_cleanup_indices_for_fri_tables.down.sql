@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS ix_prover_jobs_fri_blobs_to_clean;
+DROP INDEX IF EXISTS ix_witness_inputs_fri_blobs_to_clean;
@@ -0,0 +1,4 @@
+CREATE INDEX IF NOT EXISTS ix_prover_jobs_fri_blobs_to_clean
+    ON prover_jobs_fri (l1_batch_number) WHERE is_blob_cleaned IS NOT TRUE;
+CREATE INDEX IF NOT EXISTS ix_witness_inputs_fri_blobs_to_clean
+    ON witness_inputs_fri (l1_batch_number) WHERE is_blob_cleaned IS NOT TRUE;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS identical_contracts_verification_progress;
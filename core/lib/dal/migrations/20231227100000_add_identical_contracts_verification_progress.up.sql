@@ -0,0 +1,8 @@
+-- Singleton table recording the progress of the verifier of contracts with identical bytecode.
+CREATE TABLE IF NOT EXISTS identical_contracts_verification_progress
+(
+    -- Ensures that the table contains at most one row.
+    is_singleton             BOOLEAN   NOT NULL PRIMARY KEY DEFAULT TRUE CHECK (is_singleton),
+    last_processed_miniblock BIGINT    NOT NULL,
+    updated_at               TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS contracts_verification_info_unchecked_idx;
+ALTER TABLE contracts_verification_info DROP COLUMN IF EXISTS identical_contracts_checked;
@@ -0,0 +1,5 @@
+-- Whether previously deployed contracts with the same bytecode were verified as identical to this contract.
+ALTER TABLE contracts_verification_info
+    ADD COLUMN IF NOT EXISTS identical_contracts_checked BOOLEAN NOT NULL DEFAULT FALSE;
+CREATE INDEX IF NOT EXISTS contracts_verification_info_unchecked_idx
+    ON contracts_verification_info (address) WHERE NOT identical_contracts_checked;
@@ -729,6 +729,23 @@ impl BlocksDal<'_, '_> {
         .map(|row| L1BatchNumber(row.number as u32)))
     }
 
+    /// Returns the number of the last sealed L1 batch with the timestamp not exceeding the specified one.
+    pub async fn get_last_l1_batch_number_with_timestamp_before(
+        &mut self,
+        timestamp: u64,
+    ) -> sqlx::Result<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            "SELECT MAX(number) AS \"number\" FROM l1_batches \
+            WHERE is_finished = TRUE AND timestamp <= $1",
+            timestamp as i64
+        )
+        .instrument("get_last_l1_batch_number_with_timestamp_before")
+        .with_arg("timestamp", &timestamp)
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
     /// This method returns batches that are confirmed on L1. That is, it doesn't wait for the proofs to be generated.
     pub async fn get_ready_for_dummy_proof_l1_batches(
         &mut self,
@@ -1164,6 +1181,14 @@ impl BlocksDal<'_, '_> {
         last_batch_to_keep: Option<L1BatchNumber>,
     ) -> sqlx::Result<()> {
         let block_number = last_batch_to_keep.map_or(-1, |number| number.0 as i64);
+        // Initial writes are not linked to L1 batches with a foreign key since a node recovered
+        // from a snapshot doesn't have L1 batches preceding the snapshot.
+        sqlx::query!(
+            "DELETE FROM initial_writes WHERE l1_batch_number > $1",
+            block_number
+        )
+        .execute(self.storage.conn())
+        .await?;
         sqlx::query!("DELETE FROM l1_batches WHERE number > $1", block_number)
             .execute(self.storage.conn())
             .await?;
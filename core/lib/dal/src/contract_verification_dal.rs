@@ -1,5 +1,6 @@
 use std::{
     fmt::{Display, Formatter},
+    ops,
     time::Duration,
 };
 
@@ -10,8 +11,11 @@ use zksync_types::{
         DeployContractCalldata, VerificationIncomingRequest, VerificationInfo, VerificationRequest,
         VerificationRequestStatus,
     },
-    get_code_key, Address, CONTRACT_DEPLOYER_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH,
+    event::DEPLOY_EVENT_SIGNATURE,
+    get_code_key, Address, MiniblockNumber, CONTRACT_DEPLOYER_ADDRESS,
+    FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256,
 };
+use zksync_utils::{address_to_h256, bytecode::hash_bytecode, h256_to_account_address};
 
 use crate::{models::storage_verification_request::StorageVerificationRequest, StorageProcessor};
 
@@ -144,23 +148,211 @@ impl ContractVerificationDal<'_, '_> {
         .execute(transaction.conn())
         .await?;
 
+        transaction
+            .contract_verification_dal()
+            .insert_verification_info(verification_info)
+            .await?;
+        transaction.commit().await.context("commit()")?;
+        Ok(())
+    }
+
+    /// Inserts the verification info without updating the verification request status. Used directly
+    /// for contracts verified automatically based on a bytecode match with another verified contract.
+    /// Existing verification info is only overwritten if it is a partial match and the new info is a full match.
+    pub async fn insert_verification_info(
+        &mut self,
+        verification_info: VerificationInfo,
+    ) -> sqlx::Result<()> {
         let address = verification_info.request.req.contract_address;
+        let bytecode_hash = hash_bytecode(&verification_info.artifacts.bytecode);
+        // Contracts with the bytecode identical to a contract verified automatically are checked
+        // when the original contract is verified.
+        let identical_contracts_checked = verification_info.identical_to.is_some();
         // Serialization should always succeed.
         let verification_info_json = serde_json::to_value(verification_info)
             .expect("Failed to serialize verification info into serde_json");
         sqlx::query!(
             "INSERT INTO contracts_verification_info \
-            (address, verification_info) \
-            VALUES ($1, $2) \
+            (address, verification_info, bytecode_hash, identical_contracts_checked) \
+            VALUES ($1, $2, $3, $4) \
             ON CONFLICT (address) \
-            DO UPDATE SET verification_info = $2",
+            DO UPDATE SET verification_info = $2, bytecode_hash = $3, identical_contracts_checked = $4 \
+            WHERE (contracts_verification_info.verification_info ->> 'bytecodeMatch') = 'partial' \
+                AND (EXCLUDED.verification_info ->> 'bytecodeMatch') IS DISTINCT FROM 'partial'",
             address.as_bytes(),
-            &verification_info_json
+            &verification_info_json,
+            bytecode_hash.as_bytes(),
+            identical_contracts_checked
         )
-        .execute(transaction.conn())
+        .execute(self.storage.conn())
         .await?;
+        Ok(())
+    }
 
-        transaction.commit().await.context("commit()")?;
+    /// Returns addresses of up to `limit` unverified contracts deployed with the specified bytecode hash,
+    /// ordered by address. Only addresses greater than `after_address` are returned, so that contracts
+    /// can be iterated over in chunks.
+    pub async fn get_unverified_contracts_with_bytecode_hash(
+        &mut self,
+        bytecode_hash: H256,
+        after_address: Address,
+        limit: usize,
+    ) -> sqlx::Result<Vec<Address>> {
+        let rows = sqlx::query!(
+            "SELECT DISTINCT events.topic4 AS contract_address FROM events \
+            WHERE events.address = $1 AND events.topic1 = $2 AND events.topic3 = $3 \
+                AND events.topic4 > $4 \
+                AND NOT EXISTS ( \
+                    SELECT 1 FROM contracts_verification_info \
+                    WHERE contracts_verification_info.address = substring(events.topic4 FROM 13) \
+                ) \
+            ORDER BY events.topic4 \
+            LIMIT $5",
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            DEPLOY_EVENT_SIGNATURE.as_bytes(),
+            bytecode_hash.as_bytes(),
+            address_to_h256(&after_address).as_bytes(),
+            limit as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| h256_to_account_address(&H256::from_slice(&row.contract_address)))
+            .collect())
+    }
+
+    /// Returns verification info for a verified contract for which previously deployed contracts
+    /// with the identical bytecode were not checked yet.
+    pub async fn get_next_verification_info_with_unchecked_identical_contracts(
+        &mut self,
+    ) -> anyhow::Result<Option<VerificationInfo>> {
+        let Some(row) = sqlx::query!(
+            "SELECT verification_info FROM contracts_verification_info \
+            WHERE NOT identical_contracts_checked AND verification_info IS NOT NULL \
+            ORDER BY address \
+            LIMIT 1"
+        )
+        .fetch_optional(self.storage.conn())
+        .await?
+        else {
+            return Ok(None);
+        };
+        let info = row.verification_info.context("verification info missing")?;
+        Ok(Some(serde_json::from_value(info).context("invalid info")?))
+    }
+
+    pub async fn set_identical_contracts_checked(&mut self, address: Address) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE contracts_verification_info SET identical_contracts_checked = TRUE \
+            WHERE address = $1",
+            address.as_bytes()
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns unverified contracts deployed in the specified miniblock range that have the same bytecode
+    /// as a verified contract. Each returned pair contains the address of an unverified contract
+    /// and the address of the verified contract.
+    pub async fn get_unverified_identical_contracts(
+        &mut self,
+        miniblock_numbers: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<Vec<(Address, Address)>> {
+        let rows = sqlx::query!(
+            "SELECT DISTINCT ON (events.topic4) \
+                events.topic4 AS contract_address, contracts_verification_info.address AS verified_address \
+            FROM events \
+            JOIN contracts_verification_info ON contracts_verification_info.bytecode_hash = events.topic3 \
+            WHERE events.miniblock_number BETWEEN $1 AND $2 \
+                AND events.address = $3 AND events.topic1 = $4 \
+                AND NOT EXISTS ( \
+                    SELECT 1 FROM contracts_verification_info AS verified \
+                    WHERE verified.address = substring(events.topic4 FROM 13) \
+                ) \
+            ORDER BY events.topic4, contracts_verification_info.address",
+            miniblock_numbers.start().0 as i64,
+            miniblock_numbers.end().0 as i64,
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            DEPLOY_EVENT_SIGNATURE.as_bytes()
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let contract_address =
+                    h256_to_account_address(&H256::from_slice(&row.contract_address));
+                (contract_address, Address::from_slice(&row.verified_address))
+            })
+            .collect())
+    }
+
+    /// Returns the last miniblock scanned for contracts with the bytecode identical to verified contracts.
+    pub async fn get_last_identical_contracts_miniblock(
+        &mut self,
+    ) -> sqlx::Result<Option<MiniblockNumber>> {
+        let row = sqlx::query!(
+            "SELECT last_processed_miniblock FROM identical_contracts_verification_progress"
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(row.map(|row| MiniblockNumber(row.last_processed_miniblock as u32)))
+    }
+
+    pub async fn set_last_identical_contracts_miniblock(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO identical_contracts_verification_progress \
+            (last_processed_miniblock, updated_at) \
+            VALUES ($1, now()) \
+            ON CONFLICT (is_singleton) \
+            DO UPDATE SET last_processed_miniblock = $1, updated_at = now()",
+            miniblock_number.0 as i64
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns verification info for up to `limit` verified contracts without a stored bytecode hash
+    /// (i.e., ones verified before bytecode hashes started being recorded).
+    pub async fn get_verification_info_without_bytecode_hash(
+        &mut self,
+        limit: usize,
+    ) -> anyhow::Result<Vec<VerificationInfo>> {
+        let rows = sqlx::query!(
+            "SELECT verification_info FROM contracts_verification_info \
+            WHERE bytecode_hash IS NULL AND verification_info IS NOT NULL \
+            LIMIT $1",
+            limit as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        rows.into_iter()
+            .filter_map(|row| row.verification_info)
+            .map(|info| serde_json::from_value(info).context("invalid info"))
+            .collect()
+    }
+
+    pub async fn set_verified_bytecode_hash(
+        &mut self,
+        address: Address,
+        bytecode_hash: H256,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE contracts_verification_info SET bytecode_hash = $2 WHERE address = $1",
+            address.as_bytes(),
+            bytecode_hash.as_bytes()
+        )
+        .execute(self.storage.conn())
+        .await?;
         Ok(())
     }
 
@@ -256,12 +448,14 @@ impl ContractVerificationDal<'_, '_> {
         Ok(Some((row.bytecode, calldata)))
     }
 
-    /// Returns true if the contract has a stored contracts_verification_info.
-    pub async fn is_contract_verified(&mut self, address: Address) -> sqlx::Result<bool> {
+    /// Returns true if the contract has a stored contracts_verification_info with a full bytecode match.
+    /// Contracts with a partial match can be verified again to obtain a full match.
+    pub async fn is_contract_fully_verified(&mut self, address: Address) -> sqlx::Result<bool> {
         let count = sqlx::query!(
             "SELECT COUNT(*) as \"count!\" \
             FROM contracts_verification_info \
-            WHERE address = $1",
+            WHERE address = $1 \
+                AND (verification_info ->> 'bytecodeMatch') IS DISTINCT FROM 'partial'",
             address.as_bytes()
         )
         .fetch_one(self.storage.conn())
@@ -388,3 +582,220 @@ impl ContractVerificationDal<'_, '_> {
         Ok(Some(serde_json::from_value(info).context("invalid info")?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        contract_verification_api::BytecodeMatch, tx::IncludedTxLocation, L1BatchNumber,
+        ProtocolVersion, VmEvent,
+    };
+
+    use super::*;
+    use crate::{
+        tests::{create_miniblock_header, mock_verification_info},
+        ConnectionPool,
+    };
+
+    fn deploy_event(bytecode_hash: H256, address: Address) -> VmEvent {
+        let deployer = Address::repeat_byte(0xff);
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: CONTRACT_DEPLOYER_ADDRESS,
+            indexed_topics: vec![
+                *DEPLOY_EVENT_SIGNATURE,
+                address_to_h256(&deployer),
+                bytecode_hash,
+                address_to_h256(&address),
+            ],
+            value: vec![],
+        }
+    }
+
+    async fn prepare_storage(storage: &mut StorageProcessor<'_>) {
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+    }
+
+    async fn store_deploy_events(
+        storage: &mut StorageProcessor<'_>,
+        miniblock_number: u32,
+        events: &[VmEvent],
+    ) {
+        storage
+            .blocks_dal()
+            .insert_miniblock(&create_miniblock_header(miniblock_number))
+            .await
+            .unwrap();
+        let location = IncludedTxLocation {
+            tx_hash: H256::from_low_u64_be(miniblock_number.into()),
+            tx_index_in_miniblock: 0,
+            tx_initiator_address: Address::default(),
+        };
+        storage
+            .events_dal()
+            .save_events(
+                MiniblockNumber(miniblock_number),
+                &[(location, events.iter().collect())],
+            )
+            .await;
+    }
+
+    async fn verify_contract(storage: &mut StorageProcessor<'_>, address: Address) {
+        storage
+            .contract_verification_dal()
+            .insert_verification_info(mock_verification_info(address, BytecodeMatch::Full))
+            .await
+            .unwrap();
+    }
+
+    /// Bytecode hash of contracts verified with `mock_verification_info()`.
+    fn verified_bytecode_hash() -> H256 {
+        hash_bytecode(
+            &mock_verification_info(Address::zero(), BytecodeMatch::Full)
+                .artifacts
+                .bytecode,
+        )
+    }
+
+    #[tokio::test]
+    async fn getting_unverified_contracts_with_bytecode_hash() {
+        let pool = ConnectionPool::test_pool().await;
+        let storage = &mut pool.access_storage().await.unwrap();
+        prepare_storage(storage).await;
+        let bytecode_hash = verified_bytecode_hash();
+        let [first, second, third, other] = [1, 2, 3, 4].map(Address::repeat_byte);
+        let events = [
+            deploy_event(bytecode_hash, third),
+            deploy_event(bytecode_hash, first),
+            deploy_event(H256::repeat_byte(1), other),
+        ];
+        store_deploy_events(storage, 1, &events).await;
+        // Deployment of a contract at the same address (e.g., after self-destruction in the same transaction).
+        let events = [
+            deploy_event(bytecode_hash, second),
+            deploy_event(bytecode_hash, first),
+        ];
+        store_deploy_events(storage, 2, &events).await;
+
+        let mut dal = storage.contract_verification_dal();
+        let addresses = dal
+            .get_unverified_contracts_with_bytecode_hash(bytecode_hash, Address::zero(), 10)
+            .await
+            .unwrap();
+        assert_eq!(addresses, [first, second, third]);
+
+        // Check iteration in chunks.
+        let addresses = dal
+            .get_unverified_contracts_with_bytecode_hash(bytecode_hash, Address::zero(), 2)
+            .await
+            .unwrap();
+        assert_eq!(addresses, [first, second]);
+        let addresses = dal
+            .get_unverified_contracts_with_bytecode_hash(bytecode_hash, second, 2)
+            .await
+            .unwrap();
+        assert_eq!(addresses, [third]);
+
+        verify_contract(storage, second).await;
+        let addresses = storage
+            .contract_verification_dal()
+            .get_unverified_contracts_with_bytecode_hash(bytecode_hash, Address::zero(), 10)
+            .await
+            .unwrap();
+        assert_eq!(addresses, [first, third]);
+    }
+
+    #[tokio::test]
+    async fn getting_unverified_identical_contracts() {
+        let pool = ConnectionPool::test_pool().await;
+        let storage = &mut pool.access_storage().await.unwrap();
+        prepare_storage(storage).await;
+        let bytecode_hash = verified_bytecode_hash();
+        let [verified, first, second, other] = [1, 2, 3, 4].map(Address::repeat_byte);
+        let events = [
+            deploy_event(bytecode_hash, verified),
+            deploy_event(bytecode_hash, first),
+        ];
+        store_deploy_events(storage, 1, &events).await;
+        let events = [
+            deploy_event(bytecode_hash, second),
+            deploy_event(H256::repeat_byte(1), other),
+        ];
+        store_deploy_events(storage, 2, &events).await;
+
+        let contracts = storage
+            .contract_verification_dal()
+            .get_unverified_identical_contracts(MiniblockNumber(1)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert!(contracts.is_empty(), "{contracts:?}");
+
+        verify_contract(storage, verified).await;
+        // The verified contract itself must not be returned, which checks matching addresses
+        // with the `topic4` of deploy events.
+        let contracts = storage
+            .contract_verification_dal()
+            .get_unverified_identical_contracts(MiniblockNumber(1)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(contracts, [(first, verified), (second, verified)]);
+        let contracts = storage
+            .contract_verification_dal()
+            .get_unverified_identical_contracts(MiniblockNumber(2)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(contracts, [(second, verified)]);
+    }
+
+    #[tokio::test]
+    async fn checking_identical_contracts_for_verified_contracts() {
+        let pool = ConnectionPool::test_pool().await;
+        let storage = &mut pool.access_storage().await.unwrap();
+        let [verified, identical] = [1, 2].map(Address::repeat_byte);
+        verify_contract(storage, verified).await;
+        let mut identical_info = mock_verification_info(identical, BytecodeMatch::Full);
+        identical_info.identical_to = Some(verified);
+        storage
+            .contract_verification_dal()
+            .insert_verification_info(identical_info)
+            .await
+            .unwrap();
+
+        let mut dal = storage.contract_verification_dal();
+        let info = dal
+            .get_next_verification_info_with_unchecked_identical_contracts()
+            .await
+            .unwrap()
+            .expect("no unchecked contracts");
+        assert_eq!(info.request.req.contract_address, verified);
+
+        dal.set_identical_contracts_checked(verified).await.unwrap();
+        let info = dal
+            .get_next_verification_info_with_unchecked_identical_contracts()
+            .await
+            .unwrap();
+        assert!(info.is_none(), "{info:?}");
+    }
+
+    #[tokio::test]
+    async fn identical_contracts_verification_progress() {
+        let pool = ConnectionPool::test_pool().await;
+        let storage = &mut pool.access_storage().await.unwrap();
+        let mut dal = storage.contract_verification_dal();
+        let last_miniblock = dal.get_last_identical_contracts_miniblock().await.unwrap();
+        assert_eq!(last_miniblock, None);
+
+        dal.set_last_identical_contracts_miniblock(MiniblockNumber(5))
+            .await
+            .unwrap();
+        let last_miniblock = dal.get_last_identical_contracts_miniblock().await.unwrap();
+        assert_eq!(last_miniblock, Some(MiniblockNumber(5)));
+        dal.set_last_identical_contracts_miniblock(MiniblockNumber(7))
+            .await
+            .unwrap();
+        let last_miniblock = dal.get_last_identical_contracts_miniblock().await.unwrap();
+        assert_eq!(last_miniblock, Some(MiniblockNumber(7)));
+    }
+}
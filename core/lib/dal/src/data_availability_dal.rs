@@ -0,0 +1,144 @@
+use std::{collections::HashMap, ops};
+
+use sqlx::types::chrono::NaiveDateTime;
+use zksync_types::L1BatchNumber;
+
+use crate::StorageProcessor;
+
+#[derive(Debug)]
+pub struct DataAvailabilityDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+/// L1 batch pubdata dispatched to a data availability layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataAvailabilityBlob {
+    pub l1_batch_number: L1BatchNumber,
+    pub blob_id: String,
+    /// Data proving the inclusion of pubdata on the data availability layer. `None` if the inclusion
+    /// is not confirmed yet.
+    pub inclusion_data: Option<Vec<u8>>,
+    pub sent_at: NaiveDateTime,
+}
+
+impl DataAvailabilityDal<'_, '_> {
+    /// Returns numbers of L1 batches with metadata that are not committed on L1 and whose pubdata
+    /// was not dispatched to the data availability layer yet, in ascending order.
+    pub async fn get_ready_for_da_dispatch_l1_batches(
+        &mut self,
+        limit: usize,
+    ) -> sqlx::Result<Vec<L1BatchNumber>> {
+        let rows = sqlx::query!(
+            "SELECT number FROM l1_batches \
+            LEFT JOIN data_availability ON data_availability.l1_batch_number = l1_batches.number \
+            WHERE number != 0 AND commitment IS NOT NULL AND eth_commit_tx_id IS NULL \
+                AND data_availability.l1_batch_number IS NULL \
+            ORDER BY number \
+            LIMIT $1",
+            limit as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.number as u32))
+            .collect())
+    }
+
+    /// Records that the pubdata of the specified L1 batch was dispatched to the data availability layer.
+    pub async fn insert_l1_batch_da(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        blob_id: &str,
+        sent_at: NaiveDateTime,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO data_availability (l1_batch_number, blob_id, sent_at, created_at, updated_at) \
+            VALUES ($1, $2, $3, now(), now()) \
+            ON CONFLICT DO NOTHING",
+            l1_batch_number.0 as i64,
+            blob_id,
+            sent_at
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Saves data proving the inclusion of the L1 batch pubdata on the data availability layer.
+    pub async fn save_l1_batch_inclusion_data(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        inclusion_data: &[u8],
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE data_availability \
+            SET inclusion_data = $1, updated_at = now() \
+            WHERE l1_batch_number = $2 AND inclusion_data IS NULL",
+            inclusion_data,
+            l1_batch_number.0 as i64
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the first dispatched blob for which the inclusion on the data availability layer
+    /// is not confirmed yet.
+    pub async fn get_first_da_blob_awaiting_inclusion(
+        &mut self,
+    ) -> sqlx::Result<Option<DataAvailabilityBlob>> {
+        let row = sqlx::query!(
+            "SELECT l1_batch_number, blob_id, inclusion_data, sent_at FROM data_availability \
+            WHERE inclusion_data IS NULL \
+            ORDER BY l1_batch_number \
+            LIMIT 1"
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(row.map(|row| DataAvailabilityBlob {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            blob_id: row.blob_id,
+            inclusion_data: row.inclusion_data,
+            sent_at: row.sent_at,
+        }))
+    }
+
+    /// Returns the inclusion data for the specified L1 batch, or `None` if its pubdata wasn't dispatched
+    /// to the data availability layer or the inclusion is not confirmed yet.
+    pub async fn get_l1_batch_da_inclusion_data(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<Option<Vec<u8>>> {
+        let inclusion_data = sqlx::query_scalar!(
+            "SELECT inclusion_data FROM data_availability WHERE l1_batch_number = $1",
+            l1_batch_number.0 as i64
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(inclusion_data.flatten())
+    }
+
+    /// Returns the inclusion data for L1 batches in the specified range. L1 batches whose pubdata wasn't dispatched
+    /// to the data availability layer or whose inclusion is not confirmed yet are not included in the returned map.
+    pub async fn get_l1_batches_da_inclusion_data(
+        &mut self,
+        l1_batch_numbers: ops::RangeInclusive<L1BatchNumber>,
+    ) -> sqlx::Result<HashMap<L1BatchNumber, Vec<u8>>> {
+        let rows = sqlx::query!(
+            "SELECT l1_batch_number, inclusion_data FROM data_availability \
+            WHERE l1_batch_number BETWEEN $1 AND $2 AND inclusion_data IS NOT NULL",
+            l1_batch_numbers.start().0 as i64,
+            l1_batch_numbers.end().0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let number = L1BatchNumber(row.l1_batch_number as u32);
+                Some((number, row.inclusion_data?))
+            })
+            .collect())
+    }
+}
@@ -24,15 +24,44 @@ pub struct EthSenderDal<'a, 'c> {
 }
 
 impl EthSenderDal<'_, '_> {
-    pub async fn get_inflight_txs(&mut self) -> sqlx::Result<Vec<EthTx>> {
-        let txs = sqlx::query_as!(
-            StorageEthTx,
-            "SELECT * FROM eth_txs WHERE confirmed_eth_tx_history_id IS NULL \
-             AND id <= (SELECT COALESCE(MAX(eth_tx_id), 0) FROM eth_txs_history WHERE sent_at_block IS NOT NULL) \
-             ORDER BY id"
-        )
-        .fetch_all(self.storage.conn())
-        .await?;
+    /// Returns inflight transactions sent from the specified operator account. `None` corresponds
+    /// to the main operator account.
+    pub async fn get_inflight_txs(
+        &mut self,
+        operator_address: Option<Address>,
+    ) -> sqlx::Result<Vec<EthTx>> {
+        // `from_addr IS NOT DISTINCT FROM $1` cannot use the index on `from_addr`, so queries
+        // for the main operator account and other accounts are split.
+        let txs = if let Some(operator_address) = operator_address {
+            sqlx::query_as!(
+                StorageEthTx,
+                "SELECT * FROM eth_txs WHERE from_addr = $1 \
+                 AND confirmed_eth_tx_history_id IS NULL \
+                 AND id <= ( \
+                    SELECT COALESCE(MAX(eth_tx_id), 0) FROM eth_txs_history \
+                    JOIN eth_txs ON eth_txs.id = eth_txs_history.eth_tx_id \
+                    WHERE eth_txs_history.sent_at_block IS NOT NULL AND eth_txs.from_addr = $1 \
+                 ) \
+                 ORDER BY id",
+                operator_address.as_bytes()
+            )
+            .fetch_all(self.storage.conn())
+            .await?
+        } else {
+            sqlx::query_as!(
+                StorageEthTx,
+                "SELECT * FROM eth_txs WHERE from_addr IS NULL \
+                 AND confirmed_eth_tx_history_id IS NULL \
+                 AND id <= ( \
+                    SELECT COALESCE(MAX(eth_tx_id), 0) FROM eth_txs_history \
+                    JOIN eth_txs ON eth_txs.id = eth_txs_history.eth_tx_id \
+                    WHERE eth_txs_history.sent_at_block IS NOT NULL AND eth_txs.from_addr IS NULL \
+                 ) \
+                 ORDER BY id"
+            )
+            .fetch_all(self.storage.conn())
+            .await?
+        };
         Ok(txs.into_iter().map(|tx| tx.into()).collect())
     }
 
@@ -91,17 +120,45 @@ impl EthSenderDal<'_, '_> {
         .map(Into::into))
     }
 
-    pub async fn get_new_eth_txs(&mut self, limit: u64) -> sqlx::Result<Vec<EthTx>> {
-        let txs = sqlx::query_as!(
-            StorageEthTx,
-            "SELECT * FROM eth_txs \
-            WHERE id > (SELECT COALESCE(MAX(eth_tx_id), 0) FROM eth_txs_history) \
-            ORDER BY id \
-            LIMIT $1",
-            limit as i64
-        )
-        .fetch_all(self.storage.conn())
-        .await?;
+    /// Returns transactions from the specified operator account (`None` corresponds to the main operator)
+    /// that have no sending attempts yet.
+    pub async fn get_new_eth_txs(
+        &mut self,
+        limit: u64,
+        operator_address: Option<Address>,
+    ) -> sqlx::Result<Vec<EthTx>> {
+        let txs = if let Some(operator_address) = operator_address {
+            sqlx::query_as!(
+                StorageEthTx,
+                "SELECT * FROM eth_txs \
+                WHERE from_addr = $2 AND id > ( \
+                    SELECT COALESCE(MAX(eth_tx_id), 0) FROM eth_txs_history \
+                    JOIN eth_txs ON eth_txs.id = eth_txs_history.eth_tx_id \
+                    WHERE eth_txs.from_addr = $2 \
+                ) \
+                ORDER BY id \
+                LIMIT $1",
+                limit as i64,
+                operator_address.as_bytes()
+            )
+            .fetch_all(self.storage.conn())
+            .await?
+        } else {
+            sqlx::query_as!(
+                StorageEthTx,
+                "SELECT * FROM eth_txs \
+                WHERE from_addr IS NULL AND id > ( \
+                    SELECT COALESCE(MAX(eth_tx_id), 0) FROM eth_txs_history \
+                    JOIN eth_txs ON eth_txs.id = eth_txs_history.eth_tx_id \
+                    WHERE eth_txs.from_addr IS NULL \
+                ) \
+                ORDER BY id \
+                LIMIT $1",
+                limit as i64
+            )
+            .fetch_all(self.storage.conn())
+            .await?
+        };
         Ok(txs.into_iter().map(|tx| tx.into()).collect())
     }
 
@@ -133,18 +190,21 @@ impl EthSenderDal<'_, '_> {
         tx_type: AggregatedActionType,
         contract_address: Address,
         predicted_gas_cost: u32,
+        from_address: Option<Address>,
     ) -> sqlx::Result<EthTx> {
         let address = format!("{:#x}", contract_address);
+        let from_address = from_address.as_ref().map(Address::as_bytes);
         let eth_tx = sqlx::query_as!(
             StorageEthTx,
-            "INSERT INTO eth_txs (raw_tx, nonce, tx_type, contract_address, predicted_gas_cost, created_at, updated_at) \
-               VALUES ($1, $2, $3, $4, $5, now(), now()) \
+            "INSERT INTO eth_txs (raw_tx, nonce, tx_type, contract_address, predicted_gas_cost, from_addr, created_at, updated_at) \
+               VALUES ($1, $2, $3, $4, $5, $6, now(), now()) \
                RETURNING *",
             raw_tx,
             nonce as i64,
             tx_type.to_string(),
             address,
-            predicted_gas_cost as i64
+            predicted_gas_cost as i64,
+            from_address
         )
         .fetch_one(self.storage.conn())
         .await?;
@@ -391,11 +451,44 @@ impl EthSenderDal<'_, '_> {
         Ok(history_item.map(|tx| tx.into()))
     }
 
-    pub async fn get_next_nonce(&mut self) -> sqlx::Result<Option<u64>> {
-        let row = sqlx::query!("SELECT nonce FROM eth_txs ORDER BY id DESC LIMIT 1")
+    /// Returns the next nonce for the specified operator account. `None` corresponds to the main operator account.
+    pub async fn get_next_nonce(
+        &mut self,
+        operator_address: Option<Address>,
+    ) -> sqlx::Result<Option<u64>> {
+        let nonce = if let Some(operator_address) = operator_address {
+            sqlx::query!(
+                "SELECT nonce FROM eth_txs WHERE from_addr = $1 ORDER BY id DESC LIMIT 1",
+                operator_address.as_bytes()
+            )
             .fetch_optional(self.storage.conn())
-            .await?;
-        Ok(row.map(|row| row.nonce as u64 + 1))
+            .await?
+            .map(|row| row.nonce)
+        } else {
+            sqlx::query!(
+                "SELECT nonce FROM eth_txs WHERE from_addr IS NULL ORDER BY id DESC LIMIT 1"
+            )
+            .fetch_optional(self.storage.conn())
+            .await?
+            .map(|row| row.nonce)
+        };
+        Ok(nonce.map(|nonce| nonce as u64 + 1))
+    }
+
+    /// Returns operator accounts that have unconfirmed transactions. `None` corresponds to the main operator account.
+    pub async fn get_operators_with_unconfirmed_txs(
+        &mut self,
+    ) -> sqlx::Result<Vec<Option<Address>>> {
+        let rows = sqlx::query!(
+            "SELECT DISTINCT from_addr FROM eth_txs \
+             WHERE confirmed_eth_tx_history_id IS NULL AND has_failed = FALSE"
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| row.from_addr.as_deref().map(Address::from_slice))
+            .collect())
     }
 
     pub async fn mark_failed_transaction(&mut self, eth_tx_id: u32) -> sqlx::Result<()> {
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, ops};
 
 use sqlx::types::chrono::Utc;
 use zksync_types::{
@@ -7,7 +7,9 @@ use zksync_types::{
     MiniblockNumber, VmEvent, H256,
 };
 
-use crate::{models::storage_event::StorageL2ToL1Log, SqlxError, StorageProcessor};
+use crate::{
+    instrument::InstrumentExt, models::storage_event::StorageL2ToL1Log, SqlxError, StorageProcessor,
+};
 
 /// Wrapper around an optional event topic allowing to hex-format it for `COPY` instructions.
 #[derive(Debug)]
@@ -101,6 +103,24 @@ impl EventsDal<'_, '_> {
         .unwrap();
     }
 
+    /// Removes events for the specified range of miniblocks. Returns the number of removed events.
+    pub async fn prune_events(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            "DELETE FROM events WHERE miniblock_number BETWEEN $1 AND $2",
+            miniblocks.start().0 as i64,
+            miniblocks.end().0 as i64
+        )
+        .instrument("prune_events")
+        .with_arg("miniblocks", &miniblocks)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     /// Saves user L2-to-L1 logs from a miniblock. Logs must be ordered by transaction location
     /// and within each transaction.
     pub async fn save_user_l2_to_l1_logs(
@@ -174,6 +194,24 @@ impl EventsDal<'_, '_> {
         .unwrap();
     }
 
+    /// Removes L2-to-L1 logs for the specified range of miniblocks. Returns the number of removed logs.
+    pub async fn prune_l2_to_l1_logs(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            "DELETE FROM l2_to_l1_logs WHERE miniblock_number BETWEEN $1 AND $2",
+            miniblocks.start().0 as i64,
+            miniblocks.end().0 as i64
+        )
+        .instrument("prune_l2_to_l1_logs")
+        .with_arg("miniblocks", &miniblocks)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     pub(crate) async fn l2_to_l1_logs(
         &mut self,
         tx_hash: H256,
@@ -8,6 +8,7 @@ use zksync_types::{
 };
 
 use crate::{
+    job_notifications::{notify_queued_jobs, JobNotificationChannel},
     time_utils::{duration_to_naive_time, pg_interval_from_duration},
     StorageProcessor,
 };
@@ -50,6 +51,7 @@ impl FriProofCompressorDal<'_, '_> {
             .fetch_optional(self.storage.conn())
             .await
             .unwrap();
+        notify_queued_jobs(self.storage, JobNotificationChannel::ProofCompressionJobs).await;
     }
 
     pub async fn skip_proof_compression_job(&mut self, block_number: L1BatchNumber) {
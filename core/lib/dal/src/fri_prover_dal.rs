@@ -9,6 +9,7 @@ use zksync_types::{
 
 use crate::{
     instrument::InstrumentExt,
+    job_notifications::{notify_queued_jobs, JobNotificationChannel},
     metrics::MethodLatency,
     time_utils::{duration_to_naive_time, pg_interval_from_duration},
     StorageProcessor,
@@ -180,6 +181,39 @@ impl FriProverDal<'_, '_> {
         Ok(attempts)
     }
 
+    /// Returns IDs and circuit blob URLs of prover jobs for L1 batches up to and including
+    /// `last_l1_batch_number` whose blobs are not yet removed from the object store.
+    pub async fn get_circuit_blob_urls_to_be_cleaned(
+        &mut self,
+        last_l1_batch_number: L1BatchNumber,
+        limit: u32,
+    ) -> sqlx::Result<Vec<(i64, String)>> {
+        let rows = sqlx::query!(
+            "SELECT id, circuit_blob_url FROM prover_jobs_fri \
+            WHERE l1_batch_number <= $1 AND is_blob_cleaned IS NOT TRUE \
+            ORDER BY id LIMIT $2",
+            last_l1_batch_number.0 as i64,
+            limit as i64,
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.id, row.circuit_blob_url))
+            .collect())
+    }
+
+    pub async fn mark_circuit_blobs_as_cleaned(&mut self, ids: &[i64]) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE prover_jobs_fri SET is_blob_cleaned = TRUE WHERE id = ANY($1)",
+            ids,
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     pub async fn save_proof(
         &mut self,
         id: u32,
@@ -281,6 +315,7 @@ impl FriProverDal<'_, '_> {
             .execute(self.storage.conn())
             .await
             .unwrap();
+        notify_queued_jobs(self.storage, JobNotificationChannel::ProverJobs).await;
     }
 
     pub async fn get_prover_jobs_stats(&mut self) -> HashMap<(u8, u8), JobCountStatistics> {
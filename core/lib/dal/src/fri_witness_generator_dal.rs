@@ -11,6 +11,7 @@ use zksync_types::{
 };
 
 use crate::{
+    job_notifications::{notify_queued_jobs, JobNotificationChannel},
     metrics::MethodLatency,
     time_utils::{duration_to_naive_time, pg_interval_from_duration},
     StorageProcessor,
@@ -53,6 +54,7 @@ impl FriWitnessGeneratorDal<'_, '_> {
                 .fetch_optional(self.storage.conn())
                 .await
                 .unwrap();
+        notify_queued_jobs(self.storage, JobNotificationChannel::BasicWitnessInputs).await;
     }
 
     pub async fn get_next_basic_circuit_witness_job(
@@ -107,6 +109,50 @@ impl FriWitnessGeneratorDal<'_, '_> {
         Ok(attempts)
     }
 
+    /// Returns L1 batch numbers and Merkle tree paths blob URLs of witness inputs for L1 batches
+    /// up to and including `last_l1_batch_number` whose blobs are not yet removed from the object store.
+    pub async fn get_witness_input_blob_urls_to_be_cleaned(
+        &mut self,
+        last_l1_batch_number: L1BatchNumber,
+        limit: u32,
+    ) -> sqlx::Result<Vec<(L1BatchNumber, Option<String>)>> {
+        let rows = sqlx::query!(
+            "SELECT l1_batch_number, merkle_tree_paths_blob_url FROM witness_inputs_fri \
+            WHERE l1_batch_number <= $1 AND is_blob_cleaned IS NOT TRUE \
+            ORDER BY l1_batch_number LIMIT $2",
+            last_l1_batch_number.0 as i64,
+            limit as i64,
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let l1_batch_number = L1BatchNumber(row.l1_batch_number as u32);
+                (l1_batch_number, row.merkle_tree_paths_blob_url)
+            })
+            .collect())
+    }
+
+    pub async fn mark_witness_input_blobs_as_cleaned(
+        &mut self,
+        l1_batch_numbers: &[L1BatchNumber],
+    ) -> sqlx::Result<()> {
+        let l1_batch_numbers: Vec<_> = l1_batch_numbers
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
+        sqlx::query!(
+            "UPDATE witness_inputs_fri SET is_blob_cleaned = TRUE \
+            WHERE l1_batch_number = ANY($1)",
+            &l1_batch_numbers,
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     pub async fn mark_witness_job(
         &mut self,
         status: FriWitnessJobStatus,
@@ -386,7 +432,7 @@ impl FriWitnessGeneratorDal<'_, '_> {
     }
 
     pub async fn move_leaf_aggregation_jobs_from_waiting_to_queued(&mut self) -> Vec<(i64, u8)> {
-        sqlx::query!(
+        let queued_jobs: Vec<_> = sqlx::query!(
                 r#"
                 UPDATE leaf_aggregation_witness_jobs_fri
                 SET status='queued'
@@ -409,7 +455,11 @@ impl FriWitnessGeneratorDal<'_, '_> {
         .unwrap()
         .into_iter()
         .map(|row| (row.l1_batch_number, row.circuit_id as u8))
-        .collect()
+        .collect();
+        if !queued_jobs.is_empty() {
+            notify_queued_jobs(self.storage, JobNotificationChannel::LeafAggregationJobs).await;
+        }
+        queued_jobs
     }
 
     pub async fn update_node_aggregation_jobs_url(
@@ -565,7 +615,7 @@ impl FriWitnessGeneratorDal<'_, '_> {
     }
 
     pub async fn move_depth_zero_node_aggregation_jobs(&mut self) -> Vec<(i64, u8, u16)> {
-        sqlx::query!(
+        let queued_jobs: Vec<_> = sqlx::query!(
                 r#"
                 UPDATE node_aggregation_witness_jobs_fri
                 SET status='queued'
@@ -590,11 +640,15 @@ impl FriWitnessGeneratorDal<'_, '_> {
         .unwrap()
         .into_iter()
         .map(|row| (row.l1_batch_number, row.circuit_id as u8, row.depth as u16))
-        .collect()
+        .collect();
+        if !queued_jobs.is_empty() {
+            notify_queued_jobs(self.storage, JobNotificationChannel::NodeAggregationJobs).await;
+        }
+        queued_jobs
     }
 
     pub async fn move_depth_non_zero_node_aggregation_jobs(&mut self) -> Vec<(i64, u8, u16)> {
-        sqlx::query!(
+        let queued_jobs: Vec<_> = sqlx::query!(
                 r#"
                 UPDATE node_aggregation_witness_jobs_fri
                 SET status='queued'
@@ -618,7 +672,11 @@ impl FriWitnessGeneratorDal<'_, '_> {
         .unwrap()
         .into_iter()
         .map(|row| (row.l1_batch_number, row.circuit_id as u8, row.depth as u16))
-        .collect()
+        .collect();
+        if !queued_jobs.is_empty() {
+            notify_queued_jobs(self.storage, JobNotificationChannel::NodeAggregationJobs).await;
+        }
+        queued_jobs
     }
 
     pub async fn requeue_stuck_leaf_aggregations_jobs(
@@ -685,6 +743,7 @@ impl FriWitnessGeneratorDal<'_, '_> {
         .execute(self.storage.conn())
         .await
         .unwrap();
+        notify_queued_jobs(self.storage, JobNotificationChannel::SchedulerJobs).await;
     }
 
     pub async fn requeue_stuck_scheduler_jobs(
@@ -0,0 +1,172 @@
+//! Postgres `LISTEN` / `NOTIFY`-based notifications about queued prover pipeline jobs.
+//!
+//! DAL methods queueing witness generator, prover or proof compressor jobs emit a notification
+//! on the corresponding channel. Postgres delivers notifications when the enclosing transaction is committed
+//! (or immediately if there's no transaction), so listeners never observe jobs that are not yet visible.
+//! Job processors can subscribe to notifications using [`JobNotificationsListener`] to pick up new jobs
+//! without waiting for the next polling iteration.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use sqlx::postgres::PgListener;
+use tokio::sync::{watch, Notify};
+
+use crate::{ConnectionPool, StorageProcessor};
+
+/// Delay before receiving notifications again after a listener error. The listener reconnects
+/// to Postgres automatically on the next receive attempt.
+const LISTENER_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Postgres channel used to notify about queued jobs of a certain type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JobNotificationChannel {
+    /// Basic circuits witness generator jobs (the `witness_inputs_fri` table).
+    BasicWitnessInputs,
+    /// Leaf aggregation witness generator jobs.
+    LeafAggregationJobs,
+    /// Node aggregation witness generator jobs.
+    NodeAggregationJobs,
+    /// Scheduler witness generator jobs.
+    SchedulerJobs,
+    /// FRI prover jobs.
+    ProverJobs,
+    /// Proof compression jobs.
+    ProofCompressionJobs,
+}
+
+impl JobNotificationChannel {
+    /// Returns the name of the Postgres channel.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::BasicWitnessInputs => "witness_inputs_fri_queued",
+            Self::LeafAggregationJobs => "leaf_aggregation_witness_jobs_fri_queued",
+            Self::NodeAggregationJobs => "node_aggregation_witness_jobs_fri_queued",
+            Self::SchedulerJobs => "scheduler_witness_jobs_fri_queued",
+            Self::ProverJobs => "prover_jobs_fri_queued",
+            Self::ProofCompressionJobs => "proof_compression_jobs_fri_queued",
+        }
+    }
+}
+
+/// Notifies listeners of the specified channel that new jobs were queued.
+pub(crate) async fn notify_queued_jobs(
+    storage: &mut StorageProcessor<'_>,
+    channel: JobNotificationChannel,
+) {
+    sqlx::query("SELECT pg_notify($1, '')")
+        .bind(channel.name())
+        .execute(storage.conn())
+        .await
+        .unwrap();
+}
+
+/// Listens to job notifications on a dedicated Postgres connection and wakes up the job processor
+/// via a [`Notify`] handle.
+///
+/// Notifications are coalesced: if several jobs are queued while the processor is busy, it will be woken up
+/// once and is expected to poll for jobs until there are none left.
+pub struct JobNotificationsListener {
+    listener: PgListener,
+    notify: Arc<Notify>,
+}
+
+impl fmt::Debug for JobNotificationsListener {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("JobNotificationsListener")
+            .finish_non_exhaustive()
+    }
+}
+
+impl JobNotificationsListener {
+    /// Creates a listener for the specified channels. The listener uses a connection not managed by the `pool`.
+    pub async fn new(
+        pool: &ConnectionPool,
+        channels: &[JobNotificationChannel],
+    ) -> anyhow::Result<Self> {
+        let mut listener = PgListener::connect_with(&pool.inner)
+            .await
+            .context("failed connecting Postgres listener")?;
+        let channel_names = channels.iter().map(|channel| channel.name());
+        listener
+            .listen_all(channel_names)
+            .await
+            .with_context(|| format!("failed listening to channels {channels:?}"))?;
+        Ok(Self {
+            listener,
+            notify: Arc::default(),
+        })
+    }
+
+    /// Returns a handle notified each time jobs are queued on one of the listened channels.
+    pub fn notifier(&self) -> Arc<Notify> {
+        self.notify.clone()
+    }
+
+    /// Runs the listener until a stop signal is received.
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            tokio::select! {
+                notification = self.listener.recv() => match notification {
+                    Ok(notification) => {
+                        tracing::trace!(
+                            "Received notification on channel `{}`",
+                            notification.channel()
+                        );
+                        self.notify.notify_one();
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            "Error receiving job notifications: {err}; retrying in {LISTENER_RETRY_DELAY:?}"
+                        );
+                        // Notifications sent while the connection is broken are lost, so we wake up
+                        // the processor to poll for jobs.
+                        self.notify.notify_one();
+                        tokio::time::sleep(LISTENER_RETRY_DELAY).await;
+                    }
+                },
+                _ = stop_receiver.changed() => break,
+            }
+        }
+        tracing::info!("Stop signal received, job notifications listener is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::L1BatchNumber;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn listener_is_notified_about_queued_jobs() {
+        let pool = ConnectionPool::test_pool().await;
+        let listener =
+            JobNotificationsListener::new(&pool, &[JobNotificationChannel::ProofCompressionJobs])
+                .await
+                .unwrap();
+        let notifier = listener.notifier();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let listener_task = tokio::spawn(listener.run(stop_receiver));
+
+        let mut storage = pool.access_storage().await.unwrap();
+        let mut transaction = storage.start_transaction().await.unwrap();
+        transaction
+            .fri_proof_compressor_dal()
+            .insert_proof_compression_job(L1BatchNumber(1), "proof")
+            .await;
+        // Notifications must not be delivered before the transaction is committed.
+        let notified = tokio::time::timeout(Duration::from_millis(100), notifier.notified()).await;
+        assert!(notified.is_err());
+
+        transaction.commit().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), notifier.notified())
+            .await
+            .expect("no notification received");
+
+        stop_sender.send_replace(true);
+        listener_task.await.unwrap().unwrap();
+    }
+}
@@ -22,6 +22,17 @@ enum ProofGenerationJobStatus {
     Skipped,
 }
 
+/// Action performed by a prover via the proof data handler API.
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum ProverApiAction {
+    #[strum(serialize = "fetched_proof_generation_data")]
+    FetchedProofGenerationData,
+    #[strum(serialize = "submitted_proof")]
+    SubmittedProof,
+    #[strum(serialize = "skipped_proof_generation")]
+    SkippedProofGeneration,
+}
+
 impl ProofGenerationDal<'_, '_> {
     pub async fn get_next_block_to_be_proven(
         &mut self,
@@ -140,4 +151,24 @@ impl ProofGenerationDal<'_, '_> {
 
         result
     }
+
+    /// Records an action performed by a prover in the audit log. `prover_id` is `None` if the prover
+    /// is not authenticated.
+    pub async fn insert_prover_api_audit_record(
+        &mut self,
+        prover_id: Option<&str>,
+        action: ProverApiAction,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            "INSERT INTO prover_api_audit_log (prover_id, action, l1_batch_number, created_at) \
+             VALUES ($1, $2, $3, now())",
+            prover_id,
+            action.to_string(),
+            l1_batch_number.0 as i64,
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
 }
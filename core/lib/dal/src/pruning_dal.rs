@@ -0,0 +1,114 @@
+use zksync_types::{L1BatchNumber, MiniblockNumber};
+
+use crate::{instrument::InstrumentExt, StorageProcessor};
+
+#[derive(Debug)]
+pub struct PruningDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+/// Type of a pruning operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruneType {
+    /// Data is marked as pruned, but is not yet removed from the storage. Soft-pruned data
+    /// is no longer served by the API.
+    Soft,
+    /// Data is removed from the storage.
+    Hard,
+}
+
+impl PruneType {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Soft => "Soft",
+            Self::Hard => "Hard",
+        }
+    }
+}
+
+/// Information about pruned data in the storage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruningInfo {
+    pub last_soft_pruned_l1_batch: Option<L1BatchNumber>,
+    pub last_soft_pruned_miniblock: Option<MiniblockNumber>,
+    pub last_hard_pruned_l1_batch: Option<L1BatchNumber>,
+    pub last_hard_pruned_miniblock: Option<MiniblockNumber>,
+}
+
+impl PruningInfo {
+    /// Returns the first L1 batch which data is not (soft-)pruned.
+    pub fn first_available_l1_batch(&self) -> L1BatchNumber {
+        self.last_soft_pruned_l1_batch
+            .map_or(L1BatchNumber(0), |number| number + 1)
+    }
+
+    /// Returns the first miniblock which data is not (soft-)pruned.
+    pub fn first_available_miniblock(&self) -> MiniblockNumber {
+        self.last_soft_pruned_miniblock
+            .map_or(MiniblockNumber(0), |number| number + 1)
+    }
+}
+
+impl PruningDal<'_, '_> {
+    pub async fn get_pruning_info(&mut self) -> sqlx::Result<PruningInfo> {
+        let row = sqlx::query!(
+            "SELECT \
+                soft.pruned_l1_batch AS last_soft_pruned_l1_batch, \
+                soft.pruned_miniblock AS last_soft_pruned_miniblock, \
+                hard.pruned_l1_batch AS last_hard_pruned_l1_batch, \
+                hard.pruned_miniblock AS last_hard_pruned_miniblock \
+            FROM (SELECT 1) AS dummy \
+            LEFT JOIN ( \
+                SELECT pruned_l1_batch, pruned_miniblock FROM pruning_log \
+                WHERE prune_type = 'Soft' \
+                ORDER BY pruned_l1_batch DESC LIMIT 1 \
+            ) AS soft ON TRUE \
+            LEFT JOIN ( \
+                SELECT pruned_l1_batch, pruned_miniblock FROM pruning_log \
+                WHERE prune_type = 'Hard' \
+                ORDER BY pruned_l1_batch DESC LIMIT 1 \
+            ) AS hard ON TRUE"
+        )
+        .instrument("get_pruning_info")
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(PruningInfo {
+            last_soft_pruned_l1_batch: row
+                .last_soft_pruned_l1_batch
+                .map(|number| L1BatchNumber(number as u32)),
+            last_soft_pruned_miniblock: row
+                .last_soft_pruned_miniblock
+                .map(|number| MiniblockNumber(number as u32)),
+            last_hard_pruned_l1_batch: row
+                .last_hard_pruned_l1_batch
+                .map(|number| L1BatchNumber(number as u32)),
+            last_hard_pruned_miniblock: row
+                .last_hard_pruned_miniblock
+                .map(|number| MiniblockNumber(number as u32)),
+        })
+    }
+
+    /// Records that data up to and including the specified L1 batch and miniblock is pruned.
+    pub async fn insert_pruning_log(
+        &mut self,
+        last_pruned_l1_batch: L1BatchNumber,
+        last_pruned_miniblock: MiniblockNumber,
+        prune_type: PruneType,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO pruning_log \
+                (pruned_l1_batch, pruned_miniblock, prune_type, created_at, updated_at) \
+            VALUES ($1, $2, $3, NOW(), NOW())",
+            last_pruned_l1_batch.0 as i64,
+            last_pruned_miniblock.0 as i64,
+            prune_type.as_str()
+        )
+        .instrument("insert_pruning_log")
+        .with_arg("last_pruned_l1_batch", &last_pruned_l1_batch)
+        .with_arg("prune_type", &prune_type)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops;
+
 use zksync_types::{
     snapshots::{SnapshotFactoryDependency, SnapshotStorageLog},
     AccountTreeId, Address, L1BatchNumber, MiniblockNumber, StorageKey, H256,
@@ -101,4 +103,100 @@ impl SnapshotsCreatorDal<'_, '_> {
             })
             .collect())
     }
+
+    /// Returns the number of distinct storage keys modified in the specified miniblock range.
+    pub async fn get_changed_storage_logs_keys_count(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<u64> {
+        let count = sqlx::query!(
+            "SELECT COUNT(DISTINCT hashed_key) AS \"count!\" FROM storage_logs \
+            WHERE miniblock_number >= $1 AND miniblock_number <= $2",
+            miniblocks.start().0 as i64,
+            miniblocks.end().0 as i64
+        )
+        .instrument("get_changed_storage_logs_keys_count")
+        .with_arg("miniblocks", &miniblocks)
+        .report_latency()
+        .fetch_one(self.storage.conn())
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
+    /// Returns the latest values of storage keys modified in the specified miniblock range. Used to create
+    /// delta snapshots; unlike [`Self::get_storage_logs_chunk()`], only keys modified after the parent snapshot
+    /// are returned.
+    pub async fn get_changed_storage_logs_chunk(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+        hashed_keys_range: ops::RangeInclusive<H256>,
+    ) -> sqlx::Result<Vec<SnapshotStorageLog>> {
+        let storage_logs = sqlx::query!(
+            r#"
+            SELECT storage_logs.key as "key!",
+                   storage_logs.value as "value!",
+                   storage_logs.address as "address!",
+                   initial_writes.l1_batch_number as "l1_batch_number!",
+                   initial_writes.index as "index!"
+            FROM (SELECT hashed_key,
+                         max(ARRAY [miniblock_number, operation_number]::int[]) AS op
+                  FROM storage_logs
+                  WHERE miniblock_number >= $1 AND miniblock_number <= $2
+                    AND hashed_key >= $3 AND hashed_key <= $4
+                  GROUP BY hashed_key
+                  ORDER BY hashed_key) AS keys
+                     INNER JOIN storage_logs ON keys.hashed_key = storage_logs.hashed_key
+                AND storage_logs.miniblock_number = keys.op[1]
+                AND storage_logs.operation_number = keys.op[2]
+                     INNER JOIN initial_writes ON keys.hashed_key = initial_writes.hashed_key;
+            "#,
+            miniblocks.start().0 as i64,
+            miniblocks.end().0 as i64,
+            hashed_keys_range.start().as_bytes(),
+            hashed_keys_range.end().as_bytes(),
+        )
+        .instrument("get_changed_storage_logs_chunk")
+        .with_arg("miniblocks", &miniblocks)
+        .with_arg("min_hashed_key", &hashed_keys_range.start())
+        .with_arg("max_hashed_key", &hashed_keys_range.end())
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?
+        .into_iter()
+        .map(|row| SnapshotStorageLog {
+            key: StorageKey::new(
+                AccountTreeId::new(Address::from_slice(&row.address)),
+                H256::from_slice(&row.key),
+            ),
+            value: H256::from_slice(&row.value),
+            l1_batch_number_of_initial_write: L1BatchNumber(row.l1_batch_number as u32),
+            enumeration_index: row.index as u64,
+        })
+        .collect();
+        Ok(storage_logs)
+    }
+
+    /// Returns factory deps added in the specified miniblock range.
+    pub async fn get_factory_deps_for_miniblocks(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<Vec<SnapshotFactoryDependency>> {
+        let rows = sqlx::query!(
+            "SELECT bytecode FROM factory_deps WHERE miniblock_number >= $1 AND miniblock_number <= $2",
+            miniblocks.start().0 as i64,
+            miniblocks.end().0 as i64,
+        )
+        .instrument("get_factory_deps_for_miniblocks")
+        .with_arg("miniblocks", &miniblocks)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| SnapshotFactoryDependency {
+                bytecode: row.bytecode,
+            })
+            .collect())
+    }
 }
@@ -11,18 +11,23 @@ pub struct SnapshotsDal<'a, 'c> {
 }
 
 impl SnapshotsDal<'_, '_> {
+    /// Adds a snapshot. If `parent_l1_batch_number` is specified, the snapshot is a delta one
+    /// relative to the snapshot for the parent L1 batch.
     pub async fn add_snapshot(
         &mut self,
         l1_batch_number: L1BatchNumber,
         storage_logs_filepaths: &[String],
         factory_deps_filepaths: &str,
+        parent_l1_batch_number: Option<L1BatchNumber>,
     ) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "INSERT INTO snapshots (l1_batch_number, storage_logs_filepaths, factory_deps_filepath, created_at, updated_at) \
-             VALUES ($1, $2, $3, NOW(), NOW())",
+            "INSERT INTO snapshots (l1_batch_number, storage_logs_filepaths, factory_deps_filepath, \
+                parent_l1_batch_number, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, NOW(), NOW())",
             l1_batch_number.0 as i32,
             storage_logs_filepaths,
             factory_deps_filepaths,
+            parent_l1_batch_number.map(|number| number.0 as i32),
         )
         .instrument("add_snapshot")
         .report_latency()
@@ -52,7 +57,8 @@ impl SnapshotsDal<'_, '_> {
         l1_batch_number: L1BatchNumber,
     ) -> Result<Option<SnapshotMetadata>, sqlx::Error> {
         let record: Option<SnapshotMetadata> = sqlx::query!(
-            "SELECT l1_batch_number, factory_deps_filepath, storage_logs_filepaths FROM snapshots WHERE l1_batch_number = $1",
+            "SELECT l1_batch_number, factory_deps_filepath, storage_logs_filepaths, parent_l1_batch_number \
+             FROM snapshots WHERE l1_batch_number = $1",
             l1_batch_number.0 as i32
         )
         .instrument("get_snapshot_metadata")
@@ -63,6 +69,7 @@ impl SnapshotsDal<'_, '_> {
             l1_batch_number: L1BatchNumber(r.l1_batch_number as u32),
             factory_deps_filepath: r.factory_deps_filepath,
             storage_logs_filepaths: r.storage_logs_filepaths,
+            parent_l1_batch_number: r.parent_l1_batch_number.map(|number| L1BatchNumber(number as u32)),
         });
         Ok(record)
     }
@@ -80,7 +87,7 @@ mod tests {
         let mut conn = pool.access_storage().await.unwrap();
         let mut dal = conn.snapshots_dal();
         let l1_batch_number = L1BatchNumber(100);
-        dal.add_snapshot(l1_batch_number, &[], "gs:///bucket/factory_deps.bin")
+        dal.add_snapshot(l1_batch_number, &[], "gs:///bucket/factory_deps.bin", None)
             .await
             .expect("Failed to add snapshot");
 
@@ -103,6 +110,50 @@ mod tests {
             snapshot_metadata.l1_batch_number,
             l1_batch_number as L1BatchNumber
         );
+        assert_eq!(snapshot_metadata.parent_l1_batch_number, None);
+    }
+
+    #[tokio::test]
+    async fn adding_delta_snapshot() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        dal.add_snapshot(
+            L1BatchNumber(100),
+            &[],
+            "gs:///bucket/factory_deps.bin",
+            None,
+        )
+        .await
+        .expect("Failed to add snapshot");
+        dal.add_snapshot(
+            L1BatchNumber(110),
+            &[],
+            "gs:///bucket/factory_deps_delta.bin",
+            Some(L1BatchNumber(100)),
+        )
+        .await
+        .expect("Failed to add delta snapshot");
+
+        let snapshot_metadata = dal
+            .get_snapshot_metadata(L1BatchNumber(110))
+            .await
+            .expect("Failed to retrieve snapshot")
+            .unwrap();
+        assert_eq!(
+            snapshot_metadata.parent_l1_batch_number,
+            Some(L1BatchNumber(100))
+        );
+
+        // A parent snapshot must exist.
+        dal.add_snapshot(
+            L1BatchNumber(120),
+            &[],
+            "gs:///bucket/factory_deps_delta.bin",
+            Some(L1BatchNumber(115)),
+        )
+        .await
+        .unwrap_err();
     }
 
     #[tokio::test]
@@ -118,6 +169,7 @@ mod tests {
                 "gs:///bucket/test_file2.bin".to_string(),
             ],
             "gs:///bucket/factory_deps.bin",
+            None,
         )
         .await
         .expect("Failed to add snapshot");
@@ -107,6 +107,21 @@ impl StorageDal<'_, '_> {
         .collect()
     }
 
+    /// Returns all factory deps as `(bytecode_hash, bytecode)` tuples. Used to initialize the VM state cache
+    /// for a node recovered from a snapshot.
+    pub async fn get_all_factory_deps(&mut self) -> sqlx::Result<Vec<(H256, Vec<u8>)>> {
+        let rows = sqlx::query!("SELECT bytecode, bytecode_hash FROM factory_deps")
+            .instrument("get_all_factory_deps")
+            .report_latency()
+            .fetch_all(self.storage.conn())
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
+            .collect())
+    }
+
     /// Returns bytecode hashes for factory deps from miniblocks with number strictly greater
     /// than `block_number`.
     pub async fn get_factory_deps_for_revert(
@@ -2,8 +2,8 @@ use std::{collections::HashMap, ops, time::Instant};
 
 use sqlx::{types::chrono::Utc, Row};
 use zksync_types::{
-    get_code_key, AccountTreeId, Address, L1BatchNumber, MiniblockNumber, StorageKey, StorageLog,
-    FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
+    get_code_key, snapshots::SnapshotStorageLog, AccountTreeId, Address, L1BatchNumber,
+    MiniblockNumber, StorageKey, StorageLog, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
 };
 
 use crate::{instrument::InstrumentExt, models::storage_log::StorageTreeEntry, StorageProcessor};
@@ -87,6 +87,69 @@ impl StorageLogsDal<'_, '_> {
             .await;
     }
 
+    /// Inserts storage logs from a snapshot storage logs chunk. All logs are attributed to the snapshot miniblock
+    /// and have zero transaction hash.
+    pub async fn insert_storage_logs_from_snapshot(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+        snapshot_storage_logs: &[SnapshotStorageLog],
+    ) -> sqlx::Result<()> {
+        let mut copy = self
+            .storage
+            .conn()
+            .copy_in_raw(
+                "COPY storage_logs(
+                    hashed_key, address, key, value, operation_number, tx_hash, miniblock_number,
+                    created_at, updated_at
+                )
+                FROM STDIN WITH (DELIMITER '|')",
+            )
+            .await?;
+
+        let mut buffer = String::new();
+        let now = Utc::now().naive_utc().to_string();
+        for log in snapshot_storage_logs {
+            write_str!(
+                &mut buffer,
+                r"\\x{hashed_key:x}|\\x{address:x}|\\x{key:x}|\\x{value:x}|",
+                hashed_key = log.key.hashed_key(),
+                address = log.key.address(),
+                key = log.key.key(),
+                value = log.value
+            );
+            // Hashed keys are unique within a snapshot, so operation numbers don't need to be unique.
+            writeln_str!(
+                &mut buffer,
+                r"0|\\x{tx_hash:x}|{miniblock_number}|{now}|{now}",
+                tx_hash = H256::zero()
+            );
+        }
+        copy.send(buffer.as_bytes()).await?;
+        copy.finish().await?;
+        Ok(())
+    }
+
+    /// Removes storage logs for the specified hashed keys at the snapshot miniblock. Used when applying
+    /// a delta snapshot so that each key has a single storage log at the snapshot miniblock.
+    pub async fn remove_snapshot_storage_logs(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+        hashed_keys: &[H256],
+    ) -> sqlx::Result<()> {
+        let hashed_keys: Vec<_> = hashed_keys.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            "DELETE FROM storage_logs WHERE miniblock_number = $1 AND hashed_key = ANY($2)",
+            miniblock_number.0 as i64,
+            &hashed_keys as &[&[u8]]
+        )
+        .instrument("remove_snapshot_storage_logs")
+        .with_arg("miniblock_number", &miniblock_number)
+        .with_arg("hashed_keys.len", &hashed_keys.len())
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     /// Rolls back storage to the specified point in time.
     pub async fn rollback_storage(&mut self, last_miniblock_to_keep: MiniblockNumber) {
         let stage_start = Instant::now();
@@ -159,6 +222,59 @@ impl StorageLogsDal<'_, '_> {
         );
     }
 
+    /// Removes storage logs for the specified range of miniblocks that are not needed to restore
+    /// the latest storage state. A log is removed if the same storage slot is overwritten by a later log
+    /// in the range; logs for the earlier miniblocks that are overwritten by logs in the range are removed as well.
+    /// Returns the number of removed logs.
+    pub async fn prune_storage_logs(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<u64> {
+        let start = miniblocks.start().0 as i64;
+        let end = miniblocks.end().0 as i64;
+        let overwritten_in_range = sqlx::query!(
+            "DELETE FROM storage_logs \
+            USING ( \
+                SELECT hashed_key, MAX(ARRAY[miniblock_number, operation_number]::int8[]) AS op \
+                FROM storage_logs \
+                WHERE miniblock_number BETWEEN $1 AND $2 \
+                GROUP BY hashed_key \
+            ) AS last_storage_logs \
+            WHERE storage_logs.miniblock_number BETWEEN $1 AND $2 \
+                AND last_storage_logs.hashed_key = storage_logs.hashed_key \
+                AND ( \
+                    storage_logs.miniblock_number != last_storage_logs.op[1] \
+                    OR storage_logs.operation_number != last_storage_logs.op[2] \
+                )",
+            start,
+            end
+        )
+        .instrument("prune_storage_logs#in_range")
+        .with_arg("miniblocks", &miniblocks)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+
+        let overwritten_before_range = sqlx::query!(
+            "DELETE FROM storage_logs \
+            USING ( \
+                SELECT DISTINCT hashed_key FROM storage_logs \
+                WHERE miniblock_number BETWEEN $1 AND $2 \
+            ) AS keys_in_range \
+            WHERE storage_logs.miniblock_number < $1 \
+                AND storage_logs.hashed_key = keys_in_range.hashed_key",
+            start,
+            end
+        )
+        .instrument("prune_storage_logs#before_range")
+        .with_arg("miniblocks", &miniblocks)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+
+        Ok(overwritten_in_range.rows_affected() + overwritten_before_range.rows_affected())
+    }
+
     /// Returns all storage keys that were modified after the specified miniblock.
     async fn modified_keys_since_miniblock(
         &mut self,
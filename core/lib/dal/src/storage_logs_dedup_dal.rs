@@ -1,10 +1,13 @@
 use std::collections::HashSet;
 
 use sqlx::types::chrono::Utc;
-use zksync_types::{AccountTreeId, Address, L1BatchNumber, LogQuery, StorageKey, H256};
+use zksync_types::{
+    snapshots::SnapshotStorageLog, AccountTreeId, Address, L1BatchNumber, LogQuery, StorageKey,
+    H256,
+};
 use zksync_utils::u256_to_h256;
 
-use crate::StorageProcessor;
+use crate::{instrument::InstrumentExt, StorageProcessor};
 
 #[derive(Debug)]
 pub struct StorageLogsDedupDal<'a, 'c> {
@@ -72,6 +75,43 @@ impl StorageLogsDedupDal<'_, '_> {
         .unwrap();
     }
 
+    /// Inserts initial writes for storage logs from a snapshot storage logs chunk. Unlike [`Self::insert_initial_writes()`],
+    /// enumeration indices and L1 batches of initial writes are taken from the snapshot. Initial writes for keys
+    /// already present in the table (e.g., from a parent snapshot when applying a delta snapshot) are skipped.
+    pub async fn insert_initial_writes_from_snapshot(
+        &mut self,
+        snapshot_storage_logs: &[SnapshotStorageLog],
+    ) -> sqlx::Result<()> {
+        let (hashed_keys, (indices, l1_batch_numbers)): (Vec<_>, (Vec<_>, Vec<_>)) =
+            snapshot_storage_logs
+                .iter()
+                .map(|log| {
+                    (
+                        log.key.hashed_key().as_bytes().to_vec(),
+                        (
+                            log.enumeration_index as i64,
+                            i64::from(log.l1_batch_number_of_initial_write.0),
+                        ),
+                    )
+                })
+                .unzip();
+
+        sqlx::query!(
+            "INSERT INTO initial_writes (hashed_key, index, l1_batch_number, created_at, updated_at) \
+            SELECT u.hashed_key, u.index, u.l1_batch_number, now(), now() \
+            FROM UNNEST($1::bytea[], $2::bigint[], $3::bigint[]) AS u(hashed_key, index, l1_batch_number) \
+            ON CONFLICT (hashed_key) DO NOTHING",
+            &hashed_keys,
+            &indices,
+            &l1_batch_numbers,
+        )
+        .instrument("insert_initial_writes_from_snapshot")
+        .with_arg("snapshot_storage_logs.len", &snapshot_storage_logs.len())
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     pub async fn get_protective_reads_for_l1_batch(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -3,6 +3,10 @@ use std::time::Duration;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_types::{
     block::{L1BatchHeader, MiniblockHasher, MiniblockHeader},
+    contract_verification_api::{
+        BytecodeMatch, CompilationArtifacts, CompilerVersions, SourceCodeData,
+        VerificationIncomingRequest, VerificationInfo, VerificationRequest,
+    },
     fee::{Fee, TransactionExecutionMetrics},
     helpers::unix_timestamp_ms,
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
@@ -20,6 +24,7 @@ use crate::{
     prover_dal::{GetProverJobsParams, ProverDal},
     transactions_dal::{L2TxSubmissionResult, TransactionsDal},
     transactions_web3_dal::TransactionsWeb3Dal,
+    StorageProcessor,
 };
 
 const DEFAULT_GAS_PER_PUBDATA: u32 = 100;
@@ -165,6 +170,46 @@ async fn workflow_with_submit_tx_diff_hashes() {
     assert_eq!(result, L2TxSubmissionResult::Replaced);
 }
 
+#[tokio::test]
+async fn getting_pending_txs_for_account_limits() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let mut transactions_dal = TransactionsDal { storage };
+
+    let tx = mock_l2_transaction();
+    let initiator_address = tx.common_data.initiator_address;
+    let fee = tx.common_data.fee.clone();
+    assert_eq!(
+        transactions_dal
+            .get_pending_l2_txs_count(initiator_address)
+            .await,
+        0
+    );
+    transactions_dal
+        .insert_transaction_l2(tx.clone(), mock_tx_execution_metrics())
+        .await;
+    assert_eq!(
+        transactions_dal
+            .get_pending_l2_txs_count(initiator_address)
+            .await,
+        1
+    );
+
+    // The transaction itself is not replaced.
+    let replaced_fee = transactions_dal
+        .get_replaced_l2_tx_fee(initiator_address, tx.nonce(), tx.hash())
+        .await;
+    assert_eq!(replaced_fee, None);
+    let replaced_fee = transactions_dal
+        .get_replaced_l2_tx_fee(initiator_address, tx.nonce(), H256::random())
+        .await;
+    assert_eq!(replaced_fee, Some(fee));
+    let replaced_fee = transactions_dal
+        .get_replaced_l2_tx_fee(initiator_address, tx.nonce() + 1, H256::random())
+        .await;
+    assert_eq!(replaced_fee, None);
+}
+
 #[tokio::test]
 async fn remove_stuck_txs() {
     let connection_pool = ConnectionPool::test_pool().await;
@@ -397,3 +442,108 @@ async fn test_requeue_prover_jobs() {
         assert!(job.is_some());
     }
 }
+
+pub(crate) fn mock_verification_info(
+    address: Address,
+    bytecode_match: BytecodeMatch,
+) -> VerificationInfo {
+    let req = VerificationIncomingRequest {
+        contract_address: address,
+        source_code_data: SourceCodeData::SolSingleFile("contract Test {}".to_owned()),
+        contract_name: "Test".to_owned(),
+        compiler_versions: CompilerVersions::Solc {
+            compiler_zksolc_version: "v1.3.18".to_owned(),
+            compiler_solc_version: "0.8.23".to_owned(),
+        },
+        optimization_used: true,
+        optimizer_mode: None,
+        constructor_arguments: Default::default(),
+        is_system: false,
+    };
+    VerificationInfo {
+        request: VerificationRequest { id: 1, req },
+        artifacts: CompilationArtifacts {
+            bytecode: vec![0; 32],
+            abi: serde_json::Value::Array(vec![]),
+        },
+        verified_at: Default::default(),
+        bytecode_match,
+        identical_to: None,
+    }
+}
+
+#[tokio::test]
+async fn partially_verified_contracts_can_be_reverified() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let address = Address::repeat_byte(1);
+    assert!(!storage
+        .contract_verification_dal()
+        .is_contract_fully_verified(address)
+        .await
+        .unwrap());
+
+    storage
+        .contract_verification_dal()
+        .insert_verification_info(mock_verification_info(address, BytecodeMatch::Partial))
+        .await
+        .unwrap();
+    assert!(!storage
+        .contract_verification_dal()
+        .is_contract_fully_verified(address)
+        .await
+        .unwrap());
+
+    storage
+        .contract_verification_dal()
+        .insert_verification_info(mock_verification_info(address, BytecodeMatch::Full))
+        .await
+        .unwrap();
+    assert!(storage
+        .contract_verification_dal()
+        .is_contract_fully_verified(address)
+        .await
+        .unwrap());
+}
+
+/// Inserts verification info with the specified contract name and returns the name of the stored contract.
+async fn insert_verification_info_with_name(
+    storage: &mut StorageProcessor<'_>,
+    address: Address,
+    contract_name: &str,
+    bytecode_match: BytecodeMatch,
+) -> String {
+    let mut info = mock_verification_info(address, bytecode_match);
+    info.request.req.contract_name = contract_name.to_owned();
+    let mut dal = storage.contract_verification_dal();
+    dal.insert_verification_info(info).await.unwrap();
+    let info = dal.get_contract_verification_info(address).await.unwrap();
+    info.unwrap().request.req.contract_name
+}
+
+#[tokio::test]
+async fn verification_info_is_overwritten_only_with_full_match() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let address = Address::repeat_byte(1);
+
+    let name =
+        insert_verification_info_with_name(storage, address, "First", BytecodeMatch::Partial).await;
+    assert_eq!(name, "First");
+    // A partial match cannot be overwritten with another partial match.
+    let name =
+        insert_verification_info_with_name(storage, address, "Second", BytecodeMatch::Partial)
+            .await;
+    assert_eq!(name, "First");
+    let name =
+        insert_verification_info_with_name(storage, address, "Third", BytecodeMatch::Full).await;
+    assert_eq!(name, "Third");
+    // A full match cannot be overwritten.
+    let name =
+        insert_verification_info_with_name(storage, address, "Fourth", BytecodeMatch::Partial)
+            .await;
+    assert_eq!(name, "Third");
+    let name =
+        insert_verification_info_with_name(storage, address, "Fifth", BytecodeMatch::Full).await;
+    assert_eq!(name, "Third");
+}
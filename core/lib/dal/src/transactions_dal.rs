@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt, time::Duration};
+use std::{collections::HashMap, fmt, ops, time::Duration};
 
 use anyhow::Context;
 use bigdecimal::BigDecimal;
@@ -6,7 +6,7 @@ use itertools::Itertools;
 use sqlx::{error, types::chrono::NaiveDateTime};
 use zksync_types::{
     block::MiniblockExecutionData,
-    fee::TransactionExecutionMetrics,
+    fee::{Fee, TransactionExecutionMetrics},
     get_nonce_key,
     l1::L1Tx,
     l2::L2Tx,
@@ -16,7 +16,7 @@ use zksync_types::{
     Address, ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber, MiniblockNumber, Nonce,
     PriorityOpId, Transaction, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
-use zksync_utils::{h256_to_u32, u256_to_big_decimal};
+use zksync_utils::{bigdecimal_to_u256, h256_to_u32, u256_to_big_decimal};
 
 use crate::{
     instrument::InstrumentExt,
@@ -774,6 +774,32 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Removes transactions included into the specified range of miniblocks together with their call traces.
+    /// Protocol upgrade transactions are retained since they are referenced by protocol versions.
+    /// Returns the number of removed transactions.
+    pub async fn prune_transactions(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<u64> {
+        // Call traces are removed via the `ON DELETE CASCADE` foreign key.
+        let execution_result = sqlx::query!(
+            "DELETE FROM transactions \
+            WHERE miniblock_number BETWEEN $1 AND $2 \
+                AND hash NOT IN ( \
+                    SELECT upgrade_tx_hash FROM protocol_versions \
+                    WHERE upgrade_tx_hash IS NOT NULL \
+                )",
+            miniblocks.start().0 as i64,
+            miniblocks.end().0 as i64
+        )
+        .instrument("prune_transactions")
+        .with_arg("miniblocks", &miniblocks)
+        .report_latency()
+        .execute(self.storage.conn())
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     pub async fn remove_stuck_txs(&mut self, stuck_tx_timeout: Duration) -> usize {
         {
             let stuck_tx_timeout = pg_interval_from_duration(stuck_tx_timeout);
@@ -791,9 +817,62 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Returns the number of pending (i.e., not executed or rejected) L2 transactions
+    /// with the specified initiator.
+    pub async fn get_pending_l2_txs_count(&mut self, initiator_address: Address) -> usize {
+        let count = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM transactions
+            WHERE initiator_address = $1 AND is_priority = FALSE
+                AND miniblock_number IS NULL AND error IS NULL"#,
+            initiator_address.as_bytes()
+        )
+        .instrument("get_pending_l2_txs_count")
+        .with_arg("initiator_address", &initiator_address)
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap()
+        .count;
+        count as usize
+    }
+
+    /// Returns the fee of a pending L2 transaction with the specified initiator and nonce that would be
+    /// replaced by a transaction with `tx_hash`. Returns `None` if there is no such transaction,
+    /// or if it has the same hash.
+    pub async fn get_replaced_l2_tx_fee(
+        &mut self,
+        initiator_address: Address,
+        nonce: Nonce,
+        tx_hash: H256,
+    ) -> Option<Fee> {
+        let row = sqlx::query!(
+            "SELECT gas_limit, max_fee_per_gas, max_priority_fee_per_gas, gas_per_pubdata_limit \
+            FROM transactions \
+            WHERE initiator_address = $1 AND nonce = $2 AND hash != $3 AND is_priority = FALSE \
+                AND miniblock_number IS NULL AND error IS NULL",
+            initiator_address.as_bytes(),
+            i64::from(nonce.0),
+            tx_hash.as_bytes()
+        )
+        .instrument("get_replaced_l2_tx_fee")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()?;
+
+        let to_u256 = |value: Option<BigDecimal>| value.map(bigdecimal_to_u256).unwrap_or_default();
+        Some(Fee {
+            gas_limit: to_u256(row.gas_limit),
+            max_fee_per_gas: to_u256(row.max_fee_per_gas),
+            max_priority_fee_per_gas: to_u256(row.max_priority_fee_per_gas),
+            gas_per_pubdata_limit: to_u256(row.gas_per_pubdata_limit),
+        })
+    }
+
     /// Fetches new updates for mempool
     /// Returns new transactions and current nonces for related accounts
     /// Latter is only used to bootstrap mempool for given account
+    /// Transactions are always loaded in the order they were received, regardless of the mempool ordering policy.
     pub async fn sync_mempool(
         &mut self,
         stashed_accounts: Vec<Address>,
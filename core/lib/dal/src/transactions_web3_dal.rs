@@ -215,6 +215,32 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(tx)
     }
 
+    /// Returns transactions with the specified hashes. Transactions missing from the storage are skipped;
+    /// the order of the returned transactions is not specified. Callers are responsible for bounding
+    /// the number of `hashes`.
+    pub async fn get_transactions_by_hashes(
+        &mut self,
+        hashes: &[H256],
+        chain_id: L2ChainId,
+    ) -> Result<Vec<api::Transaction>, SqlxError> {
+        let query = format!(
+            "SELECT {}
+            FROM transactions
+            LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            WHERE transactions.hash = ANY($1)",
+            web3_transaction_select_sql()
+        );
+        let hashes: Vec<_> = hashes.iter().map(H256::as_bytes).collect();
+        let rows = sqlx::query(&query)
+            .bind(hashes)
+            .fetch_all(self.storage.conn())
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| extract_web3_transaction(row, chain_id))
+            .collect())
+    }
+
     pub async fn get_transaction_details(
         &mut self,
         hash: H256,
@@ -454,6 +480,30 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn getting_pending_transactions_by_hashes() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        let txs = [mock_l2_transaction(), mock_l2_transaction()];
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(tx.clone(), TransactionExecutionMetrics::default())
+                .await;
+        }
+
+        let hashes = [txs[0].hash(), H256::random(), txs[1].hash()];
+        let web3_txs = conn
+            .transactions_web3_dal()
+            .get_transactions_by_hashes(&hashes, L2ChainId::from(270))
+            .await
+            .unwrap();
+        assert_eq!(web3_txs.len(), 2);
+        for web3_tx in &web3_txs {
+            assert!(web3_tx.hash == hashes[0] || web3_tx.hash == hashes[2]);
+            assert_eq!(web3_tx.block_number, None);
+        }
+    }
+
     #[tokio::test]
     async fn getting_miniblock_transactions() {
         let connection_pool = ConnectionPool::test_pool().await;
@@ -87,7 +87,21 @@ mod tests {
                 max_batch_request_size: Some(200),
                 max_response_body_size_mb: Some(10),
                 websocket_requests_per_minute_limit: Some(10),
+                http_requests_per_minute_limit: Some(600),
+                http_expensive_requests_per_minute_limit: Some(60),
+                http_expensive_methods: Some(vec!["eth_call".to_owned(), "debug_*".to_owned()]),
+                http_api_key_header: None,
+                http_api_keys: Some(vec!["key1".to_owned(), "key2".to_owned()]),
+                http_method_allow_list: None,
+                http_method_deny_list: Some(vec!["debug_*".to_owned()]),
+                http_trust_forwarded_headers: Some(true),
                 tree_api_url: None,
+                api_namespaces: Some(vec![
+                    "eth".to_owned(),
+                    "net".to_owned(),
+                    "web3".to_owned(),
+                    "txpool".to_owned(),
+                ]),
             },
             contract_verification: ContractVerificationApiConfig {
                 port: 3070,
@@ -134,6 +148,13 @@ mod tests {
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_HTTP_REQUESTS_PER_MINUTE_LIMIT=600
+            API_WEB3_JSON_RPC_HTTP_EXPENSIVE_REQUESTS_PER_MINUTE_LIMIT=60
+            API_WEB3_JSON_RPC_HTTP_EXPENSIVE_METHODS="eth_call,debug_*"
+            API_WEB3_JSON_RPC_HTTP_API_KEYS="key1,key2"
+            API_WEB3_JSON_RPC_HTTP_METHOD_DENY_LIST="debug_*"
+            API_WEB3_JSON_RPC_HTTP_TRUST_FORWARDED_HEADERS=true
+            API_WEB3_JSON_RPC_API_NAMESPACES="eth,net,web3,txpool"
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_CONTRACT_VERIFICATION_THREADS_PER_SERVER=128
@@ -52,6 +52,7 @@ impl FromEnv for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use zksync_basic_types::L2ChainId;
+    use zksync_config::configs::chain::MempoolOrdering;
 
     use super::*;
     use crate::test_utils::{addr, EnvMutex};
@@ -86,6 +87,7 @@ mod tests {
                 virtual_blocks_per_miniblock: 1,
                 upload_witness_inputs_to_gcs: false,
                 enum_index_migration_chunk_size: Some(2_000),
+                mempool_ordering: MempoolOrdering::PriorityFee,
             },
             operations_manager: OperationsManagerConfig {
                 delay_interval: 100,
@@ -97,6 +99,9 @@ mod tests {
                 stuck_tx_timeout: 10,
                 remove_stuck_txs: true,
                 delay_interval: 100,
+                max_pending_txs_per_account: 16,
+                max_nonce_gap: 32,
+                replacement_fee_bump_percent: 25,
             },
             circuit_breaker: CircuitBreakerConfig {
                 sync_interval_ms: 1000,
@@ -132,6 +137,7 @@ mod tests {
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
             CHAIN_STATE_KEEPER_UPLOAD_WITNESS_INPUTS_TO_GCS="false"
             CHAIN_STATE_KEEPER_ENUM_INDEX_MIGRATION_CHUNK_SIZE="2000"
+            CHAIN_STATE_KEEPER_MEMPOOL_ORDERING="priority_fee"
             CHAIN_OPERATIONS_MANAGER_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_SYNC_INTERVAL_MS="10"
             CHAIN_MEMPOOL_SYNC_BATCH_SIZE="1000"
@@ -139,6 +145,9 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="16"
+            CHAIN_MEMPOOL_MAX_NONCE_GAP="32"
+            CHAIN_MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT="25"
             CHAIN_CIRCUIT_BREAKER_SYNC_INTERVAL_MS="1000"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_MAX_RETRY_NUMBER="5"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_RETRY_INTERVAL_SEC="2"
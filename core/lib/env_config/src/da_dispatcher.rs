@@ -0,0 +1,38 @@
+use zksync_config::configs::DADispatcherConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for DADispatcherConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("da_dispatcher", "DA_DISPATCHER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    fn expected_config() -> DADispatcherConfig {
+        DADispatcherConfig {
+            polling_interval_ms: 5_000,
+            max_rows_to_dispatch: 100,
+            file_client_path: Some("./db/da_blobs".to_owned()),
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+            DA_DISPATCHER_POLLING_INTERVAL_MS="5000"
+            DA_DISPATCHER_MAX_ROWS_TO_DISPATCH="100"
+            DA_DISPATCHER_FILE_CLIENT_PATH="./db/da_blobs"
+        "#;
+        let mut lock = MUTEX.lock();
+        lock.set_env(config);
+        let actual = DADispatcherConfig::from_env().unwrap();
+        assert_eq!(actual, expected_config());
+    }
+}
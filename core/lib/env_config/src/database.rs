@@ -34,6 +34,14 @@ impl FromEnv for PostgresConfig {
                     .context("failed to parse DATABASE_STATEMENT_TIMEOUT")
             })
             .transpose()?;
+        let job_notifications_enabled = env::var("DATABASE_JOB_NOTIFICATIONS_ENABLED")
+            .ok()
+            .map(|val| {
+                val.parse()
+                    .context("failed to parse DATABASE_JOB_NOTIFICATIONS_ENABLED")
+            })
+            .transpose()?
+            .unwrap_or(false);
 
         Ok(Self {
             master_url,
@@ -41,6 +49,7 @@ impl FromEnv for PostgresConfig {
             prover_url,
             max_connections,
             statement_timeout_sec,
+            job_notifications_enabled,
         })
     }
 }
@@ -7,16 +7,27 @@ impl FromEnv for ETHClientConfig {
         let config: Self = envy_load("eth_client", "ETH_CLIENT_")?;
         if config.web3_url.find(',').is_some() {
             anyhow::bail!(
-                "Multiple web3 URLs aren't supported anymore. Provided invalid value: {}",
+                "Multiple web3 URLs should be specified using `ETH_CLIENT_FALLBACK_WEB3_URLS`. \
+                 Provided invalid value: {}",
                 config.web3_url
             );
         }
+        if let Some(quorum) = config.quorum {
+            let urls_count = config.web3_urls().count();
+            if quorum == 0 || quorum > urls_count {
+                anyhow::bail!(
+                    "Invalid quorum {quorum}: it must be positive and not exceed the number of web3 URLs ({urls_count})"
+                );
+            }
+        }
         Ok(config)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
     use crate::test_utils::EnvMutex;
 
@@ -26,6 +37,12 @@ mod tests {
         ETHClientConfig {
             chain_id: 9,
             web3_url: "http://127.0.0.1:8545".into(),
+            fallback_web3_urls: Some(vec![
+                "http://127.0.0.1:8546".into(),
+                "http://127.0.0.1:8547".into(),
+            ]),
+            quorum: Some(2),
+            provider_timeout_ms: Some(10_000),
         }
     }
 
@@ -35,10 +52,38 @@ mod tests {
         let config = r#"
             ETH_CLIENT_CHAIN_ID="9"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_CLIENT_FALLBACK_WEB3_URLS="http://127.0.0.1:8546,http://127.0.0.1:8547"
+            ETH_CLIENT_QUORUM="2"
+            ETH_CLIENT_PROVIDER_TIMEOUT_MS="10000"
         "#;
         lock.set_env(config);
 
         let actual = ETHClientConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
+        assert_eq!(actual.provider_timeout(), Duration::from_secs(10));
+        let urls: Vec<_> = actual.web3_urls().collect();
+        assert_eq!(
+            urls,
+            [
+                "http://127.0.0.1:8545",
+                "http://127.0.0.1:8546",
+                "http://127.0.0.1:8547"
+            ]
+        );
+    }
+
+    #[test]
+    fn from_env_with_invalid_quorum() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            ETH_CLIENT_CHAIN_ID="9"
+            ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_CLIENT_QUORUM="2"
+        "#;
+        lock.set_env(config);
+        lock.remove_env(&["ETH_CLIENT_FALLBACK_WEB3_URLS"]);
+
+        let err = ETHClientConfig::from_env().unwrap_err().to_string();
+        assert!(err.contains("Invalid quorum"), "{err}");
     }
 }
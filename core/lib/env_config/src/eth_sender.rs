@@ -26,7 +26,9 @@ impl FromEnv for GasAdjusterConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::eth_sender::{ProofLoadingMode, ProofSendingMode};
+    use zksync_config::configs::eth_sender::{
+        ProofLoadingMode, ProofSendingMode, PubdataSendingMode,
+    };
 
     use super::*;
     use crate::test_utils::{hash, EnvMutex};
@@ -54,6 +56,7 @@ mod tests {
                 l1_batch_min_age_before_execute_seconds: Some(1000),
                 max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
+                pubdata_sending_mode: PubdataSendingMode::DataAvailability,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 20000000000,
@@ -77,6 +80,7 @@ mod tests {
             ETH_SENDER_SENDER_AGGREGATE_TX_POLL_PERIOD="3"
             ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
             ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+            ETH_SENDER_SENDER_COMMIT_OPERATOR_PRIVATE_KEY="0xe131bc3f481277a8f73d680d9ba404cc6f959e64296e0914dded403030d4f705"
             ETH_SENDER_SENDER_PROOF_SENDING_MODE="SkipEveryProof"
             ETH_SENDER_GAS_ADJUSTER_DEFAULT_PRIORITY_FEE_PER_GAS="20000000000"
             ETH_SENDER_GAS_ADJUSTER_MAX_BASE_FEE_SAMPLES="10000"
@@ -98,6 +102,7 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PROOF_LOADING_MODE="OldProofFromDb"
+            ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="DataAvailability"
         "#;
         lock.set_env(config);
 
@@ -107,5 +112,10 @@ mod tests {
             actual.sender.private_key().unwrap(),
             hash("27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be")
         );
+        assert_eq!(
+            actual.sender.commit_operator_private_key().unwrap(),
+            hash("e131bc3f481277a8f73d680d9ba404cc6f959e64296e0914dded403030d4f705")
+        );
+        assert_eq!(actual.sender.prove_operator_private_key(), None);
     }
 }
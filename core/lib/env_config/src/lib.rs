@@ -7,6 +7,7 @@ mod chain;
 mod circuit_synthesizer;
 mod contract_verifier;
 mod contracts;
+mod da_dispatcher;
 mod database;
 mod eth_client;
 mod eth_sender;
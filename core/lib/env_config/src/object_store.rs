@@ -56,6 +56,15 @@ mod tests {
             file_backed_base_path: "artifacts".to_string(),
             gcs_credential_file_path: "/path/to/credentials.json".to_string(),
             max_retries: 5,
+            s3_endpoint: None,
+            s3_region: "us-east-1".to_owned(),
+            s3_path_style: false,
+            s3_access_key_id: None,
+            s3_secret_access_key: None,
+            s3_multipart_part_size_mb: 64,
+            cache_path: None,
+            cache_max_size_mb: 10_240,
+            cached_buckets: vec![],
         }
     }
 
@@ -118,4 +127,62 @@ mod tests {
         let actual = SnapshotsObjectStoreConfig::from_env().unwrap().0;
         assert_eq!(actual, expected_config("/snapshots_base_url"));
     }
+
+    #[test]
+    fn s3_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_BUCKET_BASE_URL="zksync-artifacts"
+            OBJECT_STORE_MODE="S3"
+            OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials.json"
+            OBJECT_STORE_MAX_RETRIES="5"
+            OBJECT_STORE_S3_ENDPOINT="http://localhost:9000"
+            OBJECT_STORE_S3_REGION="eu-central-1"
+            OBJECT_STORE_S3_PATH_STYLE="true"
+            OBJECT_STORE_S3_ACCESS_KEY_ID="minioadmin"
+            OBJECT_STORE_S3_SECRET_ACCESS_KEY="minioadmin"
+            OBJECT_STORE_S3_MULTIPART_PART_SIZE_MB="16"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+
+        let expected = ObjectStoreConfig {
+            bucket_base_url: "zksync-artifacts".to_owned(),
+            mode: ObjectStoreMode::S3,
+            s3_endpoint: Some("http://localhost:9000".to_owned()),
+            s3_region: "eu-central-1".to_owned(),
+            s3_path_style: true,
+            s3_access_key_id: Some("minioadmin".to_owned()),
+            s3_secret_access_key: Some("minioadmin".to_owned()),
+            s3_multipart_part_size_mb: 16,
+            ..expected_config("zksync-artifacts")
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn cache_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            PROVER_OBJECT_STORE_BUCKET_BASE_URL="/prover_base_url"
+            PROVER_OBJECT_STORE_MODE="FileBacked"
+            PROVER_OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            PROVER_OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials.json"
+            PROVER_OBJECT_STORE_MAX_RETRIES="5"
+            PROVER_OBJECT_STORE_CACHE_PATH="/tmp/object_store_cache"
+            PROVER_OBJECT_STORE_CACHE_MAX_SIZE_MB="2048"
+            PROVER_OBJECT_STORE_CACHED_BUCKETS="witness_inputs,prover_jobs_fri"
+        "#;
+        lock.set_env(config);
+        let actual = ProverObjectStoreConfig::from_env().unwrap().0;
+
+        let expected = ObjectStoreConfig {
+            cache_path: Some("/tmp/object_store_cache".to_owned()),
+            cache_max_size_mb: 2_048,
+            cached_buckets: vec!["witness_inputs".to_owned(), "prover_jobs_fri".to_owned()],
+            ..expected_config("/prover_base_url")
+        };
+        assert_eq!(actual, expected);
+    }
 }
@@ -10,7 +10,11 @@ impl FromEnv for ProofDataHandlerConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::proof_data_handler::ProtocolVersionLoadingMode;
+    use std::num::NonZeroU32;
+
+    use zksync_config::configs::proof_data_handler::{
+        ProofDataHandlerAuthMode, ProtocolVersionLoadingMode,
+    };
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -23,6 +27,14 @@ mod tests {
             proof_generation_timeout_in_secs: 18000,
             protocol_version_loading_mode: ProtocolVersionLoadingMode::FromEnvVar,
             fri_protocol_version_id: 2,
+            auth_mode: ProofDataHandlerAuthMode::ApiKey,
+            prover_credentials: vec![
+                "prover-1:secret-1".to_owned(),
+                "prover-2:secret-2".to_owned(),
+            ],
+            rate_limit_requests_per_minute: NonZeroU32::new(60),
+            ip_rate_limit_requests_per_minute: NonZeroU32::new(600),
+            max_signature_age_secs: 300,
         }
     }
 
@@ -33,10 +45,22 @@ mod tests {
             PROOF_DATA_HANDLER_HTTP_PORT="3320"
             PROOF_DATA_HANDLER_PROTOCOL_VERSION_LOADING_MODE="FromEnvVar"
             PROOF_DATA_HANDLER_FRI_PROTOCOL_VERSION_ID="2"
+            PROOF_DATA_HANDLER_AUTH_MODE="ApiKey"
+            PROOF_DATA_HANDLER_PROVER_CREDENTIALS="prover-1:secret-1,prover-2:secret-2"
+            PROOF_DATA_HANDLER_RATE_LIMIT_REQUESTS_PER_MINUTE="60"
+            PROOF_DATA_HANDLER_IP_RATE_LIMIT_REQUESTS_PER_MINUTE="600"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
         let actual = ProofDataHandlerConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
+
+        let credentials = actual.prover_credentials().unwrap();
+        assert_eq!(credentials.len(), 2);
+        assert_eq!(credentials["prover-1"], "secret-1");
+
+        let debug_output = format!("{actual:?}");
+        assert!(debug_output.contains("prover-1"), "{debug_output}");
+        assert!(!debug_output.contains("secret"), "{debug_output}");
     }
 }
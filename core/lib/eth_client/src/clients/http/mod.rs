@@ -1,14 +1,17 @@
 use std::time::Duration;
 
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, LabeledFamily, Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics,
 };
 
 pub use self::{
+    multi_provider::MultiQueryClient,
     query::QueryClient,
     signing::{PKSigningClient, SigningClient},
 };
 
+mod multi_provider;
 mod query;
 mod signing;
 
@@ -56,3 +59,26 @@ struct ClientLatencies {
 
 #[vise::register]
 static LATENCIES: vise::Global<ClientLatencies> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+enum ProviderCallOutcome {
+    Success,
+    Error,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "eth_client_provider")]
+struct ProviderMetrics {
+    /// Number of calls to a specific L1 provider, grouped by the call outcome.
+    #[metrics(labels = ["provider", "outcome"])]
+    calls: LabeledFamily<(String, ProviderCallOutcome), Counter, 2>,
+    /// Number of consecutive errors returned by a specific L1 provider.
+    #[metrics(labels = ["provider"])]
+    consecutive_errors: LabeledFamily<String, Gauge<u64>>,
+    /// Number of quorum reads failed because L1 providers didn't agree on the response.
+    quorum_failures: Family<Method, Counter>,
+}
+
+#[vise::register]
+static PROVIDER_METRICS: vise::Global<ProviderMetrics> = vise::Global::new();
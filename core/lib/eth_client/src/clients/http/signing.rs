@@ -21,7 +21,7 @@ use zksync_types::{
     L1ChainId, PackedEthSignature, EIP_1559_TX_TYPE,
 };
 
-use super::{query::QueryClient, Method, LATENCIES};
+use super::{multi_provider::MultiQueryClient, query::QueryClient, Method, LATENCIES};
 use crate::{
     types::{Error, ExecutedTxStatus, FailureInfo, SignedCallResult},
     BoundEthInterface, EthInterface,
@@ -36,26 +36,41 @@ impl PKSigningClient {
         contracts_config: &ContractsConfig,
         eth_client: &ETHClientConfig,
     ) -> Self {
-        // Gather required data from the config.
-        // It's done explicitly to simplify getting rid of this function later.
-        let main_node_url = &eth_client.web3_url;
         let operator_private_key = eth_sender
             .sender
             .private_key()
             .expect("Operator private key is required for signing client");
+        Self::from_config_with_private_key(
+            eth_sender,
+            contracts_config,
+            eth_client,
+            operator_private_key,
+        )
+    }
+
+    /// Same as [`Self::from_config()`], but uses the provided operator private key instead of the one
+    /// from the config. Can be used for operator accounts dedicated to specific types of L1 operations.
+    pub fn from_config_with_private_key(
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+        operator_private_key: H256,
+    ) -> Self {
+        // Gather required data from the config.
+        // It's done explicitly to simplify getting rid of this function later.
         let diamond_proxy_addr = contracts_config.diamond_proxy_addr;
         let default_priority_fee_per_gas = eth_sender.gas_adjuster.default_priority_fee_per_gas;
         let l1_chain_id = eth_client.chain_id;
 
-        let transport =
-            web3::transports::Http::new(main_node_url).expect("Failed to create transport");
+        let query_client =
+            MultiQueryClient::from_config(eth_client).expect("Failed to create L1 client");
         let operator_address = PackedEthSignature::address_from_private_key(&operator_private_key)
             .expect("Failed to get address from private key");
 
         tracing::info!("Operator address: {:?}", operator_address);
 
-        SigningClient::new(
-            transport,
+        SigningClient::with_query_client(
+            query_client,
             zksync_contract(),
             operator_address,
             PrivateKeySigner::new(operator_private_key),
@@ -77,7 +92,7 @@ const FALLBACK_GAS_LIMIT: u64 = 3_000_000;
 #[derive(Clone)]
 pub struct SigningClient<S: EthereumSigner> {
     inner: Arc<ETHDirectClientInner<S>>,
-    query_client: QueryClient,
+    query_client: MultiQueryClient,
 }
 
 struct ETHDirectClientInner<S: EthereumSigner> {
@@ -343,6 +358,26 @@ impl<S: EthereumSigner> SigningClient<S> {
         contract_eth_addr: H160,
         default_priority_fee_per_gas: U256,
         chain_id: L1ChainId,
+    ) -> Self {
+        Self::with_query_client(
+            MultiQueryClient::single(QueryClient::from(transport)),
+            contract,
+            operator_eth_addr,
+            eth_signer,
+            contract_eth_addr,
+            default_priority_fee_per_gas,
+            chain_id,
+        )
+    }
+
+    fn with_query_client(
+        query_client: MultiQueryClient,
+        contract: ethabi::Contract,
+        operator_eth_addr: H160,
+        eth_signer: S,
+        contract_eth_addr: H160,
+        default_priority_fee_per_gas: U256,
+        chain_id: L1ChainId,
     ) -> Self {
         Self {
             inner: Arc::new(ETHDirectClientInner {
@@ -353,7 +388,7 @@ impl<S: EthereumSigner> SigningClient<S> {
                 contract,
                 default_priority_fee_per_gas,
             }),
-            query_client: transport.into(),
+            query_client,
         }
     }
 }
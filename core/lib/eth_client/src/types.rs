@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 // External uses
 use zksync_types::web3::{
     ethabi,
@@ -22,6 +24,12 @@ pub enum Error {
     /// Incorrect fee provided for a transaction.
     #[error("Max fee {0} less than priority fee {1}")]
     WrongFeeProvided(U256, U256),
+    /// Ethereum gateways didn't agree on the response for a quorum read.
+    #[error("Quorum of {quorum} Ethereum gateways not reached; at most {agreed} gateways agreed on the response")]
+    NoQuorum { quorum: usize, agreed: usize },
+    /// Ethereum gateway didn't respond in time.
+    #[error("Request to ethereum gateway timed out after {0:?}")]
+    Timeout(Duration),
 }
 
 /// Representation of a signed transaction.
@@ -40,7 +48,7 @@ pub struct SignedCallResult {
 }
 
 /// State of the executed Ethereum transaction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExecutedTxStatus {
     /// The hash of the executed L1 transaction.
     pub tx_hash: H256,
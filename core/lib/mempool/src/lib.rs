@@ -4,6 +4,6 @@ mod tests;
 mod types;
 
 pub use crate::{
-    mempool_store::{MempoolInfo, MempoolStore},
-    types::L2TxFilter,
+    mempool_store::{AccountTransactionsView, MempoolInfo, MempoolStore},
+    types::{AccountLimitError, AccountLimits, L2TxFilter, MempoolOrdering, MempoolRejection},
 };
@@ -1,15 +1,20 @@
 use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
 
 use zksync_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
+    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction, H256,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{
+    AccountLimits, AccountTransactions, L2TxFilter, MempoolOrdering, MempoolRejection, MempoolScore,
+};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// L2 transactions rejected by the mempool because they violate [`AccountLimits`], or because their account
+    /// was evicted from the full mempool.
+    pub rejected_transactions: Vec<(H256, MempoolRejection)>,
 }
 
 #[derive(Debug)]
@@ -19,6 +24,16 @@ pub struct MempoolStats {
     pub l2_priority_queue_size: usize,
 }
 
+/// Snapshot of L2 transactions of a single account in the mempool. Transactions may be mapped
+/// to a lighter representation (e.g., hashes) so that the snapshot is cheap to take.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountTransactionsView<T = L2Tx> {
+    /// Transactions ready for execution, ordered by nonce.
+    pub pending: Vec<T>,
+    /// Transactions that cannot be executed until a nonce gap is filled, ordered by nonce.
+    pub queued: Vec<T>,
+}
+
 #[derive(Debug)]
 pub struct MempoolStore {
     /// Pending L1 transactions
@@ -30,24 +45,37 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
+    rejected_transactions: Vec<(H256, MempoolRejection)>,
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    ordering: MempoolOrdering,
+    account_limits: AccountLimits,
 }
 
 impl MempoolStore {
-    pub fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
+    pub fn new(next_priority_id: PriorityOpId, capacity: u64, ordering: MempoolOrdering) -> Self {
         Self {
             l1_transactions: HashMap::new(),
             l2_transactions_per_account: HashMap::new(),
             l2_priority_queue: BTreeSet::new(),
             next_priority_id,
             stashed_accounts: vec![],
+            rejected_transactions: vec![],
             size: 0,
             capacity,
+            ordering,
+            account_limits: AccountLimits::default(),
         }
     }
 
+    /// Sets limits for pending transactions of a single account. By default, no limits are applied.
+    #[must_use]
+    pub fn with_account_limits(mut self, account_limits: AccountLimits) -> Self {
+        self.account_limits = account_limits;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -101,15 +129,24 @@ impl MempoolStore {
         initial_nonces: &HashMap<Address, Nonce>,
     ) {
         let account = transaction.initiator_account();
+        let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
+        let check_result = match self.l2_transactions_per_account.get(&account) {
+            Some(txs) => txs.check_limits(&transaction, &self.account_limits),
+            None => AccountTransactions::new(account_nonce, self.ordering)
+                .check_limits(&transaction, &self.account_limits),
+        };
+        if let Err(err) = check_result {
+            let tx_hash = transaction.hash();
+            tracing::debug!("L2 transaction {tx_hash:?} is rejected by mempool: {err}");
+            self.rejected_transactions.push((tx_hash, err.into()));
+            return;
+        }
 
         let metadata = match self.l2_transactions_per_account.entry(account) {
             hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
-            hash_map::Entry::Vacant(entry) => {
-                let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
-                entry
-                    .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
-            }
+            hash_map::Entry::Vacant(entry) => entry
+                .insert(AccountTransactions::new(account_nonce, self.ordering))
+                .insert(transaction),
         };
         if let Some(score) = metadata.previous_score {
             self.l2_priority_queue.remove(&score);
@@ -132,7 +169,8 @@ impl MempoolStore {
                 .is_some()
     }
 
-    /// Returns next transaction for execution from mempool
+    /// Returns next transaction for execution from mempool. L1 transactions always go first;
+    /// L2 transactions are ordered according to the [`MempoolOrdering`] policy.
     pub fn next_transaction(&mut self, filter: &L2TxFilter) -> Option<Transaction> {
         if let Some(transaction) = self.l1_transactions.remove(&self.next_priority_id) {
             self.next_priority_id += 1;
@@ -206,9 +244,12 @@ impl MempoolStore {
     }
 
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
+        // GC must go first since it may reject transactions of evicted accounts.
+        let purged_accounts = self.gc();
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
-            purged_accounts: self.gc(),
+            purged_accounts,
+            rejected_transactions: std::mem::take(&mut self.rejected_transactions),
         }
     }
 
@@ -220,24 +261,96 @@ impl MempoolStore {
         }
     }
 
+    /// Returns the number of L2 transactions ready for execution and the number of L2 transactions
+    /// blocked by nonce gaps.
+    pub fn l2_transaction_counts(&self) -> (usize, usize) {
+        let accounts = self.l2_transactions_per_account.values();
+        accounts.fold((0, 0), |(pending, queued), txs| {
+            let ready_len = txs.ready_len();
+            (pending + ready_len, queued + txs.len() - ready_len)
+        })
+    }
+
+    /// Returns L2 transactions of the specified account mapped with `map_fn`, or `None` if there are
+    /// no such transactions in the mempool.
+    pub fn account_view<T>(
+        &self,
+        account: Address,
+        map_fn: impl FnMut(&L2Tx) -> T,
+    ) -> Option<AccountTransactionsView<T>> {
+        let txs = self.l2_transactions_per_account.get(&account)?;
+        if txs.len() == 0 {
+            return None;
+        }
+        let (pending, queued) = txs.split_by_readiness(map_fn);
+        Some(AccountTransactionsView { pending, queued })
+    }
+
+    /// Returns L2 transactions of all accounts in the mempool mapped with `map_fn`.
+    pub fn account_views<T>(
+        &self,
+        mut map_fn: impl FnMut(&L2Tx) -> T,
+    ) -> HashMap<Address, AccountTransactionsView<T>> {
+        self.l2_transactions_per_account
+            .keys()
+            .filter_map(|&account| Some((account, self.account_view(account, &mut map_fn)?)))
+            .collect()
+    }
+
     fn gc(&mut self) -> Vec<Address> {
         if self.size >= self.capacity {
-            let index: HashSet<_> = self
-                .l2_priority_queue
-                .iter()
-                .map(|pointer| pointer.account)
-                .collect();
-            let transactions = std::mem::take(&mut self.l2_transactions_per_account);
-            let (kept, drained) = transactions
-                .into_iter()
-                .partition(|(address, _)| index.contains(address));
-            self.l2_transactions_per_account = kept;
-            self.size = self
-                .l2_transactions_per_account
-                .iter()
-                .fold(0, |agg, (_, tnxs)| agg + tnxs.len() as u64);
-            return drained.into_keys().collect();
+            let purged_accounts = self.purge_stuck_accounts();
+            if self.ordering == MempoolOrdering::PriorityFee {
+                self.evict_lowest_paying_accounts();
+            }
+            return purged_accounts;
         }
         vec![]
     }
+
+    /// Removes accounts that have no transactions ready for execution (e.g., because of a nonce gap).
+    fn purge_stuck_accounts(&mut self) -> Vec<Address> {
+        let index: HashSet<_> = self
+            .l2_priority_queue
+            .iter()
+            .map(|pointer| pointer.account)
+            .collect();
+        let transactions = std::mem::take(&mut self.l2_transactions_per_account);
+        let (kept, drained) = transactions
+            .into_iter()
+            .partition(|(address, _)| index.contains(address));
+        self.l2_transactions_per_account = kept;
+        self.size = self
+            .l2_transactions_per_account
+            .iter()
+            .fold(0, |agg, (_, tnxs)| agg + tnxs.len() as u64);
+        drained.into_keys().collect()
+    }
+
+    /// Evicts accounts with the lowest-paying next transactions until the mempool is below capacity.
+    /// Unlike purged accounts, pending transactions of evicted accounts are not removed; they are reported
+    /// as rejected instead, so that their senders can find out what happened to them.
+    fn evict_lowest_paying_accounts(&mut self) {
+        while self.size >= self.capacity {
+            // The lowest-paying transaction (the most recent one among those with equal fees)
+            // is the first one in the priority queue.
+            let Some(score) = self.l2_priority_queue.pop_first() else {
+                break;
+            };
+            let transactions = self
+                .l2_transactions_per_account
+                .remove(&score.account)
+                .expect("mempool: dangling pointer in priority queue");
+            self.size -= transactions.len() as u64;
+            tracing::debug!(
+                "Evicted account {:?} with {} transactions from mempool",
+                score.account,
+                transactions.len()
+            );
+            let rejected = transactions
+                .hashes()
+                .map(|tx_hash| (tx_hash, MempoolRejection::Evicted));
+            self.rejected_transactions.extend(rejected);
+        }
+    }
 }
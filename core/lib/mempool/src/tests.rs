@@ -12,11 +12,14 @@ use zksync_types::{
     H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::MempoolStore,
+    types::{AccountLimitError, AccountLimits, L2TxFilter, MempoolOrdering, MempoolRejection},
+};
 
 #[test]
 fn basic_flow() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account0 = Address::random();
     let account1 = Address::random();
     let transactions = vec![
@@ -63,7 +66,7 @@ fn basic_flow() {
 
 #[test]
 fn missing_txns() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account = Address::random();
     let transactions = vec![
         gen_l2_tx(account, Nonce(6)),
@@ -103,7 +106,7 @@ fn missing_txns() {
 
 #[test]
 fn prioritize_l1_txns() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account = Address::random();
     let transactions = vec![
         gen_l2_tx(account, Nonce(0)),
@@ -119,7 +122,7 @@ fn prioritize_l1_txns() {
 
 #[test]
 fn l1_txns_priority_id() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let transactions = vec![
         gen_l1_tx(PriorityOpId(1)),
         gen_l1_tx(PriorityOpId(2)),
@@ -144,7 +147,7 @@ fn l1_txns_priority_id() {
 
 #[test]
 fn rejected_tx() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account = Address::random();
     let transactions = vec![
         gen_l2_tx(account, Nonce(0)),
@@ -184,7 +187,7 @@ fn rejected_tx() {
 
 #[test]
 fn replace_tx() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account = Address::random();
     mempool.insert(vec![gen_l2_tx(account, Nonce(0))], HashMap::new());
     // replace it
@@ -202,7 +205,7 @@ fn replace_tx() {
 
 #[test]
 fn two_ready_txs() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account0 = Address::random();
     let account1 = Address::random();
     let transactions = vec![gen_l2_tx(account0, Nonce(0)), gen_l2_tx(account1, Nonce(0))];
@@ -218,7 +221,7 @@ fn two_ready_txs() {
 
 #[test]
 fn mempool_size() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account0 = Address::random();
     let account1 = Address::random();
     let transactions = vec![
@@ -255,7 +258,7 @@ fn filtering() {
         gas_per_pubdata: 0u32,
     };
 
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account0 = Address::random();
     let account1 = Address::random();
 
@@ -298,7 +301,7 @@ fn stashed_accounts() {
         fee_per_gas: 0u64,
         gas_per_pubdata: 0u32,
     };
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
     let account0 = Address::random();
     let account1 = Address::random();
 
@@ -321,7 +324,7 @@ fn stashed_accounts() {
 
 #[test]
 fn mempool_capacity() {
-    let mut mempool = MempoolStore::new(PriorityOpId(0), 5);
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 5, MempoolOrdering::Fifo);
     let account0 = Address::random();
     let account1 = Address::random();
     let account2 = Address::random();
@@ -365,6 +368,206 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn priority_fee_ordering() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::PriorityFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let timestamp = unix_timestamp_ms();
+    let transactions = vec![
+        gen_l2_tx_with_priority_fee(account0, Nonce(0), timestamp, 1),
+        gen_l2_tx_with_priority_fee(account0, Nonce(1), timestamp + 1, 10),
+        gen_l2_tx_with_priority_fee(account1, Nonce(0), timestamp + 2, 5),
+        gen_l2_tx_with_priority_fee(account2, Nonce(0), timestamp + 3, 5),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    // Transactions with equal fees are ordered by the received timestamp. The transaction
+    // with the highest fee cannot be executed before its predecessor with the same initiator.
+    let order: Vec<_> = (0..4)
+        .map(|_| view(mempool.next_transaction(&L2TxFilter::default())))
+        .collect();
+    assert_eq!(
+        order,
+        [(account1, 0), (account2, 0), (account0, 0), (account0, 1)]
+    );
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+}
+
+#[test]
+fn fifo_ordering_ignores_priority_fee() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let timestamp = unix_timestamp_ms();
+    let transactions = vec![
+        gen_l2_tx_with_priority_fee(account0, Nonce(0), timestamp, 1),
+        gen_l2_tx_with_priority_fee(account1, Nonce(0), timestamp + 1, 100),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 0)
+    );
+}
+
+#[test]
+fn lowest_paying_accounts_are_evicted() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 4, MempoolOrdering::PriorityFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let account3 = Address::random();
+    let timestamp = unix_timestamp_ms();
+    let transactions = vec![
+        gen_l2_tx_with_priority_fee(account0, Nonce(0), timestamp, 10),
+        gen_l2_tx_with_priority_fee(account0, Nonce(1), timestamp, 10),
+        gen_l2_tx_with_priority_fee(account1, Nonce(0), timestamp + 1, 1),
+        gen_l2_tx_with_priority_fee(account2, Nonce(0), timestamp + 2, 5),
+        // Not executable because of the nonce gap; should be purged first.
+        gen_l2_tx_with_priority_fee(account3, Nonce(1), timestamp + 3, 100),
+    ];
+    let evicted_tx_hash = transactions[2].hash();
+    mempool.insert(transactions, HashMap::new());
+
+    let mempool_info = mempool.get_mempool_info();
+    assert_eq!(mempool_info.purged_accounts, [account3]);
+    // Transactions of evicted accounts are rejected rather than purged.
+    assert_eq!(
+        mempool_info.rejected_transactions,
+        [(evicted_tx_hash, MempoolRejection::Evicted)]
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+    let mempool_info = mempool.get_mempool_info();
+    assert!(mempool_info.purged_accounts.is_empty());
+    assert!(mempool_info.rejected_transactions.is_empty());
+
+    let order: Vec<_> = (0..3)
+        .map(|_| view(mempool.next_transaction(&L2TxFilter::default())))
+        .collect();
+    assert_eq!(order, [(account0, 0), (account0, 1), (account2, 0)]);
+}
+
+#[test]
+fn account_limits_are_enforced() {
+    let limits = AccountLimits {
+        max_pending_txs: 2,
+        max_nonce_gap: 3,
+        replacement_fee_bump_percent: 0,
+    };
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo).with_account_limits(limits);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_fee(account0, Nonce(0), 100, 10),
+        gen_l2_tx_with_fee(account0, Nonce(1), 100, 10),
+        gen_l2_tx_with_fee(account0, Nonce(2), 100, 10),
+        gen_l2_tx_with_fee(account1, Nonce(3), 100, 10),
+        gen_l2_tx_with_fee(account1, Nonce(4), 100, 10),
+    ];
+    let rejected_hashes = [transactions[2].hash(), transactions[4].hash()];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+
+    let rejected_transactions = mempool.get_mempool_info().rejected_transactions;
+    assert_eq!(
+        rejected_transactions,
+        [
+            (
+                rejected_hashes[0],
+                AccountLimitError::TooManyPendingTxs(2).into()
+            ),
+            (
+                rejected_hashes[1],
+                AccountLimitError::NonceGapTooLarge {
+                    expected_nonce: Nonce(0),
+                    max_gap: 3,
+                    nonce: Nonce(4),
+                }
+                .into()
+            ),
+        ]
+    );
+    assert!(mempool.get_mempool_info().rejected_transactions.is_empty());
+}
+
+#[test]
+fn underpriced_replacement_is_rejected() {
+    let limits = AccountLimits {
+        replacement_fee_bump_percent: 10,
+        ..AccountLimits::default()
+    };
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo).with_account_limits(limits);
+    let account = Address::random();
+    let tx = gen_l2_tx_with_fee(account, Nonce(0), 100, 10);
+    mempool.insert(vec![tx.clone()], HashMap::new());
+    // Re-inserting the same transaction is not a replacement.
+    mempool.insert(vec![tx], HashMap::new());
+    assert!(mempool.get_mempool_info().rejected_transactions.is_empty());
+
+    let underpriced_tx = gen_l2_tx_with_fee(account, Nonce(0), 200, 10);
+    let underpriced_tx_hash = underpriced_tx.hash();
+    mempool.insert(vec![underpriced_tx], HashMap::new());
+    let rejected_transactions = mempool.get_mempool_info().rejected_transactions;
+    assert_eq!(
+        rejected_transactions,
+        [(
+            underpriced_tx_hash,
+            AccountLimitError::ReplacementUnderpriced(10).into()
+        )]
+    );
+
+    let replacement_tx = gen_l2_tx_with_fee(account, Nonce(0), 110, 11);
+    let replacement_tx_hash = replacement_tx.hash();
+    mempool.insert(vec![replacement_tx], HashMap::new());
+    assert!(mempool.get_mempool_info().rejected_transactions.is_empty());
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    let next_tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(next_tx.hash(), replacement_tx_hash);
+}
+
+#[test]
+fn account_views() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, MempoolOrdering::Fifo);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx(account0, Nonce(0)),
+        gen_l2_tx(account0, Nonce(1)),
+        gen_l2_tx(account0, Nonce(3)),
+        gen_l2_tx(account1, Nonce(1)),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.l2_transaction_counts(), (2, 2));
+
+    let account_view = mempool.account_view(account0, |tx| tx.nonce().0).unwrap();
+    assert_eq!(account_view.pending, [0, 1]);
+    assert_eq!(account_view.queued, [3]);
+    assert!(mempool
+        .account_view(Address::random(), L2Tx::clone)
+        .is_none());
+
+    // Executing transactions should update views.
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    let views = mempool.account_views(L2Tx::hash);
+    assert_eq!(views.len(), 2);
+    assert_eq!(views[&account0].pending.len(), 1);
+    assert!(views[&account1].pending.is_empty());
+    assert_eq!(views[&account1].queued.len(), 1);
+    assert_eq!(mempool.l2_transaction_counts(), (1, 2));
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -384,6 +587,47 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_priority_fee(
+    address: Address,
+    nonce: Nonce,
+    received_at_ms: u64,
+    max_priority_fee_per_gas: u64,
+) -> Transaction {
+    let mut tx = gen_l2_tx_with_timestamp(address, nonce, received_at_ms);
+    match &mut tx.common_data {
+        ExecuteTransactionCommon::L2(data) => {
+            data.fee.max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+        }
+        _ => unreachable!(),
+    }
+    tx
+}
+
+fn gen_l2_tx_with_fee(
+    address: Address,
+    nonce: Nonce,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+) -> Transaction {
+    let mut txn = L2Tx::new(
+        Address::default(),
+        Vec::new(),
+        nonce,
+        Fee {
+            max_fee_per_gas: max_fee_per_gas.into(),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.into(),
+            ..Fee::default()
+        },
+        address,
+        U256::zero(),
+        None,
+        Default::default(),
+    );
+    // Transactions must have distinct hashes to be distinguishable in rejections.
+    txn.set_input(vec![], H256::random());
+    txn.into()
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
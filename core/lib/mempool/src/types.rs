@@ -1,6 +1,19 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-use zksync_types::{fee::Fee, l2::L2Tx, Address, Nonce, Transaction, U256};
+use zksync_types::{fee::Fee, l2::L2Tx, Address, Nonce, Transaction, H256, U256};
+
+/// Policy used to order L2 transactions in the mempool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MempoolOrdering {
+    /// Transactions are ordered by the time they were received by the mempool.
+    #[default]
+    Fifo,
+    /// Transactions are ordered by their priority fee (`max_priority_fee_per_gas`) in descending order;
+    /// transactions with equal fees are ordered by the time they were received. Once the mempool
+    /// reaches its capacity, accounts with the lowest-paying transactions are evicted from it.
+    /// The ordering only applies to transactions inserted into the mempool.
+    PriorityFee,
+}
 
 /// Pending mempool transactions of account
 #[derive(Debug)]
@@ -10,17 +23,45 @@ pub(crate) struct AccountTransactions {
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    /// ordering policy used to compute transaction scores
+    ordering: MempoolOrdering,
 }
 
 impl AccountTransactions {
-    pub fn new(nonce: Nonce) -> Self {
+    pub fn new(nonce: Nonce, ordering: MempoolOrdering) -> Self {
         Self {
             transactions: HashMap::new(),
             nonce,
+            ordering,
         }
     }
 
-    /// Inserts new transaction for given account. Returns insertion metadata
+    /// Checks whether the transaction can be inserted for this account without violating `limits`.
+    pub fn check_limits(
+        &self,
+        transaction: &L2Tx,
+        limits: &AccountLimits,
+    ) -> Result<(), AccountLimitError> {
+        let nonce = transaction.common_data.nonce;
+        if nonce < self.nonce {
+            // Old transactions are skipped on insertion anyway.
+            return Ok(());
+        }
+        limits.check_nonce_gap(self.nonce, nonce)?;
+        match self.transactions.get(&nonce) {
+            // Re-inserting the same transaction is not a replacement.
+            Some(replaced_tx) if replaced_tx.common_data.input == transaction.common_data.input => {
+                Ok(())
+            }
+            Some(replaced_tx) => {
+                limits.check_replacement(&replaced_tx.common_data.fee, &transaction.common_data.fee)
+            }
+            None => limits.check_pending_count(self.transactions.len()),
+        }
+    }
+
+    /// Inserts new transaction for given account. Returns insertion metadata.
+    /// The transaction is expected to be checked using [`Self::check_limits()`] beforehand.
     pub fn insert(&mut self, transaction: L2Tx) -> InsertionMetadata {
         let mut metadata = InsertionMetadata::default();
         let nonce = transaction.common_data.nonce;
@@ -28,11 +69,11 @@ impl AccountTransactions {
         if nonce < self.nonce {
             return metadata;
         }
-        let new_score = Self::score_for_transaction(&transaction);
+        let new_score = self.score_for_transaction(&transaction);
         let previous_score = self
             .transactions
             .insert(nonce, transaction)
-            .map(|tx| Self::score_for_transaction(&tx));
+            .map(|tx| self.score_for_transaction(&tx));
         metadata.is_new = previous_score.is_none();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
@@ -52,7 +93,7 @@ impl AccountTransactions {
         let score = self
             .transactions
             .get(&self.nonce)
-            .map(Self::score_for_transaction);
+            .map(|tx| self.score_for_transaction(tx));
         (transaction, score)
     }
 
@@ -65,28 +106,61 @@ impl AccountTransactions {
         self.nonce = self.nonce.min(tx_nonce);
         self.transactions
             .get(&(tx_nonce + 1))
-            .map(Self::score_for_transaction)
+            .map(|tx| self.score_for_transaction(tx))
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
+    /// Returns hashes of all transactions of the account.
+    pub fn hashes(&self) -> impl Iterator<Item = H256> + '_ {
+        self.transactions.values().map(L2Tx::hash)
+    }
+
+    /// Returns the number of transactions ready for execution, i.e., having consecutive nonces
+    /// starting from the account nonce.
+    pub fn ready_len(&self) -> usize {
+        (0..)
+            .take_while(|&offset| self.transactions.contains_key(&(self.nonce + offset)))
+            .count()
+    }
+
+    /// Returns account transactions ordered by nonce and mapped with `map_fn`, split into ones
+    /// ready for execution and ones blocked by a nonce gap.
+    pub fn split_by_readiness<T>(&self, map_fn: impl FnMut(&L2Tx) -> T) -> (Vec<T>, Vec<T>) {
+        let mut transactions: Vec<_> = self.transactions.values().collect();
+        transactions.sort_unstable_by_key(|tx| tx.common_data.nonce);
+        let mut transactions: Vec<_> = transactions.into_iter().map(map_fn).collect();
+        let queued = transactions.split_off(self.ready_len());
+        (transactions, queued)
+    }
+
+    fn score_for_transaction(&self, transaction: &L2Tx) -> MempoolScore {
+        let fee_data = transaction.common_data.fee.clone();
+        let priority_fee = match self.ordering {
+            MempoolOrdering::Fifo => U256::zero(),
+            MempoolOrdering::PriorityFee => fee_data.max_priority_fee_per_gas,
+        };
         MempoolScore {
             account: transaction.initiator_account(),
             received_at_ms: transaction.received_timestamp_ms,
-            fee_data: transaction.common_data.fee.clone(),
+            priority_fee,
+            fee_data,
         }
     }
 }
 
-/// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Mempool score of transaction. Used to prioritize L2 transactions in mempool.
+/// Transactions are ordered by the priority fee (higher fee goes first), and then
+/// by the received at timestamp (earlier transaction goes first).
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
     pub account: Address,
     pub received_at_ms: u64,
+    /// Fee used to prioritize the transaction. Always zero for [`MempoolOrdering::Fifo`],
+    /// so that transactions are ordered by `received_at_ms` only.
+    pub priority_fee: U256,
     // Not used for actual scoring, but state keeper would request
     // transactions that have acceptable fee values (so transactions
     // with fee too low would be ignored until prices go down).
@@ -103,6 +177,10 @@ impl MempoolScore {
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        match self.priority_fee.cmp(&other.priority_fee) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -117,6 +195,107 @@ impl PartialOrd for MempoolScore {
     }
 }
 
+/// Limits applied to pending transactions of a single account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountLimits {
+    /// Maximum number of pending transactions from a single account.
+    pub max_pending_txs: usize,
+    /// Maximum difference between the transaction nonce and the next nonce expected for the account.
+    pub max_nonce_gap: u32,
+    /// Minimum increase (in percent) of fees required to replace a transaction with the same nonce.
+    pub replacement_fee_bump_percent: u32,
+}
+
+impl Default for AccountLimits {
+    /// Returns limits that do not restrict transactions in any way.
+    fn default() -> Self {
+        Self {
+            max_pending_txs: usize::MAX,
+            max_nonce_gap: u32::MAX,
+            replacement_fee_bump_percent: 0,
+        }
+    }
+}
+
+impl AccountLimits {
+    /// Checks that an account with `pending_count` pending transactions can accept a new one.
+    pub fn check_pending_count(&self, pending_count: usize) -> Result<(), AccountLimitError> {
+        if pending_count >= self.max_pending_txs {
+            return Err(AccountLimitError::TooManyPendingTxs(self.max_pending_txs));
+        }
+        Ok(())
+    }
+
+    /// Checks that the `nonce` of a new transaction is not too far ahead of the `expected_nonce`
+    /// for the account.
+    pub fn check_nonce_gap(
+        &self,
+        expected_nonce: Nonce,
+        nonce: Nonce,
+    ) -> Result<(), AccountLimitError> {
+        if nonce.0.saturating_sub(expected_nonce.0) > self.max_nonce_gap {
+            return Err(AccountLimitError::NonceGapTooLarge {
+                expected_nonce,
+                max_gap: self.max_nonce_gap,
+                nonce,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that a transaction with the `new_fee` can replace a pending transaction
+    /// with the same nonce and the `replaced_fee`.
+    pub fn check_replacement(
+        &self,
+        replaced_fee: &Fee,
+        new_fee: &Fee,
+    ) -> Result<(), AccountLimitError> {
+        let is_bumped = |replaced: U256, new: U256| {
+            let min_fee =
+                replaced.saturating_mul(U256::from(100 + self.replacement_fee_bump_percent)) / 100;
+            new >= min_fee
+        };
+        if is_bumped(replaced_fee.max_fee_per_gas, new_fee.max_fee_per_gas)
+            && is_bumped(
+                replaced_fee.max_priority_fee_per_gas,
+                new_fee.max_priority_fee_per_gas,
+            )
+        {
+            Ok(())
+        } else {
+            Err(AccountLimitError::ReplacementUnderpriced(
+                self.replacement_fee_bump_percent,
+            ))
+        }
+    }
+}
+
+/// Violation of [`AccountLimits`] by an L2 transaction.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AccountLimitError {
+    #[error("too many pending transactions from the account; at most {0} are allowed")]
+    TooManyPendingTxs(usize),
+    #[error("nonce gap is too large. expected nonce: {expected_nonce}, max allowed gap: {max_gap}, actual: {nonce}")]
+    NonceGapTooLarge {
+        expected_nonce: Nonce,
+        max_gap: u32,
+        nonce: Nonce,
+    },
+    #[error("replacement transaction underpriced; max fee per gas and max priority fee per gas must be increased by at least {0}%")]
+    ReplacementUnderpriced(u32),
+}
+
+/// Reason for rejecting an L2 transaction by the mempool.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MempoolRejection {
+    /// The transaction violates [`AccountLimits`].
+    #[error(transparent)]
+    AccountLimit(#[from] AccountLimitError),
+    /// The transaction account was evicted from the full mempool because of low fees.
+    #[error("evicted: mempool full")]
+    Evicted,
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct InsertionMetadata {
     pub new_score: Option<MempoolScore>,
@@ -158,6 +337,7 @@ mod tests {
         let score = MempoolScore {
             account: Address::random(),
             received_at_ms: Default::default(), // Not important
+            priority_fee: Default::default(),   // Not important
             fee_data: Fee {
                 gas_limit: Default::default(), // Not important
                 max_fee_per_gas: U256::from(MAX_FEE_PER_GAS),
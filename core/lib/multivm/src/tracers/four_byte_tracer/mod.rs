@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_system_constants::BOOTLOADER_ADDRESS;
+use zksync_types::{vm_trace::FourByteTrace, Address};
+
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer counting function selectors of far calls together with calldata sizes, similar to the `4byteTracer` in Geth.
+///
+/// Calls made by the bootloader and calls to system contracts and precompiles (i.e., to addresses in the kernel space)
+/// are not counted, since they are not made by the transaction code.
+#[derive(Debug, Clone)]
+pub struct FourByteTracer {
+    calls: FourByteTrace,
+    result: Arc<OnceCell<FourByteTrace>>,
+}
+
+impl FourByteTracer {
+    pub fn new(result: Arc<OnceCell<FourByteTrace>>) -> Self {
+        Self {
+            calls: FourByteTrace::new(),
+            result,
+        }
+    }
+
+    fn should_record_call(caller: &Address, callee: &Address) -> bool {
+        *caller != BOOTLOADER_ADDRESS && !is_kernel_space_address(callee)
+    }
+
+    fn record_call(&mut self, selector: &[u8], calldata_len: usize) {
+        let key = format!(
+            "0x{}-{}",
+            hex::encode(selector),
+            calldata_len - selector.len()
+        );
+        *self.calls.entry(key).or_default() += 1;
+    }
+
+    fn store_result(&mut self) {
+        let calls = std::mem::take(&mut self.calls);
+        self.result.set(calls).unwrap();
+    }
+}
+
+/// Checks whether the address belongs to the kernel space (i.e., is less than 2^16).
+fn is_kernel_space_address(address: &Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
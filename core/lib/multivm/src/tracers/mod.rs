@@ -1,8 +1,12 @@
 pub mod call_tracer;
+pub mod four_byte_tracer;
 mod multivm_dispatcher;
+pub mod prestate_tracer;
 pub mod storage_invocation;
 pub mod validator;
 
 pub use call_tracer::CallTracer;
+pub use four_byte_tracer::FourByteTracer;
 pub use multivm_dispatcher::TracerDispatcher;
+pub use prestate_tracer::PrestateTracer;
 pub use storage_invocation::StorageInvocations;
@@ -0,0 +1,172 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
+
+use once_cell::sync::OnceCell;
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_types::{
+    get_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    vm_trace::{AccountState, PrestateTrace},
+    AccountTreeId, Address, StorageKey, H256, U256,
+};
+use zksync_utils::{h256_to_u256, u256_to_h256};
+
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer collecting the state of accounts touched during execution, similar to the `prestateTracer` in Geth.
+///
+/// Unlike in Ethereum, balances, nonces and bytecodes of accounts are stored in the storage of system contracts.
+/// These storage slots are reported as the corresponding fields of accounts; all other touched storage slots
+/// (including ones of the system contracts) are reported as is.
+#[derive(Debug, Clone)]
+pub struct PrestateTracer {
+    diff_mode: bool,
+    /// Addresses participating in far calls. Balance and nonce slots are hashed,
+    /// so they can only be attributed to accounts from this set.
+    touched_accounts: BTreeSet<Address>,
+    /// Values of the touched storage slots before they were first accessed.
+    initial_values: HashMap<StorageKey, H256>,
+    result: Arc<OnceCell<PrestateTrace>>,
+}
+
+impl PrestateTracer {
+    pub fn new(diff_mode: bool, result: Arc<OnceCell<PrestateTrace>>) -> Self {
+        Self {
+            diff_mode,
+            touched_accounts: BTreeSet::new(),
+            initial_values: HashMap::new(),
+            result,
+        }
+    }
+
+    /// Records a storage slot that is about to be accessed.
+    fn record_storage_access<S: WriteStorage>(
+        &mut self,
+        address: Address,
+        key: U256,
+        storage: &StoragePtr<S>,
+    ) {
+        let key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(key));
+        self.initial_values
+            .entry(key)
+            .or_insert_with(|| storage.borrow_mut().read_value(&key));
+    }
+
+    fn store_result<S: WriteStorage>(&mut self, storage: StoragePtr<S>) {
+        let mut storage = storage.borrow_mut();
+        let initial_values = std::mem::take(&mut self.initial_values);
+        let mut accounts = std::mem::take(&mut self.touched_accounts);
+        accounts.extend(initial_values.keys().map(|key| *key.address()));
+        let account_keys: HashSet<_> = accounts
+            .iter()
+            .flat_map(|address| {
+                [
+                    storage_key_for_eth_balance(address),
+                    get_nonce_key(address),
+                    get_code_key(address),
+                ]
+            })
+            .collect();
+
+        let mut pre = BTreeMap::new();
+        let mut post = BTreeMap::new();
+        for address in accounts {
+            let mut state_before = AccountState::default();
+            let mut state_after = AccountState::default();
+            Self::load_account_fields(
+                &mut state_before,
+                &address,
+                &mut *storage,
+                |key, storage| {
+                    initial_values
+                        .get(key)
+                        .copied()
+                        .unwrap_or_else(|| storage.read_value(key))
+                },
+            );
+            Self::load_account_fields(&mut state_after, &address, &mut *storage, |key, storage| {
+                storage.read_value(key)
+            });
+
+            let account_slots = initial_values
+                .iter()
+                .filter(|(key, _)| *key.address() == address && !account_keys.contains(*key));
+            for (key, &initial_value) in account_slots {
+                state_before.storage.insert(*key.key(), initial_value);
+                state_after
+                    .storage
+                    .insert(*key.key(), storage.read_value(key));
+            }
+
+            if !self.diff_mode {
+                pre.insert(address, state_before);
+            } else if let Some((account_pre, account_post)) = diff(state_before, state_after) {
+                pre.insert(address, account_pre);
+                post.insert(address, account_post);
+            }
+        }
+
+        let result = if self.diff_mode {
+            PrestateTrace::Diff { pre, post }
+        } else {
+            PrestateTrace::Prestate(pre)
+        };
+        self.result.set(result).unwrap();
+    }
+
+    fn load_account_fields<S: WriteStorage>(
+        state: &mut AccountState,
+        address: &Address,
+        storage: &mut S,
+        mut read_value: impl FnMut(&StorageKey, &mut S) -> H256,
+    ) {
+        let balance = read_value(&storage_key_for_eth_balance(address), storage);
+        state.balance = Some(h256_to_u256(balance));
+        let full_nonce = read_value(&get_nonce_key(address), storage);
+        let (nonce, _) = decompose_full_nonce(h256_to_u256(full_nonce));
+        state.nonce = Some(nonce.low_u64());
+        let code_hash = read_value(&get_code_key(address), storage);
+        if !code_hash.is_zero() {
+            state.code = storage.load_factory_dep(code_hash).map(Into::into);
+        }
+    }
+}
+
+/// Computes the diff between the account states in the same way as Geth does: `pre` contains the full
+/// state of the modified account (but only modified storage slots), and `post` only contains the modified fields.
+fn diff(
+    mut state_before: AccountState,
+    state_after: AccountState,
+) -> Option<(AccountState, AccountState)> {
+    if state_before == state_after {
+        return None;
+    }
+
+    let mut post = AccountState {
+        balance: state_after
+            .balance
+            .filter(|_| state_before.balance != state_after.balance),
+        nonce: state_after
+            .nonce
+            .filter(|_| state_before.nonce != state_after.nonce),
+        code: None,
+        storage: BTreeMap::new(),
+    };
+    if state_before.code != state_after.code {
+        post.code = state_after.code;
+    }
+    state_before.storage.retain(|key, initial_value| {
+        let final_value = state_after.storage[key];
+        if final_value == *initial_value {
+            false
+        } else {
+            post.storage.insert(*key, final_value);
+            true
+        }
+    });
+    Some((state_before, post))
+}
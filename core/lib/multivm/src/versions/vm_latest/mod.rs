@@ -10,6 +10,7 @@ pub use self::{
     tracers::{
         dispatcher::TracerDispatcher,
         traits::{ToTracerPointer, TracerPointer, VmTracer},
+        StructLogTracer,
     },
     types::internals::ZkSyncVmState,
     utils::transaction_encoding::TransactionVmExt,
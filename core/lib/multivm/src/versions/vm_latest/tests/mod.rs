@@ -4,16 +4,19 @@ mod default_aa;
 // mod invalid_bytecode;
 mod bytecode_publishing;
 mod call_tracer;
+mod four_byte_tracer;
 mod gas_limit;
 mod get_used_contracts;
 mod is_write_initial;
 mod l1_tx_execution;
 mod l2_blocks;
 mod nonce_holder;
+mod prestate_tracer;
 mod refunds;
 mod require_eip712;
 mod rollbacks;
 mod simple_execution;
+mod struct_log_tracer;
 mod tester;
 mod tracing_execution_error;
 mod upgrade;
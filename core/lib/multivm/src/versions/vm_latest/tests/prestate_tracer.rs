@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_types::{vm_trace::PrestateTrace, Address, Execute, H256, U256};
+use zksync_utils::u256_to_h256;
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::PrestateTracer,
+    vm_latest::{
+        constants::BLOCK_GAS_LIMIT,
+        tests::{tester::VmTesterBuilder, utils::read_test_contract},
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+fn trace_counter_increment(diff_mode: bool) -> (PrestateTrace, Address, Address) {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_gas_limit(BLOCK_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let account_address = account.address;
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let tracer = PrestateTracer::new(diff_mode, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let trace = result.get().cloned().unwrap();
+    (trace, address, account_address)
+}
+
+#[test]
+fn prestate_tracer_basics() {
+    let (trace, address, account_address) = trace_counter_increment(false);
+    let PrestateTrace::Prestate(accounts) = trace else {
+        panic!("Unexpected trace: {trace:?}");
+    };
+
+    let counter_state = &accounts[&address];
+    assert_eq!(counter_state.storage[&H256::zero()], H256::zero());
+    let account_state = &accounts[&account_address];
+    assert_eq!(account_state.nonce, Some(0));
+    assert!(account_state.balance.unwrap() > U256::zero());
+}
+
+#[test]
+fn prestate_tracer_in_diff_mode() {
+    let (trace, address, account_address) = trace_counter_increment(true);
+    let PrestateTrace::Diff { pre, post } = trace else {
+        panic!("Unexpected trace: {trace:?}");
+    };
+
+    assert_eq!(pre[&address].storage[&H256::zero()], H256::zero());
+    assert_eq!(
+        post[&address].storage[&H256::zero()],
+        u256_to_h256(6_u32.into())
+    );
+    // The counter balance and nonce are not changed.
+    assert_eq!(post[&address].balance, None);
+    assert_eq!(post[&address].nonce, None);
+
+    assert_eq!(pre[&account_address].nonce, Some(0));
+    assert_eq!(post[&account_address].nonce, Some(1));
+    // The account has paid the fee.
+    assert!(post[&account_address].balance.unwrap() < pre[&account_address].balance.unwrap());
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_types::{api::StructLogOptions, vm_trace::StructLog, Address, Execute};
+use zksync_utils::u256_to_h256;
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    vm_latest::{
+        constants::BLOCK_GAS_LIMIT,
+        tests::{tester::VmTesterBuilder, utils::read_test_contract},
+        HistoryEnabled, StructLogTracer, ToTracerPointer,
+    },
+};
+
+fn trace_counter_increment(options: StructLogOptions) -> Vec<StructLog> {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_gas_limit(BLOCK_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let tracer = StructLogTracer::new(options, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    Arc::try_unwrap(result).unwrap().take().unwrap()
+}
+
+#[test]
+fn test_basic_behavior() {
+    let logs = trace_counter_increment(StructLogOptions::default());
+    assert!(!logs.is_empty());
+    assert!(logs.iter().all(|log| log.registers.len() == 15));
+    // Memory is not recorded by default.
+    assert!(logs.iter().all(|log| log.memory.is_none()));
+
+    let storage_logs: Vec<_> = logs.iter().filter(|log| log.storage.is_some()).collect();
+    assert!(!storage_logs.is_empty());
+    // The counter value must be written to the storage.
+    // Only the accessed slot is recorded for each storage instruction.
+    assert!(storage_logs
+        .iter()
+        .all(|log| log.storage.as_ref().unwrap().len() == 1));
+    let counter_written = storage_logs.iter().any(|log| {
+        log.op.contains("StorageWrite")
+            && log
+                .storage
+                .as_ref()
+                .unwrap()
+                .values()
+                .any(|value| *value == u256_to_h256(6.into()))
+    });
+    assert!(counter_written, "{storage_logs:#?}");
+}
+
+#[test]
+fn test_memory_recording() {
+    let options = StructLogOptions {
+        enable_memory: true,
+        ..StructLogOptions::default()
+    };
+    let logs = trace_counter_increment(options);
+    assert!(logs.iter().all(|log| log.memory.is_some()));
+}
+
+#[test]
+fn test_limits() {
+    let full_logs = trace_counter_increment(StructLogOptions::default());
+    assert!(full_logs.len() > 10);
+
+    let options = StructLogOptions {
+        disable_storage: true,
+        enable_memory: false,
+        limit: Some(10),
+    };
+    let logs = trace_counter_increment(options);
+    // The execution is cut off at the limit, and recorded instructions are a prefix of the full trace.
+    assert_eq!(logs.len(), 10);
+    assert!(logs
+        .iter()
+        .all(|log| log.memory.is_none() && log.storage.is_none()));
+    let pcs: Vec<_> = logs.iter().map(|log| (log.pc, &log.op)).collect();
+    let full_pcs: Vec<_> = full_logs[..10]
+        .iter()
+        .map(|log| (log.pc, &log.op))
+        .collect();
+    assert_eq!(pcs, full_pcs);
+}
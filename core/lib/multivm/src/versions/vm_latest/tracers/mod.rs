@@ -2,11 +2,13 @@ pub(crate) use default_tracers::DefaultExecutionTracer;
 pub(crate) use pubdata_tracer::PubdataTracer;
 pub(crate) use refunds::RefundsTracer;
 pub(crate) use result_tracer::ResultTracer;
+pub use struct_log::StructLogTracer;
 
 pub(crate) mod default_tracers;
 pub(crate) mod pubdata_tracer;
 pub(crate) mod refunds;
 pub(crate) mod result_tracer;
+pub(crate) mod struct_log;
 
 pub mod dispatcher;
 pub(crate) mod traits;
@@ -0,0 +1,155 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use zk_evm_1_4_0::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_system_constants::BOOTLOADER_ADDRESS;
+use zksync_types::{api::StructLogOptions, vm_trace::StructLog, AccountTreeId, StorageKey};
+use zksync_utils::u256_to_h256;
+
+use crate::{
+    interface::{
+        dyn_tracers::{vm_1_3_3, vm_1_4_0::DynTracer},
+        tracer::VmExecutionStopReason,
+    },
+    vm_latest::{
+        bootloader_state::BootloaderState,
+        old_vm::{history_recorder::HistoryMode, memory::SimpleMemory, utils::heap_page_from_base},
+        tracers::{traits::VmTracer, utils::computational_gas_price},
+        types::internals::ZkSyncVmState,
+    },
+};
+
+/// Tracer recording executed instructions, similar to the default struct logger in Geth.
+///
+/// Instructions executed by the bootloader are not recorded since they are not a part of the transaction.
+/// Once [`StructLogOptions::limit`] instructions are recorded, the remaining instructions are skipped.
+/// Only the latest VM version is supported; older VMs leave the result unset.
+#[derive(Debug, Clone)]
+pub struct StructLogTracer {
+    options: StructLogOptions,
+    logs: Vec<StructLog>,
+    result: Arc<OnceCell<Vec<StructLog>>>,
+}
+
+impl StructLogTracer {
+    pub fn new(options: StructLogOptions, result: Arc<OnceCell<Vec<StructLog>>>) -> Self {
+        Self {
+            options,
+            logs: vec![],
+            result,
+        }
+    }
+
+    fn limit_reached(&self) -> bool {
+        self.options
+            .limit
+            .map_or(false, |limit| self.logs.len() >= limit)
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        let current = state.vm_local_state.callstack.current;
+        if current.this_address == BOOTLOADER_ADDRESS || self.limit_reached() {
+            return;
+        }
+
+        let memory = self.options.enable_memory.then(|| {
+            let heap_page = heap_page_from_base(current.base_memory_page).0;
+            let heap_words = (current.heap_bound + 31) / 32;
+            memory
+                .dump_page_content_as_u256_words(heap_page, 0..heap_words)
+                .into_iter()
+                .map(u256_to_h256)
+                .collect()
+        });
+
+        let opcode = data.opcode.variant.opcode;
+        let storage = match opcode {
+            Opcode::Log(LogOpcode::StorageRead | LogOpcode::StorageWrite)
+                if !self.options.disable_storage =>
+            {
+                let key = u256_to_h256(data.src0_value.value);
+                let value = if opcode == Opcode::Log(LogOpcode::StorageWrite) {
+                    u256_to_h256(data.src1_value.value)
+                } else {
+                    let storage_key =
+                        StorageKey::new(AccountTreeId::new(current.this_address), key);
+                    storage.borrow_mut().read_value(&storage_key)
+                };
+                Some(BTreeMap::from([(key, value)]))
+            }
+            _ => None,
+        };
+
+        self.logs.push(StructLog {
+            pc: current.pc.as_u64(),
+            op: format!("{opcode:?}"),
+            gas: current.ergs_remaining,
+            gas_cost: computational_gas_price(state, &data),
+            depth: state.vm_local_state.callstack.inner.len(),
+            registers: state
+                .vm_local_state
+                .registers
+                .iter()
+                .map(|register| register.value)
+                .collect(),
+            memory,
+            storage,
+        });
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        let logs = std::mem::take(&mut self.logs);
+        self.result.set(logs).unwrap();
+    }
+}
+
+// Older VMs don't support struct logs; the implementations below only allow to pass the tracer to them.
+
+impl<S: WriteStorage, H: crate::vm_virtual_blocks::HistoryMode>
+    vm_1_3_3::DynTracer<S, crate::vm_virtual_blocks::SimpleMemory<H>> for StructLogTracer
+{
+}
+
+impl<H: crate::vm_virtual_blocks::HistoryMode> crate::vm_virtual_blocks::ExecutionEndTracer<H>
+    for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_virtual_blocks::HistoryMode>
+    crate::vm_virtual_blocks::ExecutionProcessing<S, H> for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_virtual_blocks::HistoryMode>
+    crate::vm_virtual_blocks::VmTracer<S, H> for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_refunds_enhancement::HistoryMode>
+    vm_1_3_3::DynTracer<S, crate::vm_refunds_enhancement::SimpleMemory<H>> for StructLogTracer
+{
+}
+
+impl<S: WriteStorage, H: crate::vm_refunds_enhancement::HistoryMode>
+    crate::vm_refunds_enhancement::VmTracer<S, H> for StructLogTracer
+{
+}
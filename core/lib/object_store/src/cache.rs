@@ -0,0 +1,482 @@
+//! Read-through cache storing objects fetched from an [`ObjectStore`] on the local disk.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use tokio::{fs, io};
+
+use crate::{
+    metrics::CACHE_METRICS,
+    raw::{Bucket, ObjectKeysPage, ObjectStore, ObjectStoreError},
+};
+
+/// Characters escaped in object keys, so that each key maps to a single file in the bucket directory.
+const KEY_ENCODE_SET: &AsciiSet = &CONTROLS.add(b'/').add(b'\\').add(b'%');
+/// Directory for objects being written to the cache. Cannot clash with bucket directories.
+const TMP_DIR_NAME: &str = "tmp";
+
+type CacheKey = (Bucket, String);
+
+#[derive(Debug, Clone, Copy)]
+struct CacheEntry {
+    size: u64,
+    last_access: u64,
+}
+
+/// In-memory index of the cached objects tracking their sizes and access order.
+#[derive(Debug, Default)]
+struct LruIndex {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Cached objects keyed by their last access "time"; the least recently used object goes first.
+    access_order: BTreeMap<u64, CacheKey>,
+    total_size: u64,
+    access_counter: u64,
+}
+
+impl LruIndex {
+    fn next_access(&mut self) -> u64 {
+        self.access_counter += 1;
+        self.access_counter
+    }
+
+    fn contains(&self, bucket: Bucket, key: &str) -> bool {
+        self.entries.contains_key(&(bucket, key.to_owned()))
+    }
+
+    /// Marks the object as recently used. Returns `false` if the object is not cached.
+    fn touch(&mut self, bucket: Bucket, key: &str) -> bool {
+        let access = self.next_access();
+        let Some(entry) = self.entries.get_mut(&(bucket, key.to_owned())) else {
+            return false;
+        };
+        let cache_key = self
+            .access_order
+            .remove(&entry.last_access)
+            .expect("cache index is inconsistent");
+        entry.last_access = access;
+        self.access_order.insert(access, cache_key);
+        true
+    }
+
+    fn insert(&mut self, bucket: Bucket, key: String, size: u64) {
+        self.remove(bucket, &key);
+        let last_access = self.next_access();
+        self.entries
+            .insert((bucket, key.clone()), CacheEntry { size, last_access });
+        self.access_order.insert(last_access, (bucket, key));
+        self.total_size += size;
+    }
+
+    fn remove(&mut self, bucket: Bucket, key: &str) -> bool {
+        let Some(entry) = self.entries.remove(&(bucket, key.to_owned())) else {
+            return false;
+        };
+        self.access_order.remove(&entry.last_access);
+        self.total_size -= entry.size;
+        true
+    }
+
+    /// Removes least recently used objects from the index until the total size of cached objects
+    /// does not exceed `max_size`. Returns the removed objects.
+    fn evict(&mut self, max_size: u64) -> Vec<CacheKey> {
+        let mut evicted = vec![];
+        while self.total_size > max_size {
+            let Some((_, cache_key)) = self.access_order.pop_first() else {
+                break;
+            };
+            let entry = self.entries.remove(&cache_key).unwrap();
+            self.total_size -= entry.size;
+            evicted.push(cache_key);
+        }
+        evicted
+    }
+}
+
+/// [`ObjectStore`] wrapper caching objects from the specified buckets on the local disk.
+///
+/// Objects are cached when they are fetched from the wrapped store; once the total size
+/// of cached objects exceeds the configured limit, the least recently used objects are evicted.
+/// Storing or removing an object invalidates its cached copy. The cache assumes that objects
+/// are not modified by other store clients, which holds for prover artifacts keyed by L1 batch
+/// and job IDs.
+///
+/// Cached objects are placed at `{cache_dir}/{bucket}/{encoded_key}` and are reused
+/// after a restart.
+#[derive(Debug)]
+pub(crate) struct CachingObjectStore {
+    inner: Box<dyn ObjectStore>,
+    cache_dir: PathBuf,
+    max_size: u64,
+    cached_buckets: HashSet<Bucket>,
+    index: Mutex<LruIndex>,
+    tmp_file_counter: AtomicU64,
+}
+
+impl CachingObjectStore {
+    /// Creates a cache in the `cache_dir` directory, restoring the index of objects cached
+    /// by previous instances.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cache directory cannot be initialized.
+    pub async fn new(
+        inner: Box<dyn ObjectStore>,
+        cache_dir: PathBuf,
+        max_size: u64,
+        cached_buckets: HashSet<Bucket>,
+    ) -> Self {
+        let this = Self {
+            inner,
+            cache_dir,
+            max_size,
+            cached_buckets,
+            index: Mutex::default(),
+            tmp_file_counter: AtomicU64::new(0),
+        };
+        this.restore_index().await.unwrap_or_else(|err| {
+            let cache_dir = this.cache_dir.display();
+            panic!("failed initializing object store cache at `{cache_dir}`: {err}");
+        });
+        this
+    }
+
+    async fn restore_index(&self) -> io::Result<()> {
+        // Objects in the temporary directory may be partially written, so we just drop them.
+        let tmp_dir = self.cache_dir.join(TMP_DIR_NAME);
+        match fs::remove_dir_all(&tmp_dir).await {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => { /* the directory is removed or did not exist */ }
+        }
+        fs::create_dir_all(&tmp_dir).await?;
+
+        let mut cached_objects = vec![];
+        for &bucket in &self.cached_buckets {
+            let bucket_dir = self.cache_dir.join(bucket.as_str());
+            fs::create_dir_all(&bucket_dir).await?;
+            let mut entries = fs::read_dir(&bucket_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                let file_name = entry.file_name();
+                let Some(file_name) = file_name.to_str() else {
+                    continue;
+                };
+                if !metadata.is_file() {
+                    continue;
+                }
+                let key = percent_decode_str(file_name).decode_utf8_lossy();
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                cached_objects.push((modified, bucket, key.into_owned(), metadata.len()));
+            }
+        }
+        cached_objects.sort_unstable_by_key(|(modified, ..)| *modified);
+
+        let object_count = cached_objects.len();
+        let evicted = {
+            let mut index = self.index.lock().unwrap();
+            for (_, bucket, key, size) in cached_objects {
+                index.insert(bucket, key, size);
+            }
+            let evicted = index.evict(self.max_size);
+            CACHE_METRICS.size.set(index.total_size);
+            evicted
+        };
+        tracing::info!(
+            "Restored object store cache at `{}` with {object_count} objects",
+            self.cache_dir.display()
+        );
+        self.remove_files(evicted).await;
+        Ok(())
+    }
+
+    fn cache_path(&self, bucket: Bucket, key: &str) -> PathBuf {
+        let file_name = utf8_percent_encode(key, KEY_ENCODE_SET).to_string();
+        self.cache_dir.join(bucket.as_str()).join(file_name)
+    }
+
+    async fn cache_object(&self, bucket: Bucket, key: &str, value: &[u8]) -> io::Result<()> {
+        let size = value.len() as u64;
+        if size > self.max_size {
+            return Ok(());
+        }
+
+        // Write the object to a temporary file first, so that a partially written object
+        // is never read from the cache.
+        let tmp_file_name = self.tmp_file_counter.fetch_add(1, Ordering::Relaxed);
+        let tmp_path = self
+            .cache_dir
+            .join(TMP_DIR_NAME)
+            .join(tmp_file_name.to_string());
+        fs::write(&tmp_path, value).await?;
+        if let Err(err) = fs::rename(&tmp_path, self.cache_path(bucket, key)).await {
+            fs::remove_file(&tmp_path).await.ok();
+            return Err(err);
+        }
+
+        let evicted = {
+            let mut index = self.index.lock().unwrap();
+            index.insert(bucket, key.to_owned(), size);
+            let evicted = index.evict(self.max_size);
+            CACHE_METRICS.size.set(index.total_size);
+            evicted
+        };
+        CACHE_METRICS.evicted_objects.inc_by(evicted.len() as u64);
+        self.remove_files(evicted).await;
+        Ok(())
+    }
+
+    async fn invalidate(&self, bucket: Bucket, key: &str) {
+        if !self.cached_buckets.contains(&bucket) {
+            return;
+        }
+        let is_removed = {
+            let mut index = self.index.lock().unwrap();
+            let is_removed = index.remove(bucket, key);
+            CACHE_METRICS.size.set(index.total_size);
+            is_removed
+        };
+        if is_removed {
+            self.remove_files(vec![(bucket, key.to_owned())]).await;
+        }
+    }
+
+    async fn remove_files(&self, cache_keys: Vec<CacheKey>) {
+        for (bucket, key) in cache_keys {
+            let path = self.cache_path(bucket, &key);
+            if let Err(err) = fs::remove_file(&path).await {
+                if err.kind() != io::ErrorKind::NotFound {
+                    tracing::warn!("Failed removing cached object `{}`: {err}", path.display());
+                }
+            }
+        }
+    }
+
+    /// Reads the object from the cache. Returns `None` if the object is not cached.
+    async fn read_cached(&self, bucket: Bucket, key: &str) -> Option<Vec<u8>> {
+        let is_cached = self.index.lock().unwrap().touch(bucket, key);
+        if !is_cached {
+            return None;
+        }
+        match fs::read(self.cache_path(bucket, key)).await {
+            Ok(value) => Some(value),
+            Err(err) => {
+                // The file may have been removed by a concurrent eviction or externally;
+                // in any case, the index entry is stale.
+                tracing::warn!("Failed reading cached object `{key}` from bucket {bucket}: {err}");
+                let mut index = self.index.lock().unwrap();
+                index.remove(bucket, key);
+                CACHE_METRICS.size.set(index.total_size);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CachingObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        if !self.cached_buckets.contains(&bucket) {
+            return self.inner.get_raw(bucket, key).await;
+        }
+
+        if let Some(value) = self.read_cached(bucket, key).await {
+            CACHE_METRICS.hits[&bucket.as_str()].inc();
+            return Ok(value);
+        }
+        CACHE_METRICS.misses[&bucket.as_str()].inc();
+        let value = self.inner.get_raw(bucket, key).await?;
+        if let Err(err) = self.cache_object(bucket, key, &value).await {
+            tracing::warn!("Failed caching object `{key}` from bucket {bucket}: {err}");
+        }
+        Ok(value)
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.invalidate(bucket, key).await;
+        self.inner.put_raw(bucket, key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.invalidate(bucket, key).await;
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<ObjectKeysPage, ObjectStoreError> {
+        self.inner.list_raw(bucket, prefix, page_token).await
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        if self.cached_buckets.contains(&bucket) && self.index.lock().unwrap().contains(bucket, key)
+        {
+            return Ok(true);
+        }
+        self.inner.exists_raw(bucket, key).await
+    }
+
+    async fn remove_many_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        for key in keys {
+            self.invalidate(bucket, key).await;
+        }
+        self.inner.remove_many_raw(bucket, keys).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::mock::MockStore;
+
+    async fn create_cache(cache_dir: &Path, max_size: u64) -> (Arc<MockStore>, CachingObjectStore) {
+        let inner = Arc::new(MockStore::default());
+        let cached_buckets = HashSet::from([Bucket::WitnessInput]);
+        let cache = CachingObjectStore::new(
+            Box::new(inner.clone()),
+            cache_dir.to_owned(),
+            max_size,
+            cached_buckets,
+        )
+        .await;
+        (inner, cache)
+    }
+
+    #[tokio::test]
+    async fn objects_are_read_through_cache() {
+        let dir = TempDir::new("object_store_cache").unwrap();
+        let (inner, cache) = create_cache(dir.path(), 1_024).await;
+        for bucket in [Bucket::WitnessInput, Bucket::ProofsFri] {
+            inner.put_raw(bucket, "test/key", vec![1; 8]).await.unwrap();
+            let value = cache.get_raw(bucket, "test/key").await.unwrap();
+            assert_eq!(value, [1; 8]);
+            inner.remove_raw(bucket, "test/key").await.unwrap();
+        }
+
+        let value = cache
+            .get_raw(Bucket::WitnessInput, "test/key")
+            .await
+            .unwrap();
+        assert_eq!(value, [1; 8]);
+        assert!(cache
+            .exists_raw(Bucket::WitnessInput, "test/key")
+            .await
+            .unwrap());
+        assert!(dir.path().join("witness_inputs/test%2Fkey").is_file());
+        // Objects in uncached buckets are always fetched from the store.
+        let err = cache
+            .get_raw(Bucket::ProofsFri, "test/key")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_objects_are_evicted() {
+        let dir = TempDir::new("object_store_cache").unwrap();
+        let (inner, cache) = create_cache(dir.path(), 10).await;
+        for key in ["a", "b", "c", "large"] {
+            let size = if key == "large" { 11 } else { 4 };
+            inner
+                .put_raw(Bucket::WitnessInput, key, vec![0; size])
+                .await
+                .unwrap();
+        }
+
+        cache.get_raw(Bucket::WitnessInput, "a").await.unwrap();
+        cache.get_raw(Bucket::WitnessInput, "b").await.unwrap();
+        cache.get_raw(Bucket::WitnessInput, "a").await.unwrap();
+        cache.get_raw(Bucket::WitnessInput, "c").await.unwrap();
+        // Objects larger than the cache size limit are not cached at all.
+        cache.get_raw(Bucket::WitnessInput, "large").await.unwrap();
+
+        let index = cache.index.lock().unwrap();
+        assert_eq!(index.total_size, 8);
+        assert!(index.contains(Bucket::WitnessInput, "a"));
+        assert!(!index.contains(Bucket::WitnessInput, "b"));
+        assert!(index.contains(Bucket::WitnessInput, "c"));
+        assert!(!index.contains(Bucket::WitnessInput, "large"));
+        assert!(!dir.path().join("witness_inputs/b").exists());
+        assert!(!dir.path().join("witness_inputs/large").exists());
+    }
+
+    #[tokio::test]
+    async fn updating_objects_invalidates_cache() {
+        let dir = TempDir::new("object_store_cache").unwrap();
+        let (_, cache) = create_cache(dir.path(), 1_024).await;
+        cache
+            .put_raw(Bucket::WitnessInput, "key", vec![1])
+            .await
+            .unwrap();
+        cache.get_raw(Bucket::WitnessInput, "key").await.unwrap();
+        cache
+            .put_raw(Bucket::WitnessInput, "key", vec![2])
+            .await
+            .unwrap();
+        let value = cache.get_raw(Bucket::WitnessInput, "key").await.unwrap();
+        assert_eq!(value, [2]);
+
+        cache
+            .remove_many_raw(Bucket::WitnessInput, &["key".to_owned()])
+            .await
+            .unwrap();
+        assert!(!cache.exists_raw(Bucket::WitnessInput, "key").await.unwrap());
+        assert!(!dir.path().join("witness_inputs/key").exists());
+        assert_eq!(cache.index.lock().unwrap().total_size, 0);
+    }
+
+    #[tokio::test]
+    async fn cache_is_restored_after_restart() {
+        let dir = TempDir::new("object_store_cache").unwrap();
+        let (inner, cache) = create_cache(dir.path(), 1_024).await;
+        for key in ["a", "b/c"] {
+            inner
+                .put_raw(Bucket::WitnessInput, key, vec![1; 4])
+                .await
+                .unwrap();
+            cache.get_raw(Bucket::WitnessInput, key).await.unwrap();
+        }
+        drop(cache);
+
+        // The new inner store is empty, so objects can only be served from the cache.
+        let (_, cache) = create_cache(dir.path(), 1_024).await;
+        for key in ["a", "b/c"] {
+            let value = cache.get_raw(Bucket::WitnessInput, key).await.unwrap();
+            assert_eq!(value, [1; 4]);
+        }
+        drop(cache);
+
+        // Restoring the cache with a smaller size limit should evict excessive objects.
+        let (_, cache) = create_cache(dir.path(), 6).await;
+        let index = cache.index.lock().unwrap();
+        assert_eq!(index.total_size, 4);
+        assert_eq!(index.entries.len(), 1);
+    }
+}
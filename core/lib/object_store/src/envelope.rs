@@ -0,0 +1,174 @@
+//! Envelope wrapping serialized values stored via `<dyn ObjectStore>::put()`.
+//!
+//! An enveloped object has the following layout:
+//!
+//! | Offset | Size | Contents                                               |
+//! |--------|------|--------------------------------------------------------|
+//! | 0      | 4    | Magic bytes `b"zkOE"`                                  |
+//! | 4      | 1    | Envelope format version (currently, 1)                 |
+//! | 5      | 1    | [`Codec`] used to encode the payload                   |
+//! | 6      | 4    | Big-endian CRC32 checksum of the encoded payload       |
+//! | 10     | ..   | Encoded payload                                        |
+//!
+//! Objects not starting with the magic bytes were written before envelopes were introduced;
+//! they are returned as-is.
+
+use std::io::{Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::raw::BoxedError;
+
+const MAGIC: [u8; 4] = *b"zkOE";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 10;
+const CHECKSUM_RANGE: std::ops::Range<usize> = 6..HEADER_LEN;
+const ZSTD_LEVEL: i32 = 3;
+
+/// Codec used to encode serialized values in the object store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Codec {
+    /// Payload is stored without compression.
+    None,
+    /// Payload is compressed using gzip.
+    Gzip,
+    /// Payload is compressed using zstd.
+    Zstd,
+}
+
+impl Codec {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Gzip => 1,
+            Self::Zstd => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, BoxedError> {
+        Ok(match byte {
+            0 => Self::None,
+            1 => Self::Gzip,
+            2 => Self::Zstd,
+            _ => return Err(format!("unknown envelope codec: {byte}").into()),
+        })
+    }
+}
+
+/// Wraps the serialized `payload` into an envelope, encoding it with the specified `codec`.
+pub(crate) fn encode(codec: Codec, payload: &[u8]) -> Result<Vec<u8>, BoxedError> {
+    let mut buffer = Vec::with_capacity(HEADER_LEN + payload.len());
+    buffer.extend_from_slice(&MAGIC);
+    buffer.push(VERSION);
+    buffer.push(codec.to_byte());
+    buffer.extend_from_slice(&[0; 4]); // placeholder for the checksum
+
+    match codec {
+        Codec::None => buffer.extend_from_slice(payload),
+        Codec::Gzip => {
+            let mut encoder = GzEncoder::new(buffer, Compression::default());
+            encoder.write_all(payload)?;
+            buffer = encoder.finish()?;
+        }
+        Codec::Zstd => zstd::stream::copy_encode(payload, &mut buffer, ZSTD_LEVEL)?,
+    }
+
+    let checksum = crc32fast::hash(&buffer[HEADER_LEN..]);
+    buffer[CHECKSUM_RANGE].copy_from_slice(&checksum.to_be_bytes());
+    Ok(buffer)
+}
+
+/// Unwraps the serialized payload from an envelope. Objects without an envelope are returned as-is.
+///
+/// # Errors
+///
+/// Returns an error if the envelope is malformed, the checksum does not match, or the payload
+/// cannot be decoded.
+pub(crate) fn decode(mut bytes: Vec<u8>) -> Result<Vec<u8>, BoxedError> {
+    if !bytes.starts_with(&MAGIC) {
+        return Ok(bytes);
+    }
+    if bytes.len() < HEADER_LEN {
+        return Err("truncated envelope header".into());
+    }
+
+    let version = bytes[MAGIC.len()];
+    if version != VERSION {
+        return Err(format!("unsupported envelope version: {version}").into());
+    }
+    let codec = Codec::from_byte(bytes[MAGIC.len() + 1])?;
+    let expected_checksum = u32::from_be_bytes(bytes[CHECKSUM_RANGE].try_into().unwrap());
+    let encoded_payload = &bytes[HEADER_LEN..];
+    let actual_checksum = crc32fast::hash(encoded_payload);
+    if actual_checksum != expected_checksum {
+        let err = format!(
+            "envelope checksum mismatch: expected {expected_checksum:08x}, got {actual_checksum:08x}"
+        );
+        return Err(err.into());
+    }
+
+    match codec {
+        Codec::None => {
+            bytes.drain(..HEADER_LEN);
+            Ok(bytes)
+        }
+        Codec::Gzip => {
+            let mut payload = vec![];
+            GzDecoder::new(encoded_payload).read_to_end(&mut payload)?;
+            Ok(payload)
+        }
+        Codec::Zstd => zstd::stream::decode_all(encoded_payload).map_err(From::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOAD: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, lorem ipsum";
+
+    #[test]
+    fn envelope_roundtrip() {
+        for codec in [Codec::None, Codec::Gzip, Codec::Zstd] {
+            let encoded = encode(codec, PAYLOAD).unwrap();
+            assert!(encoded.starts_with(&MAGIC));
+            assert_eq!(encoded[5], codec.to_byte());
+            let decoded = decode(encoded).unwrap();
+            assert_eq!(decoded, PAYLOAD, "{codec:?}");
+        }
+    }
+
+    #[test]
+    fn legacy_objects_are_returned_as_is() {
+        let decoded = decode(PAYLOAD.to_vec()).unwrap();
+        assert_eq!(decoded, PAYLOAD);
+        assert_eq!(decode(vec![]).unwrap(), b"");
+    }
+
+    #[test]
+    fn corrupted_payload_is_detected() {
+        for codec in [Codec::None, Codec::Gzip, Codec::Zstd] {
+            let mut encoded = encode(codec, PAYLOAD).unwrap();
+            *encoded.last_mut().unwrap() ^= 1;
+            let err = decode(encoded).unwrap_err().to_string();
+            assert!(err.contains("checksum mismatch"), "{err}");
+        }
+    }
+
+    #[test]
+    fn malformed_headers_are_rejected() {
+        let err = decode(b"zkOE\x01".to_vec()).unwrap_err().to_string();
+        assert!(err.contains("truncated"), "{err}");
+
+        let mut encoded = encode(Codec::None, PAYLOAD).unwrap();
+        encoded[4] = 2;
+        let err = decode(encoded).unwrap_err().to_string();
+        assert!(err.contains("unsupported envelope version"), "{err}");
+
+        let mut encoded = encode(Codec::None, PAYLOAD).unwrap();
+        encoded[5] = 100;
+        let err = decode(encoded).unwrap_err().to_string();
+        assert!(err.contains("unknown envelope codec"), "{err}");
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use tokio::{fs, io};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectKeysPage, ObjectStore, ObjectStoreError};
 
 impl From<io::Error> for ObjectStoreError {
     fn from(err: io::Error) -> Self {
@@ -21,19 +21,7 @@ pub(crate) struct FileBackedObjectStore {
 
 impl FileBackedObjectStore {
     pub async fn new(base_dir: String) -> Self {
-        for bucket in &[
-            Bucket::ProverJobs,
-            Bucket::WitnessInput,
-            Bucket::LeafAggregationWitnessJobs,
-            Bucket::NodeAggregationWitnessJobs,
-            Bucket::SchedulerWitnessJobs,
-            Bucket::ProverJobsFri,
-            Bucket::LeafAggregationWitnessJobsFri,
-            Bucket::NodeAggregationWitnessJobsFri,
-            Bucket::SchedulerWitnessJobsFri,
-            Bucket::ProofsFri,
-            Bucket::StorageSnapshot,
-        ] {
+        for bucket in Bucket::ALL {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path)
                 .await
@@ -71,6 +59,39 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<ObjectKeysPage, ObjectStoreError> {
+        let mut entries = fs::read_dir(self.storage_prefix_raw(bucket)).await?;
+        let mut keys = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            if let Ok(key) = entry.file_name().into_string() {
+                keys.push(key);
+            }
+        }
+        Ok(ObjectKeysPage::paginate(
+            keys,
+            prefix,
+            page_token,
+            ObjectKeysPage::LOCAL_PAGE_SIZE,
+        ))
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        match fs::metadata(filename).await {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.base_dir, bucket)
     }
@@ -125,4 +146,61 @@ mod test {
             .await;
         assert!(result.is_ok(), "result must be OK");
     }
+
+    #[tokio::test]
+    async fn test_exists() {
+        let dir = TempDir::new("test-data").unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await;
+        object_store
+            .put_raw(Bucket::ProverJobs, "test-key.bin", vec![0, 1])
+            .await
+            .unwrap();
+
+        let exists = object_store
+            .exists_raw(Bucket::ProverJobs, "test-key.bin")
+            .await
+            .unwrap();
+        assert!(exists);
+        let exists = object_store
+            .exists_raw(Bucket::ProverJobs, "missing-key.bin")
+            .await
+            .unwrap();
+        assert!(!exists);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_remove_many() {
+        let dir = TempDir::new("test-data").unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await;
+        for key in ["1_0.bin", "1_1.bin", "2_0.bin"] {
+            object_store
+                .put_raw(Bucket::ProverJobsFri, key, vec![0, 1])
+                .await
+                .unwrap();
+        }
+
+        let page = object_store
+            .list_raw(Bucket::ProverJobsFri, "1_", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["1_0.bin", "1_1.bin"]);
+        assert_eq!(page.next_page_token, None);
+
+        let keys = [
+            "1_0.bin".to_owned(),
+            "1_1.bin".to_owned(),
+            "3_0.bin".to_owned(),
+        ];
+        object_store
+            .remove_many_raw(Bucket::ProverJobsFri, &keys)
+            .await
+            .unwrap();
+        let page = object_store
+            .list_raw(Bucket::ProverJobsFri, "", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["2_0.bin"]);
+    }
 }
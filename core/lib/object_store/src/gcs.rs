@@ -1,8 +1,9 @@
 //! GCS-based [`ObjectStore`] implementation.
 
-use std::{fmt, future::Future, time::Duration};
+use std::{fmt, future::Future};
 
 use async_trait::async_trait;
+use futures::{stream, StreamExt, TryStreamExt};
 use google_cloud_auth::{credentials::CredentialsFile, error::Error};
 use google_cloud_storage::{
     client::{Client, ClientConfig},
@@ -11,6 +12,7 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error as HttpError,
@@ -20,32 +22,16 @@ use http::StatusCode;
 
 use crate::{
     metrics::GCS_METRICS,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectKeysPage, ObjectStore, ObjectStoreError},
+    retries::{retry, retry_if},
 };
 
-async fn retry<T, E, Fut, F>(max_retries: u16, mut f: F) -> Result<T, E>
-where
-    E: fmt::Display,
-    Fut: Future<Output = Result<T, E>>,
-    F: FnMut() -> Fut,
-{
-    let mut retries = 1;
-    let mut backoff = 1;
-    loop {
-        match f().await {
-            Ok(result) => return Ok(result),
-            Err(err) => {
-                tracing::warn!(%err, "Failed GCS request {retries}/{max_retries}, retrying.");
-                if retries > max_retries {
-                    return Err(err);
-                }
-                retries += 1;
-                tokio::time::sleep(Duration::from_secs(backoff)).await;
-                backoff *= 2;
-            }
-        }
-    }
-}
+/// Maximum number of objects returned by a single listing request. This is the upper bound
+/// recommended by GCS.
+const LIST_PAGE_SIZE: i32 = 1_000;
+/// Maximum number of concurrent requests when removing multiple objects. The GCS client
+/// does not support batch requests, so each object is removed with a separate request.
+const MAX_CONCURRENT_REMOVALS: usize = 32;
 
 pub struct GoogleCloudStorage {
     bucket_prefix: String,
@@ -208,51 +194,100 @@ impl ObjectStore for GoogleCloudStorage {
         self.remove_inner(bucket.as_str(), key).await
     }
 
-    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
-        format!(
-            "https://storage.googleapis.com/{}/{}",
-            self.bucket_prefix.clone(),
-            bucket.as_str()
-        )
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use std::sync::atomic::{AtomicU16, Ordering};
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<ObjectKeysPage, ObjectStoreError> {
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing objects in GCS with prefix {bucket_prefix}{prefix} from bucket {}",
+            self.bucket_prefix
+        );
 
-    use super::*;
+        let request = ListObjectsRequest {
+            bucket: self.bucket_prefix.clone(),
+            prefix: Some(format!("{bucket_prefix}{prefix}")),
+            page_token: page_token.map(str::to_owned),
+            max_results: Some(LIST_PAGE_SIZE),
+            ..ListObjectsRequest::default()
+        };
+        let response = retry(self.max_retries, || self.client.list_objects(&request))
+            .await
+            .map_err(ObjectStoreError::from)?;
 
-    #[tokio::test]
-    async fn test_retry_success_immediate() {
-        let result = retry(2, || async { Ok::<_, &'static str>(42) }).await;
-        assert_eq!(result, Ok(42));
+        let objects = response.items.unwrap_or_default();
+        let keys = objects
+            .into_iter()
+            .filter_map(|object| {
+                let key = object.name.strip_prefix(&bucket_prefix)?;
+                Some(key.to_owned())
+            })
+            .collect();
+        Ok(ObjectKeysPage {
+            keys,
+            next_page_token: response.next_page_token,
+        })
     }
 
-    #[tokio::test]
-    async fn test_retry_failure_exhausted() {
-        let result = retry(2, || async { Err::<i32, _>("oops") }).await;
-        assert_eq!(result, Err("oops"));
-    }
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Checking existence of key {filename} in GCS bucket {}",
+            self.bucket_prefix
+        );
 
-    async fn retry_success_after_n_retries(n: u16) -> Result<u32, String> {
-        let retries = AtomicU16::new(0);
-        let result = retry(n, || async {
-            let retries = retries.fetch_add(1, Ordering::Relaxed);
-            if retries + 1 == n {
-                Ok(42)
-            } else {
-                Err("oops")
-            }
+        // Only fetches object metadata, not its contents.
+        let request = GetObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            object: filename,
+            ..GetObjectRequest::default()
+        };
+        let is_transient =
+            |err: &ObjectStoreError| !matches!(err, ObjectStoreError::KeyNotFound(_));
+        let result = retry_if(self.max_retries, is_transient, || async {
+            self.client
+                .get_object(&request)
+                .await
+                .map_err(ObjectStoreError::from)
         })
         .await;
 
-        result.map_err(|_| "Retry failed".to_string())
+        match result {
+            Ok(_) => Ok(true),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
     }
 
-    #[tokio::test]
-    async fn test_retry_success_after_retry() {
-        let result = retry(2, || retry_success_after_n_retries(2)).await;
-        assert_eq!(result, Ok(42));
+    async fn remove_many_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        tracing::trace!(
+            "Removing {} objects from GCS bucket {} with prefix {bucket}/",
+            keys.len(),
+            self.bucket_prefix
+        );
+        stream::iter(keys)
+            .map(|key| async move {
+                match self.remove_inner(bucket.as_str(), key).await {
+                    Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => Ok(()),
+                    Err(err) => Err(err),
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_REMOVALS)
+            .try_collect()
+            .await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!(
+            "https://storage.googleapis.com/{}/{}",
+            self.bucket_prefix.clone(),
+            bucket.as_str()
+        )
     }
 }
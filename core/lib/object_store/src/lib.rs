@@ -1,8 +1,12 @@
 //! This crate provides the [object storage abstraction](ObjectStore) that allows to get,
-//! put and remove binary blobs. The following implementations are available:
+//! put, remove and list binary blobs. The following implementations are available:
 //!
 //! - File-based storage saving blobs as separate files in the local filesystem
 //! - GCS-based storage
+//! - S3-compatible storage (AWS S3, MinIO etc.)
+//!
+//! Any of these implementations can be wrapped into a read-through cache storing fetched objects
+//! on the local disk; the cache is enabled for specific buckets in the configuration.
 //!
 //! These implementations are not exposed externally. Instead, a store trait object
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
@@ -12,7 +16,8 @@
 //! Besides the lower-level storage abstraction, the crate provides high-level
 //! typesafe `<dyn ObjectStore>::get()` and `<dyn ObjectStore>::put()` methods
 //! to store [(de)serializable objects](StoredObject). Prefer using these methods
-//! whenever possible.
+//! whenever possible. Objects stored using these methods are wrapped into an envelope
+//! recording the [`Codec`] used to compress the object and its checksum.
 
 // Linter settings.
 #![warn(missing_debug_implementations, bare_trait_objects)]
@@ -23,12 +28,16 @@
     clippy::doc_markdown
 )]
 
+mod cache;
+mod envelope;
 mod file;
 mod gcs;
 mod metrics;
 mod mock;
 mod objects;
 mod raw;
+mod retries;
+mod s3;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
@@ -39,6 +48,7 @@ pub mod _reexports {
 }
 
 pub use self::{
+    envelope::Codec,
     objects::{AggregationsKey, CircuitKey, ClosedFormInputKey, FriCircuitKey, StoredObject},
-    raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStoreFactory},
+    raw::{Bucket, ObjectKeysPage, ObjectStore, ObjectStoreError, ObjectStoreFactory},
 };
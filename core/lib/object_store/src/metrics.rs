@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, LabeledFamily, LatencyObserver, Metrics};
+use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, LatencyObserver, Metrics, Unit};
 
 use crate::Bucket;
 
@@ -29,3 +29,46 @@ impl GcsMetrics {
 
 #[vise::register]
 pub(crate) static GCS_METRICS: vise::Global<GcsMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_object_store_s3")]
+pub(crate) struct S3Metrics {
+    /// Latency to fetch an object from S3.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
+    fetching_time: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Latency to store an object in S3.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
+    storing_time: LabeledFamily<&'static str, Histogram<Duration>>,
+}
+
+impl S3Metrics {
+    pub fn start_fetch(&self, bucket: Bucket) -> LatencyObserver<'_> {
+        self.fetching_time[&bucket.as_str()].start()
+    }
+
+    pub fn start_store(&self, bucket: Bucket) -> LatencyObserver<'_> {
+        self.storing_time[&bucket.as_str()].start()
+    }
+}
+
+#[vise::register]
+pub(crate) static S3_METRICS: vise::Global<S3Metrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_object_store_cache")]
+pub(crate) struct CacheMetrics {
+    /// Number of objects served from the local disk cache.
+    #[metrics(labels = ["bucket"])]
+    pub hits: LabeledFamily<&'static str, Counter>,
+    /// Number of objects that were not found in the local disk cache and were fetched from the store.
+    #[metrics(labels = ["bucket"])]
+    pub misses: LabeledFamily<&'static str, Counter>,
+    /// Number of objects evicted from the local disk cache because of its size limit.
+    pub evicted_objects: Counter,
+    /// Total size of objects in the local disk cache.
+    #[metrics(unit = Unit::Bytes)]
+    pub size: Gauge<u64>,
+}
+
+#[vise::register]
+pub(crate) static CACHE_METRICS: vise::Global<CacheMetrics> = vise::Global::new();
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use tokio::sync::Mutex;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectKeysPage, ObjectStore, ObjectStoreError};
 
 type BucketMap = HashMap<String, Vec<u8>>;
 
@@ -46,6 +46,31 @@ impl ObjectStore for MockStore {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<ObjectKeysPage, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        let keys = lock
+            .get(&bucket)
+            .map(|bucket_map| bucket_map.keys().cloned().collect())
+            .unwrap_or_default();
+        Ok(ObjectKeysPage::paginate(
+            keys,
+            prefix,
+            page_token,
+            ObjectKeysPage::LOCAL_PAGE_SIZE,
+        ))
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        let bucket_map = lock.get(&bucket);
+        Ok(bucket_map.map_or(false, |bucket_map| bucket_map.contains_key(key)))
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
@@ -2,7 +2,7 @@
 
 use std::io::Read;
 
-use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use flate2::read::GzDecoder;
 use zksync_types::{
     aggregated_operations::L1BatchProofForL1,
     proofs::{AggregationRound, PrepareBasicCircuitsJob},
@@ -24,7 +24,29 @@ use zksync_types::{
     L1BatchNumber,
 };
 
-use crate::raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError};
+use crate::{
+    envelope::{self, Codec},
+    raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError},
+};
+
+/// Deserializes JSON-encoded snapshot data. Snapshots written before envelopes were introduced
+/// are gzipped on their own, so they are decompressed first.
+fn deserialize_snapshot_json<T: serde::de::DeserializeOwned>(
+    bytes: Vec<u8>,
+) -> Result<T, BoxedError> {
+    const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decoder = GzDecoder::new(&bytes[..]);
+        let mut decompressed_bytes = Vec::new();
+        decoder
+            .read_to_end(&mut decompressed_bytes)
+            .map_err(BoxedError::from)?;
+        serde_json::from_slice(&decompressed_bytes).map_err(From::from)
+    } else {
+        serde_json::from_slice(&bytes).map_err(From::from)
+    }
+}
 
 /// Object that can be stored in an [`ObjectStore`].
 pub trait StoredObject: Sized {
@@ -34,6 +56,10 @@ pub trait StoredObject: Sized {
     /// that borrow data; see [`CircuitKey`] for an example.
     type Key<'a>: Copy;
 
+    /// Codec used to encode the serialized value in the object store. Values are wrapped
+    /// into an envelope recording the codec and the checksum of the encoded data.
+    const CODEC: Codec = Codec::None;
+
     /// Encodes the object key to a string.
     fn encode_key(key: Self::Key<'_>) -> String;
 
@@ -73,6 +99,7 @@ macro_rules! serialize_using_bincode {
 
 impl StoredObject for SnapshotFactoryDependencies {
     const BUCKET: Bucket = Bucket::StorageSnapshot;
+    const CODEC: Codec = Codec::Gzip;
     type Key<'a> = L1BatchNumber;
 
     fn encode_key(key: Self::Key<'_>) -> String {
@@ -81,23 +108,17 @@ impl StoredObject for SnapshotFactoryDependencies {
 
     //TODO use better language agnostic serialization format like protobuf
     fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
-        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-        serde_json::to_writer(&mut encoder, self).map_err(BoxedError::from)?;
-        encoder.finish().map_err(From::from)
+        serde_json::to_vec(self).map_err(From::from)
     }
 
     fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
-        let mut decoder = GzDecoder::new(&bytes[..]);
-        let mut decompressed_bytes = Vec::new();
-        decoder
-            .read_to_end(&mut decompressed_bytes)
-            .map_err(BoxedError::from)?;
-        serde_json::from_slice(&decompressed_bytes).map_err(From::from)
+        deserialize_snapshot_json(bytes)
     }
 }
 
 impl StoredObject for SnapshotStorageLogsChunk {
     const BUCKET: Bucket = Bucket::StorageSnapshot;
+    const CODEC: Codec = Codec::Gzip;
     type Key<'a> = SnapshotStorageLogsStorageKey;
 
     fn encode_key(key: Self::Key<'_>) -> String {
@@ -109,23 +130,17 @@ impl StoredObject for SnapshotStorageLogsChunk {
 
     //TODO use better language agnostic serialization format like protobuf
     fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
-        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-        serde_json::to_writer(&mut encoder, self).map_err(BoxedError::from)?;
-        encoder.finish().map_err(From::from)
+        serde_json::to_vec(self).map_err(From::from)
     }
 
     fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
-        let mut decoder = GzDecoder::new(&bytes[..]);
-        let mut decompressed_bytes = Vec::new();
-        decoder
-            .read_to_end(&mut decompressed_bytes)
-            .map_err(BoxedError::from)?;
-        serde_json::from_slice(&decompressed_bytes).map_err(From::from)
+        deserialize_snapshot_json(bytes)
     }
 }
 
 impl StoredObject for WitnessBlockState {
     const BUCKET: Bucket = Bucket::WitnessInput;
+    const CODEC: Codec = Codec::Zstd;
     type Key<'a> = L1BatchNumber;
 
     fn encode_key(key: Self::Key<'_>) -> String {
@@ -137,6 +152,7 @@ impl StoredObject for WitnessBlockState {
 
 impl StoredObject for PrepareBasicCircuitsJob {
     const BUCKET: Bucket = Bucket::WitnessInput;
+    const CODEC: Codec = Codec::Zstd;
     type Key<'a> = L1BatchNumber;
 
     fn encode_key(key: Self::Key<'_>) -> String {
@@ -275,20 +291,23 @@ impl StoredObject for L1BatchProofForL1 {
 }
 
 impl dyn ObjectStore + '_ {
-    /// Fetches the value for the given key if it exists.
+    /// Fetches the value for the given key if it exists. Both enveloped values and values
+    /// stored before envelopes were introduced are supported.
     ///
     /// # Errors
     ///
     /// Returns an error if an object with the `key` does not exist, cannot be accessed,
-    /// or cannot be deserialized.
+    /// or cannot be deserialized (including the case when its checksum does not match).
     pub async fn get<V: StoredObject>(&self, key: V::Key<'_>) -> Result<V, ObjectStoreError> {
         let key = V::encode_key(key);
         let bytes = self.get_raw(V::BUCKET, &key).await?;
+        let bytes = envelope::decode(bytes).map_err(ObjectStoreError::Serialization)?;
         V::deserialize(bytes).map_err(ObjectStoreError::Serialization)
     }
 
     /// Stores the value associating it with the key. If the key already exists,
-    /// the value is replaced.
+    /// the value is replaced. The value is wrapped into an envelope and encoded using
+    /// [`StoredObject::CODEC`].
     ///
     /// # Errors
     ///
@@ -300,6 +319,7 @@ impl dyn ObjectStore + '_ {
     ) -> Result<String, ObjectStoreError> {
         let key = V::encode_key(key);
         let bytes = value.serialize().map_err(ObjectStoreError::Serialization)?;
+        let bytes = envelope::encode(V::CODEC, &bytes).map_err(ObjectStoreError::Serialization)?;
         self.put_raw(V::BUCKET, &key, bytes).await?;
         Ok(key)
     }
@@ -311,7 +331,63 @@ impl dyn ObjectStore + '_ {
 
 #[cfg(test)]
 mod tests {
+    use flate2::{write::GzEncoder, Compression};
+    use zksync_types::{
+        snapshots::{SnapshotFactoryDependency, SnapshotStorageLog},
+        StorageKey, H256,
+    };
+
     use super::*;
+    use crate::ObjectStoreFactory;
+
+    #[tokio::test]
+    async fn snapshot_objects_are_enveloped() {
+        let store = ObjectStoreFactory::mock().create_store().await;
+        let deps = SnapshotFactoryDependencies {
+            factory_deps: vec![SnapshotFactoryDependency {
+                bytecode: vec![1; 64],
+            }],
+        };
+        let key = store.put(L1BatchNumber(1), &deps).await.unwrap();
+
+        let raw_bytes = store.get_raw(Bucket::StorageSnapshot, &key).await.unwrap();
+        assert!(raw_bytes.starts_with(b"zkOE"));
+        let json = envelope::decode(raw_bytes).unwrap();
+        let restored: SnapshotFactoryDependencies = serde_json::from_slice(&json).unwrap();
+        assert_eq!(restored.factory_deps[0].bytecode, [1; 64]);
+
+        let restored: SnapshotFactoryDependencies = store.get(L1BatchNumber(1)).await.unwrap();
+        assert_eq!(restored.factory_deps[0].bytecode, [1; 64]);
+    }
+
+    #[tokio::test]
+    async fn reading_legacy_gzipped_snapshot_objects() {
+        let store = ObjectStoreFactory::mock().create_store().await;
+        let chunk_key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: L1BatchNumber(1),
+            chunk_id: 0,
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: vec![SnapshotStorageLog {
+                key: StorageKey::new(Default::default(), H256::repeat_byte(1)),
+                value: H256::repeat_byte(2),
+                l1_batch_number_of_initial_write: L1BatchNumber(1),
+                enumeration_index: 1,
+            }],
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, &chunk).unwrap();
+        let gzipped = encoder.finish().unwrap();
+        let key = SnapshotStorageLogsChunk::encode_key(chunk_key);
+        store
+            .put_raw(Bucket::StorageSnapshot, &key, gzipped)
+            .await
+            .unwrap();
+
+        let restored: SnapshotStorageLogsChunk = store.get(chunk_key).await.unwrap();
+        assert_eq!(restored.storage_logs.len(), 1);
+        assert_eq!(restored.storage_logs[0].value, chunk.storage_logs[0].value);
+    }
 
     #[test]
     fn test_storage_logs_filesnames_generate_corretly() {
@@ -1,9 +1,19 @@
-use std::{error, fmt, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+    path::PathBuf,
+    sync::{Arc, Weak},
+};
 
 use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
-use crate::{file::FileBackedObjectStore, gcs::GoogleCloudStorage, mock::MockStore};
+use crate::{
+    cache::CachingObjectStore, file::FileBackedObjectStore, gcs::GoogleCloudStorage,
+    mock::MockStore, s3::S3Store,
+};
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -23,6 +33,25 @@ pub enum Bucket {
 }
 
 impl Bucket {
+    pub(crate) const ALL: [Self; 11] = [
+        Self::ProverJobs,
+        Self::WitnessInput,
+        Self::LeafAggregationWitnessJobs,
+        Self::NodeAggregationWitnessJobs,
+        Self::SchedulerWitnessJobs,
+        Self::ProverJobsFri,
+        Self::LeafAggregationWitnessJobsFri,
+        Self::NodeAggregationWitnessJobsFri,
+        Self::SchedulerWitnessJobsFri,
+        Self::ProofsFri,
+        Self::StorageSnapshot,
+    ];
+
+    /// Parses a bucket from its name as returned by [`Self::as_str()`].
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|bucket| bucket.as_str() == name)
+    }
+
     pub(crate) fn as_str(self) -> &'static str {
         match self {
             Self::ProverJobs => "prover_jobs",
@@ -80,6 +109,46 @@ impl error::Error for ObjectStoreError {
     }
 }
 
+/// Page of object keys returned by [`ObjectStore::list_raw()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectKeysPage {
+    /// Object keys in the same form as accepted by other [`ObjectStore`] methods
+    /// (i.e., not including the bucket prefix).
+    pub keys: Vec<String>,
+    /// Token to pass to [`ObjectStore::list_raw()`] to retrieve the next page.
+    /// `None` if this page is the last one.
+    pub next_page_token: Option<String>,
+}
+
+impl ObjectKeysPage {
+    /// Maximum number of keys in a page returned by local stores (mock and file-backed ones).
+    pub(crate) const LOCAL_PAGE_SIZE: usize = 1_000;
+
+    /// Paginates keys for local stores. The page token is the last key on the previous page.
+    pub(crate) fn paginate(
+        mut keys: Vec<String>,
+        prefix: &str,
+        page_token: Option<&str>,
+        page_size: usize,
+    ) -> Self {
+        keys.retain(|key| {
+            key.starts_with(prefix) && page_token.map_or(true, |token| key.as_str() > token)
+        });
+        keys.sort_unstable();
+
+        let next_page_token = if keys.len() > page_size {
+            keys.truncate(page_size);
+            keys.last().cloned()
+        } else {
+            None
+        };
+        Self {
+            keys,
+            next_page_token,
+        }
+    }
+}
+
 /// Functionality to fetch and store byte blobs from an object store (AWS S3, Google Cloud Storage,
 /// Azure Blobstore etc).
 ///
@@ -116,6 +185,49 @@ pub trait ObjectStore: fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Lists keys of the objects in the given bucket that start with the specified `prefix`.
+    /// The results are paginated; to get the next page, pass [`ObjectKeysPage::next_page_token`]
+    /// of the previous page as `page_token`. The order of keys is implementation-specific.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listing operation fails.
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<ObjectKeysPage, ObjectStoreError>;
+
+    /// Checks whether an object with the given key exists in the given bucket without fetching it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object cannot be accessed.
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError>;
+
+    /// Removes values associated with the given keys from the given bucket. Keys that do not exist
+    /// are ignored. The default implementation removes keys one by one; S3 overrides it with batched
+    /// `DeleteObjects` requests, and GCS with concurrent removals (its client has no batch API).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if removal fails for any of the keys. In this case, some of the keys
+    /// may be already removed.
+    async fn remove_many_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        for key in keys {
+            match self.remove_raw(bucket, key).await {
+                Ok(()) | Err(ObjectStoreError::KeyNotFound(_)) => { /* continue */ }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
 
@@ -138,11 +250,44 @@ impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
         (**self).remove_raw(bucket, key).await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        page_token: Option<&str>,
+    ) -> Result<ObjectKeysPage, ObjectStoreError> {
+        (**self).list_raw(bucket, prefix, page_token).await
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        (**self).exists_raw(bucket, key).await
+    }
+
+    async fn remove_many_raw(
+        &self,
+        bucket: Bucket,
+        keys: &[String],
+    ) -> Result<(), ObjectStoreError> {
+        (**self).remove_many_raw(bucket, keys).await
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         (**self).storage_prefix_raw(bucket)
     }
 }
 
+/// Local disk cache shared by all object stores using the same cache directory.
+#[derive(Debug)]
+struct SharedCache {
+    config: ObjectStoreConfig,
+    store: Weak<CachingObjectStore>,
+}
+
+/// Local disk caches keyed by the cache directory. Caches in the same directory would interfere with each other
+/// (e.g., by removing each other's temporary files) and would not share the size limit, so a single cache
+/// is created per directory in the process and is shared by all stores created by [`ObjectStoreFactory`]s.
+static SHARED_CACHES: Lazy<Mutex<HashMap<PathBuf, SharedCache>>> = Lazy::new(Mutex::default);
+
 #[derive(Debug)]
 enum ObjectStoreOrigin {
     Config(ObjectStoreConfig),
@@ -161,7 +306,12 @@ impl ObjectStoreFactory {
     /// # Panics
     ///
     /// If the GCS-backed implementation is configured, this constructor will panic if called
-    /// outside the Tokio runtime.
+    /// outside the Tokio runtime. The S3-backed implementation panics on [`Self::create_store()`]
+    /// if its configuration is invalid (e.g., credentials are missing). Likewise,
+    /// [`Self::create_store()`] panics if the local disk cache is configured with unknown buckets
+    /// or its directory cannot be initialized, or if the cache directory is already used by a store
+    /// with a different configuration. Stores using the same cache directory share a single cache
+    /// (including its size limit).
     pub fn new(config: ObjectStoreConfig) -> Self {
         Self {
             origin: ObjectStoreOrigin::Config(config),
@@ -186,6 +336,50 @@ impl ObjectStoreFactory {
     }
 
     async fn create_from_config(config: &ObjectStoreConfig) -> Box<dyn ObjectStore> {
+        let Some(cache_path) = &config.cache_path else {
+            return Self::create_backend(config).await;
+        };
+        if config.cached_buckets.is_empty() {
+            return Self::create_backend(config).await;
+        }
+
+        let mut shared_caches = SHARED_CACHES.lock().await;
+        if let Some(cache) = shared_caches.get(&PathBuf::from(cache_path)) {
+            if let Some(store) = cache.store.upgrade() {
+                // Not using `assert_eq!` since configs may contain secrets.
+                assert!(
+                    cache.config == *config,
+                    "local disk cache at `{cache_path}` is shared by object stores with different configurations"
+                );
+                return Box::new(store);
+            }
+        }
+        let store = Self::create_backend(config).await;
+
+        let cached_buckets: HashSet<_> = config
+            .cached_buckets
+            .iter()
+            .map(|name| {
+                Bucket::from_name(name)
+                    .unwrap_or_else(|| panic!("unknown bucket `{name}` specified for caching"))
+            })
+            .collect();
+        tracing::trace!(
+            "Initialized local disk cache at `{cache_path}` for buckets {cached_buckets:?}"
+        );
+        let max_size = config.cache_max_size_mb * 1_024 * 1_024;
+        let store =
+            CachingObjectStore::new(store, cache_path.into(), max_size, cached_buckets).await;
+        let store = Arc::new(store);
+        let cache = SharedCache {
+            config: config.clone(),
+            store: Arc::downgrade(&store),
+        };
+        shared_caches.insert(cache_path.into(), cache);
+        Box::new(store)
+    }
+
+    async fn create_backend(config: &ObjectStoreConfig) -> Box<dyn ObjectStore> {
         let gcs_credential_file_path = match config.mode {
             ObjectStoreMode::GCSWithCredentialFile => Some(config.gcs_credential_file_path.clone()),
             _ => None,
@@ -218,6 +412,136 @@ impl ObjectStoreFactory {
                 let store = FileBackedObjectStore::new(config.file_backed_base_path.clone()).await;
                 Box::new(store)
             }
+            ObjectStoreMode::S3 => {
+                tracing::trace!("Initialized S3 Object store");
+                let store = S3Store::new(config);
+                Box::new(store)
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn paginating_keys() {
+        let keys: Vec<_> = ["b", "a1", "a3", "a2", "c"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let page = ObjectKeysPage::paginate(keys.clone(), "a", None, 2);
+        assert_eq!(page.keys, ["a1", "a2"]);
+        assert_eq!(page.next_page_token.as_deref(), Some("a2"));
+        let page = ObjectKeysPage::paginate(keys.clone(), "a", Some("a2"), 2);
+        assert_eq!(page.keys, ["a3"]);
+        assert_eq!(page.next_page_token, None);
+
+        let page = ObjectKeysPage::paginate(keys, "", None, 5);
+        assert_eq!(page.keys, ["a1", "a2", "a3", "b", "c"]);
+        assert_eq!(page.next_page_token, None);
+    }
+
+    #[tokio::test]
+    async fn listing_and_removing_keys_in_mock_store() {
+        let store = ObjectStoreFactory::mock().create_store().await;
+        for key in ["1_0.bin", "1_1.bin", "2_0.bin"] {
+            store
+                .put_raw(Bucket::ProverJobsFri, key, vec![1])
+                .await
+                .unwrap();
+        }
+        assert!(store
+            .exists_raw(Bucket::ProverJobsFri, "2_0.bin")
+            .await
+            .unwrap());
+        assert!(!store
+            .exists_raw(Bucket::ProofsFri, "2_0.bin")
+            .await
+            .unwrap());
+
+        let page = store
+            .list_raw(Bucket::ProverJobsFri, "1_", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["1_0.bin", "1_1.bin"]);
+        store
+            .remove_many_raw(Bucket::ProverJobsFri, &page.keys)
+            .await
+            .unwrap();
+
+        let page = store
+            .list_raw(Bucket::ProverJobsFri, "", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["2_0.bin"]);
+    }
+
+    fn cached_file_backed_config(base_path: &Path, cache_path: &Path) -> ObjectStoreConfig {
+        ObjectStoreConfig {
+            bucket_base_url: String::new(),
+            mode: ObjectStoreMode::FileBacked,
+            file_backed_base_path: base_path.to_str().unwrap().to_owned(),
+            gcs_credential_file_path: String::new(),
+            max_retries: 1,
+            s3_endpoint: None,
+            s3_region: "us-east-1".to_owned(),
+            s3_path_style: false,
+            s3_access_key_id: None,
+            s3_secret_access_key: None,
+            s3_multipart_part_size_mb: 64,
+            cache_path: Some(cache_path.to_str().unwrap().to_owned()),
+            cache_max_size_mb: 1,
+            cached_buckets: vec!["witness_inputs".to_owned()],
+        }
+    }
+
+    #[tokio::test]
+    async fn stores_share_local_disk_cache() {
+        let base_dir = TempDir::new("object_store").unwrap();
+        let cache_dir = TempDir::new("object_store_cache").unwrap();
+        let config = cached_file_backed_config(base_dir.path(), cache_dir.path());
+        let factory = ObjectStoreFactory::new(config.clone());
+        let store = factory.create_store().await;
+        let other_store = factory.create_store().await;
+        let other_factory_store = ObjectStoreFactory::new(config).create_store().await;
+
+        let shared_caches = SHARED_CACHES.lock().await;
+        let cache = &shared_caches[cache_dir.path()];
+        assert_eq!(cache.store.strong_count(), 3);
+        drop(shared_caches);
+
+        // Each object is larger than a half of the size limit, so only one of them can be cached at a time.
+        let object_size = 600 * 1_024;
+        for key in ["a", "b"] {
+            store
+                .put_raw(Bucket::WitnessInput, key, vec![0; object_size])
+                .await
+                .unwrap();
+        }
+        store.get_raw(Bucket::WitnessInput, "a").await.unwrap();
+        other_store
+            .get_raw(Bucket::WitnessInput, "b")
+            .await
+            .unwrap();
+        let cached_objects = std::fs::read_dir(cache_dir.path().join("witness_inputs"))
+            .unwrap()
+            .count();
+        assert_eq!(cached_objects, 1);
+
+        // Stores are still usable after one of them is dropped.
+        drop(store);
+        drop(other_store);
+        let value = other_factory_store
+            .get_raw(Bucket::WitnessInput, "a")
+            .await
+            .unwrap();
+        assert_eq!(value.len(), object_size);
+    }
+}
@@ -0,0 +1,102 @@
+//! Retry logic shared by networked [`ObjectStore`](crate::ObjectStore) implementations.
+
+use std::{fmt, future::Future, time::Duration};
+
+/// Retries `f` with exponential backoff until it succeeds or `max_retries` is exhausted.
+pub(crate) async fn retry<T, E, Fut, F>(max_retries: u16, f: F) -> Result<T, E>
+where
+    E: fmt::Display,
+    Fut: Future<Output = Result<T, E>>,
+    F: FnMut() -> Fut,
+{
+    retry_if(max_retries, |_| true, f).await
+}
+
+/// Same as [`retry()`], but only retries errors for which `is_transient` returns `true`.
+/// Other errors are returned immediately.
+pub(crate) async fn retry_if<T, E, Fut, F>(
+    max_retries: u16,
+    is_transient: impl Fn(&E) -> bool,
+    mut f: F,
+) -> Result<T, E>
+where
+    E: fmt::Display,
+    Fut: Future<Output = Result<T, E>>,
+    F: FnMut() -> Fut,
+{
+    let mut retries = 1;
+    let mut backoff = 1;
+    loop {
+        match f().await {
+            Ok(result) => return Ok(result),
+            Err(err) => {
+                if !is_transient(&err) {
+                    return Err(err);
+                }
+                tracing::warn!(%err, "Failed object store request {retries}/{max_retries}, retrying.");
+                if retries > max_retries {
+                    return Err(err);
+                }
+                retries += 1;
+                tokio::time::sleep(Duration::from_secs(backoff)).await;
+                backoff *= 2;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU16, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_retry_success_immediate() {
+        let result = retry(2, || async { Ok::<_, &'static str>(42) }).await;
+        assert_eq!(result, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn test_retry_failure_exhausted() {
+        let result = retry(2, || async { Err::<i32, _>("oops") }).await;
+        assert_eq!(result, Err("oops"));
+    }
+
+    async fn retry_success_after_n_retries(n: u16) -> Result<u32, String> {
+        let retries = AtomicU16::new(0);
+        let result = retry(n, || async {
+            let retries = retries.fetch_add(1, Ordering::Relaxed);
+            if retries + 1 == n {
+                Ok(42)
+            } else {
+                Err("oops")
+            }
+        })
+        .await;
+
+        result.map_err(|_| "Retry failed".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_retry_success_after_retry() {
+        let result = retry(2, || retry_success_after_n_retries(2)).await;
+        assert_eq!(result, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn test_retry_if_does_not_retry_permanent_errors() {
+        let calls = AtomicU16::new(0);
+        let result = retry_if(
+            5,
+            |err: &&str| *err != "permanent",
+            || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Err::<i32, _>("permanent")
+            },
+        )
+        .await;
+        assert_eq!(result, Err("permanent"));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
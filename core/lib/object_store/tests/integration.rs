@@ -1,7 +1,7 @@
 //! Integration tests for object store.
 
 use tokio::fs;
-use zksync_object_store::{Bucket, ObjectStoreFactory};
+use zksync_object_store::{Bucket, ObjectStoreError, ObjectStoreFactory};
 use zksync_types::{
     proofs::{PrepareBasicCircuitsJob, StorageLogMetadata},
     L1BatchNumber,
@@ -29,8 +29,12 @@ async fn prepare_basic_circuits_job_serialization() {
     let job: PrepareBasicCircuitsJob = store.get(L1BatchNumber(1)).await.unwrap();
 
     let key = store.put(L1BatchNumber(2), &job).await.unwrap();
+    // New objects are wrapped in an envelope, so the raw bytes must differ...
     let serialized_job = store.get_raw(Bucket::WitnessInput, &key).await.unwrap();
-    assert_eq!(serialized_job, snapshot);
+    assert_ne!(serialized_job, snapshot);
+    // ...but the enveloped payload must be the same.
+    let restored_job: PrepareBasicCircuitsJob = store.get(L1BatchNumber(2)).await.unwrap();
+    assert_eq!(bincode::serialize(&restored_job).unwrap(), snapshot);
     assert_job_integrity(
         job.next_enumeration_index(),
         job.into_merkle_paths().collect(),
@@ -64,3 +68,28 @@ async fn prepare_basic_circuits_job_compatibility() {
 
     assert_job_integrity(job_tuple.1, job_tuple.0);
 }
+
+#[tokio::test]
+async fn corrupted_objects_are_rejected() {
+    let snapshot = fs::read("./tests/snapshots/prepare-basic-circuits-job-full.bin")
+        .await
+        .unwrap();
+    let job: PrepareBasicCircuitsJob = bincode::deserialize(&snapshot).unwrap();
+    let store = ObjectStoreFactory::mock().create_store().await;
+    let key = store.put(L1BatchNumber(1), &job).await.unwrap();
+
+    let mut serialized_job = store.get_raw(Bucket::WitnessInput, &key).await.unwrap();
+    let last_byte = serialized_job.last_mut().unwrap();
+    *last_byte = !*last_byte;
+    store
+        .put_raw(Bucket::WitnessInput, &key, serialized_job)
+        .await
+        .unwrap();
+
+    let err = store
+        .get::<PrepareBasicCircuitsJob>(L1BatchNumber(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ObjectStoreError::Serialization(_)), "{err}");
+    assert!(err.to_string().contains("checksum mismatch"), "{err}");
+}
@@ -5,7 +5,11 @@ use std::{
 
 use anyhow::Context as _;
 pub use async_trait::async_trait;
-use tokio::{sync::watch, task::JoinHandle, time::sleep};
+use tokio::{
+    sync::{watch, Notify},
+    task::JoinHandle,
+    time::{sleep, timeout},
+};
 use vise::{Buckets, Counter, Histogram, LabeledFamily, Metrics};
 use zksync_utils::panic_extractor::try_extract_panic_message;
 
@@ -43,6 +47,13 @@ pub trait JobProcessor: Sync + Send {
     /// Should mark the job as failed
     async fn save_failure(&self, job_id: Self::JobId, started_at: Instant, error: String);
 
+    /// Returns a handle notified when new jobs may be available (e.g., by a Postgres `LISTEN` / `NOTIFY` listener).
+    /// If provided, an idle processor wakes up on a notification instead of waiting for the polling backoff
+    /// to elapse. Polling is still used as a fallback, so notifications may be lost without stalling the processor.
+    fn new_jobs_notifier(&self) -> Option<&Notify> {
+        None
+    }
+
     /// Function that processes a job
     async fn process_job(
         &self,
@@ -93,7 +104,16 @@ pub trait JobProcessor: Sync + Send {
                 return Ok(());
             } else {
                 tracing::trace!("Backing off for {} ms", backoff);
-                sleep(Duration::from_millis(backoff)).await;
+                let backoff_duration = Duration::from_millis(backoff);
+                if let Some(notifier) = self.new_jobs_notifier() {
+                    if timeout(backoff_duration, notifier.notified()).await.is_ok() {
+                        tracing::trace!("Notified about new {} jobs", Self::SERVICE_NAME);
+                        backoff = Self::POLLING_INTERVAL_MS;
+                        continue;
+                    }
+                } else {
+                    sleep(backoff_duration).await;
+                }
                 backoff = (backoff * Self::BACKOFF_MULTIPLIER).min(Self::MAX_BACKOFF_MS);
             }
         }
@@ -106,7 +126,7 @@ pub trait JobProcessor: Sync + Send {
         &self,
         job_id: Self::JobId,
         started_at: Instant,
-        task: JoinHandle<anyhow::Result<Self::JobArtifacts>>,
+        mut task: JoinHandle<anyhow::Result<Self::JobArtifacts>>,
     ) -> anyhow::Result<()> {
         let attempts = self.get_job_attempts(&job_id).await?;
         let max_attempts = self.max_attempts();
@@ -121,15 +141,16 @@ pub trait JobProcessor: Sync + Send {
 
         let result = loop {
             tracing::trace!(
-                "Polling {} task with id {:?}. Is finished: {}",
+                "Waiting for {} task with id {:?}",
                 Self::SERVICE_NAME,
-                job_id,
-                task.is_finished()
+                job_id
             );
-            if task.is_finished() {
-                break task.await;
+            // Awaiting the task directly allows saving its result as soon as it completes;
+            // the timeout is only used to periodically log progress.
+            let polling_interval = Duration::from_millis(Self::POLLING_INTERVAL_MS);
+            if let Ok(result) = timeout(polling_interval, &mut task).await {
+                break result;
             }
-            sleep(Duration::from_millis(Self::POLLING_INTERVAL_MS)).await;
         };
         let error_message = match result {
             Ok(Ok(data)) => {
@@ -24,7 +24,9 @@ use std::{collections::HashMap, convert::TryInto, mem, path::Path, time::Instant
 use itertools::{Either, Itertools};
 use zksync_dal::StorageProcessor;
 use zksync_storage::{db::NamedColumnFamily, RocksDB};
-use zksync_types::{L1BatchNumber, StorageKey, StorageValue, H256, U256};
+use zksync_types::{
+    snapshots::SnapshotRecoveryStatus, L1BatchNumber, StorageKey, StorageValue, H256, U256,
+};
 use zksync_utils::{h256_to_u256, u256_to_h256};
 
 use self::metrics::METRICS;
@@ -134,6 +136,17 @@ impl RocksdbStorage {
     /// Panics if the local L1 batch number is greater than the last sealed L1 batch number
     /// in Postgres.
     pub async fn update_from_postgres(&mut self, conn: &mut StorageProcessor<'_>) {
+        if self.l1_batch_number() == L1BatchNumber(0) {
+            let snapshot_recovery = conn
+                .snapshot_recovery_dal()
+                .get_applied_snapshot_status()
+                .await
+                .unwrap();
+            if let Some(snapshot_recovery) = snapshot_recovery {
+                self.recover_from_snapshot(conn, &snapshot_recovery).await;
+            }
+        }
+
         let latency = METRICS.update.start();
         let latest_l1_batch_number = conn
             .blocks_dal()
@@ -192,6 +205,93 @@ impl RocksdbStorage {
         }
     }
 
+    /// Initializes this storage from the snapshot that Postgres was recovered from. Storage logs are loaded
+    /// in chunks (by the first byte of the hashed key) so that the entire snapshot doesn't need to fit into memory.
+    /// If recovery is interrupted, it is restarted from scratch on the next update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if Postgres snapshot recovery is not finished.
+    async fn recover_from_snapshot(
+        &mut self,
+        conn: &mut StorageProcessor<'_>,
+        snapshot_recovery: &SnapshotRecoveryStatus,
+    ) {
+        let started_at = Instant::now();
+        let l1_batch_number = snapshot_recovery.l1_batch_number;
+        let miniblock_number = snapshot_recovery.miniblock_number;
+        assert!(
+            snapshot_recovery.is_finished(),
+            "Postgres snapshot recovery for L1 batch #{l1_batch_number} is not finished"
+        );
+        tracing::info!(
+            "Recovering secondary storage from snapshot for L1 batch #{l1_batch_number} \
+             (miniblock #{miniblock_number})"
+        );
+
+        let mut recovered_key_count = 0;
+        for first_byte in 0..=u8::MAX {
+            let mut range_start = H256::zero();
+            range_start.0[0] = first_byte;
+            let mut range_end = H256::repeat_byte(0xff);
+            range_end.0[0] = first_byte;
+
+            let entries = conn
+                .storage_logs_dal()
+                .get_tree_entries_for_miniblock(miniblock_number, range_start..=range_end)
+                .await
+                .unwrap();
+            recovered_key_count += entries.len();
+
+            let mut write_batch = self.db.new_write_batch();
+            for entry in entries {
+                // Keys of tree entries are hashed keys interpreted as little-endian numbers.
+                let mut hashed_key = H256::zero();
+                entry.key.to_little_endian(&mut hashed_key.0);
+                write_batch.put_cf(
+                    StateKeeperColumnFamily::State,
+                    hashed_key.as_bytes(),
+                    &StateValue::new(entry.value, Some(entry.leaf_index)).serialize(),
+                );
+            }
+            self.db
+                .write(write_batch)
+                .expect("failed to save state data into RocksDB");
+        }
+
+        let factory_deps = conn.storage_dal().get_all_factory_deps().await.unwrap();
+        let factory_dep_count = factory_deps.len();
+        let mut write_batch = self.db.new_write_batch();
+        for (hash, bytecode) in factory_deps {
+            write_batch.put_cf(
+                StateKeeperColumnFamily::FactoryDeps,
+                hash.as_bytes(),
+                &bytecode,
+            );
+        }
+        // Enumeration indices are taken from the snapshot, so the enum index migration is not needed.
+        write_batch.put_cf(
+            StateKeeperColumnFamily::State,
+            Self::ENUM_INDEX_MIGRATION_CURSOR,
+            &[],
+        );
+        // The block number must be written last, so that an interrupted recovery is restarted.
+        write_batch.put_cf(
+            StateKeeperColumnFamily::State,
+            Self::BLOCK_NUMBER_KEY,
+            &serialize_block_number(l1_batch_number.0 + 1),
+        );
+        self.db
+            .write(write_batch)
+            .expect("failed to save state data into RocksDB");
+
+        tracing::info!(
+            "Recovered secondary storage from snapshot for L1 batch #{l1_batch_number} in {:?}: \
+             {recovered_key_count} keys, {factory_dep_count} factory deps",
+            started_at.elapsed()
+        );
+    }
+
     async fn apply_storage_logs(
         &mut self,
         storage_logs: HashMap<StorageKey, H256>,
@@ -569,6 +669,51 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn rocksdb_storage_recovery_from_snapshot() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        prepare_postgres(&mut conn).await;
+        // Emulate a snapshot for L1 batch #1: all logs are attributed to its only miniblock.
+        let storage_logs = gen_storage_logs(20..100);
+        create_miniblock(&mut conn, MiniblockNumber(1), storage_logs.clone()).await;
+        create_l1_batch(&mut conn, L1BatchNumber(1), &storage_logs).await;
+        insert_factory_deps(&mut conn, MiniblockNumber(1), 0..2).await;
+        conn.snapshot_recovery_dal()
+            .set_applied_snapshot_status(&SnapshotRecoveryStatus {
+                l1_batch_number: L1BatchNumber(1),
+                l1_batch_root_hash: H256::zero(),
+                miniblock_number: MiniblockNumber(1),
+                miniblock_hash: H256::zero(),
+                storage_logs_chunks_processed: vec![true],
+            })
+            .await
+            .unwrap();
+
+        let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+        let mut storage = RocksdbStorage::new(dir.path());
+        storage.update_from_postgres(&mut conn).await;
+
+        assert_eq!(storage.l1_batch_number(), L1BatchNumber(2));
+        assert_eq!(storage.enum_migration_start_from(), None);
+        for log in &storage_logs {
+            assert_eq!(storage.read_value(&log.key), log.value);
+            let expected_index = conn
+                .storage_logs_dedup_dal()
+                .get_enumeration_index_for_key(log.key)
+                .await;
+            assert_eq!(storage.get_enumeration_index(&log.key), expected_index);
+        }
+        // Logs from the genesis miniblock are not a part of the snapshot.
+        for log in gen_storage_logs(0..20) {
+            assert!(storage.is_write_initial(&log.key));
+        }
+        for i in 0..2 {
+            let bytecode = storage.load_factory_dep(H256::repeat_byte(i));
+            assert_eq!(bytecode, Some(vec![i; 64]));
+        }
+    }
+
     async fn insert_factory_deps(
         conn: &mut StorageProcessor<'_>,
         miniblock_number: MiniblockNumber,
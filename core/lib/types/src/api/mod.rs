@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use strum::Display;
@@ -12,9 +14,9 @@ pub use crate::transaction_request::{
 };
 use crate::{
     protocol_version::L1VerifierConfig,
-    vm_trace::{Call, CallType},
+    vm_trace::{Call, CallType, FourByteTrace, PrestateTrace, StructLogTrace},
     web3::types::{AccessList, Index, H2048},
-    Address, MiniblockNumber, ProtocolVersionId,
+    Address, MiniblockNumber, Nonce, ProtocolVersionId,
 };
 
 pub mod en;
@@ -555,11 +557,40 @@ pub struct GetLogsFilter {
 }
 
 /// Result of debugging block
-/// For some reasons geth returns result as {result: DebugCall}
+/// For some reasons geth returns result as {result: DebugTrace}
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct ResultDebugCall {
-    pub result: DebugCall,
+pub struct ResultDebugTrace {
+    pub result: DebugTrace,
+}
+
+/// Kept for backward compatibility; prefer [`ResultDebugTrace`].
+pub type ResultDebugCall = ResultDebugTrace;
+
+/// Output of a tracer for a single transaction. The variant depends on the tracer requested in [`TracerConfig`].
+///
+/// Like in Geth, the output is not tagged with the tracer, so the variant is determined by its shape
+/// during deserialization, and **the order of variants matters**. `Prestate` goes before `FourByte`,
+/// since prestate traces are keyed by addresses, which never parse as `4byteTracer` keys
+/// (`{selector}-{calldata size}`), but not vice versa. As a consequence, an empty object
+/// is deserialized as an empty `Prestate` trace.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum DebugTrace {
+    /// Output of `callTracer`.
+    Call(DebugCall),
+    /// Output of the struct logger.
+    StructLogs(StructLogTrace),
+    /// Output of `prestateTracer`.
+    Prestate(PrestateTrace),
+    /// Output of `4byteTracer`.
+    FourByte(FourByteTrace),
+}
+
+impl From<DebugCall> for DebugTrace {
+    fn from(call: DebugCall) -> Self {
+        Self::Call(call)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -622,24 +653,53 @@ pub struct ProtocolVersion {
     pub l2_system_upgrade_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
     CallTracer,
+    PrestateTracer,
+    #[serde(rename = "4byteTracer")]
+    FourByteTracer,
 }
 
+/// Tracer-specific options. Options not applicable to the requested tracer are ignored.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct CallTracerConfig {
+#[serde(default, rename_all = "camelCase")]
+pub struct TracerOptions {
+    /// Whether `callTracer` should only return the top-level call.
     pub only_top_call: bool,
+    /// Whether `prestateTracer` should return state changes instead of the prestate.
+    pub diff_mode: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Kept for backward compatibility; prefer [`TracerOptions`].
+pub type CallTracerConfig = TracerOptions;
+
+/// Options of the struct logger. Similar to Geth, they are specified at the top level of [`TracerConfig`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StructLogOptions {
+    /// Whether to omit accessed storage slots.
+    pub disable_storage: bool,
+    /// Whether to record the heap contents of the current frame. Disabled by default since the heap
+    /// is copied on each step.
+    pub enable_memory: bool,
+    /// Maximum number of recorded instructions. The API server applies its own default and maximum
+    /// if this limit is not specified or is too large, respectively.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
-    pub tracer: SupportedTracers,
+    /// Tracer to use. If not specified, `debug_traceCall` uses the struct logger,
+    /// and other methods use `callTracer`.
     #[serde(default)]
-    pub tracer_config: CallTracerConfig,
+    pub tracer: Option<SupportedTracers>,
+    #[serde(default)]
+    pub tracer_config: TracerOptions,
+    #[serde(flatten)]
+    pub struct_log_options: StructLogOptions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -702,3 +762,111 @@ pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
 }
+
+/// Number of L2 transactions in the mempool, as returned by `txpool_status`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolStatus {
+    /// Number of transactions ready for execution.
+    pub pending: U64,
+    /// Number of transactions that cannot be executed until a nonce gap is filled.
+    pub queued: U64,
+}
+
+/// L2 transactions in the mempool keyed by the initiator address and nonce. Returned by `txpool_content`
+/// (with full transactions) and `txpool_inspect` (with short textual transaction summaries).
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolContent<T> {
+    pub pending: BTreeMap<Address, BTreeMap<Nonce, T>>,
+    pub queued: BTreeMap<Address, BTreeMap<Nonce, T>>,
+}
+
+/// L2 transactions in the mempool initiated by a single account keyed by nonce, as returned
+/// by `txpool_contentFrom`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolAccountContent<T> {
+    pub pending: BTreeMap<Nonce, T>,
+    pub queued: BTreeMap<Nonce, T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm_trace::AccountState;
+
+    #[test]
+    fn deserializing_debug_trace() {
+        let call: DebugTrace = serde_json::from_value(serde_json::json!({
+            "type": "Call",
+            "from": Address::repeat_byte(1),
+            "to": Address::repeat_byte(2),
+            "gas": "0x10",
+            "gasUsed": "0x5",
+            "value": "0x0",
+            "output": "0x",
+            "input": "0x01020304",
+            "error": null,
+            "revertReason": null,
+            "calls": [],
+        }))
+        .unwrap();
+        assert!(matches!(call, DebugTrace::Call(_)), "{call:?}");
+
+        let struct_logs: DebugTrace = serde_json::from_value(serde_json::json!({
+            "gas": 21_000,
+            "failed": false,
+            "returnValue": "0x",
+            "structLogs": [],
+        }))
+        .unwrap();
+        assert!(
+            matches!(struct_logs, DebugTrace::StructLogs(_)),
+            "{struct_logs:?}"
+        );
+
+        let four_byte: DebugTrace = serde_json::from_value(serde_json::json!({
+            "0xa9059cbb-64": 2,
+            "0x70a08231-32": 1,
+        }))
+        .unwrap();
+        let DebugTrace::FourByte(four_byte) = four_byte else {
+            panic!("unexpected trace: {four_byte:?}");
+        };
+        assert_eq!(four_byte["0xa9059cbb-64"], 2);
+        assert_eq!(four_byte["0x70a08231-32"], 1);
+
+        let account_state = AccountState {
+            balance: Some(U256::from(1_000)),
+            nonce: Some(1),
+            ..AccountState::default()
+        };
+        let prestate = PrestateTrace::Prestate(BTreeMap::from([(
+            Address::repeat_byte(1),
+            account_state.clone(),
+        )]));
+        let trace: DebugTrace =
+            serde_json::from_value(serde_json::to_value(&prestate).unwrap()).unwrap();
+        let DebugTrace::Prestate(trace) = trace else {
+            panic!("unexpected trace: {trace:?}");
+        };
+        assert_eq!(trace, prestate);
+
+        let diff = PrestateTrace::Diff {
+            pre: BTreeMap::from([(Address::repeat_byte(1), account_state)]),
+            post: BTreeMap::new(),
+        };
+        let trace: DebugTrace =
+            serde_json::from_value(serde_json::to_value(&diff).unwrap()).unwrap();
+        let DebugTrace::Prestate(trace) = trace else {
+            panic!("unexpected trace: {trace:?}");
+        };
+        assert_eq!(trace, diff);
+
+        // An empty prestate (e.g., for a transaction not touching any accounts) must not be mistaken
+        // for a `4byteTracer` output.
+        let trace: DebugTrace = serde_json::from_value(serde_json::json!({})).unwrap();
+        let DebugTrace::Prestate(trace) = trace else {
+            panic!("unexpected trace: {trace:?}");
+        };
+        assert_eq!(trace, PrestateTrace::Prestate(BTreeMap::new()));
+    }
+}
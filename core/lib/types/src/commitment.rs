@@ -92,6 +92,10 @@ pub struct L1BatchWithMetadata {
     pub header: L1BatchHeader,
     pub metadata: L1BatchMetadata,
     pub factory_deps: Vec<Vec<u8>>,
+    /// Data proving the inclusion of the L1 batch pubdata on a data availability layer (validium mode).
+    /// If set, it's sent to L1 in the commit transaction instead of the pubdata.
+    #[serde(default)]
+    pub da_inclusion_data: Option<Vec<u8>>,
 }
 
 impl L1BatchWithMetadata {
@@ -106,6 +110,7 @@ impl L1BatchWithMetadata {
                 .collect(),
             header,
             metadata,
+            da_inclusion_data: None,
         }
     }
 
@@ -209,7 +214,11 @@ impl L1BatchWithMetadata {
                         .to_vec(),
                 ),
                 Token::Bytes(self.metadata.l2_l1_messages_compressed.clone()),
-                Token::Bytes(self.construct_pubdata()),
+                Token::Bytes(
+                    self.da_inclusion_data
+                        .clone()
+                        .unwrap_or_else(|| self.construct_pubdata()),
+                ),
             ])
         }
     }
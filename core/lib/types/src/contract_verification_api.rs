@@ -215,12 +215,32 @@ pub struct CompilationArtifacts {
     pub abi: serde_json::Value,
 }
 
+/// Type of the match between the deployed bytecode and the bytecode compiled from the verified sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BytecodeMatch {
+    /// Bytecodes are identical.
+    #[default]
+    Full,
+    /// Bytecodes differ only in the metadata hash appended by the compiler, i.e., the sources are functionally
+    /// identical to the deployed ones but may differ in comments, file paths etc.
+    Partial,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VerificationInfo {
     pub request: VerificationRequest,
     pub artifacts: CompilationArtifacts,
     pub verified_at: DateTime<Utc>,
+    /// Type of the bytecode match. Absent for contracts verified before partial matches were supported,
+    /// which are always full matches.
+    #[serde(default)]
+    pub bytecode_match: BytecodeMatch,
+    /// Address of the verified contract with the identical bytecode if this contract was verified automatically
+    /// based on the bytecode match. `None` if the contract was verified via a verification request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identical_to: Option<Address>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +9,8 @@ pub struct EthTx {
     pub tx_type: AggregatedActionType,
     pub created_at_timestamp: u64,
     pub predicted_gas_cost: u64,
+    /// Address of the operator account sending the transaction. `None` corresponds to the main operator account.
+    pub from_addr: Option<Address>,
 }
 
 impl std::fmt::Debug for EthTx {
@@ -21,6 +23,7 @@ impl std::fmt::Debug for EthTx {
             .field("tx_type", &self.tx_type)
             .field("created_at_timestamp", &self.created_at_timestamp)
             .field("predicted_gas_cost", &self.predicted_gas_cost)
+            .field("from_addr", &self.from_addr)
             .finish()
     }
 }
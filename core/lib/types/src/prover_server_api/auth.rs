@@ -0,0 +1,130 @@
+//! Authentication of requests to the proof data handler API.
+//!
+//! Each request carries the prover identity in the [`PROVER_ID_HEADER`] header. Depending on the server
+//! configuration, the request is authenticated either by an API key passed in the `Authorization: Bearer ...` header,
+//! or by an HMAC-SHA256 signature in the [`SIGNATURE_HEADER`] header. The signature covers the request timestamp
+//! (the [`TIMESTAMP_HEADER`] header; seconds since Unix epoch), the request nonce (the [`NONCE_HEADER`] header),
+//! the request path and the request body, and is keyed by the secret shared between the server and the prover.
+//! The server rejects signed requests with a nonce already used by the prover, so that requests cannot be replayed.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header containing the prover identity.
+pub const PROVER_ID_HEADER: &str = "x-prover-id";
+/// Header containing the request timestamp (seconds since Unix epoch) for HMAC-signed requests.
+pub const TIMESTAMP_HEADER: &str = "x-prover-timestamp";
+/// Header containing a unique nonce for HMAC-signed requests. Nonces must not be reused by a prover
+/// (at least within the window for request timestamps accepted by the server).
+pub const NONCE_HEADER: &str = "x-prover-nonce";
+/// Header containing the hex-encoded HMAC-SHA256 signature of a request.
+pub const SIGNATURE_HEADER: &str = "x-prover-signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn request_mac(secret: &str, timestamp: u64, nonce: &str, path: &str, body: &[u8]) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b"\n");
+    mac.update(nonce.as_bytes());
+    mac.update(b"\n");
+    mac.update(path.as_bytes());
+    mac.update(b"\n");
+    mac.update(body);
+    mac
+}
+
+/// Computes a hex-encoded signature of a request with the specified timestamp, nonce, path (e.g., `/submit_proof/1`)
+/// and body.
+pub fn sign_request(secret: &str, timestamp: u64, nonce: &str, path: &str, body: &[u8]) -> String {
+    let mac = request_mac(secret, timestamp, nonce, path, body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Checks a hex-encoded request signature produced by [`sign_request()`]. The comparison is performed
+/// in constant time.
+pub fn verify_request_signature(
+    secret: &str,
+    timestamp: u64,
+    nonce: &str,
+    path: &str,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    request_mac(secret, timestamp, nonce, path, body)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_and_verifying_requests() {
+        let body = br#"{"Proof":{}}"#;
+        let signature = sign_request("secret", 1_700_000_000, "0", "/submit_proof/1", body);
+        assert_eq!(signature.len(), 64);
+        assert!(verify_request_signature(
+            "secret",
+            1_700_000_000,
+            "0",
+            "/submit_proof/1",
+            body,
+            &signature
+        ));
+
+        assert!(!verify_request_signature(
+            "other_secret",
+            1_700_000_000,
+            "0",
+            "/submit_proof/1",
+            body,
+            &signature
+        ));
+        assert!(!verify_request_signature(
+            "secret",
+            1_700_000_000,
+            "1",
+            "/submit_proof/1",
+            body,
+            &signature
+        ));
+        assert!(!verify_request_signature(
+            "secret",
+            1_700_000_001,
+            "0",
+            "/submit_proof/1",
+            body,
+            &signature
+        ));
+        assert!(!verify_request_signature(
+            "secret",
+            1_700_000_000,
+            "0",
+            "/submit_proof/2",
+            body,
+            &signature
+        ));
+        assert!(!verify_request_signature(
+            "secret",
+            1_700_000_000,
+            "0",
+            "/submit_proof/1",
+            b"{}",
+            &signature
+        ));
+        assert!(!verify_request_signature(
+            "secret",
+            1_700_000_000,
+            "0",
+            "/submit_proof/1",
+            body,
+            "not a signature"
+        ));
+    }
+}
@@ -7,6 +7,8 @@ use crate::{
     protocol_version::{FriProtocolVersionId, L1VerifierConfig},
 };
 
+pub mod auth;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProofGenerationData {
     pub l1_batch_number: L1BatchNumber,
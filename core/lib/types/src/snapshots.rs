@@ -1,5 +1,8 @@
+use std::ops;
+
 use serde::{Deserialize, Serialize};
-use zksync_basic_types::{L1BatchNumber, MiniblockNumber};
+use zksync_basic_types::{L1BatchNumber, MiniblockNumber, H256, U256};
+use zksync_utils::u256_to_h256;
 
 use crate::{commitment::L1BatchWithMetadata, StorageKey, StorageValue};
 
@@ -16,6 +19,8 @@ pub struct SnapshotMetadata {
     pub l1_batch_number: L1BatchNumber,
     pub factory_deps_filepath: String,
     pub storage_logs_filepaths: Vec<String>,
+    /// L1 batch number of the parent snapshot for delta snapshots; `None` for full snapshots.
+    pub parent_l1_batch_number: Option<L1BatchNumber>,
 }
 
 //contains all data not contained in factory_deps/storage_logs files to perform restore process
@@ -28,6 +33,18 @@ pub struct SnapshotHeader {
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
     pub last_l1_batch_with_metadata: L1BatchWithMetadata,
+    /// L1 batch number of the parent snapshot if this is a delta snapshot. Delta snapshots only contain
+    /// storage logs and factory deps changed after the parent snapshot, so they must be applied on top of
+    /// the chain of their ancestors starting from a full snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_l1_batch_number: Option<L1BatchNumber>,
+}
+
+impl SnapshotHeader {
+    /// Checks whether this is a delta snapshot.
+    pub fn is_delta(&self) -> bool {
+        self.parent_l1_batch_number.is_some()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,3 +88,43 @@ pub struct SnapshotFactoryDependencies {
 pub struct SnapshotFactoryDependency {
     pub bytecode: Vec<u8>,
 }
+
+/// Status of snapshot recovery process stored in Postgres.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRecoveryStatus {
+    pub l1_batch_number: L1BatchNumber,
+    pub l1_batch_root_hash: H256,
+    pub miniblock_number: MiniblockNumber,
+    pub miniblock_hash: H256,
+    /// Whether each storage logs chunk of the snapshot is applied; indexed by the chunk ID.
+    pub storage_logs_chunks_processed: Vec<bool>,
+}
+
+impl SnapshotRecoveryStatus {
+    /// Checks whether all storage logs chunks are applied.
+    pub fn is_finished(&self) -> bool {
+        self.storage_logs_chunks_processed
+            .iter()
+            .all(|&processed| processed)
+    }
+}
+
+/// Returns the range of hashed storage keys covered by the storage logs chunk with the specified ID
+/// if the key space is split into `chunks_count` chunks.
+pub fn get_chunk_hashed_keys_range(chunk_id: u64, chunks_count: u64) -> ops::RangeInclusive<H256> {
+    assert!(chunks_count > 0);
+    let mut stride = U256::MAX / chunks_count;
+    let stride_minus_one = if stride < U256::MAX {
+        stride += U256::one();
+        stride - 1
+    } else {
+        stride // `stride` is really 1 << 256 == U256::MAX + 1
+    };
+
+    let start = stride * chunk_id;
+    let (mut end, is_overflow) = stride_minus_one.overflowing_add(start);
+    if is_overflow {
+        end = U256::MAX;
+    }
+    u256_to_h256(start)..=u256_to_h256(end)
+}
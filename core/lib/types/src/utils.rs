@@ -77,12 +77,35 @@ pub fn deployed_address_create(sender: Address, deploy_nonce: U256) -> Address {
     Address::from_slice(&keccak256(&bytes)[12..])
 }
 
+/// Pre-calculates the address of the contract deployed via CREATE2 (i.e., using the `create2` or `create2Account`
+/// methods of the `ContractDeployer` system contract).
+pub fn deployed_address_create2(
+    sender: Address,
+    salt: H256,
+    bytecode_hash: H256,
+    constructor_input: &[u8],
+) -> Address {
+    let prefix_bytes = keccak256("zksyncCreate2".as_bytes());
+    let address_bytes = address_to_h256(&sender);
+    let input_hash = keccak256(constructor_input);
+
+    let mut bytes = [0u8; 160];
+    bytes[..32].copy_from_slice(&prefix_bytes);
+    bytes[32..64].copy_from_slice(address_bytes.as_bytes());
+    bytes[64..96].copy_from_slice(salt.as_bytes());
+    bytes[96..128].copy_from_slice(bytecode_hash.as_bytes());
+    bytes[128..].copy_from_slice(&input_hash);
+
+    Address::from_slice(&keccak256(&bytes)[12..])
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
     use crate::{
-        utils::storage_key_for_standard_token_balance, AccountTreeId, Address, StorageKey, H256,
+        utils::{deployed_address_create2, storage_key_for_standard_token_balance},
+        AccountTreeId, Address, StorageKey, H256,
     };
 
     #[test]
@@ -106,4 +129,30 @@ mod tests {
             assert_eq!(expected_storage_key, calculated_storage_key);
         }
     }
+
+    #[test]
+    fn test_deployed_address_create2() {
+        // Expected addresses follow `ContractDeployer.getNewAddressCreate2()`:
+        // `keccak256(keccak256("zksyncCreate2") ++ sender ++ salt ++ bytecode_hash ++ keccak256(input))[12..]`.
+        let sender = Address::from_str("0x36615Cf349d7F6344891B1e7CA7C72883F5dc049").unwrap();
+        let bytecode_hash =
+            H256::from_str("0x010001cb6a6e8d5f6829522f19fa9568660e0a9cd53b2e8be4deb0a679452e41")
+                .unwrap();
+
+        let address = deployed_address_create2(sender, H256::zero(), bytecode_hash, &[]);
+        let expected_address =
+            Address::from_str("0x3cfd6d969361fd475d81ba3e3306263ec88e1c61").unwrap();
+        assert_eq!(address, expected_address);
+
+        let constructor_input = H256::from_low_u64_be(42);
+        let address = deployed_address_create2(
+            sender,
+            H256::repeat_byte(1),
+            bytecode_hash,
+            constructor_input.as_bytes(),
+        );
+        let expected_address =
+            Address::from_str("0x7b732e1f05d82eb7f6b95a0686d7c2304566fc34").unwrap();
+        assert_eq!(address, expected_address);
+    }
 }
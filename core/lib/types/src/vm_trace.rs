@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     fmt::Display,
 };
@@ -9,7 +9,7 @@ use zk_evm::zkevm_opcode_defs::FarCallOpcode;
 use zksync_system_constants::BOOTLOADER_ADDRESS;
 use zksync_utils::u256_to_h256;
 
-use crate::{Address, U256};
+use crate::{Address, Bytes, H256, U256};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum VmTrace {
@@ -201,6 +201,80 @@ impl fmt::Debug for Call {
     }
 }
 
+/// State of an account as reported by the prestate tracer. Only the account storage slots
+/// touched during execution are included.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AccountState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Transaction nonce of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// Deployed bytecode of the account, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Output of the prestate tracer.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// States of all accounts touched during execution, taken before the execution.
+    Prestate(BTreeMap<Address, AccountState>),
+    /// Changes in the state of accounts modified during execution. `pre` contains states
+    /// of the modified accounts before the execution, and `post` contains only the modified parts
+    /// of their states after the execution.
+    Diff {
+        pre: BTreeMap<Address, AccountState>,
+        post: BTreeMap<Address, AccountState>,
+    },
+}
+
+/// Output of the 4-byte tracer: number of calls for each `{selector}-{calldata size}` pair,
+/// where the calldata size excludes the selector.
+pub type FourByteTrace = BTreeMap<String, usize>;
+
+/// Instruction executed by the VM, similar to an entry in `structLogs` returned by the default tracer in Geth.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// Program counter of the instruction.
+    pub pc: u64,
+    /// EraVM opcode of the instruction, e.g. `Add(Main)`.
+    pub op: String,
+    /// Ergs remaining in the current frame before executing the instruction.
+    pub gas: u32,
+    /// Base ergs cost of the instruction.
+    pub gas_cost: u32,
+    /// Depth of the call stack, including near calls.
+    pub depth: usize,
+    /// Values of the general-purpose registers. EraVM is register-based, so registers
+    /// are reported instead of the stack.
+    pub registers: Vec<U256>,
+    /// Heap of the current frame as 32-byte words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<H256>>,
+    /// Storage slot accessed by the instruction together with its value (the written value for writes).
+    /// Only reported for storage instructions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Output of the struct logger.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTrace {
+    /// Gas used by the transaction.
+    pub gas: u64,
+    /// Whether the transaction has failed.
+    pub failed: bool,
+    /// Data returned by the transaction.
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ViolatedValidationRule {
     TouchedUnallowedStorageSlots(Address, U256),
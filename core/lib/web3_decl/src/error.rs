@@ -1,12 +1,16 @@
 //! Definition of errors that can occur in the zkSync Web3 API.
 
 use thiserror::Error;
-use zksync_types::api::SerializationTransactionError;
+use zksync_types::{api::SerializationTransactionError, L1BatchNumber, MiniblockNumber};
 
 #[derive(Debug, Error)]
 pub enum Web3Error {
     #[error("Block with such an ID doesn't exist yet")]
     NoBlock,
+    #[error("Block #{0} is pruned; the first available block is #{1}")]
+    PrunedBlock(MiniblockNumber, MiniblockNumber),
+    #[error("L1 batch #{0} is pruned; the first available L1 batch is #{1}")]
+    PrunedL1Batch(L1BatchNumber, L1BatchNumber),
     #[error("Request timeout")]
     RequestTimeout,
     #[error("Internal error")]
@@ -37,6 +41,10 @@ pub enum Web3Error {
     InvalidFilterBlockHash,
     #[error("Query returned more than {0} results. Try smaller range of blocks")]
     TooManyLogs(usize),
+    #[error("Struct logs limit {0} exceeds the maximum of {1}")]
+    TooManyStructLogs(usize, usize),
     #[error("Tree API is not available")]
     TreeApiUnavailable,
+    #[error("Mempool is not available on this node")]
+    MempoolUnavailable,
 }
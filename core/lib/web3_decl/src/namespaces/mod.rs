@@ -4,18 +4,19 @@ pub mod eth;
 pub mod eth_subscribe;
 pub mod net;
 pub mod snapshots;
+pub mod txpool;
 pub mod web3;
 pub mod zks;
 
 #[cfg(feature = "client")]
 pub use self::{
     debug::DebugNamespaceClient, en::EnNamespaceClient, eth::EthNamespaceClient,
-    net::NetNamespaceClient, snapshots::SnapshotsNamespaceServer, web3::Web3NamespaceClient,
-    zks::ZksNamespaceClient,
+    net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient, txpool::TxPoolNamespaceClient,
+    web3::Web3NamespaceClient, zks::ZksNamespaceClient,
 };
 #[cfg(feature = "server")]
 pub use self::{
     debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
-    net::NetNamespaceServer, snapshots::SnapshotsNamespaceClient, web3::Web3NamespaceServer,
-    zks::ZksNamespaceServer,
+    net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer, txpool::TxPoolNamespaceServer,
+    web3::Web3NamespaceServer, zks::ZksNamespaceServer,
 };
@@ -280,6 +280,16 @@ impl PubSubFilter {
     }
 }
 
+/// Options of the `newPendingTransactions` subscription. For compatibility with Geth, the options
+/// can also be specified as a single boolean corresponding to `full_transactions`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransactionsOptions {
+    /// If set, full transaction bodies are sent to the subscriber instead of transaction hashes.
+    #[serde(default)]
+    pub full_transactions: bool,
+}
+
 #[derive(Default, Clone)]
 pub struct PubSubFilterBuilder {
     filter: PubSubFilter,
@@ -343,6 +353,7 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
 pub enum PubSubResult {
     Header(BlockHeader),
     Log(Log),
+    FullTx(zksync_types::api::Transaction),
     TxHash(H256),
     Syncing(bool),
 }
@@ -47,5 +47,9 @@ impl RestApi {
                 "/contract_verification/info/{address}",
                 web::get().to(Self::verification_info),
             )
+            // Etherscan-compatible API
+            .app_data(super::etherscan::form_config())
+            .route("/api", web::get().to(Self::etherscan_get))
+            .route("/api", web::post().to(Self::etherscan_post))
     }
 }
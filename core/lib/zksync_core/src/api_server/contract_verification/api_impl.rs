@@ -15,25 +15,22 @@ impl RestApi {
     #[tracing::instrument(skip(query))]
     fn validate_contract_verification_query(
         query: &VerificationIncomingRequest,
-    ) -> Result<(), HttpResponse> {
+    ) -> Result<(), &'static str> {
         if query.source_code_data.compiler_type() != query.compiler_versions.compiler_type() {
-            return Err(HttpResponse::BadRequest().body("incorrect compiler versions"));
+            return Err("incorrect compiler versions");
         }
 
         Ok(())
     }
 
-    /// Add a contract verification job to the queue if the requested contract wasn't previously verified.
-    #[tracing::instrument(skip(self_, request))]
-    pub async fn verification(
-        self_: web::Data<Self>,
-        Json(request): Json<VerificationIncomingRequest>,
-    ) -> ActixResult<HttpResponse> {
-        let method_latency = METRICS.call[&"contract_verification"].start();
-        if let Err(res) = Self::validate_contract_verification_query(&request) {
-            return Ok(res);
-        }
-        let mut storage = self_
+    /// Validates the request and adds it to the verification queue. Returns the ID of the added request,
+    /// or a user-facing error message if the request is rejected.
+    pub(super) async fn queue_verification_request(
+        &self,
+        request: VerificationIncomingRequest,
+    ) -> Result<usize, &'static str> {
+        Self::validate_contract_verification_query(&request)?;
+        let mut storage = self
             .master_connection_pool
             .access_storage_tagged("api")
             .await
@@ -44,17 +41,15 @@ impl RestApi {
             .is_contract_deployed_at_address(request.contract_address)
             .await
         {
-            return Ok(
-                HttpResponse::BadRequest().body("There is no deployed contract on this address")
-            );
+            return Err("There is no deployed contract on this address");
         }
         if storage
             .contract_verification_dal()
-            .is_contract_verified(request.contract_address)
+            .is_contract_fully_verified(request.contract_address)
             .await
             .unwrap()
         {
-            return Ok(HttpResponse::BadRequest().body("This contract is already verified"));
+            return Err("This contract is already verified");
         }
 
         let request_id = storage
@@ -62,6 +57,20 @@ impl RestApi {
             .add_contract_verification_request(request)
             .await
             .unwrap();
+        Ok(request_id)
+    }
+
+    /// Add a contract verification job to the queue if the requested contract wasn't previously verified.
+    #[tracing::instrument(skip(self_, request))]
+    pub async fn verification(
+        self_: web::Data<Self>,
+        Json(request): Json<VerificationIncomingRequest>,
+    ) -> ActixResult<HttpResponse> {
+        let method_latency = METRICS.call[&"contract_verification"].start();
+        let request_id = match self_.queue_verification_request(request).await {
+            Ok(request_id) => request_id,
+            Err(message) => return Ok(HttpResponse::BadRequest().body(message)),
+        };
 
         method_latency.observe();
         ok_json(request_id)
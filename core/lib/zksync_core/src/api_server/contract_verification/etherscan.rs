@@ -0,0 +1,527 @@
+//! Etherscan-compatible contract verification API (`/api?module=contract&action=...`).
+//!
+//! The API maps Etherscan requests onto the same verification queue as the native API, so that Etherscan
+//! verification plugins for Hardhat and Foundry can be used. zkSync-specific parameters (e.g., the `zksolc` version)
+//! are passed as additional request parameters.
+
+use std::{collections::HashMap, time::Instant};
+
+use actix_web::{error::InternalError, web, HttpResponse, Result as ActixResult};
+use serde::Serialize;
+use zksync_types::{
+    contract_verification_api::{
+        CompilerVersions, SourceCodeData, VerificationIncomingRequest, VerificationInfo,
+    },
+    Address,
+};
+
+use super::{api_decl::RestApi, metrics::METRICS};
+
+/// Result returned by Etherscan for ABI / source code requests for unverified contracts.
+const NOT_VERIFIED_MESSAGE: &str = "Contract source code not verified";
+/// Maximum size of a form-encoded request body. Matches the default limit for JSON bodies used by the native API;
+/// the default form limit (16 KiB) is too small for most standard JSON inputs.
+const MAX_FORM_SIZE: usize = 2 * 1_024 * 1_024;
+
+/// Returns config for form-encoded Etherscan requests. Malformed bodies are reported in the Etherscan format.
+pub(super) fn form_config() -> web::FormConfig {
+    web::FormConfig::default()
+        .limit(MAX_FORM_SIZE)
+        .error_handler(|err, _| {
+            let response = EtherscanResponse::from(Err(format!("Invalid request body: {err}")));
+            InternalError::from_response(err, HttpResponse::Ok().json(response)).into()
+        })
+}
+
+/// Etherscan API request parameters with lowercased names. Etherscan clients may pass parameters both
+/// in the query string and in the form-encoded body, and are not consistent with parameter name casing.
+type EtherscanParams = HashMap<String, String>;
+
+fn normalize_params(params: impl IntoIterator<Item = (String, String)>) -> EtherscanParams {
+    params
+        .into_iter()
+        .map(|(name, value)| (name.to_lowercase(), value))
+        .collect()
+}
+
+fn required_param<'a>(params: &'a EtherscanParams, name: &str) -> Result<&'a str, String> {
+    params
+        .get(name)
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("Missing or empty `{name}` parameter"))
+}
+
+fn parse_address(params: &EtherscanParams, name: &str) -> Result<Address, String> {
+    required_param(params, name)?
+        .parse()
+        .map_err(|_| format!("Invalid address in `{name}` parameter"))
+}
+
+/// Converts an Etherscan `verifysourcecode` request to a native verification request.
+fn parse_verification_request(
+    params: &EtherscanParams,
+) -> Result<VerificationIncomingRequest, String> {
+    let contract_address = parse_address(params, "contractaddress")?;
+    let optimization_used = params.get("optimizationused").map(String::as_str) == Some("1");
+    let runs = params
+        .get("runs")
+        .filter(|runs| !runs.is_empty())
+        .map(|runs| runs.parse::<u32>())
+        .transpose()
+        .map_err(|_| "Invalid `runs` parameter".to_owned())?;
+    let source_code = required_param(params, "sourcecode")?;
+    let source_code_data = match params.get("codeformat").map(String::as_str) {
+        None | Some("solidity-single-file") => {
+            SourceCodeData::SolSingleFile(source_code.to_owned())
+        }
+        Some("solidity-standard-json-input") => {
+            let mut input = serde_json::from_str(source_code)
+                .map_err(|err| format!("Invalid standard JSON input: {err}"))?;
+            apply_optimizer_settings(&mut input, optimization_used, runs);
+            SourceCodeData::StandardJsonInput(input)
+        }
+        Some(format) => return Err(format!("Unsupported code format: `{format}`")),
+    };
+
+    // Etherscan uses full compiler versions (e.g., `v0.8.17+commit.8df45f5f`), while the verifier
+    // only knows release versions.
+    let solc_version = required_param(params, "compilerversion")?;
+    let solc_version = solc_version.strip_prefix('v').unwrap_or(solc_version);
+    let solc_version = solc_version.split('+').next().unwrap_or(solc_version);
+    let zksolc_version = required_param(params, "zksolcversion")?;
+    let zksolc_version = if zksolc_version.starts_with('v') {
+        zksolc_version.to_owned()
+    } else {
+        format!("v{zksolc_version}")
+    };
+
+    // Etherscan misspells this parameter; we accept both spellings.
+    let constructor_arguments = params
+        .get("constructorarguements")
+        .or_else(|| params.get("constructorarguments"))
+        .map_or("", String::as_str);
+    let constructor_arguments = constructor_arguments
+        .strip_prefix("0x")
+        .unwrap_or(constructor_arguments);
+    let constructor_arguments = hex::decode(constructor_arguments)
+        .map_err(|_| "Invalid hex in constructor arguments".to_owned())?;
+
+    Ok(VerificationIncomingRequest {
+        contract_address,
+        source_code_data,
+        contract_name: required_param(params, "contractname")?.to_owned(),
+        compiler_versions: CompilerVersions::Solc {
+            compiler_zksolc_version: zksolc_version,
+            compiler_solc_version: solc_version.to_owned(),
+        },
+        optimization_used,
+        optimizer_mode: None,
+        constructor_arguments: constructor_arguments.into(),
+        is_system: false,
+    })
+}
+
+/// Fills in optimizer settings of a standard JSON input from the Etherscan parameters, unless they are specified
+/// in the input explicitly. For single-file sources, the verifier derives optimizer settings on its own; `zksolc`
+/// doesn't have an equivalent of `runs`.
+fn apply_optimizer_settings(
+    input: &mut serde_json::Map<String, serde_json::Value>,
+    optimization_used: bool,
+    runs: Option<u32>,
+) {
+    let settings = input
+        .entry("settings")
+        .or_insert_with(|| serde_json::json!({}));
+    // Malformed inputs are rejected by the verifier.
+    let Some(optimizer) = settings.as_object_mut().and_then(|settings| {
+        settings
+            .entry("optimizer")
+            .or_insert_with(|| serde_json::json!({}))
+            .as_object_mut()
+    }) else {
+        return;
+    };
+    optimizer
+        .entry("enabled")
+        .or_insert(optimization_used.into());
+    if let Some(runs) = runs {
+        optimizer.entry("runs").or_insert(runs.into());
+    }
+}
+
+/// Converts a native verification request status to the `checkverifystatus` result.
+/// Returns an error for requests that are not successfully verified (yet).
+fn verification_status_result(
+    status: &str,
+    error: Option<String>,
+    compilation_errors: Option<Vec<String>>,
+) -> Result<String, String> {
+    match status {
+        "successful" => Ok("Pass - Verified".to_owned()),
+        "queued" | "in_progress" => Err("Pending in queue".to_owned()),
+        "failed" => {
+            let mut message = "Fail - Unable to verify".to_owned();
+            if let Some(error) = error {
+                message += &format!(". {error}");
+            }
+            if let Some(compilation_errors) = compilation_errors {
+                message += &format!(". Compilation errors: {}", compilation_errors.join("; "));
+            }
+            Err(message)
+        }
+        _ => Err(format!("Unknown verification status: `{status}`")),
+    }
+}
+
+/// Etherscan API response envelope. Errors are returned with the 200 status code as well.
+#[derive(Debug, Serialize)]
+struct EtherscanResponse {
+    status: &'static str,
+    message: &'static str,
+    result: serde_json::Value,
+}
+
+impl From<Result<serde_json::Value, String>> for EtherscanResponse {
+    fn from(result: Result<serde_json::Value, String>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status: "1",
+                message: "OK",
+                result,
+            },
+            Err(message) => Self {
+                status: "0",
+                message: "NOTOK",
+                result: message.into(),
+            },
+        }
+    }
+}
+
+/// Entry of the `getsourcecode` result.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct EtherscanSourceCode {
+    source_code: String,
+    #[serde(rename = "ABI")]
+    abi: String,
+    contract_name: String,
+    compiler_version: String,
+    zk_compiler_version: String,
+    optimization_used: String,
+    runs: String,
+    constructor_arguments: String,
+    #[serde(rename = "EVMVersion")]
+    evm_version: String,
+    library: String,
+    license_type: String,
+    proxy: String,
+    implementation: String,
+    swarm_source: String,
+}
+
+impl EtherscanSourceCode {
+    fn not_verified() -> Self {
+        Self {
+            abi: NOT_VERIFIED_MESSAGE.to_owned(),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<VerificationInfo> for EtherscanSourceCode {
+    fn from(info: VerificationInfo) -> Self {
+        let request = info.request.req;
+        let runs = match &request.source_code_data {
+            SourceCodeData::StandardJsonInput(input) => input
+                .get("settings")
+                .and_then(|settings| settings.pointer("/optimizer/runs"))
+                .map(ToString::to_string),
+            _ => None,
+        };
+        let source_code = match request.source_code_data {
+            SourceCodeData::SolSingleFile(source) | SourceCodeData::YulSingleFile(source) => source,
+            // Etherscan wraps standard JSON input in an additional pair of braces to distinguish it
+            // from the single-file source code.
+            SourceCodeData::StandardJsonInput(input) => {
+                format!("{{{}}}", serde_json::Value::Object(input))
+            }
+            SourceCodeData::VyperMultiFile(sources) => serde_json::to_string(&sources).unwrap(),
+        };
+        Self {
+            source_code,
+            abi: info.artifacts.abi.to_string(),
+            contract_name: request.contract_name,
+            compiler_version: request.compiler_versions.compiler_version(),
+            zk_compiler_version: request.compiler_versions.zk_compiler_version(),
+            optimization_used: if request.optimization_used { "1" } else { "0" }.to_owned(),
+            runs: runs.unwrap_or_default(),
+            constructor_arguments: hex::encode(request.constructor_arguments.0),
+            evm_version: "Default".to_owned(),
+            proxy: "0".to_owned(),
+            ..Self::default()
+        }
+    }
+}
+
+impl RestApi {
+    /// Handles Etherscan API requests sent via `GET`.
+    #[tracing::instrument(skip(self_, query))]
+    pub async fn etherscan_get(
+        self_: web::Data<Self>,
+        query: web::Query<HashMap<String, String>>,
+    ) -> ActixResult<HttpResponse> {
+        let params = normalize_params(query.into_inner());
+        Ok(HttpResponse::Ok().json(self_.handle_etherscan_request(params).await))
+    }
+
+    /// Handles Etherscan API requests sent via `POST`. Parameters may be supplied both in the query string
+    /// and in the form-encoded body; the latter take precedence.
+    #[tracing::instrument(skip(self_, query, form))]
+    pub async fn etherscan_post(
+        self_: web::Data<Self>,
+        query: web::Query<HashMap<String, String>>,
+        form: web::Form<HashMap<String, String>>,
+    ) -> ActixResult<HttpResponse> {
+        let params = normalize_params(query.into_inner().into_iter().chain(form.into_inner()));
+        Ok(HttpResponse::Ok().json(self_.handle_etherscan_request(params).await))
+    }
+
+    async fn handle_etherscan_request(&self, params: EtherscanParams) -> EtherscanResponse {
+        let module = params.get("module").map_or("", String::as_str);
+        if module != "contract" {
+            return Err(format!("Unsupported module: `{module}`")).into();
+        }
+
+        let action = params.get("action").map_or("", String::as_str);
+        let started_at = Instant::now();
+        let (method, result) = match action {
+            "verifysourcecode" => (
+                "etherscan_verify_source_code",
+                self.etherscan_verify_source_code(&params).await,
+            ),
+            "checkverifystatus" => (
+                "etherscan_check_verify_status",
+                self.etherscan_check_verify_status(&params).await,
+            ),
+            "getabi" => ("etherscan_get_abi", self.etherscan_get_abi(&params).await),
+            "getsourcecode" => (
+                "etherscan_get_source_code",
+                self.etherscan_get_source_code(&params).await,
+            ),
+            _ => return Err(format!("Unsupported action: `{action}`")).into(),
+        };
+        METRICS.call[&method].observe(started_at.elapsed());
+        result.into()
+    }
+
+    async fn etherscan_verify_source_code(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<serde_json::Value, String> {
+        let request = parse_verification_request(params)?;
+        let request_id = self
+            .queue_verification_request(request)
+            .await
+            .map_err(str::to_owned)?;
+        // Etherscan clients expect the request ID (aka GUID) to be a string.
+        Ok(request_id.to_string().into())
+    }
+
+    async fn etherscan_check_verify_status(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<serde_json::Value, String> {
+        let request_id: usize = required_param(params, "guid")?
+            .parse()
+            .map_err(|_| "Invalid `guid` parameter".to_owned())?;
+        let status = self
+            .replica_connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_verification_request_status(request_id)
+            .await
+            .unwrap()
+            .ok_or_else(|| "Unknown verification request".to_owned())?;
+
+        verification_status_result(&status.status, status.error, status.compilation_errors)
+            .map(Into::into)
+    }
+
+    async fn etherscan_verification_info(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<Option<VerificationInfo>, String> {
+        let address = parse_address(params, "address")?;
+        let info = self
+            .replica_connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_contract_verification_info(address)
+            .await
+            .unwrap();
+        Ok(info)
+    }
+
+    async fn etherscan_get_abi(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<serde_json::Value, String> {
+        let info = self
+            .etherscan_verification_info(params)
+            .await?
+            .ok_or_else(|| NOT_VERIFIED_MESSAGE.to_owned())?;
+        // Etherscan returns ABI as a JSON-encoded string.
+        Ok(info.artifacts.abi.to_string().into())
+    }
+
+    async fn etherscan_get_source_code(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<serde_json::Value, String> {
+        let source_code = match self.etherscan_verification_info(params).await? {
+            Some(info) => EtherscanSourceCode::from(info),
+            None => EtherscanSourceCode::not_verified(),
+        };
+        Ok(serde_json::to_value([source_code]).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verification_params(extra_params: &[(&str, &str)]) -> EtherscanParams {
+        let params = [
+            ("module", "contract"),
+            ("action", "verifysourcecode"),
+            (
+                "contractaddress",
+                "0x0000000000000000000000000000000000001234",
+            ),
+            ("sourceCode", "contract Counter {}"),
+            ("contractname", "Counter"),
+            ("compilerversion", "v0.8.17+commit.8df45f5f"),
+            ("zksolcVersion", "1.3.18"),
+            ("optimizationUsed", "1"),
+            ("runs", "200"),
+        ];
+        let params = params.iter().chain(extra_params);
+        normalize_params(params.map(|&(name, value)| (name.to_owned(), value.to_owned())))
+    }
+
+    #[test]
+    fn parsing_single_file_verification_request() {
+        let params = verification_params(&[("constructorArguements", "00ff")]);
+        let request = parse_verification_request(&params).unwrap();
+
+        assert_eq!(request.contract_address, Address::from_low_u64_be(0x1234));
+        assert_matches::assert_matches!(
+            &request.source_code_data,
+            SourceCodeData::SolSingleFile(source) if source == "contract Counter {}"
+        );
+        assert_eq!(request.contract_name, "Counter");
+        assert_eq!(request.compiler_versions.compiler_version(), "0.8.17");
+        assert_eq!(request.compiler_versions.zk_compiler_version(), "v1.3.18");
+        assert!(request.optimization_used);
+        assert_eq!(request.constructor_arguments.0, [0, 0xff]);
+    }
+
+    #[test]
+    fn parsing_standard_json_verification_request() {
+        let params = verification_params(&[
+            ("codeformat", "solidity-standard-json-input"),
+            ("sourceCode", r#"{"language":"Solidity","sources":{}}"#),
+        ]);
+        let request = parse_verification_request(&params).unwrap();
+        assert_matches::assert_matches!(
+            &request.source_code_data,
+            SourceCodeData::StandardJsonInput(input) if input["language"] == "Solidity"
+        );
+        assert!(request.constructor_arguments.0.is_empty());
+
+        let params = verification_params(&[
+            ("codeformat", "solidity-standard-json-input"),
+            ("sourceCode", "contract Counter {}"),
+        ]);
+        let err = parse_verification_request(&params).unwrap_err();
+        assert!(err.contains("Invalid standard JSON input"), "{err}");
+    }
+
+    #[test]
+    fn applying_optimizer_settings_to_standard_json_input() {
+        let params = verification_params(&[
+            ("codeformat", "solidity-standard-json-input"),
+            ("sourceCode", r#"{"language":"Solidity","sources":{}}"#),
+        ]);
+        let request = parse_verification_request(&params).unwrap();
+        let SourceCodeData::StandardJsonInput(input) = &request.source_code_data else {
+            panic!("Unexpected source code: {:?}", request.source_code_data);
+        };
+        assert_eq!(
+            input["settings"]["optimizer"],
+            serde_json::json!({ "enabled": true, "runs": 200 })
+        );
+
+        // Explicit settings in the input take precedence.
+        let params = verification_params(&[
+            ("codeformat", "solidity-standard-json-input"),
+            (
+                "sourceCode",
+                r#"{"language":"Solidity","sources":{},"settings":{"optimizer":{"enabled":false,"runs":1}}}"#,
+            ),
+        ]);
+        let request = parse_verification_request(&params).unwrap();
+        let SourceCodeData::StandardJsonInput(input) = &request.source_code_data else {
+            panic!("Unexpected source code: {:?}", request.source_code_data);
+        };
+        assert_eq!(
+            input["settings"]["optimizer"],
+            serde_json::json!({ "enabled": false, "runs": 1 })
+        );
+
+        let params = verification_params(&[("runs", "many")]);
+        let err = parse_verification_request(&params).unwrap_err();
+        assert!(err.contains("runs"), "{err}");
+    }
+
+    #[test]
+    fn parsing_invalid_verification_request() {
+        let mut params = verification_params(&[]);
+        params.remove("zksolcversion");
+        let err = parse_verification_request(&params).unwrap_err();
+        assert!(err.contains("zksolcversion"), "{err}");
+
+        let params = verification_params(&[("codeformat", "vyper-json")]);
+        let err = parse_verification_request(&params).unwrap_err();
+        assert!(err.contains("Unsupported code format"), "{err}");
+    }
+
+    #[test]
+    fn converting_verification_status() {
+        assert_eq!(
+            verification_status_result("successful", None, None).unwrap(),
+            "Pass - Verified"
+        );
+        assert_eq!(
+            verification_status_result("queued", None, None).unwrap_err(),
+            "Pending in queue"
+        );
+        let err = verification_status_result(
+            "failed",
+            Some("Compilation error".to_owned()),
+            Some(vec!["missing semicolon".to_owned()]),
+        )
+        .unwrap_err();
+        assert!(
+            err.starts_with("Fail - Unable to verify. Compilation error"),
+            "{err}"
+        );
+        assert!(err.contains("missing semicolon"), "{err}");
+    }
+}
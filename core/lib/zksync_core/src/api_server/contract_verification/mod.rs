@@ -11,6 +11,7 @@ use self::api_decl::RestApi;
 
 mod api_decl;
 mod api_impl;
+mod etherscan;
 mod metrics;
 
 fn start_server(api: RestApi, bind_to: SocketAddr, threads: usize) -> Server {
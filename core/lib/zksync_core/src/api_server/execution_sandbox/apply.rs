@@ -99,6 +99,18 @@ pub(super) fn apply_vm_in_sandbox<T>(
             // This may be wrong only during transition period.
             max_virtual_blocks_to_create: 1,
         }
+    } else if block_args.is_replay {
+        // The state corresponds to the previous miniblock, so L2 block info in storage doesn't need to be reset.
+        let replayed_l2_block_info = rt_handle.block_on(read_l2_block_info(
+            &mut connection,
+            block_args.resolved_block_number,
+        ));
+        L2BlockEnv {
+            number: replayed_l2_block_info.l2_block_number,
+            timestamp: replayed_l2_block_info.l2_block_timestamp,
+            prev_block_hash: current_l2_block_info.l2_block_hash,
+            max_virtual_blocks_to_create: 1,
+        }
     } else if current_l2_block_info.l2_block_number == 0 {
         // Special case:
         // - For environments, where genesis block was created before virtual block upgrade it doesn't matter what we put here.
@@ -339,18 +351,26 @@ impl BlockArgs {
                 );
                 });
 
-                (
-                    self.resolved_block_number,
-                    l1_batch_number,
-                    l1_batch_timestamp,
-                )
+                let state_l2_block_number = if self.is_replay {
+                    self.resolved_block_number - 1
+                } else {
+                    self.resolved_block_number
+                };
+                (state_l2_block_number, l1_batch_number, l1_batch_timestamp)
             };
 
+        // When replaying a miniblock, it should be executed with its own protocol version
+        // rather than with the version of the preceding miniblock.
+        let version_l2_block_number = if self.is_replay {
+            self.resolved_block_number
+        } else {
+            state_l2_block_number
+        };
         // Blocks without version specified are considered to be of `Version9`.
         // TODO: remove `unwrap_or` when protocol version ID will be assigned for each block.
         let protocol_version = connection
             .blocks_dal()
-            .get_miniblock_protocol_version_id(state_l2_block_number)
+            .get_miniblock_protocol_version_id(version_l2_block_number)
             .await
             .unwrap()
             .unwrap_or(ProtocolVersionId::Version9);
@@ -49,6 +49,22 @@ impl TxExecutionArgs {
         }
     }
 
+    /// Arguments for re-executing transactions from a sealed miniblock. Transactions are executed as is,
+    /// without overriding nonces or balances.
+    pub fn for_replay(
+        enforced_base_fee: u64,
+        vm_execution_cache_misses_limit: Option<usize>,
+    ) -> Self {
+        let missed_storage_invocation_limit = vm_execution_cache_misses_limit.unwrap_or(usize::MAX);
+        Self {
+            execution_mode: TxExecutionMode::VerifyExecute,
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            enforced_base_fee: Some(enforced_base_fee),
+            missed_storage_invocation_limit,
+        }
+    }
+
     pub fn for_gas_estimate(
         vm_execution_cache_misses_limit: Option<usize>,
         tx: &Transaction,
@@ -185,3 +201,51 @@ async fn execute_tx_in_sandbox(
         vm_metrics::collect_tx_execution_metrics(total_factory_deps, &execution_result);
     (execution_result, tx_execution_metrics)
 }
+
+/// Re-executes transactions from a sealed miniblock on top of the state before this miniblock.
+/// Transactions are executed sequentially in a single VM instance; each transaction is executed
+/// with the specified custom tracers. Returns execution results in the same order as transactions.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn replay_miniblock_transactions(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    execution_args: TxExecutionArgs,
+    connection_pool: ConnectionPool,
+    block_args: BlockArgs,
+    transactions: Vec<(Transaction, Vec<ApiTracer>)>,
+) -> Vec<VmExecutionResultAndLogs> {
+    let Some((first_tx, _)) = transactions.first() else {
+        return vec![];
+    };
+    let first_tx = first_tx.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let span = span!(Level::DEBUG, "replay_in_sandbox").entered();
+        let results = apply::apply_vm_in_sandbox(
+            vm_permit,
+            shared_args,
+            &execution_args,
+            &connection_pool,
+            first_tx,
+            block_args,
+            |vm, _| {
+                let results = transactions.into_iter().map(|(tx, custom_tracers)| {
+                    vm.push_transaction(tx);
+                    let storage_invocation_tracer =
+                        StorageInvocations::new(execution_args.missed_storage_invocation_limit);
+                    let custom_tracers: Vec<_> = custom_tracers
+                        .into_iter()
+                        .map(|tracer| tracer.into_boxed())
+                        .chain(vec![storage_invocation_tracer.into_tracer_pointer()])
+                        .collect();
+                    vm.inspect(custom_tracers.into(), VmExecutionMode::OneTx)
+                });
+                results.collect()
+            },
+        );
+        span.exit();
+        results
+    })
+    .await
+    .unwrap()
+}
@@ -11,7 +11,10 @@ use zksync_utils::bytecode::{compress_bytecode, hash_bytecode};
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{execute_tx_eth_call, execute_tx_with_pending_state, TxExecutionArgs},
+    execute::{
+        execute_tx_eth_call, execute_tx_with_pending_state, replay_miniblock_transactions,
+        TxExecutionArgs,
+    },
     tracers::ApiTracer,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
 };
@@ -238,6 +241,9 @@ pub(crate) struct BlockArgs {
     block_id: api::BlockId,
     resolved_block_number: MiniblockNumber,
     l1_batch_timestamp_s: Option<u64>,
+    /// If set, the VM is initialized with the state *before* the resolved miniblock, so that
+    /// its transactions can be re-executed.
+    is_replay: bool,
 }
 
 impl BlockArgs {
@@ -247,6 +253,7 @@ impl BlockArgs {
             block_id,
             resolved_block_number,
             l1_batch_timestamp_s: None,
+            is_replay: false,
         }
     }
 
@@ -266,7 +273,32 @@ impl BlockArgs {
         let Some(resolved_block_number) = resolved_block_number else {
             return Ok(None);
         };
+        Self::for_sealed_miniblock(connection, block_id, resolved_block_number, false)
+            .await
+            .map(Some)
+    }
+
+    /// Loads information necessary to re-execute transactions in the specified sealed miniblock.
+    /// Returns `None` for the genesis miniblock, which cannot be re-executed.
+    pub async fn for_replay(
+        connection: &mut StorageProcessor<'_>,
+        miniblock_number: MiniblockNumber,
+    ) -> Result<Option<Self>, SqlxError> {
+        if miniblock_number == MiniblockNumber(0) {
+            return Ok(None);
+        }
+        let block_id = api::BlockId::Number(api::BlockNumber::Number(miniblock_number.0.into()));
+        Self::for_sealed_miniblock(connection, block_id, miniblock_number, true)
+            .await
+            .map(Some)
+    }
 
+    async fn for_sealed_miniblock(
+        connection: &mut StorageProcessor<'_>,
+        block_id: api::BlockId,
+        resolved_block_number: MiniblockNumber,
+        is_replay: bool,
+    ) -> Result<Self, SqlxError> {
         let l1_batch_number = connection
             .storage_web3_dal()
             .resolve_l1_batch_number_of_miniblock(resolved_block_number)
@@ -280,11 +312,12 @@ impl BlockArgs {
             l1_batch_timestamp_s.is_some(),
             "Missing batch timestamp for non-pending block"
         );
-        Ok(Some(Self {
+        Ok(Self {
             block_id,
             resolved_block_number,
             l1_batch_timestamp_s,
-        }))
+            is_replay,
+        })
     }
 
     pub fn resolved_block_number(&self) -> MiniblockNumber {
@@ -1,14 +1,30 @@
 use std::sync::Arc;
 
-use multivm::{tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer};
+use multivm::{
+    tracers::{CallTracer, FourByteTracer, PrestateTracer},
+    vm_latest::{HistoryMode, StructLogTracer},
+    MultiVMTracer, MultiVmTracerPointer,
+};
 use once_cell::sync::OnceCell;
 use zksync_state::WriteStorage;
-use zksync_types::vm_trace::Call;
+use zksync_types::{
+    api::StructLogOptions,
+    vm_trace::{Call, FourByteTrace, PrestateTrace, StructLog},
+};
 
 /// Custom tracers supported by our API
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer {
+        diff_mode: bool,
+        result: Arc<OnceCell<PrestateTrace>>,
+    },
+    FourByteTracer(Arc<OnceCell<FourByteTrace>>),
+    StructLogTracer {
+        options: StructLogOptions,
+        result: Arc<OnceCell<Vec<StructLog>>>,
+    },
 }
 
 impl ApiTracer {
@@ -20,6 +36,13 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer { diff_mode, result } => {
+                PrestateTracer::new(diff_mode, result).into_tracer_pointer()
+            }
+            ApiTracer::FourByteTracer(result) => FourByteTracer::new(result).into_tracer_pointer(),
+            ApiTracer::StructLogTracer { options, result } => {
+                StructLogTracer::new(options, result).into_tracer_pointer()
+            }
         }
     }
 }
@@ -18,9 +18,13 @@ use multivm::{
         },
     },
 };
-use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
+use zksync_config::configs::{
+    api::Web3JsonRpcConfig,
+    chain::{MempoolConfig, StateKeeperConfig},
+};
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool};
+use zksync_mempool::AccountLimits;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
     fee::{Fee, TransactionExecutionMetrics},
@@ -45,7 +49,10 @@ use crate::{
     },
     l1_gas_price::L1GasPriceProvider,
     metrics::{TxStage, APP_METRICS},
-    state_keeper::seal_criteria::{ConditionalSealer, SealData},
+    state_keeper::{
+        seal_criteria::{ConditionalSealer, SealData},
+        types::account_limits,
+    },
 };
 
 mod proxy;
@@ -228,12 +235,16 @@ pub struct TxSenderConfig {
     pub vm_execution_cache_misses_limit: Option<usize>,
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
+    /// Per-account limits for pending transactions. Not enforced if set to `None`
+    /// (e.g., on the external node, where transactions are checked by the main node).
+    pub account_limits: Option<AccountLimits>,
 }
 
 impl TxSenderConfig {
     pub fn new(
         state_keeper_config: &StateKeeperConfig,
         web3_json_config: &Web3JsonRpcConfig,
+        mempool_config: &MempoolConfig,
         chain_id: L2ChainId,
     ) -> Self {
         Self {
@@ -246,6 +257,7 @@ impl TxSenderConfig {
             validation_computational_gas_limit: state_keeper_config
                 .validation_computational_gas_limit,
             chain_id,
+            account_limits: Some(account_limits(mempool_config)),
         }
     }
 }
@@ -369,6 +381,8 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             );
         }
 
+        self.validate_account_limits(&tx).await?;
+
         let nonce = tx.common_data.nonce.0;
         let hash = tx.hash();
         let expected_nonce = self.get_expected_nonce(&tx).await;
@@ -389,7 +403,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         match submission_res_handle {
             L2TxSubmissionResult::AlreadyExecuted => Err(SubmitTxError::NonceIsTooLow(
                 expected_nonce.0,
-                expected_nonce.0 + self.0.sender_config.max_nonce_ahead,
+                expected_nonce.0 + self.max_nonce_ahead(),
                 nonce,
             )),
             L2TxSubmissionResult::Duplicate => Err(SubmitTxError::IncorrectTx(TxDuplication(hash))),
@@ -485,15 +499,16 @@ impl<G: L1GasPriceProvider> TxSender<G> {
 
     async fn validate_account_nonce(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
         let expected_nonce = self.get_expected_nonce(tx).await;
+        let max_nonce_ahead = self.max_nonce_ahead();
 
         if tx.common_data.nonce.0 < expected_nonce.0 {
             Err(SubmitTxError::NonceIsTooLow(
                 expected_nonce.0,
-                expected_nonce.0 + self.0.sender_config.max_nonce_ahead,
+                expected_nonce.0 + max_nonce_ahead,
                 tx.nonce().0,
             ))
         } else {
-            let max_nonce = expected_nonce.0 + self.0.sender_config.max_nonce_ahead;
+            let max_nonce = expected_nonce.0 + max_nonce_ahead;
             if !(expected_nonce.0..=max_nonce).contains(&tx.common_data.nonce.0) {
                 Err(SubmitTxError::NonceIsTooHigh(
                     expected_nonce.0,
@@ -506,6 +521,44 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         }
     }
 
+    fn max_nonce_ahead(&self) -> u32 {
+        let config = &self.0.sender_config;
+        match &config.account_limits {
+            Some(limits) => cmp::min(config.max_nonce_ahead, limits.max_nonce_gap),
+            None => config.max_nonce_ahead,
+        }
+    }
+
+    /// Checks that the transaction does not violate per-account limits for pending transactions,
+    /// i.e., that it is sufficiently priced if it replaces a pending transaction, or that
+    /// the account does not have too many pending transactions otherwise.
+    async fn validate_account_limits(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        let Some(limits) = &self.0.sender_config.account_limits else {
+            return Ok(());
+        };
+        let mut storage = self
+            .0
+            .master_connection_pool
+            .as_ref()
+            .unwrap() // Checked by the caller
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let mut transactions_dal = storage.transactions_dal();
+
+        let initiator = tx.initiator_account();
+        let replaced_fee = transactions_dal
+            .get_replaced_l2_tx_fee(initiator, tx.nonce(), tx.hash())
+            .await;
+        if let Some(replaced_fee) = replaced_fee {
+            limits.check_replacement(&replaced_fee, &tx.common_data.fee)?;
+        } else {
+            let pending_count = transactions_dal.get_pending_l2_txs_count(initiator).await;
+            limits.check_pending_count(pending_count)?;
+        }
+        Ok(())
+    }
+
     async fn get_expected_nonce(&self, tx: &L2Tx) -> Nonce {
         let mut connection = self
             .0
@@ -3,6 +3,7 @@ use multivm::{
     tracers::validator::ValidationError,
 };
 use thiserror::Error;
+use zksync_mempool::AccountLimitError;
 use zksync_types::{l2::error::TxCheckError, U256};
 
 use crate::api_server::execution_sandbox::SandboxExecutionError;
@@ -66,6 +67,10 @@ pub enum SubmitTxError {
     /// than required to start the invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGas,
+    #[error("too many pending transactions from the account; at most {0} are allowed")]
+    TooManyPendingTxs(usize),
+    #[error("replacement transaction underpriced; fees must be increased by at least {0}%")]
+    ReplacementUnderpriced(u32),
     /// Error returned from main node
     #[error("{0}")]
     ProxyError(#[from] zksync_web3_decl::jsonrpsee::core::Error),
@@ -98,6 +103,8 @@ impl SubmitTxError {
             Self::FeePerPubdataByteTooHigh => "pubdata-price-limit-too-high",
             Self::InsufficientFundsForTransfer => "insufficient-funds-for-transfer",
             Self::IntrinsicGas => "intrinsic-gas",
+            Self::TooManyPendingTxs(_) => "too-many-pending-txs",
+            Self::ReplacementUnderpriced(_) => "replacement-underpriced",
             Self::ProxyError(_) => "proxy-error",
         }
     }
@@ -111,6 +118,28 @@ impl SubmitTxError {
     }
 }
 
+impl From<AccountLimitError> for SubmitTxError {
+    fn from(err: AccountLimitError) -> Self {
+        match err {
+            AccountLimitError::TooManyPendingTxs(max_pending_txs) => {
+                Self::TooManyPendingTxs(max_pending_txs)
+            }
+            AccountLimitError::NonceGapTooLarge {
+                expected_nonce,
+                max_gap,
+                nonce,
+            } => Self::NonceIsTooHigh(
+                expected_nonce.0,
+                expected_nonce.0.saturating_add(max_gap),
+                nonce.0,
+            ),
+            AccountLimitError::ReplacementUnderpriced(bump_percent) => {
+                Self::ReplacementUnderpriced(bump_percent)
+            }
+        }
+    }
+}
+
 impl From<SandboxExecutionError> for SubmitTxError {
     fn from(err: SandboxExecutionError) -> SubmitTxError {
         match err {
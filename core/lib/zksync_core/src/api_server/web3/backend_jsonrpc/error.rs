@@ -10,6 +10,8 @@ pub fn into_jsrpc_error(err: Web3Error) -> Error {
         code: match err {
             Web3Error::InternalError | Web3Error::NotImplemented => ErrorCode::InternalError,
             Web3Error::NoBlock
+            | Web3Error::PrunedBlock(..)
+            | Web3Error::PrunedL1Batch(..)
             | Web3Error::NoSuchFunction
             | Web3Error::RLPError(_)
             | Web3Error::InvalidTransactionData(_)
@@ -18,11 +20,12 @@ pub fn into_jsrpc_error(err: Web3Error) -> Error {
             | Web3Error::InvalidFeeParams(_)
             | Web3Error::LogsLimitExceeded(_, _, _)
             | Web3Error::TooManyLogs(_)
+            | Web3Error::TooManyStructLogs(..)
             | Web3Error::InvalidFilterBlockHash => ErrorCode::InvalidParams,
             Web3Error::SubmitTransactionError(_, _) | Web3Error::SerializationError(_) => 3.into(),
             Web3Error::PubSubTimeout => 4.into(),
             Web3Error::RequestTimeout => 5.into(),
-            Web3Error::TreeApiUnavailable => 6.into(),
+            Web3Error::TreeApiUnavailable | Web3Error::MempoolUnavailable => 6.into(),
         },
         message: match err {
             Web3Error::SubmitTransactionError(_, _) => err.to_string(),
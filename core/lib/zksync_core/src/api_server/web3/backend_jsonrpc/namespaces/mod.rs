@@ -6,3 +6,4 @@ pub mod web3;
 pub mod zks;
 
 pub mod snapshots;
+pub mod txpool;
@@ -0,0 +1,57 @@
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_derive::rpc;
+use zksync_types::{
+    api::{Transaction, TxPoolAccountContent, TxPoolContent, TxPoolStatus},
+    Address,
+};
+
+use crate::{
+    l1_gas_price::L1GasPriceProvider,
+    web3::{backend_jsonrpc::error::into_jsrpc_error, namespaces::TxPoolNamespace},
+};
+
+#[rpc]
+pub trait TxPoolNamespaceT {
+    #[rpc(name = "txpool_status")]
+    fn status(&self) -> Result<TxPoolStatus>;
+
+    #[rpc(name = "txpool_content")]
+    fn content(&self) -> BoxFuture<Result<TxPoolContent<Transaction>>>;
+
+    #[rpc(name = "txpool_inspect")]
+    fn inspect(&self) -> Result<TxPoolContent<String>>;
+
+    #[rpc(name = "txpool_contentFrom")]
+    fn content_from(
+        &self,
+        address: Address,
+    ) -> BoxFuture<Result<TxPoolAccountContent<Transaction>>>;
+}
+
+impl<G: L1GasPriceProvider + Send + Sync + 'static> TxPoolNamespaceT for TxPoolNamespace<G> {
+    fn status(&self) -> Result<TxPoolStatus> {
+        self.status_impl().map_err(into_jsrpc_error)
+    }
+
+    fn content(&self) -> BoxFuture<Result<TxPoolContent<Transaction>>> {
+        let self_ = self.clone();
+        Box::pin(async move { self_.content_impl().await.map_err(into_jsrpc_error) })
+    }
+
+    fn inspect(&self) -> Result<TxPoolContent<String>> {
+        self.inspect_impl().map_err(into_jsrpc_error)
+    }
+
+    fn content_from(
+        &self,
+        address: Address,
+    ) -> BoxFuture<Result<TxPoolAccountContent<Transaction>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .content_from_impl(address)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+}
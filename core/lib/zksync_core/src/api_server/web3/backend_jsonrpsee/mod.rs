@@ -20,6 +20,8 @@ pub fn into_jsrpc_error(err: Web3Error) -> ErrorObjectOwned {
         match err {
             Web3Error::InternalError | Web3Error::NotImplemented => ErrorCode::InternalError.code(),
             Web3Error::NoBlock
+            | Web3Error::PrunedBlock(..)
+            | Web3Error::PrunedL1Batch(..)
             | Web3Error::NoSuchFunction
             | Web3Error::RLPError(_)
             | Web3Error::InvalidTransactionData(_)
@@ -28,11 +30,12 @@ pub fn into_jsrpc_error(err: Web3Error) -> ErrorObjectOwned {
             | Web3Error::InvalidFeeParams(_)
             | Web3Error::InvalidFilterBlockHash
             | Web3Error::LogsLimitExceeded(_, _, _)
-            | Web3Error::TooManyLogs(_) => ErrorCode::InvalidParams.code(),
+            | Web3Error::TooManyLogs(_)
+            | Web3Error::TooManyStructLogs(..) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _) | Web3Error::SerializationError(_) => 3,
             Web3Error::PubSubTimeout => 4,
             Web3Error::RequestTimeout => 5,
-            Web3Error::TreeApiUnavailable => 6,
+            Web3Error::TreeApiUnavailable | Web3Error::MempoolUnavailable => 6,
         },
         match err {
             Web3Error::SubmitTransactionError(ref message, _) => message.clone(),
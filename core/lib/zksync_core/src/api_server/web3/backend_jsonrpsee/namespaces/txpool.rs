@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use zksync_types::{
+    api::{Transaction, TxPoolAccountContent, TxPoolContent, TxPoolStatus},
+    Address,
+};
+use zksync_web3_decl::{jsonrpsee::core::RpcResult, namespaces::TxPoolNamespaceServer};
+
+use crate::{
+    api_server::web3::{backend_jsonrpsee::into_jsrpc_error, namespaces::TxPoolNamespace},
+    l1_gas_price::L1GasPriceProvider,
+};
+
+#[async_trait]
+impl<G: L1GasPriceProvider + Send + Sync + 'static> TxPoolNamespaceServer for TxPoolNamespace<G> {
+    async fn status(&self) -> RpcResult<TxPoolStatus> {
+        self.status_impl().map_err(into_jsrpc_error)
+    }
+
+    async fn content(&self) -> RpcResult<TxPoolContent<Transaction>> {
+        self.content_impl().await.map_err(into_jsrpc_error)
+    }
+
+    async fn inspect(&self) -> RpcResult<TxPoolContent<String>> {
+        self.inspect_impl().map_err(into_jsrpc_error)
+    }
+
+    async fn content_from(&self, address: Address) -> RpcResult<TxPoolAccountContent<Transaction>> {
+        self.content_from_impl(address)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+}
@@ -0,0 +1,935 @@
+//! Per-client rate limiting and method filtering for the HTTP JSON-RPC server.
+//!
+//! Clients are identified by an API key (if the request contains one of the configured keys) or by their IP address.
+//! By default, the IP address is the peer address of the connection. Forwarding headers are controlled by clients,
+//! so they are only honored if the server is configured to trust them (i.e., if it runs behind a proxy that sets
+//! these headers); in this case, the IP address is taken from the last entry of the `X-Forwarded-For` header
+//! (i.e., the one added by the closest proxy) or from the `X-Real-IP` header. Clients that cannot be identified
+//! share a single rate limiting budget.
+//!
+//! Limits are applied to individual JSON-RPC calls, so a batch request consumes as many units of the budget
+//! as there are calls in it. Expensive methods (e.g., `eth_call`) have a separate budget on top of the common one.
+//! A call consumes budget only if it's allowed by all applicable budgets.
+//!
+//! Neither of the supported JSON-RPC server implementations exposes the peer address of a connection to request
+//! middleware. Thus, limits are enforced by [`ClientLimitsServer`], which accepts client connections itself,
+//! records the peer address of each connection and forwards allowed calls to the JSON-RPC server listening
+//! on a loopback address.
+
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use futures::future;
+use hyper::{
+    body::Bytes, client::HttpConnector, header::CONTENT_TYPE, server::conn::AddrStream, HeaderMap,
+    StatusCode,
+};
+use serde_json::Value;
+use tokio::sync::watch;
+use zksync_config::configs::api::Web3JsonRpcConfig;
+
+use super::metrics::{ClientKind, ClientLimitRejection, CLIENT_LIMITS_METRICS};
+
+/// Default header containing API keys.
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+/// Methods that are limited by the expensive methods budget by default. Patterns ending with `*` match
+/// all methods with the specified prefix.
+pub const DEFAULT_EXPENSIVE_METHODS: &[&str] = &[
+    "eth_call",
+    "eth_estimateGas",
+    "eth_getLogs",
+    "zks_estimateFee",
+    "zks_estimateGasL1ToL2",
+    "debug_*",
+];
+/// JSON-RPC error code returned for rate-limited calls.
+const RATE_LIMITED_ERROR_CODE: i64 = 429;
+/// JSON-RPC error code returned for calls to disallowed methods ("method not found").
+const METHOD_NOT_ALLOWED_ERROR_CODE: i64 = -32601;
+/// JSON-RPC error code returned for oversized requests (the same as used by `jsonrpsee`).
+const OVERSIZED_REQUEST_ERROR_CODE: i64 = -32007;
+/// Interval between pruning rate limiting state for inactive clients.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Per-client limits for the HTTP JSON-RPC server.
+#[derive(Debug, Clone)]
+pub struct ClientLimits {
+    /// Maximum number of calls per minute for a single client.
+    pub requests_per_minute: Option<NonZeroU32>,
+    /// Maximum number of calls to expensive methods per minute for a single client. These calls are counted
+    /// towards `requests_per_minute` as well.
+    pub expensive_requests_per_minute: Option<NonZeroU32>,
+    /// Patterns of expensive methods. Patterns ending with `*` match all methods with the specified prefix.
+    pub expensive_methods: Vec<String>,
+    /// Name of the header containing API keys.
+    pub api_key_header: String,
+    /// API keys recognized by the server. Clients with a recognized key have a separate budget
+    /// (i.e., are not limited by their IP address); unrecognized keys are ignored.
+    pub api_keys: HashSet<String>,
+    /// If specified, only methods matching one of these patterns are allowed.
+    pub method_allow_list: Option<Vec<String>>,
+    /// Methods matching one of these patterns are not allowed.
+    pub method_deny_list: Vec<String>,
+    /// Whether to identify clients by the `X-Forwarded-For` / `X-Real-IP` headers rather than by the peer address.
+    /// Should only be enabled if the server is only reachable via a proxy overwriting these headers.
+    pub trust_forwarded_headers: bool,
+}
+
+impl ClientLimits {
+    /// Checks whether these limits don't restrict any calls.
+    pub fn is_disabled(&self) -> bool {
+        self.requests_per_minute.is_none()
+            && self.expensive_requests_per_minute.is_none()
+            && self.method_allow_list.is_none()
+            && self.method_deny_list.is_empty()
+    }
+}
+
+impl Default for ClientLimits {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: None,
+            expensive_requests_per_minute: None,
+            expensive_methods: DEFAULT_EXPENSIVE_METHODS
+                .iter()
+                .copied()
+                .map(String::from)
+                .collect(),
+            api_key_header: DEFAULT_API_KEY_HEADER.to_owned(),
+            api_keys: HashSet::new(),
+            method_allow_list: None,
+            method_deny_list: vec![],
+            trust_forwarded_headers: false,
+        }
+    }
+}
+
+/// Client limits settings as specified in the node configuration.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClientLimitsConfig<'a> {
+    pub requests_per_minute: Option<u32>,
+    pub expensive_requests_per_minute: Option<u32>,
+    pub expensive_methods: Option<&'a [String]>,
+    pub api_key_header: Option<&'a str>,
+    pub api_keys: &'a [String],
+    pub method_allow_list: Option<&'a [String]>,
+    pub method_deny_list: &'a [String],
+    pub trust_forwarded_headers: bool,
+}
+
+impl From<ClientLimitsConfig<'_>> for ClientLimits {
+    fn from(config: ClientLimitsConfig<'_>) -> Self {
+        let defaults = Self::default();
+        Self {
+            requests_per_minute: config.requests_per_minute.and_then(NonZeroU32::new),
+            expensive_requests_per_minute: config
+                .expensive_requests_per_minute
+                .and_then(NonZeroU32::new),
+            expensive_methods: config
+                .expensive_methods
+                .map_or(defaults.expensive_methods, <[_]>::to_vec),
+            api_key_header: config
+                .api_key_header
+                .map_or(defaults.api_key_header, str::to_owned),
+            api_keys: config.api_keys.iter().cloned().collect(),
+            method_allow_list: config.method_allow_list.map(<[_]>::to_vec),
+            method_deny_list: config.method_deny_list.to_vec(),
+            trust_forwarded_headers: config.trust_forwarded_headers,
+        }
+    }
+}
+
+impl From<&Web3JsonRpcConfig> for ClientLimits {
+    fn from(config: &Web3JsonRpcConfig) -> Self {
+        ClientLimitsConfig {
+            requests_per_minute: config.http_requests_per_minute_limit,
+            expensive_requests_per_minute: config.http_expensive_requests_per_minute_limit,
+            expensive_methods: config.http_expensive_methods.as_deref(),
+            api_key_header: config.http_api_key_header.as_deref(),
+            api_keys: config.http_api_keys.as_deref().unwrap_or_default(),
+            method_allow_list: config.http_method_allow_list.as_deref(),
+            method_deny_list: config.http_method_deny_list.as_deref().unwrap_or_default(),
+            trust_forwarded_headers: config.http_trust_forwarded_headers.unwrap_or(false),
+        }
+        .into()
+    }
+}
+
+fn matches_pattern(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+fn matches_any(patterns: &[String], method: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| matches_pattern(pattern, method))
+}
+
+/// Client identity used as a rate limiting key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ClientKey {
+    ApiKey(String),
+    Ip(IpAddr),
+    Unknown,
+}
+
+impl ClientKey {
+    fn kind(&self) -> ClientKind {
+        match self {
+            Self::ApiKey(_) => ClientKind::ApiKey,
+            Self::Ip(_) => ClientKind::Ip,
+            Self::Unknown => ClientKind::Unknown,
+        }
+    }
+}
+
+/// Error returned if a call is rejected by [`ClientLimiter`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub(crate) enum ClientLimitError {
+    #[error("method `{0}` is not allowed")]
+    MethodNotAllowed(String),
+    #[error("too many requests")]
+    RateLimited,
+    #[error("too many requests to expensive methods")]
+    ExpensiveRateLimited,
+}
+
+impl ClientLimitError {
+    pub fn code(&self) -> i64 {
+        match self {
+            Self::MethodNotAllowed(_) => METHOD_NOT_ALLOWED_ERROR_CODE,
+            Self::RateLimited | Self::ExpensiveRateLimited => RATE_LIMITED_ERROR_CODE,
+        }
+    }
+
+    fn rejection(&self) -> ClientLimitRejection {
+        match self {
+            Self::MethodNotAllowed(_) => ClientLimitRejection::MethodNotAllowed,
+            Self::RateLimited => ClientLimitRejection::RateLimited,
+            Self::ExpensiveRateLimited => ClientLimitRejection::ExpensiveRateLimited,
+        }
+    }
+}
+
+/// Rate limiting budget of a client. The budget is replenished continuously at the rate of `limit` units
+/// per minute, and can accumulate up to `limit` units.
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    units: f64,
+    updated_at: Instant,
+}
+
+impl Budget {
+    fn full(limit: Option<NonZeroU32>, now: Instant) -> Self {
+        Self {
+            units: limit.map_or(0.0, |limit| f64::from(limit.get())),
+            updated_at: now,
+        }
+    }
+
+    fn replenish(&mut self, limit: NonZeroU32, now: Instant) {
+        let limit = f64::from(limit.get());
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        self.units = (self.units + elapsed.as_secs_f64() * limit / 60.0).min(limit);
+        self.updated_at = now;
+    }
+
+    fn is_full(&mut self, limit: Option<NonZeroU32>, now: Instant) -> bool {
+        let Some(limit) = limit else {
+            return true;
+        };
+        self.replenish(limit, now);
+        self.units >= f64::from(limit.get())
+    }
+}
+
+/// Rate limiting budgets of a single client.
+#[derive(Debug)]
+struct ClientBudgets {
+    all_requests: Budget,
+    expensive_requests: Budget,
+}
+
+impl ClientBudgets {
+    fn full(limits: &ClientLimits, now: Instant) -> Self {
+        Self {
+            all_requests: Budget::full(limits.requests_per_minute, now),
+            expensive_requests: Budget::full(limits.expensive_requests_per_minute, now),
+        }
+    }
+
+    fn is_full(&mut self, limits: &ClientLimits, now: Instant) -> bool {
+        self.all_requests.is_full(limits.requests_per_minute, now)
+            && self
+                .expensive_requests
+                .is_full(limits.expensive_requests_per_minute, now)
+    }
+}
+
+/// Enforces [`ClientLimits`] for individual JSON-RPC calls.
+#[derive(Debug)]
+pub(crate) struct ClientLimiter {
+    limits: ClientLimits,
+    budgets: Mutex<HashMap<ClientKey, ClientBudgets>>,
+    last_pruned_at: Mutex<Instant>,
+}
+
+impl ClientLimiter {
+    pub fn new(limits: ClientLimits) -> Self {
+        Self {
+            limits,
+            budgets: Mutex::default(),
+            last_pruned_at: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Identifies the client based on the peer address of the connection (if known) and HTTP request headers.
+    pub fn client_key(&self, peer_ip: Option<IpAddr>, headers: &HeaderMap) -> ClientKey {
+        let api_key = headers
+            .get(self.limits.api_key_header.as_str())
+            .and_then(|value| value.to_str().ok());
+        if let Some(api_key) = api_key {
+            if self.limits.api_keys.contains(api_key) {
+                return ClientKey::ApiKey(api_key.to_owned());
+            }
+        }
+
+        let ip = if self.limits.trust_forwarded_headers {
+            Self::forwarded_ip(headers)
+        } else {
+            peer_ip
+        };
+        ip.map_or(ClientKey::Unknown, ClientKey::Ip)
+    }
+
+    fn forwarded_ip(headers: &HeaderMap) -> Option<IpAddr> {
+        let forwarded_ip = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .last();
+        let real_ip = headers
+            .get("x-real-ip")
+            .and_then(|value| value.to_str().ok());
+        forwarded_ip.or(real_ip)?.trim().parse().ok()
+    }
+
+    /// Checks whether a call to the specified method from the specified client should be processed.
+    pub fn check_call(&self, client: &ClientKey, method: &str) -> Result<(), ClientLimitError> {
+        let result = self.check_call_inner(client, method);
+        if let Err(err) = &result {
+            tracing::debug!("Rejected call to `{method}` from client {client:?}: {err}");
+            let labels = (err.rejection(), client.kind()).into();
+            CLIENT_LIMITS_METRICS.rejected_calls[&labels].inc();
+        }
+        self.prune_if_necessary();
+        result
+    }
+
+    fn check_call_inner(&self, client: &ClientKey, method: &str) -> Result<(), ClientLimitError> {
+        let is_denied = matches_any(&self.limits.method_deny_list, method);
+        let is_allowed = self
+            .limits
+            .method_allow_list
+            .as_ref()
+            .map_or(true, |allow_list| matches_any(allow_list, method));
+        if is_denied || !is_allowed {
+            return Err(ClientLimitError::MethodNotAllowed(method.to_owned()));
+        }
+
+        let all_requests_limit = self.limits.requests_per_minute;
+        let expensive_requests_limit = self
+            .limits
+            .expensive_requests_per_minute
+            .filter(|_| matches_any(&self.limits.expensive_methods, method));
+        if all_requests_limit.is_none() && expensive_requests_limit.is_none() {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut budgets = self.budgets.lock().unwrap();
+        let budgets = budgets
+            .entry(client.clone())
+            .or_insert_with(|| ClientBudgets::full(&self.limits, now));
+        // Check all applicable budgets before consuming any of them, so that rejected calls don't consume budget.
+        if let Some(limit) = all_requests_limit {
+            budgets.all_requests.replenish(limit, now);
+            if budgets.all_requests.units < 1.0 {
+                return Err(ClientLimitError::RateLimited);
+            }
+        }
+        if let Some(limit) = expensive_requests_limit {
+            budgets.expensive_requests.replenish(limit, now);
+            if budgets.expensive_requests.units < 1.0 {
+                return Err(ClientLimitError::ExpensiveRateLimited);
+            }
+        }
+
+        if all_requests_limit.is_some() {
+            budgets.all_requests.units -= 1.0;
+        }
+        if expensive_requests_limit.is_some() {
+            budgets.expensive_requests.units -= 1.0;
+        }
+        Ok(())
+    }
+
+    /// Applies limits to all calls in a JSON-RPC request (which may be a batch).
+    fn filter_request(&self, client: &ClientKey, body: &[u8]) -> FilteredRequest {
+        let Ok(request) = serde_json::from_slice::<Value>(body) else {
+            return FilteredRequest::Unchanged;
+        };
+        let (calls, is_batch) = match request {
+            Value::Array(calls) => (calls, true),
+            call => (vec![call], false),
+        };
+
+        let mut allowed = Vec::with_capacity(calls.len());
+        let mut rejected = vec![];
+        let mut has_rejected_calls = false;
+        for call in calls {
+            let Some(method) = call.get("method").and_then(Value::as_str) else {
+                allowed.push(call); // Let the server handle malformed calls
+                continue;
+            };
+            match self.check_call(client, method) {
+                Ok(()) => allowed.push(call),
+                Err(err) => {
+                    has_rejected_calls = true;
+                    if let Some(id) = call.get("id") {
+                        rejected.push(error_response(id.clone(), &err));
+                    }
+                }
+            }
+        }
+
+        if !has_rejected_calls {
+            return FilteredRequest::Unchanged;
+        }
+        let allowed = match (is_batch, allowed.is_empty()) {
+            (_, true) => None,
+            (true, false) => Some(Value::Array(allowed)),
+            (false, false) => Some(allowed.pop().unwrap()),
+        };
+        FilteredRequest::Filtered {
+            allowed,
+            rejected,
+            is_batch,
+        }
+    }
+
+    /// Removes rate limiting state for clients that have fully replenished their budget, so that the state
+    /// doesn't grow indefinitely.
+    fn prune_if_necessary(&self) {
+        let Ok(mut last_pruned_at) = self.last_pruned_at.try_lock() else {
+            return; // Another thread is pruning the state right now
+        };
+        if last_pruned_at.elapsed() < PRUNE_INTERVAL {
+            return;
+        }
+        let now = Instant::now();
+        let mut budgets = self.budgets.lock().unwrap();
+        budgets.retain(|_, budgets| !budgets.is_full(&self.limits, now));
+        budgets.shrink_to_fit();
+        *last_pruned_at = now;
+    }
+}
+
+/// `tower` layer enforcing [`ClientLimits`] for HTTP requests; used by [`ClientLimitsServer`].
+#[derive(Debug, Clone)]
+pub(crate) struct ClientLimitsLayer {
+    limiter: Arc<ClientLimiter>,
+    max_request_body_size: usize,
+    max_response_body_size: usize,
+}
+
+impl ClientLimitsLayer {
+    /// Creates a layer with the body size limits; these should match the limits of the wrapped server.
+    pub fn new(
+        limiter: Arc<ClientLimiter>,
+        max_request_body_size: usize,
+        max_response_body_size: usize,
+    ) -> Self {
+        Self {
+            limiter,
+            max_request_body_size,
+            max_response_body_size,
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for ClientLimitsLayer {
+    type Service = ClientLimitsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientLimitsService {
+            inner,
+            limiter: self.limiter.clone(),
+            max_request_body_size: self.max_request_body_size,
+            max_response_body_size: self.max_response_body_size,
+        }
+    }
+}
+
+/// Service produced by [`ClientLimitsLayer`]. Rejected calls are removed from the request and are responded to
+/// with JSON-RPC errors; the remaining calls are forwarded to the wrapped service.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientLimitsService<S> {
+    inner: S,
+    limiter: Arc<ClientLimiter>,
+    max_request_body_size: usize,
+    max_response_body_size: usize,
+}
+
+/// Outcome of applying limits to a JSON-RPC request.
+#[derive(Debug)]
+enum FilteredRequest {
+    /// All calls are allowed (or the request couldn't be parsed, in which case the server will return an error).
+    Unchanged,
+    /// Some calls are rejected.
+    Filtered {
+        /// Request with the allowed calls, or `None` if all calls are rejected.
+        allowed: Option<Value>,
+        /// Error responses for rejected calls. Rejected notifications are not responded to.
+        rejected: Vec<Value>,
+        is_batch: bool,
+    },
+}
+
+fn error_response(id: Value, err: &ClientLimitError) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": err.code(),
+            "message": err.to_string(),
+        },
+    })
+}
+
+/// Reads the body, returning `Ok(None)` if it's larger than `limit` bytes.
+async fn to_bytes_limited(body: hyper::Body, limit: usize) -> Result<Option<Bytes>, hyper::Error> {
+    match hyper::body::to_bytes(http_body::Limited::new(body, limit)).await {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(err) => match err.downcast::<hyper::Error>() {
+            Ok(err) => Err(*err),
+            Err(_) => Ok(None), // The only other possible error is `LengthLimitError`
+        },
+    }
+}
+
+fn oversized_response(message: &str) -> hyper::Response<hyper::Body> {
+    let error = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {
+            "code": OVERSIZED_REQUEST_ERROR_CODE,
+            "message": message,
+        },
+    });
+    let mut response = json_response(Some(&error));
+    *response.status_mut() = StatusCode::PAYLOAD_TOO_LARGE;
+    response
+}
+
+fn json_response(value: Option<&Value>) -> hyper::Response<hyper::Body> {
+    let value = value.filter(|value| value.as_array().map_or(true, |calls| !calls.is_empty()));
+    let body = if let Some(value) = value {
+        hyper::Body::from(serde_json::to_vec(value).unwrap())
+    } else {
+        // Per JSON-RPC spec, nothing is returned if the request consists only of notifications.
+        hyper::Body::empty()
+    };
+    hyper::Response::builder()
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .unwrap()
+}
+
+impl<S> tower::Service<hyper::Request<hyper::Body>> for ClientLimitsService<S>
+where
+    S: tower::Service<hyper::Request<hyper::Body>, Response = hyper::Response<hyper::Body>>
+        + Clone
+        + Send
+        + 'static,
+    S::Error: From<hyper::Error> + Send,
+    S::Future: Send,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::Request<hyper::Body>) -> Self::Future {
+        // Use the service that was driven to readiness, and leave a clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+        let max_request_body_size = self.max_request_body_size;
+        let max_response_body_size = self.max_response_body_size;
+
+        Box::pin(async move {
+            // The peer address is set by `ClientLimitsServer`; if it's not available, the client can only be identified
+            // by an API key or (if trusted) forwarding headers.
+            let peer_ip = request.extensions().get::<SocketAddr>().map(SocketAddr::ip);
+            let client = limiter.client_key(peer_ip, request.headers());
+            let (mut parts, body) = request.into_parts();
+            let Some(body) = to_bytes_limited(body, max_request_body_size).await? else {
+                return Ok(oversized_response("Request is too big"));
+            };
+            let (allowed, mut rejected, is_batch) = match limiter.filter_request(&client, &body) {
+                FilteredRequest::Unchanged => {
+                    let request = hyper::Request::from_parts(parts, body.into());
+                    return inner.call(request).await;
+                }
+                FilteredRequest::Filtered {
+                    allowed,
+                    rejected,
+                    is_batch,
+                } => (allowed, rejected, is_batch),
+            };
+
+            let Some(allowed) = allowed else {
+                let response = if is_batch {
+                    Some(Value::Array(rejected))
+                } else {
+                    rejected.pop()
+                };
+                return Ok(json_response(response.as_ref()));
+            };
+            // Only batch requests can get here.
+            let body = serde_json::to_vec(&allowed).unwrap();
+            parts.headers.remove(hyper::header::CONTENT_LENGTH);
+            let request = hyper::Request::from_parts(parts, body.into());
+            let response = inner.call(request).await?;
+
+            let (parts, body) = response.into_parts();
+            let Some(body) = to_bytes_limited(body, max_response_body_size).await? else {
+                return Ok(oversized_response("Response is too big"));
+            };
+            let responses = if body.is_empty() {
+                // All allowed calls were notifications.
+                Some(vec![])
+            } else {
+                serde_json::from_slice::<Vec<Value>>(&body).ok()
+            };
+            let Some(mut responses) = responses else {
+                // Not a batch response (e.g., an error); return it as is.
+                return Ok(hyper::Response::from_parts(parts, body.into()));
+            };
+            responses.append(&mut rejected);
+            let mut response = json_response(Some(&Value::Array(responses)));
+            *response.status_mut() = parts.status;
+            Ok(response)
+        })
+    }
+}
+
+/// Service forwarding requests to the JSON-RPC server behind [`ClientLimitsServer`].
+#[derive(Debug, Clone)]
+struct ForwardingService {
+    client: hyper::Client<HttpConnector>,
+    upstream_addr: SocketAddr,
+}
+
+impl tower::Service<hyper::Request<hyper::Body>> for ForwardingService {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: hyper::Request<hyper::Body>) -> Self::Future {
+        let path_and_query = request
+            .uri()
+            .path_and_query()
+            .map_or("/", |path_and_query| path_and_query.as_str());
+        *request.uri_mut() = hyper::Uri::builder()
+            .scheme("http")
+            .authority(self.upstream_addr.to_string())
+            .path_and_query(path_and_query)
+            .build()
+            .expect("invalid upstream URI");
+
+        let response = self.client.request(request);
+        Box::pin(async move {
+            match response.await {
+                Ok(response) => Ok(response),
+                Err(err) => {
+                    tracing::warn!("Failed forwarding request to JSON-RPC server: {err}");
+                    let mut response = hyper::Response::new(hyper::Body::empty());
+                    *response.status_mut() = StatusCode::BAD_GATEWAY;
+                    Ok(response)
+                }
+            }
+        })
+    }
+}
+
+/// HTTP server enforcing [`ClientLimits`] in front of a JSON-RPC server.
+///
+/// The server accepts client connections, inserts the peer address of each connection into request extensions
+/// (this is where [`ClientLimitsService`] takes it from) and forwards requests that are not fully rejected
+/// to the JSON-RPC server at `upstream_addr`, which should be only reachable via a loopback interface.
+#[derive(Debug)]
+pub(crate) struct ClientLimitsServer {
+    layer: ClientLimitsLayer,
+    upstream_addr: SocketAddr,
+}
+
+impl ClientLimitsServer {
+    pub fn new(layer: ClientLimitsLayer, upstream_addr: SocketAddr) -> Self {
+        Self {
+            layer,
+            upstream_addr,
+        }
+    }
+
+    /// Binds the server to the specified address. Returns the local address of the server and the future
+    /// running the server until a stop signal is received. Must be called in the context of a Tokio runtime.
+    pub fn bind(
+        self,
+        addr: SocketAddr,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<(SocketAddr, impl Future<Output = anyhow::Result<()>> + Send)> {
+        let forwarding_service = ForwardingService {
+            client: hyper::Client::new(),
+            upstream_addr: self.upstream_addr,
+        };
+        let layer = self.layer;
+        let make_service = hyper::service::make_service_fn(move |conn: &AddrStream| {
+            let peer_addr = conn.remote_addr();
+            let service = tower::ServiceBuilder::new()
+                .map_request(move |mut request: hyper::Request<hyper::Body>| {
+                    request.extensions_mut().insert(peer_addr);
+                    request
+                })
+                .layer(layer.clone())
+                .service(forwarding_service.clone());
+            future::ready(Ok::<_, Infallible>(service))
+        });
+
+        let server = hyper::Server::try_bind(&addr)
+            .with_context(|| format!("Failed binding client limits server to {addr}"))?
+            .serve(make_service);
+        let local_addr = server.local_addr();
+        let server = server.with_graceful_shutdown(async move {
+            // If the stop signal sender is dropped, the server should stop as well.
+            stop_receiver.changed().await.ok();
+        });
+        let server = async move { server.await.context("client limits server failed") };
+        Ok((local_addr, server))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> ClientLimits {
+        ClientLimits {
+            requests_per_minute: NonZeroU32::new(2),
+            expensive_requests_per_minute: NonZeroU32::new(1),
+            api_keys: HashSet::from(["key".to_owned()]),
+            ..ClientLimits::default()
+        }
+    }
+
+    #[test]
+    fn identifying_clients() {
+        let limiter = ClientLimiter::new(limits());
+        let peer_ip: IpAddr = "10.0.0.3".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(limiter.client_key(None, &headers), ClientKey::Unknown);
+        assert_eq!(
+            limiter.client_key(Some(peer_ip), &headers),
+            ClientKey::Ip(peer_ip)
+        );
+
+        // Forwarding headers are not trusted by default.
+        headers.insert("x-real-ip", "10.0.0.1".parse().unwrap());
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.2".parse().unwrap());
+        assert_eq!(limiter.client_key(None, &headers), ClientKey::Unknown);
+        assert_eq!(
+            limiter.client_key(Some(peer_ip), &headers),
+            ClientKey::Ip(peer_ip)
+        );
+
+        headers.insert(DEFAULT_API_KEY_HEADER, "unknown".parse().unwrap());
+        assert_eq!(
+            limiter.client_key(Some(peer_ip), &headers),
+            ClientKey::Ip(peer_ip)
+        );
+        headers.insert(DEFAULT_API_KEY_HEADER, "key".parse().unwrap());
+        assert_eq!(
+            limiter.client_key(Some(peer_ip), &headers),
+            ClientKey::ApiKey("key".to_owned())
+        );
+    }
+
+    #[test]
+    fn identifying_clients_behind_trusted_proxy() {
+        let limiter = ClientLimiter::new(ClientLimits {
+            trust_forwarded_headers: true,
+            ..limits()
+        });
+        let peer_ip: IpAddr = "10.0.0.3".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            limiter.client_key(Some(peer_ip), &headers),
+            ClientKey::Unknown
+        );
+
+        headers.insert("x-real-ip", "10.0.0.1".parse().unwrap());
+        assert_eq!(
+            limiter.client_key(Some(peer_ip), &headers),
+            ClientKey::Ip("10.0.0.1".parse().unwrap())
+        );
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.2".parse().unwrap());
+        assert_eq!(
+            limiter.client_key(Some(peer_ip), &headers),
+            ClientKey::Ip("10.0.0.2".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn filtering_methods() {
+        let limiter = ClientLimiter::new(ClientLimits {
+            method_allow_list: Some(vec!["eth_*".to_owned(), "zks_*".to_owned()]),
+            method_deny_list: vec!["eth_getLogs".to_owned()],
+            ..ClientLimits::default()
+        });
+        let client = ClientKey::Unknown;
+
+        limiter.check_call(&client, "eth_blockNumber").unwrap();
+        limiter.check_call(&client, "zks_L1ChainId").unwrap();
+        for _ in 0..10 {
+            limiter.check_call(&client, "eth_call").unwrap();
+        }
+        assert_eq!(
+            limiter.check_call(&client, "eth_getLogs"),
+            Err(ClientLimitError::MethodNotAllowed("eth_getLogs".to_owned()))
+        );
+        assert_eq!(
+            limiter.check_call(&client, "debug_traceCall"),
+            Err(ClientLimitError::MethodNotAllowed(
+                "debug_traceCall".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn limiting_requests_per_client() {
+        let limiter = ClientLimiter::new(limits());
+        let client = ClientKey::Ip("10.0.0.1".parse().unwrap());
+        let other_client = ClientKey::ApiKey("key".to_owned());
+
+        limiter.check_call(&client, "eth_call").unwrap();
+        assert_eq!(
+            limiter.check_call(&client, "debug_traceCall"),
+            Err(ClientLimitError::ExpensiveRateLimited)
+        );
+        // The rejected call hasn't consumed the common budget.
+        limiter.check_call(&client, "eth_blockNumber").unwrap();
+        assert_eq!(
+            limiter.check_call(&client, "eth_blockNumber"),
+            Err(ClientLimitError::RateLimited)
+        );
+
+        // Other clients have separate budgets.
+        limiter.check_call(&other_client, "eth_call").unwrap();
+        limiter
+            .check_call(&other_client, "eth_blockNumber")
+            .unwrap();
+
+        // Calls rejected by the common budget don't consume the expensive budget either.
+        let limiter = ClientLimiter::new(ClientLimits {
+            requests_per_minute: NonZeroU32::new(1),
+            expensive_requests_per_minute: NonZeroU32::new(2),
+            ..limits()
+        });
+        limiter.check_call(&client, "eth_blockNumber").unwrap();
+        assert_eq!(
+            limiter.check_call(&client, "eth_call"),
+            Err(ClientLimitError::RateLimited)
+        );
+        assert_eq!(
+            limiter.budgets.lock().unwrap()[&client]
+                .expensive_requests
+                .units,
+            2.0
+        );
+    }
+
+    #[test]
+    fn filtering_batch_requests() {
+        let limiter = ClientLimiter::new(ClientLimits {
+            method_deny_list: vec!["debug_*".to_owned()],
+            ..ClientLimits::default()
+        });
+        let client = ClientKey::Unknown;
+
+        let request = br#"{"jsonrpc":"2.0","id":1,"method":"eth_blockNumber"}"#;
+        let filtered = limiter.filter_request(&client, request);
+        assert!(
+            matches!(filtered, FilteredRequest::Unchanged),
+            "{filtered:?}"
+        );
+
+        let request = br#"{"jsonrpc":"2.0","id":1,"method":"debug_traceCall"}"#;
+        let filtered = limiter.filter_request(&client, request);
+        let FilteredRequest::Filtered {
+            allowed,
+            rejected,
+            is_batch,
+        } = filtered
+        else {
+            panic!("unexpected outcome: {filtered:?}");
+        };
+        assert_eq!(allowed, None);
+        assert!(!is_batch);
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0]["id"], 1);
+        assert_eq!(rejected[0]["error"]["code"], METHOD_NOT_ALLOWED_ERROR_CODE);
+
+        let request = br#"[
+            {"jsonrpc":"2.0","id":1,"method":"eth_blockNumber"},
+            {"jsonrpc":"2.0","id":2,"method":"debug_traceCall"},
+            {"jsonrpc":"2.0","method":"debug_traceBlockByNumber"}
+        ]"#;
+        let filtered = limiter.filter_request(&client, request);
+        let FilteredRequest::Filtered {
+            allowed,
+            rejected,
+            is_batch,
+        } = filtered
+        else {
+            panic!("unexpected outcome: {filtered:?}");
+        };
+        assert!(is_batch);
+        let allowed = allowed.unwrap();
+        assert_eq!(allowed.as_array().unwrap().len(), 1);
+        assert_eq!(allowed[0]["method"], "eth_blockNumber");
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0]["id"], 2);
+    }
+}
@@ -179,6 +179,7 @@ pub(super) enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    Syncing,
 }
 
 #[derive(Debug, Metrics)]
@@ -231,3 +232,42 @@ pub(super) struct FilterMetrics {
 
 #[vise::register]
 pub(super) static FILTER_METRICS: vise::Global<FilterMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum ClientLimitRejection {
+    MethodNotAllowed,
+    RateLimited,
+    ExpensiveRateLimited,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum ClientKind {
+    ApiKey,
+    Ip,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct ClientLimitLabels {
+    rejection: ClientLimitRejection,
+    client: ClientKind,
+}
+
+impl From<(ClientLimitRejection, ClientKind)> for ClientLimitLabels {
+    fn from((rejection, client): (ClientLimitRejection, ClientKind)) -> Self {
+        Self { rejection, client }
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_web3_client_limits")]
+pub(super) struct ClientLimitsMetrics {
+    /// Number of JSON-RPC calls rejected by per-client limits, grouped by the rejection reason
+    /// and the way the client was identified.
+    pub rejected_calls: Family<ClientLimitLabels, Counter>,
+}
+
+#[vise::register]
+pub(super) static CLIENT_LIMITS_METRICS: vise::Global<ClientLimitsMetrics> = vise::Global::new();
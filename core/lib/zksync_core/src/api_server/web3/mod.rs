@@ -1,4 +1,9 @@
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
@@ -6,7 +11,7 @@ use futures::future;
 use jsonrpc_core::MetaIoHandler;
 use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::PubSubHandler;
-use serde::Deserialize;
+use serde::{de::IntoDeserializer, Deserialize};
 use tokio::{
     sync::{mpsc, oneshot, watch, Mutex},
     task::JoinHandle,
@@ -23,7 +28,7 @@ use zksync_web3_decl::{
     },
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, NetNamespaceServer,
-        SnapshotsNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
+        SnapshotsNamespaceServer, TxPoolNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
     },
     types::Filter,
 };
@@ -34,17 +39,18 @@ use self::{
         error::internal_error,
         namespaces::{
             debug::DebugNamespaceT, en::EnNamespaceT, eth::EthNamespaceT, net::NetNamespaceT,
-            web3::Web3NamespaceT, zks::ZksNamespaceT,
+            txpool::TxPoolNamespaceT, web3::Web3NamespaceT, zks::ZksNamespaceT,
         },
         pub_sub::Web3PubSub,
     },
+    client_limits::{ClientLimiter, ClientLimits, ClientLimitsLayer, ClientLimitsServer},
     metrics::API_METRICS,
     namespaces::{
-        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace, Web3Namespace,
-        ZksNamespace,
+        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
+        TxPoolNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, PubSubEvent},
-    state::{Filters, InternalApiConfig, RpcState, SealedMiniblockNumber},
+    state::{BlockStartInfo, Filters, InternalApiConfig, RpcState, SealedMiniblockNumber},
 };
 use crate::{
     api_server::{
@@ -52,11 +58,13 @@ use crate::{
         web3::backend_jsonrpc::batch_limiter_middleware::RateLimitMetadata,
     },
     l1_gas_price::L1GasPriceProvider,
+    state_keeper::{MempoolGuard, SealedMiniblocksSender},
     sync_layer::SyncState,
 };
 
 pub mod backend_jsonrpc;
 pub mod backend_jsonrpsee;
+pub mod client_limits;
 mod metrics;
 pub mod namespaces;
 mod pubsub;
@@ -66,6 +74,8 @@ pub(crate) mod tests;
 
 /// Timeout for graceful shutdown logic within API servers.
 const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+/// Maximum request body size for `jsonrpsee` servers (the same as the `jsonrpsee` default).
+const MAX_REQUEST_BODY_SIZE: u32 = 10 * 1_024 * 1_024;
 
 /// Represents all kinds of `Filter`.
 #[derive(Debug, Clone)]
@@ -101,6 +111,8 @@ pub enum Namespace {
     En,
     Pubsub,
     Snapshots,
+    /// Mempool introspection (`txpool_*` methods). Requires the state keeper to run in the same process.
+    TxPool,
 }
 
 impl Namespace {
@@ -114,6 +126,14 @@ impl Namespace {
     ];
 }
 
+impl FromStr for Namespace {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::deserialize(s.into_deserializer())
+    }
+}
+
 /// Handles to the initialized API server.
 #[derive(Debug)]
 pub struct ApiServerHandles {
@@ -136,13 +156,16 @@ pub struct ApiBuilder<G> {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<usize>,
     websocket_requests_per_minute_limit: Option<u32>,
+    client_limits: Option<ClientLimits>,
     sync_state: Option<SyncState>,
+    mempool: Option<MempoolGuard>,
     threads: Option<usize>,
     vm_concurrency_limit: Option<usize>,
     polling_interval: Option<Duration>,
     namespaces: Option<Vec<Namespace>>,
     logs_translator_enabled: bool,
     tree_api_url: Option<String>,
+    sealed_miniblocks_sender: Option<SealedMiniblocksSender>,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -154,6 +177,7 @@ impl<G> ApiBuilder<G> {
             last_miniblock_pool: pool.clone(),
             pool,
             sync_state: None,
+            mempool: None,
             tx_sender: None,
             vm_barrier: None,
             filters_limit: None,
@@ -161,6 +185,7 @@ impl<G> ApiBuilder<G> {
             batch_request_size_limit: None,
             response_body_size_limit: None,
             websocket_requests_per_minute_limit: None,
+            client_limits: None,
             threads: None,
             vm_concurrency_limit: None,
             polling_interval: None,
@@ -168,6 +193,7 @@ impl<G> ApiBuilder<G> {
             config,
             logs_translator_enabled: false,
             tree_api_url: None,
+            sealed_miniblocks_sender: None,
             pub_sub_events_sender: None,
         }
     }
@@ -235,11 +261,33 @@ impl<G> ApiBuilder<G> {
         self
     }
 
+    /// Sets per-client rate limits and method filters. Only supported for the HTTP transport.
+    /// Limits that don't restrict any calls are ignored.
+    pub fn with_client_limits(mut self, client_limits: ClientLimits) -> Self {
+        self.client_limits = Some(client_limits).filter(|limits| !limits.is_disabled());
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.sync_state = Some(sync_state);
         self
     }
 
+    /// Provides the mempool of the state keeper running in the same process. Required
+    /// for the [`Namespace::TxPool`] namespace to return meaningful results.
+    pub fn with_mempool(mut self, mempool: MempoolGuard) -> Self {
+        self.mempool = Some(mempool);
+        self
+    }
+
+    /// Provides the channel with miniblocks sealed by the state keeper running in the same process.
+    /// If provided, WebSocket subscriptions for new blocks and logs are notified about sealed miniblocks
+    /// directly instead of polling Postgres.
+    pub fn with_sealed_miniblocks(mut self, sender: SealedMiniblocksSender) -> Self {
+        self.sealed_miniblocks_sender = Some(sender);
+        self
+    }
+
     pub fn with_threads(mut self, threads: usize) -> Self {
         self.threads = Some(threads);
         self
@@ -298,8 +346,10 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             connection_pool: self.pool,
             tx_sender: self.tx_sender.expect("TxSender is not provided"),
             sync_state: self.sync_state,
+            mempool: self.mempool,
             api_config: self.config,
             last_sealed_miniblock,
+            start_info: BlockStartInfo::default(),
             logs_translator_enabled: self.logs_translator_enabled,
             tree_api: self
                 .tree_api_url
@@ -338,6 +388,10 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             rpc.merge(DebugNamespace::new(rpc_state.clone()).await.into_rpc())
                 .expect("Can't merge debug namespace");
         }
+        if namespaces.contains(&Namespace::TxPool) {
+            rpc.merge(TxPoolNamespace::new(rpc_state.clone()).into_rpc())
+                .expect("Can't merge txpool namespace");
+        }
         if namespaces.contains(&Namespace::Snapshots) {
             rpc.merge(SnapshotsNamespace::new(rpc_state).into_rpc())
                 .expect("Can't merge snapshots namespace");
@@ -413,6 +467,14 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         let (health_check, health_updater) = ReactiveHealthCheck::new("http_api");
         let vm_barrier = self.vm_barrier.take().unwrap();
         // ^ `unwrap()` is safe by construction
+        // Response size is not limited for the `jsonrpc` backend (see the warning above).
+        let client_limits_layer = self.client_limits.take().map(|limits| {
+            ClientLimitsLayer::new(
+                Arc::new(ClientLimiter::new(limits)),
+                MAX_REQUEST_BODY_SIZE as usize,
+                usize::MAX,
+            )
+        });
 
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .enable_all()
@@ -425,12 +487,29 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
 
         let (local_addr_sender, local_addr) = oneshot::channel();
         let server_task = tokio::task::spawn_blocking(move || {
+            // If client limits are enforced, the server is only reachable via `ClientLimitsServer`.
+            let server_addr = if client_limits_layer.is_some() {
+                SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0)
+            } else {
+                addr
+            };
             let server = jsonrpc_http_server::ServerBuilder::new(io_handler)
                 .threads(1)
                 .event_loop_executor(runtime.handle().clone())
-                .start_http(&addr)
+                .start_http(&server_addr)
                 .context("jsonrpc_http::Server::start_http")?;
-            local_addr_sender.send(*server.address()).ok();
+            let server_addr = *server.address();
+
+            let (local_addr, client_limits_server) = if let Some(layer) = client_limits_layer {
+                let _guard = runtime.enter();
+                let (local_addr, client_limits_server) =
+                    ClientLimitsServer::new(layer, server_addr)
+                        .bind(addr, stop_receiver.clone())?;
+                (local_addr, Some(runtime.spawn(client_limits_server)))
+            } else {
+                (server_addr, None)
+            };
+            local_addr_sender.send(local_addr).ok();
 
             let close_handle = server.close_handle();
             let closing_vm_barrier = vm_barrier.clone();
@@ -447,6 +526,11 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
 
             health_updater.update(HealthStatus::Ready.into());
             server.wait();
+            if let Some(client_limits_server) = client_limits_server {
+                runtime
+                    .block_on(client_limits_server)
+                    .context("Client limits server panicked")??;
+            }
             drop(health_updater);
             tracing::info!("HTTP JSON-RPC server stopped");
             runtime.block_on(Self::wait_for_vm(vm_barrier, "HTTP"));
@@ -508,6 +592,9 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         if namespaces.contains(&Namespace::Net) {
             io.extend_with(NetNamespace::new(zksync_network_id).to_delegate());
         }
+        if namespaces.contains(&Namespace::TxPool) {
+            io.extend_with(TxPoolNamespace::new(rpc_state.clone()).to_delegate());
+        }
         if namespaces.contains(&Namespace::Debug) {
             let debug_ns = DebugNamespace::new(rpc_state).await;
             io.extend_with(debug_ns.to_delegate());
@@ -522,6 +609,11 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         if self.response_body_size_limit.is_some() {
             tracing::info!("`response_body_size_limit` is not supported for `jsonrpc` backend, this value is ignored");
         }
+        if self.client_limits.is_some() {
+            tracing::info!(
+                "`client_limits` are not supported for WebSocket transport, this value is ignored"
+            );
+        }
 
         let (health_check, health_updater) = ReactiveHealthCheck::new("ws_api");
         let websocket_requests_per_second_limit = self.websocket_requests_per_minute_limit;
@@ -552,6 +644,12 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             if let Some(sender) = self.pub_sub_events_sender.take() {
                 pub_sub.set_events_sender(sender);
             }
+            if let Some(sync_state) = self.sync_state.clone() {
+                pub_sub.set_sync_state(sync_state);
+            }
+            if let Some(sender) = self.sealed_miniblocks_sender.take() {
+                pub_sub.set_sealed_miniblocks_sender(sender);
+            }
             let polling_interval = self
                 .polling_interval
                 .context("Polling interval is not set")?;
@@ -559,6 +657,7 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             tasks.extend(pub_sub.spawn_notifiers(
                 self.pool.clone(),
                 polling_interval,
+                self.config.l2_chain_id,
                 stop_receiver.clone(),
             ));
             io_handler.extend_with(pub_sub.to_delegate());
@@ -639,7 +738,14 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             if self.websocket_requests_per_minute_limit.is_some() {
                 tracing::info!("`websocket_requests_per_second_limit` is not supported for `jsonrpsee` backend, this value is ignored");
             }
+            if self.client_limits.is_some() {
+                tracing::info!("`client_limits` are not supported for WebSocket transport, this value is ignored");
+            }
         }
+        let client_limits = match transport {
+            ApiTransport::Http(_) => self.client_limits.take(),
+            ApiTransport::WebSocket(_) => None,
+        };
 
         let (runtime_thread_name, health_check_name) = match transport {
             ApiTransport::Http(_) => ("jsonrpsee-http-worker", "http_api"),
@@ -656,6 +762,13 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             .response_body_size_limit
             .map(|limit| limit as u32)
             .unwrap_or(u32::MAX);
+        let client_limits_layer = client_limits.map(|limits| {
+            ClientLimitsLayer::new(
+                Arc::new(ClientLimiter::new(limits)),
+                MAX_REQUEST_BODY_SIZE as usize,
+                response_body_size_limit as usize,
+            )
+        });
 
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .enable_all()
@@ -679,6 +792,7 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
                 vm_barrier,
                 batch_request_config,
                 response_body_size_limit,
+                client_limits_layer,
             ));
             runtime.shutdown_timeout(GRACEFUL_SHUTDOWN_TIMEOUT);
             res
@@ -712,6 +826,7 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         vm_barrier: VmConcurrencyBarrier,
         batch_request_config: BatchRequestConfig,
         response_body_size_limit: u32,
+        client_limits_layer: Option<ClientLimitsLayer>,
     ) -> anyhow::Result<()> {
         let (transport_str, is_http, addr) = match transport {
             ApiTransport::Http(addr) => ("HTTP", true, addr),
@@ -740,6 +855,12 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
             .option_layer(cors);
+        // If client limits are enforced, the server is only reachable via `ClientLimitsServer`.
+        let server_addr = if client_limits_layer.is_some() {
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0)
+        } else {
+            addr
+        };
 
         let server_builder = if is_http {
             ServerBuilder::default().http_only().max_connections(5_000)
@@ -750,13 +871,21 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         let server = server_builder
             .set_batch_request_config(batch_request_config)
             .set_middleware(middleware)
+            .max_request_body_size(MAX_REQUEST_BODY_SIZE)
             .max_response_body_size(response_body_size_limit)
-            .build(addr)
+            .build(server_addr)
             .await
             .with_context(|| format!("Failed building {transport_str} JSON-RPC server"))?;
-        let local_addr = server.local_addr().with_context(|| {
+        let server_addr = server.local_addr().with_context(|| {
             format!("Failed getting local address for {transport_str} JSON-RPC server")
         })?;
+        let (local_addr, client_limits_server) = if let Some(layer) = client_limits_layer {
+            let (local_addr, client_limits_server) =
+                ClientLimitsServer::new(layer, server_addr).bind(addr, stop_receiver.clone())?;
+            (local_addr, Some(tokio::spawn(client_limits_server)))
+        } else {
+            (server_addr, None)
+        };
         local_addr_sender.send(local_addr).ok();
         let server_handle = server.start(rpc);
 
@@ -778,6 +907,11 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         health_updater.update(HealthStatus::Ready.into());
 
         server_handle.stopped().await;
+        if let Some(client_limits_server) = client_limits_server {
+            client_limits_server
+                .await
+                .context("Client limits server panicked")??;
+        }
         drop(health_updater);
         tracing::info!("{transport_str} JSON-RPC server stopped");
         Self::wait_for_vm(vm_barrier, transport_str).await;
@@ -5,30 +5,42 @@ use once_cell::sync::OnceCell;
 use zksync_dal::ConnectionPool;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        self, BlockId, BlockNumber, DebugCall, DebugTrace, ResultDebugTrace, StructLogOptions,
+        SupportedTracers, TracerConfig, TracerOptions,
+    },
     l2::L2Tx,
     transaction_request::CallRequest,
-    vm_trace::Call,
-    AccountTreeId, L2ChainId, H256, USED_BOOTLOADER_MEMORY_BYTES,
+    vm_trace::{Call, FourByteTrace, PrestateTrace, StructLogTrace},
+    AccountTreeId, L2ChainId, MiniblockNumber, Transaction, H256, USED_BOOTLOADER_MEMORY_BYTES,
 };
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
     api_server::{
         execution_sandbox::{
-            execute_tx_eth_call, ApiTracer, BlockArgs, TxSharedArgs, VmConcurrencyLimiter,
+            execute_tx_eth_call, replay_miniblock_transactions, ApiTracer, BlockArgs,
+            TxExecutionArgs, TxSharedArgs, VmConcurrencyLimiter,
         },
         tx_sender::ApiContracts,
         web3::{
             backend_jsonrpc::error::internal_error,
             metrics::API_METRICS,
             resolve_block,
-            state::{RpcState, SealedMiniblockNumber},
+            state::{BlockStartInfo, RpcState, SealedMiniblockNumber},
         },
     },
     l1_gas_price::L1GasPriceProvider,
 };
 
+/// Number of instructions recorded by the struct logger if the limit is not specified in the request.
+const DEFAULT_STRUCT_LOGS_LIMIT: usize = 10_000;
+/// Maximum number of instructions recorded by the struct logger.
+const MAX_STRUCT_LOGS_LIMIT: usize = 100_000;
+/// Maximum number of instructions recorded by the struct logger if memory recording is enabled.
+/// The heap of the current frame is copied on each step, so the limit is lower than [`MAX_STRUCT_LOGS_LIMIT`].
+const MAX_STRUCT_LOGS_WITH_MEMORY_LIMIT: usize = 1_000;
+
 #[derive(Debug, Clone)]
 pub struct DebugNamespace {
     connection_pool: ConnectionPool,
@@ -38,6 +50,7 @@ pub struct DebugNamespace {
     vm_concurrency_limiter: Arc<VmConcurrencyLimiter>,
     storage_caches: PostgresStorageCaches,
     last_sealed_miniblock: SealedMiniblockNumber,
+    start_info: BlockStartInfo,
     chain_id: L2ChainId,
 }
 
@@ -54,6 +67,7 @@ impl DebugNamespace {
             vm_concurrency_limiter: state.tx_sender.vm_concurrency_limiter(),
             storage_caches: state.tx_sender.storage_caches(),
             last_sealed_miniblock: state.last_sealed_miniblock,
+            start_info: state.start_info,
             chain_id: sender_config.chain_id,
         }
     }
@@ -63,38 +77,61 @@ impl DebugNamespace {
         &self,
         block_id: BlockId,
         options: Option<TracerConfig>,
-    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
+    ) -> Result<Vec<ResultDebugTrace>, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_block";
 
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let (tracer, tracer_options) = unpack_tracer_config(options);
         let mut connection = self
             .connection_pool
             .access_storage_tagged("api")
             .await
             .unwrap();
         let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
-        let call_trace = connection
-            .blocks_web3_dal()
-            .get_trace_for_miniblock(block_number)
-            .await
-            .unwrap();
-        let call_trace = call_trace
+        self.start_info
+            .ensure_miniblock_not_pruned(&mut connection, block_number, METHOD_NAME)
+            .await?;
+        let traces = if tracer == SupportedTracers::CallTracer {
+            let call_trace = connection
+                .blocks_web3_dal()
+                .get_trace_for_miniblock(block_number)
+                .await
+                .unwrap();
+            call_trace
+                .into_iter()
+                .map(|call_trace| {
+                    let mut result: DebugCall = call_trace.into();
+                    if tracer_options.only_top_call {
+                        result.calls = vec![];
+                    }
+                    result.into()
+                })
+                .collect()
+        } else {
+            let transactions = connection
+                .transactions_web3_dal()
+                .get_raw_miniblock_transactions(block_number)
+                .await
+                .map_err(|err| internal_error(METHOD_NAME, err))?;
+            drop(connection);
+            self.replay_miniblock(
+                METHOD_NAME,
+                block_number,
+                transactions,
+                0,
+                tracer,
+                &tracer_options,
+            )
+            .await?
+        };
+        let traces = traces
             .into_iter()
-            .map(|call_trace| {
-                let mut result: DebugCall = call_trace.into();
-                if only_top_call {
-                    result.calls = vec![];
-                }
-                ResultDebugCall { result }
-            })
+            .map(|result| ResultDebugTrace { result })
             .collect();
 
         let block_diff = self.last_sealed_miniblock.diff(block_number);
         method_latency.observe(block_diff);
-        Ok(call_trace)
+        Ok(traces)
     }
 
     #[tracing::instrument(skip(self))]
@@ -102,25 +139,145 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Option<DebugCall> {
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
-        let call_trace = self
+    ) -> Result<Option<DebugTrace>, Web3Error> {
+        const METHOD_NAME: &str = "debug_trace_transaction";
+
+        let (tracer, tracer_options) = unpack_tracer_config(options);
+        let mut connection = self
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
-            .transactions_dal()
-            .get_call_trace(tx_hash)
-            .await;
-        call_trace.map(|call_trace| {
-            let mut result: DebugCall = call_trace.into();
-            if only_top_call {
-                result.calls = vec![];
+            .unwrap();
+        if tracer == SupportedTracers::CallTracer {
+            let call_trace = connection.transactions_dal().get_call_trace(tx_hash).await;
+            return Ok(call_trace.map(|call_trace| {
+                let mut result: DebugCall = call_trace.into();
+                if tracer_options.only_top_call {
+                    result.calls = vec![];
+                }
+                result.into()
+            }));
+        }
+
+        let tx = connection
+            .transactions_web3_dal()
+            .get_transaction(api::TransactionId::Hash(tx_hash), self.chain_id)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        // Transactions that are not included into a miniblock yet cannot be replayed.
+        let Some((block_number, tx_index)) =
+            tx.and_then(|tx| Some((tx.block_number?, tx.transaction_index?)))
+        else {
+            return Ok(None);
+        };
+        let block_number = MiniblockNumber(block_number.as_u32());
+        self.start_info
+            .ensure_miniblock_not_pruned(&mut connection, block_number, METHOD_NAME)
+            .await?;
+        let tx_index = tx_index.as_usize();
+        let mut transactions = connection
+            .transactions_web3_dal()
+            .get_raw_miniblock_transactions(block_number)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        drop(connection);
+
+        // Transactions after the traced one don't influence its execution.
+        transactions.truncate(tx_index + 1);
+        let mut traces = self
+            .replay_miniblock(
+                METHOD_NAME,
+                block_number,
+                transactions,
+                tx_index,
+                tracer,
+                &tracer_options,
+            )
+            .await?;
+        Ok(traces.pop())
+    }
+
+    /// Re-executes transactions from a sealed miniblock with the specified tracer and returns traces
+    /// for transactions starting from `first_traced_tx`. Preceding transactions are executed
+    /// without tracing, so that traced transactions observe the same state as during the original execution.
+    async fn replay_miniblock(
+        &self,
+        method_name: &'static str,
+        block_number: MiniblockNumber,
+        transactions: Vec<Transaction>,
+        first_traced_tx: usize,
+        tracer: SupportedTracers,
+        tracer_options: &TracerOptions,
+    ) -> Result<Vec<DebugTrace>, Web3Error> {
+        let mut connection = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let block_args = BlockArgs::for_replay(&mut connection, block_number)
+            .await
+            .map_err(|err| internal_error(method_name, err))?;
+        let Some(block_args) = block_args else {
+            // The genesis miniblock doesn't contain transactions.
+            return Ok(vec![]);
+        };
+        let header = connection
+            .blocks_dal()
+            .get_miniblock_header(block_number)
+            .await
+            .map_err(|err| internal_error(method_name, err))?
+            .ok_or(Web3Error::NoBlock)?;
+        drop(connection);
+
+        let mut shared_args = self.shared_args();
+        shared_args.l1_gas_price = header.l1_gas_price;
+        shared_args.fair_l2_gas_price = header.l2_fair_gas_price;
+        let execution_args = TxExecutionArgs::for_replay(
+            header.base_fee_per_gas,
+            self.vm_execution_cache_misses_limit,
+        );
+
+        let mut tx_hashes = Vec::with_capacity(transactions.len());
+        let mut tracer_outputs = vec![];
+        let transactions = transactions
+            .into_iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                tx_hashes.push(tx.hash());
+                if i < first_traced_tx {
+                    return (tx, vec![]);
+                }
+                let (output, api_tracer) = TracerOutput::new(tracer, tracer_options);
+                tracer_outputs.push(output);
+                (tx, vec![api_tracer])
+            })
+            .collect();
+
+        let vm_permit = self.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
+        let results = replay_miniblock_transactions(
+            vm_permit,
+            shared_args,
+            execution_args,
+            self.connection_pool.clone(),
+            block_args,
+            transactions,
+        )
+        .await;
+        for (tx_hash, result) in tx_hashes.iter().zip(&results) {
+            if let ExecutionResult::Halt { reason } = &result.result {
+                // Transactions included into a miniblock cannot halt, so this means that replay has diverged
+                // from the original execution.
+                tracing::warn!(
+                    "Transaction {tx_hash:?} halted when replaying miniblock #{block_number}: {reason}"
+                );
             }
-            result
-        })
+        }
+
+        tracer_outputs
+            .into_iter()
+            .map(|output| output.into_trace().ok_or(Web3Error::InternalError))
+            .collect()
     }
 
     #[tracing::instrument(skip(self, request, block_id))]
@@ -129,14 +286,15 @@ impl DebugNamespace {
         request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<DebugTrace, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_call";
 
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let mut options = options.unwrap_or_default();
+        if options.tracer.is_none() {
+            options.struct_log_options = limit_struct_logs(options.struct_log_options)?;
+        }
 
         let mut connection = self
             .connection_pool
@@ -147,6 +305,13 @@ impl DebugNamespace {
             .await
             .map_err(|err| internal_error("debug_trace_call", err))?
             .ok_or(Web3Error::NoBlock)?;
+        self.start_info
+            .ensure_miniblock_not_pruned(
+                &mut connection,
+                block_args.resolved_block_number(),
+                METHOD_NAME,
+            )
+            .await?;
         drop(connection);
 
         let tx = L2Tx::from_request(request.into(), USED_BOOTLOADER_MEMORY_BYTES)?;
@@ -155,12 +320,26 @@ impl DebugNamespace {
         let vm_permit = self.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
 
-        // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
-        } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+        let struct_logs_result = Arc::new(OnceCell::default());
+        let mut tracer_output = None;
+        let custom_tracers = match options.tracer {
+            None => vec![ApiTracer::StructLogTracer {
+                options: options.struct_log_options,
+                result: struct_logs_result.clone(),
+            }],
+            // We don't need properly trace if we only need top call
+            Some(SupportedTracers::CallTracer) if options.tracer_config.only_top_call => vec![],
+            Some(SupportedTracers::CallTracer) => {
+                vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+            }
+            Some(
+                tracer @ (SupportedTracers::PrestateTracer | SupportedTracers::FourByteTracer),
+            ) => {
+                let (output, api_tracer) = TracerOutput::new(tracer, &options.tracer_config);
+                tracer_output = Some(output);
+                vec![api_tracer]
+            }
         };
 
         let result = execute_tx_eth_call(
@@ -185,6 +364,27 @@ impl DebugNamespace {
             }
         };
 
+        let block_diff = self.last_sealed_miniblock.diff_with_block_args(&block_args);
+        if let Some(tracer_output) = tracer_output {
+            let trace = tracer_output.into_trace().ok_or(Web3Error::InternalError)?;
+            method_latency.observe(block_diff);
+            return Ok(trace);
+        }
+        if options.tracer.is_none() {
+            // Struct logs are only produced by the latest VM version.
+            let struct_logs = struct_logs_result
+                .get()
+                .cloned()
+                .ok_or(Web3Error::NotImplemented)?;
+            method_latency.observe(block_diff);
+            return Ok(DebugTrace::StructLogs(StructLogTrace {
+                gas: result.statistics.gas_used.into(),
+                failed: revert_reason.is_some(),
+                return_value: output.into(),
+                struct_logs,
+            }));
+        }
+
         // We had only one copy of Arc this arc is already dropped it's safe to unwrap
         let trace = Arc::try_unwrap(call_tracer_result)
             .unwrap()
@@ -200,9 +400,8 @@ impl DebugNamespace {
             trace,
         );
 
-        let block_diff = self.last_sealed_miniblock.diff_with_block_args(&block_args);
         method_latency.observe(block_diff);
-        Ok(call.into())
+        Ok(DebugCall::from(call).into())
     }
 
     fn shared_args(&self) -> TxSharedArgs {
@@ -217,3 +416,112 @@ impl DebugNamespace {
         }
     }
 }
+
+/// Applies the server-side default and maximum to the number of instructions recorded by the struct logger.
+fn limit_struct_logs(mut options: StructLogOptions) -> Result<StructLogOptions, Web3Error> {
+    let max_limit = if options.enable_memory {
+        MAX_STRUCT_LOGS_WITH_MEMORY_LIMIT
+    } else {
+        MAX_STRUCT_LOGS_LIMIT
+    };
+    let limit = options
+        .limit
+        .unwrap_or(DEFAULT_STRUCT_LOGS_LIMIT.min(max_limit));
+    if limit > max_limit {
+        return Err(Web3Error::TooManyStructLogs(limit, max_limit));
+    }
+    options.limit = Some(limit);
+    Ok(options)
+}
+
+/// Unpacks tracer options for methods using `callTracer` if the tracer is not specified.
+fn unpack_tracer_config(options: Option<TracerConfig>) -> (SupportedTracers, TracerOptions) {
+    let options = options.unwrap_or_default();
+    let tracer = options.tracer.unwrap_or(SupportedTracers::CallTracer);
+    (tracer, options.tracer_config)
+}
+
+/// Output of a tracer that is not based on call traces.
+#[derive(Debug)]
+enum TracerOutput {
+    Prestate(Arc<OnceCell<PrestateTrace>>),
+    FourByte(Arc<OnceCell<FourByteTrace>>),
+}
+
+impl TracerOutput {
+    fn new(tracer: SupportedTracers, options: &TracerOptions) -> (Self, ApiTracer) {
+        match tracer {
+            SupportedTracers::PrestateTracer => {
+                let result = Arc::new(OnceCell::new());
+                let api_tracer = ApiTracer::PrestateTracer {
+                    diff_mode: options.diff_mode,
+                    result: result.clone(),
+                };
+                (Self::Prestate(result), api_tracer)
+            }
+            SupportedTracers::FourByteTracer => {
+                let result = Arc::new(OnceCell::new());
+                (
+                    Self::FourByte(result.clone()),
+                    ApiTracer::FourByteTracer(result),
+                )
+            }
+            SupportedTracers::CallTracer => {
+                unreachable!("`callTracer` output is handled separately")
+            }
+        }
+    }
+
+    fn into_trace(self) -> Option<DebugTrace> {
+        match self {
+            Self::Prestate(result) => result.get().cloned().map(DebugTrace::Prestate),
+            Self::FourByte(result) => result.get().cloned().map(DebugTrace::FourByte),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn limiting_struct_logs() {
+        let options = limit_struct_logs(StructLogOptions::default()).unwrap();
+        assert_eq!(options.limit, Some(DEFAULT_STRUCT_LOGS_LIMIT));
+        assert!(!options.enable_memory);
+
+        let options = StructLogOptions {
+            enable_memory: true,
+            ..StructLogOptions::default()
+        };
+        let options = limit_struct_logs(options).unwrap();
+        assert_eq!(options.limit, Some(MAX_STRUCT_LOGS_WITH_MEMORY_LIMIT));
+
+        let options = StructLogOptions {
+            limit: Some(42),
+            ..StructLogOptions::default()
+        };
+        let options = limit_struct_logs(options).unwrap();
+        assert_eq!(options.limit, Some(42));
+
+        let options = StructLogOptions {
+            limit: Some(MAX_STRUCT_LOGS_LIMIT + 1),
+            ..StructLogOptions::default()
+        };
+        let err = limit_struct_logs(options).unwrap_err();
+        assert_matches!(err, Web3Error::TooManyStructLogs(_, MAX_STRUCT_LOGS_LIMIT));
+
+        let options = StructLogOptions {
+            enable_memory: true,
+            limit: Some(MAX_STRUCT_LOGS_WITH_MEMORY_LIMIT + 1),
+            ..StructLogOptions::default()
+        };
+        let err = limit_struct_logs(options).unwrap_err();
+        assert_matches!(
+            err,
+            Web3Error::TooManyStructLogs(_, MAX_STRUCT_LOGS_WITH_MEMORY_LIMIT)
+        );
+    }
+}
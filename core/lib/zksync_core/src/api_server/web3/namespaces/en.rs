@@ -32,12 +32,18 @@ impl<G: L1GasPriceProvider> EnNamespace<G> {
         block_number: MiniblockNumber,
         include_transactions: bool,
     ) -> Result<Option<SyncBlock>, Web3Error> {
+        const METHOD_NAME: &str = "en_syncL2Block";
+
         let mut storage = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
             .unwrap();
+        self.state
+            .start_info
+            .ensure_miniblock_not_pruned(&mut storage, block_number, METHOD_NAME)
+            .await?;
         storage
             .sync_dal()
             .sync_block(
@@ -46,6 +52,6 @@ impl<G: L1GasPriceProvider> EnNamespace<G> {
                 include_transactions,
             )
             .await
-            .map_err(|err| internal_error("en_syncL2Block", err))
+            .map_err(|err| internal_error(METHOD_NAME, err))
     }
 }
@@ -18,15 +18,11 @@ use zksync_web3_decl::{
 };
 
 use crate::{
-    api_server::{
-        execution_sandbox::BlockArgs,
-        web3::{
-            backend_jsonrpc::error::internal_error,
-            metrics::{BlockCallObserver, API_METRICS},
-            resolve_block,
-            state::RpcState,
-            TypedFilter,
-        },
+    api_server::web3::{
+        backend_jsonrpc::error::internal_error,
+        metrics::{BlockCallObserver, API_METRICS},
+        state::RpcState,
+        TypedFilter,
     },
     l1_gas_price::L1GasPriceProvider,
 };
@@ -89,10 +85,10 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
             .access_storage_tagged("api")
             .await
             .unwrap();
-        let block_args = BlockArgs::new(&mut connection, block_id)
-            .await
-            .map_err(|err| internal_error("eth_call", err))?
-            .ok_or(Web3Error::NoBlock)?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id, METHOD_NAME)
+            .await?;
         drop(connection);
 
         let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
@@ -192,7 +188,10 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
             .access_storage_tagged("api")
             .await
             .unwrap();
-        let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
+        let block_number = self
+            .state
+            .resolve_block(&mut connection, block_id, METHOD_NAME)
+            .await?;
         let balance = connection
             .storage_web3_dal()
             .standard_token_historical_balance(
@@ -259,6 +258,17 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
             .state
             .resolve_filter_block_number(filter.from_block)
             .await?;
+        let mut connection = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        self.state
+            .start_info
+            .ensure_miniblock_not_pruned(&mut connection, from_block, METHOD_NAME)
+            .await?;
+        drop(connection);
         let logs = self
             .filter_changes(&mut TypedFilter::Events(filter, from_block))
             .await?;
@@ -283,12 +293,13 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         };
         let method_latency = API_METRICS.start_block_call(method_name, block_id);
 
-        let block = self
+        let mut connection = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
+            .unwrap();
+        let block = connection
             .blocks_web3_dal()
             .get_block_by_web3_block_id(
                 block_id,
@@ -296,15 +307,19 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
                 self.state.api_config.l2_chain_id,
             )
             .await
-            .map_err(|err| internal_error(method_name, err));
+            .map_err(|err| internal_error(method_name, err))?;
 
-        if let Ok(Some(block)) = &block {
-            let block_number = MiniblockNumber(block.number.as_u32());
-            self.report_latency_with_block_id(method_latency, block_number);
-        } else {
+        let Some(block) = block else {
             method_latency.observe_without_diff();
-        }
-        block
+            return Ok(None);
+        };
+        let block_number = MiniblockNumber(block.number.as_u32());
+        self.state
+            .start_info
+            .ensure_miniblock_not_pruned(&mut connection, block_number, method_name)
+            .await?;
+        self.report_latency_with_block_id(method_latency, block_number);
+        Ok(Some(block))
     }
 
     #[tracing::instrument(skip(self))]
@@ -315,23 +330,28 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         const METHOD_NAME: &str = "get_block_transaction_count";
 
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let tx_count = self
+        let mut connection = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
+            .unwrap();
+        let tx_count = connection
             .blocks_web3_dal()
             .get_block_tx_count(block_id)
             .await
-            .map_err(|err| internal_error(METHOD_NAME, err));
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
 
-        if let Ok(Some((block_number, _))) = &tx_count {
-            self.report_latency_with_block_id(method_latency, *block_number);
-        } else {
+        let Some((block_number, tx_count)) = tx_count else {
             method_latency.observe_without_diff();
-        }
-        Ok(tx_count?.map(|(_, count)| count))
+            return Ok(None);
+        };
+        self.state
+            .start_info
+            .ensure_miniblock_not_pruned(&mut connection, block_number, METHOD_NAME)
+            .await?;
+        self.report_latency_with_block_id(method_latency, block_number);
+        Ok(Some(tx_count))
     }
 
     #[tracing::instrument(skip(self))]
@@ -350,7 +370,10 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
             .access_storage_tagged("api")
             .await
             .unwrap();
-        let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
+        let block_number = self
+            .state
+            .resolve_block(&mut connection, block_id, METHOD_NAME)
+            .await?;
         let contract_code = connection
             .storage_web3_dal()
             .get_contract_code_unchecked(address, block_number)
@@ -384,7 +407,10 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
             .access_storage_tagged("api")
             .await
             .unwrap();
-        let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
+        let block_number = self
+            .state
+            .resolve_block(&mut connection, block_id, METHOD_NAME)
+            .await?;
         let value = connection
             .storage_web3_dal()
             .get_historical_value_unchecked(&storage_key, block_number)
@@ -426,7 +452,10 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
                 (nonce, None)
             }
             _ => {
-                let block_number = resolve_block(&mut connection, block_id, method_name).await?;
+                let block_number = self
+                    .state
+                    .resolve_block(&mut connection, block_id, method_name)
+                    .await?;
                 let nonce = connection
                     .storage_web3_dal()
                     .get_address_historical_nonce(address, block_number)
@@ -724,8 +753,10 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
             .access_storage_tagged("api")
             .await
             .unwrap();
-        let newest_miniblock =
-            resolve_block(&mut connection, BlockId::Number(newest_block), METHOD_NAME).await?;
+        let newest_miniblock = self
+            .state
+            .resolve_block(&mut connection, BlockId::Number(newest_block), METHOD_NAME)
+            .await?;
 
         let mut base_fee_per_gas = connection
             .blocks_web3_dal()
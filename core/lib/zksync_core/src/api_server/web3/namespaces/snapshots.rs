@@ -93,6 +93,7 @@ impl<G: L1GasPriceProvider> SnapshotsNamespace<G> {
                 last_l1_batch_with_metadata: l1_batch_with_metadata,
                 storage_logs_chunks: chunks,
                 factory_deps_filepath: snapshot_metadata.factory_deps_filepath,
+                parent_l1_batch_number: snapshot_metadata.parent_l1_batch_number,
             }))
         } else {
             method_latency.observe();
@@ -0,0 +1,222 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use zksync_mempool::AccountTransactionsView;
+use zksync_types::{api, l2::L2Tx, Address, Nonce, H256, U256};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::{
+    api_server::web3::{
+        backend_jsonrpc::error::internal_error, metrics::API_METRICS, state::RpcState,
+    },
+    l1_gas_price::L1GasPriceProvider,
+    state_keeper::MempoolGuard,
+};
+
+/// Maximum number of transaction hashes loaded from the storage with a single query.
+const MAX_HASHES_PER_QUERY: usize = 1_000;
+
+/// Reference to a mempool transaction. Only references are collected while the mempool is locked;
+/// full transactions are loaded from the storage afterwards.
+type TxRef = (Nonce, H256);
+
+fn tx_ref(tx: &L2Tx) -> TxRef {
+    (tx.nonce(), tx.hash())
+}
+
+/// Introspection of the node mempool. Only L2 transactions are reported. Transactions are considered
+/// pending if they are ready for execution, and queued if they cannot be executed until a nonce gap
+/// is filled.
+#[derive(Debug)]
+pub struct TxPoolNamespace<G> {
+    state: RpcState<G>,
+}
+
+impl<G> Clone for TxPoolNamespace<G> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<G: L1GasPriceProvider> TxPoolNamespace<G> {
+    pub fn new(state: RpcState<G>) -> Self {
+        Self { state }
+    }
+
+    fn mempool(&self) -> Result<&MempoolGuard, Web3Error> {
+        self.state
+            .mempool
+            .as_ref()
+            .ok_or(Web3Error::MempoolUnavailable)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn status_impl(&self) -> Result<api::TxPoolStatus, Web3Error> {
+        const METHOD_NAME: &str = "txpool_status";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let (pending, queued) = self.mempool()?.l2_transaction_counts();
+        method_latency.observe();
+        Ok(api::TxPoolStatus {
+            pending: (pending as u64).into(),
+            queued: (queued as u64).into(),
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn content_impl(&self) -> Result<api::TxPoolContent<api::Transaction>, Web3Error> {
+        const METHOD_NAME: &str = "txpool_content";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let views = self.mempool()?.account_views(tx_ref);
+        let mut transactions = self.load_transactions(METHOD_NAME, views.values()).await?;
+        let mut content = api::TxPoolContent::default();
+        for (address, view) in views {
+            let pending = take_loaded(&view.pending, &mut transactions);
+            let queued = take_loaded(&view.queued, &mut transactions);
+            insert_non_empty(&mut content.pending, address, pending);
+            insert_non_empty(&mut content.queued, address, queued);
+        }
+        method_latency.observe();
+        Ok(content)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn inspect_impl(&self) -> Result<api::TxPoolContent<String>, Web3Error> {
+        const METHOD_NAME: &str = "txpool_inspect";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        // Only the summarized fields are copied while the mempool is locked; strings are formatted afterwards.
+        let views = self
+            .mempool()?
+            .account_views(|tx| (tx.nonce(), TxSummary::new(tx)));
+        let mut content = api::TxPoolContent::default();
+        for (address, view) in views {
+            insert_non_empty(
+                &mut content.pending,
+                address,
+                format_summaries(view.pending),
+            );
+            insert_non_empty(&mut content.queued, address, format_summaries(view.queued));
+        }
+        method_latency.observe();
+        Ok(content)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn content_from_impl(
+        &self,
+        address: Address,
+    ) -> Result<api::TxPoolAccountContent<api::Transaction>, Web3Error> {
+        const METHOD_NAME: &str = "txpool_content_from";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let Some(view) = self.mempool()?.account_view(address, tx_ref) else {
+            method_latency.observe();
+            return Ok(api::TxPoolAccountContent::default());
+        };
+        let mut transactions = self
+            .load_transactions(METHOD_NAME, [&view].into_iter())
+            .await?;
+        let content = api::TxPoolAccountContent {
+            pending: take_loaded(&view.pending, &mut transactions),
+            queued: take_loaded(&view.queued, &mut transactions),
+        };
+        method_latency.observe();
+        Ok(content)
+    }
+
+    /// Loads API representations of the mempool transactions from the storage. Transactions
+    /// are loaded in chunks so that the size of the query parameters is bounded.
+    async fn load_transactions(
+        &self,
+        method_name: &'static str,
+        views: impl Iterator<Item = &AccountTransactionsView<TxRef>>,
+    ) -> Result<HashMap<H256, api::Transaction>, Web3Error> {
+        let hashes: Vec<_> = views
+            .flat_map(|view| view.pending.iter().chain(&view.queued))
+            .map(|&(_, hash)| hash)
+            .collect();
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(method_name, err))?;
+
+        let mut transactions = HashMap::with_capacity(hashes.len());
+        for chunk in hashes.chunks(MAX_HASHES_PER_QUERY) {
+            let chunk_transactions = storage
+                .transactions_web3_dal()
+                .get_transactions_by_hashes(chunk, self.state.api_config.l2_chain_id)
+                .await
+                .map_err(|err| internal_error(method_name, err))?;
+            transactions.extend(chunk_transactions.into_iter().map(|tx| (tx.hash, tx)));
+        }
+        Ok(transactions)
+    }
+}
+
+/// Maps loaded transactions by nonce, skipping transactions missing from `loaded`
+/// (e.g., ones that were executed or replaced after the mempool snapshot was taken).
+fn take_loaded(
+    tx_refs: &[TxRef],
+    loaded: &mut HashMap<H256, api::Transaction>,
+) -> BTreeMap<Nonce, api::Transaction> {
+    tx_refs
+        .iter()
+        .filter_map(|&(nonce, hash)| Some((nonce, loaded.remove(&hash)?)))
+        .collect()
+}
+
+fn insert_non_empty<T>(
+    content: &mut BTreeMap<Address, BTreeMap<Nonce, T>>,
+    address: Address,
+    transactions: BTreeMap<Nonce, T>,
+) {
+    if !transactions.is_empty() {
+        content.insert(address, transactions);
+    }
+}
+
+/// Fields of a transaction included into its textual summary.
+#[derive(Debug)]
+struct TxSummary {
+    contract_address: Address,
+    value: U256,
+    gas_limit: U256,
+    max_fee_per_gas: U256,
+}
+
+impl TxSummary {
+    fn new(tx: &L2Tx) -> Self {
+        Self {
+            contract_address: tx.execute.contract_address,
+            value: tx.execute.value,
+            gas_limit: tx.common_data.fee.gas_limit,
+            max_fee_per_gas: tx.common_data.fee.max_fee_per_gas,
+        }
+    }
+}
+
+/// Formats a short textual summary of a transaction in the same format as used by Geth.
+impl fmt::Display for TxSummary {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{:?}: {} wei + {} gas × {} wei",
+            self.contract_address, self.value, self.gas_limit, self.max_fee_per_gas
+        )
+    }
+}
+
+fn format_summaries(summaries: Vec<(Nonce, TxSummary)>) -> BTreeMap<Nonce, String> {
+    summaries
+        .into_iter()
+        .map(|(nonce, summary)| (nonce, summary.to_string()))
+        .collect()
+}
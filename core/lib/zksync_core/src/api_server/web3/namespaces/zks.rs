@@ -270,6 +270,10 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
             Some(number) => number,
             None => return Ok(None),
         };
+        self.state
+            .start_info
+            .ensure_l1_batch_not_pruned(&mut storage, l1_batch_number, METHOD_NAME)
+            .await?;
         let (first_miniblock_of_l1_batch, _) = storage
             .blocks_web3_dal()
             .get_miniblock_range_of_l1_batch(l1_batch_number)
@@ -466,12 +470,17 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
         const METHOD_NAME: &str = "get_block_details";
 
         let method_latency = API_METRICS.start_call(METHOD_NAME);
-        let block_details = self
+        let mut storage = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
+            .unwrap();
+        self.state
+            .start_info
+            .ensure_miniblock_not_pruned(&mut storage, block_number, METHOD_NAME)
+            .await?;
+        let block_details = storage
             .blocks_web3_dal()
             .get_block_details(
                 block_number,
@@ -492,12 +501,17 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
         const METHOD_NAME: &str = "get_raw_block_transactions";
 
         let method_latency = API_METRICS.start_call(METHOD_NAME);
-        let transactions = self
+        let mut storage = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
+            .unwrap();
+        self.state
+            .start_info
+            .ensure_miniblock_not_pruned(&mut storage, block_number, METHOD_NAME)
+            .await?;
+        let transactions = storage
             .transactions_web3_dal()
             .get_raw_miniblock_transactions(block_number)
             .await
@@ -550,12 +564,17 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
         const METHOD_NAME: &str = "get_l1_batch";
 
         let method_latency = API_METRICS.start_call(METHOD_NAME);
-        let l1_batch = self
+        let mut storage = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
+            .unwrap();
+        self.state
+            .start_info
+            .ensure_l1_batch_not_pruned(&mut storage, batch_number, METHOD_NAME)
+            .await?;
+        let l1_batch = storage
             .blocks_web3_dal()
             .get_l1_batch_details(batch_number)
             .await
@@ -6,18 +6,25 @@ use anyhow::Context as _;
 use jsonrpc_core::error::{Error, ErrorCode};
 use jsonrpc_pubsub::{typed, SubscriptionId};
 use tokio::{
-    sync::{mpsc, watch, RwLock},
+    sync::{broadcast, mpsc, watch, RwLock},
     task::JoinHandle,
-    time::{interval, Duration},
+    time::{interval, Duration, Interval},
 };
 use zksync_dal::ConnectionPool;
-use zksync_types::{MiniblockNumber, H128, H256};
-use zksync_web3_decl::types::{BlockHeader, Log, PubSubFilter, PubSubResult};
+use zksync_system_constants::EMPTY_UNCLES_HASH;
+use zksync_types::{api, L2ChainId, MiniblockNumber, H128, H160, H2048, H256, U256, U64};
+use zksync_web3_decl::types::{
+    BlockHeader, Bytes, Index, Log, PendingTransactionsOptions, PubSubFilter, PubSubResult,
+};
 
 use super::{
     metrics::{SubscriptionType, PUB_SUB_METRICS},
     namespaces::eth::EVENT_TOPIC_NUMBER_LIMIT,
 };
+use crate::{
+    state_keeper::{SealedMiniblock, SealedMiniblocksSender},
+    sync_layer::SyncState,
+};
 
 pub(super) type SubscriptionMap<T> = Arc<RwLock<HashMap<SubscriptionId, T>>>;
 
@@ -28,16 +35,73 @@ pub(super) enum PubSubEvent {
     NotifyIterationFinished(SubscriptionType),
 }
 
+/// Update awaited by a notifier on each iteration.
+#[derive(Debug)]
+enum NotifierUpdate {
+    /// Miniblock pushed by the miniblock sealer.
+    Pushed(Arc<SealedMiniblock>),
+    /// Postgres should be polled for updates.
+    Poll,
+    /// The notifier should stop.
+    Stop,
+}
+
 /// Manager of notifications for a certain type of subscriptions.
 #[derive(Debug)]
 struct PubSubNotifier<V> {
     subscribers: SubscriptionMap<V>,
     connection_pool: ConnectionPool,
     polling_interval: Duration,
+    /// Receiver of miniblocks pushed by the miniblock sealer. If set, Postgres is only polled
+    /// if pushed miniblocks cannot be used directly (e.g., if some miniblocks were skipped), or if no miniblocks
+    /// were pushed during the polling interval (not all miniblocks are pushed; e.g., fictive miniblocks aren't).
+    sealed_miniblocks: Option<broadcast::Receiver<Arc<SealedMiniblock>>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
 impl<V: Clone> PubSubNotifier<V> {
+    async fn next_update(
+        &mut self,
+        timer: &mut Interval,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> NotifierUpdate {
+        if *stop_receiver.borrow() {
+            return NotifierUpdate::Stop;
+        }
+
+        let Some(sealed_miniblocks) = &mut self.sealed_miniblocks else {
+            return tokio::select! {
+                _ = timer.tick() => NotifierUpdate::Poll,
+                _ = stop_receiver.changed() => NotifierUpdate::Stop,
+            };
+        };
+        let miniblock = tokio::select! {
+            miniblock = sealed_miniblocks.recv() => miniblock,
+            _ = timer.tick() => return NotifierUpdate::Poll,
+            _ = stop_receiver.changed() => return NotifierUpdate::Stop,
+        };
+        match miniblock {
+            Ok(miniblock) => {
+                // Postgres doesn't need to be polled while miniblocks are pushed.
+                timer.reset();
+                NotifierUpdate::Pushed(miniblock)
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped_count)) => {
+                tracing::info!(
+                    "Pub-sub notifier lagged behind by {skipped_count} miniblocks; polling Postgres"
+                );
+                NotifierUpdate::Poll
+            }
+            Err(broadcast::error::RecvError::Closed) => {
+                tracing::info!(
+                    "Sealed miniblocks channel is closed; pub-sub notifier switches to polling Postgres"
+                );
+                self.sealed_miniblocks = None;
+                NotifierUpdate::Poll
+            }
+        }
+    }
+
     async fn sealed_miniblock_number(&self) -> anyhow::Result<MiniblockNumber> {
         self.connection_pool
             .access_storage_tagged("api")
@@ -61,19 +125,29 @@ impl<V: Clone> PubSubNotifier<V> {
 }
 
 impl PubSubNotifier<typed::Sink<PubSubResult>> {
-    async fn notify_blocks(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_blocks(
+        mut self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
         let mut last_block_number = self.sealed_miniblock_number().await?;
         let mut timer = interval(self.polling_interval);
         loop {
-            if *stop_receiver.borrow() {
-                tracing::info!("Stop signal received, pubsub_block_notifier is shutting down");
-                break;
-            }
-            timer.tick().await;
-
-            let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Blocks].start();
-            let new_blocks = self.new_blocks(last_block_number).await?;
-            db_latency.observe();
+            let new_blocks = match self.next_update(&mut timer, &mut stop_receiver).await {
+                NotifierUpdate::Stop => {
+                    tracing::info!("Stop signal received, pubsub_block_notifier is shutting down");
+                    break;
+                }
+                NotifierUpdate::Pushed(miniblock) if miniblock.number == last_block_number + 1 => {
+                    vec![sealed_miniblock_header(&miniblock)]
+                }
+                NotifierUpdate::Pushed(_) | NotifierUpdate::Poll => {
+                    let db_latency =
+                        PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Blocks].start();
+                    let new_blocks = self.new_blocks(last_block_number).await?;
+                    db_latency.observe();
+                    new_blocks
+                }
+            };
 
             if let Some(last_block) = new_blocks.last() {
                 last_block_number = MiniblockNumber(last_block.number.unwrap().as_u32());
@@ -112,15 +186,52 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
             .with_context(|| format!("get_block_headers_after({last_block_number})"))
     }
 
-    async fn notify_txs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_syncing(
+        mut self,
+        sync_state: SyncState,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let mut is_syncing = !sync_state.is_synced();
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if let NotifierUpdate::Stop = self.next_update(&mut timer, &mut stop_receiver).await {
+                tracing::info!("Stop signal received, pubsub_syncing_notifier is shutting down");
+                break;
+            }
+
+            let new_is_syncing = !sync_state.is_synced();
+            if new_is_syncing != is_syncing {
+                is_syncing = new_is_syncing;
+                let notify_latency =
+                    PUB_SUB_METRICS.notify_subscribers_latency[&SubscriptionType::Syncing].start();
+                for sink in self.current_subscribers().await {
+                    if sink.notify(Ok(PubSubResult::Syncing(is_syncing))).is_ok() {
+                        PUB_SUB_METRICS.notify[&SubscriptionType::Syncing].inc();
+                    }
+                }
+                notify_latency.observe();
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::Syncing,
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl PubSubNotifier<(typed::Sink<PubSubResult>, PendingTransactionsOptions)> {
+    async fn notify_txs(
+        mut self,
+        l2_chain_id: L2ChainId,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
         let mut last_time = chrono::Utc::now().naive_utc();
         let mut timer = interval(self.polling_interval);
         loop {
-            if *stop_receiver.borrow() {
+            if let NotifierUpdate::Stop = self.next_update(&mut timer, &mut stop_receiver).await {
                 tracing::info!("Stop signal received, pubsub_tx_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Txs].start();
             let (new_txs, new_last_time) = self.new_txs(last_time).await?;
@@ -128,12 +239,27 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
 
             if let Some(new_last_time) = new_last_time {
                 last_time = new_last_time;
+                let subscribers = self.current_subscribers().await;
+                let has_full_tx_subscribers = subscribers
+                    .iter()
+                    .any(|(_, options)| options.full_transactions);
+                let full_txs = if has_full_tx_subscribers {
+                    self.load_full_txs(&new_txs, l2_chain_id).await?
+                } else {
+                    vec![]
+                };
+
                 let notify_latency =
                     PUB_SUB_METRICS.notify_subscribers_latency[&SubscriptionType::Txs].start();
-
-                for sink in self.current_subscribers().await {
-                    for tx_hash in new_txs.iter().cloned() {
-                        if sink.notify(Ok(PubSubResult::TxHash(tx_hash))).is_err() {
+                for (sink, options) in subscribers {
+                    let notifications: Box<dyn Iterator<Item = PubSubResult>> =
+                        if options.full_transactions {
+                            Box::new(full_txs.iter().cloned().map(PubSubResult::FullTx))
+                        } else {
+                            Box::new(new_txs.iter().copied().map(PubSubResult::TxHash))
+                        };
+                    for notification in notifications {
+                        if sink.notify(Ok(notification)).is_err() {
                             // Subscriber disconnected.
                             break;
                         }
@@ -160,25 +286,75 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
             .await
             .context("get_pending_txs_hashes_after()")
     }
+
+    /// Loads full transactions with the specified hashes preserving their order. Transactions
+    /// that are no longer present in the storage (e.g., replaced ones) are skipped.
+    async fn load_full_txs(
+        &self,
+        hashes: &[H256],
+        l2_chain_id: L2ChainId,
+    ) -> anyhow::Result<Vec<api::Transaction>> {
+        let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Txs].start();
+        let transactions = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?
+            .transactions_web3_dal()
+            .get_transactions_by_hashes(hashes, l2_chain_id)
+            .await
+            .context("get_transactions_by_hashes()")?;
+        db_latency.observe();
+
+        let mut transactions: HashMap<_, _> =
+            transactions.into_iter().map(|tx| (tx.hash, tx)).collect();
+        Ok(hashes
+            .iter()
+            .filter_map(|hash| transactions.remove(hash))
+            .collect())
+    }
 }
 
 impl PubSubNotifier<(typed::Sink<PubSubResult>, PubSubFilter)> {
-    async fn notify_logs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_logs(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut last_block_number = self.sealed_miniblock_number().await?;
         let mut timer = interval(self.polling_interval);
         loop {
-            if *stop_receiver.borrow() {
-                tracing::info!("Stop signal received, pubsub_logs_notifier is shutting down");
-                break;
-            }
-            timer.tick().await;
-
-            let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
-            let new_logs = self.new_logs(last_block_number).await?;
-            db_latency.observe();
+            let new_logs = match self.next_update(&mut timer, &mut stop_receiver).await {
+                NotifierUpdate::Stop => {
+                    tracing::info!("Stop signal received, pubsub_logs_notifier is shutting down");
+                    break;
+                }
+                NotifierUpdate::Pushed(miniblock) if miniblock.number == last_block_number + 1 => {
+                    last_block_number = miniblock.number;
+                    sealed_miniblock_logs(&miniblock)
+                }
+                NotifierUpdate::Pushed(_) | NotifierUpdate::Poll
+                    if self.sealed_miniblocks.is_some() =>
+                {
+                    // If miniblocks are pushed, we need to track the last processed miniblock regardless
+                    // of whether it contains logs, so that the following pushed miniblocks can be used directly.
+                    let db_latency =
+                        PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
+                    let sealed_miniblock_number = self.sealed_miniblock_number().await?;
+                    let new_logs = self.new_logs(last_block_number).await?;
+                    db_latency.observe();
+                    last_block_number = last_block_number.max(sealed_miniblock_number);
+                    new_logs
+                }
+                NotifierUpdate::Pushed(_) | NotifierUpdate::Poll => {
+                    let db_latency =
+                        PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
+                    let new_logs = self.new_logs(last_block_number).await?;
+                    db_latency.observe();
+                    new_logs
+                }
+            };
 
             if let Some(last_log) = new_logs.last() {
-                last_block_number = MiniblockNumber(last_log.block_number.unwrap().as_u32());
+                let last_log_block_number =
+                    MiniblockNumber(last_log.block_number.unwrap().as_u32());
+                last_block_number = last_block_number.max(last_log_block_number);
                 let notify_latency =
                     PUB_SUB_METRICS.notify_subscribers_latency[&SubscriptionType::Logs].start();
 
@@ -212,14 +388,70 @@ impl PubSubNotifier<(typed::Sink<PubSubResult>, PubSubFilter)> {
     }
 }
 
+/// Converts a pushed miniblock to a header in the same way as [`BlocksWeb3Dal::get_block_headers_after()`] does.
+///
+/// [`BlocksWeb3Dal::get_block_headers_after()`]: zksync_dal::blocks_web3_dal::BlocksWeb3Dal::get_block_headers_after()
+fn sealed_miniblock_header(miniblock: &SealedMiniblock) -> BlockHeader {
+    BlockHeader {
+        hash: Some(miniblock.hash),
+        parent_hash: H256::zero(),
+        uncles_hash: EMPTY_UNCLES_HASH,
+        author: H160::zero(),
+        state_root: H256::zero(),
+        transactions_root: H256::zero(),
+        receipts_root: H256::zero(),
+        number: Some(U64::from(miniblock.number.0)),
+        gas_used: U256::zero(),
+        gas_limit: U256::zero(),
+        base_fee_per_gas: None,
+        extra_data: Bytes::default(),
+        logs_bloom: H2048::default(),
+        timestamp: U256::from(miniblock.timestamp),
+        difficulty: U256::zero(),
+        mix_hash: None,
+        nonce: None,
+    }
+}
+
+/// Converts events in a pushed miniblock to logs in the same way as [`EventsWeb3Dal::get_all_logs()`] does.
+/// The L1 batch number is not set since the batch containing the miniblock is not sealed yet.
+///
+/// [`EventsWeb3Dal::get_all_logs()`]: zksync_dal::events_web3_dal::EventsWeb3Dal::get_all_logs()
+fn sealed_miniblock_logs(miniblock: &SealedMiniblock) -> Vec<Log> {
+    let events = miniblock
+        .events
+        .iter()
+        .flat_map(|(location, events)| events.iter().map(move |event| (location, event)));
+    let logs = events
+        .enumerate()
+        .map(|(event_index_in_block, (location, event))| Log {
+            address: event.address,
+            topics: event.indexed_topics.clone(),
+            data: Bytes(event.value.clone()),
+            block_hash: Some(miniblock.hash),
+            block_number: Some(U64::from(miniblock.number.0)),
+            l1_batch_number: None,
+            transaction_hash: Some(location.tx_hash),
+            transaction_index: Some(Index::from(location.tx_index_in_miniblock)),
+            log_index: Some(U256::from(event_index_in_block)),
+            transaction_log_index: Some(U256::from(event_index_in_block)),
+            log_type: None,
+            removed: Some(false),
+        });
+    logs.collect()
+}
+
 /// Subscription support for Web3 APIs.
 #[derive(Debug, Clone)]
 pub(super) struct EthSubscribe {
     // `jsonrpc` backend executes task subscription on a separate thread that has no tokio context.
     pub runtime_handle: tokio::runtime::Handle,
     active_block_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
-    active_tx_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
+    active_tx_subs: SubscriptionMap<(typed::Sink<PubSubResult>, PendingTransactionsOptions)>,
     active_log_subs: SubscriptionMap<(typed::Sink<PubSubResult>, PubSubFilter)>,
+    active_sync_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
+    sync_state: Option<SyncState>,
+    sealed_miniblocks_sender: Option<SealedMiniblocksSender>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -230,6 +462,9 @@ impl EthSubscribe {
             active_block_subs: SubscriptionMap::default(),
             active_tx_subs: SubscriptionMap::default(),
             active_log_subs: SubscriptionMap::default(),
+            active_sync_subs: SubscriptionMap::default(),
+            sync_state: None,
+            sealed_miniblocks_sender: None,
             events_sender: None,
         }
     }
@@ -238,6 +473,24 @@ impl EthSubscribe {
         self.events_sender = Some(sender);
     }
 
+    /// Sets the sync state used by the `syncing` subscription. If not set, the node is considered
+    /// to be always synced, and subscribers are notified only once after subscribing.
+    pub fn set_sync_state(&mut self, sync_state: SyncState) {
+        self.sync_state = Some(sync_state);
+    }
+
+    /// Sets the sender of miniblocks pushed by the miniblock sealer. If set, block and log notifiers
+    /// use pushed miniblocks instead of polling Postgres.
+    pub fn set_sealed_miniblocks_sender(&mut self, sender: SealedMiniblocksSender) {
+        self.sealed_miniblocks_sender = Some(sender);
+    }
+
+    fn is_syncing(&self) -> bool {
+        self.sync_state
+            .as_ref()
+            .map_or(false, |sync_state| !sync_state.is_synced())
+    }
+
     /// Assigns ID for the subscriber if the connection is open, returns error otherwise.
     fn assign_id(
         subscriber: typed::Subscriber<PubSubResult>,
@@ -275,12 +528,25 @@ impl EthSubscribe {
                 Some(SubscriptionType::Blocks)
             }
             "newPendingTransactions" => {
-                let mut tx_subs = self.active_tx_subs.write().await;
-                let Ok((sink, id)) = Self::assign_id(subscriber) else {
-                    return;
+                let options = match params {
+                    // Geth-compatible way to specify `fullTransactions`.
+                    Some(serde_json::Value::Bool(full_transactions)) => {
+                        Ok(PendingTransactionsOptions { full_transactions })
+                    }
+                    Some(params) => serde_json::from_value(params),
+                    None => Ok(PendingTransactionsOptions::default()),
                 };
-                tx_subs.insert(id, sink);
-                Some(SubscriptionType::Txs)
+                if let Ok(options) = options {
+                    let mut tx_subs = self.active_tx_subs.write().await;
+                    let Ok((sink, id)) = Self::assign_id(subscriber) else {
+                        return;
+                    };
+                    tx_subs.insert(id, (sink, options));
+                    Some(SubscriptionType::Txs)
+                } else {
+                    Self::reject(subscriber);
+                    None
+                }
             }
             "logs" => {
                 let filter = params.map(serde_json::from_value).transpose();
@@ -307,11 +573,15 @@ impl EthSubscribe {
                 }
             }
             "syncing" => {
-                let Ok((sink, _id)) = Self::assign_id(subscriber) else {
+                let mut sync_subs = self.active_sync_subs.write().await;
+                let Ok((sink, id)) = Self::assign_id(subscriber) else {
                     return;
                 };
-                let _ = sink.notify(Ok(PubSubResult::Syncing(false)));
-                None
+                // Notify the subscriber about the current status; further notifications are sent
+                // by the notifier once the status changes.
+                let _ = sink.notify(Ok(PubSubResult::Syncing(self.is_syncing())));
+                sync_subs.insert(id, sink);
+                Some(SubscriptionType::Syncing)
             }
             _ => {
                 Self::reject(subscriber);
@@ -335,6 +605,8 @@ impl EthSubscribe {
             Some(SubscriptionType::Txs)
         } else if self.active_log_subs.write().await.remove(&id).is_some() {
             Some(SubscriptionType::Logs)
+        } else if self.active_sync_subs.write().await.remove(&id).is_some() {
+            Some(SubscriptionType::Syncing)
         } else {
             None
         };
@@ -355,13 +627,15 @@ impl EthSubscribe {
         &self,
         connection_pool: ConnectionPool,
         polling_interval: Duration,
+        l2_chain_id: L2ChainId,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(4);
         let notifier = PubSubNotifier {
             subscribers: self.active_block_subs.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            sealed_miniblocks: self.subscribe_to_sealed_miniblocks(),
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_blocks(stop_receiver.clone()));
@@ -371,15 +645,31 @@ impl EthSubscribe {
             subscribers: self.active_tx_subs.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            // Pending transactions are unrelated to sealed miniblocks, so they are always polled.
+            sealed_miniblocks: None,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_txs(stop_receiver.clone()));
+        let notifier_task = tokio::spawn(notifier.notify_txs(l2_chain_id, stop_receiver.clone()));
         notifier_tasks.push(notifier_task);
 
+        if let Some(sync_state) = self.sync_state.clone() {
+            let notifier = PubSubNotifier {
+                subscribers: self.active_sync_subs.clone(),
+                connection_pool: connection_pool.clone(),
+                polling_interval,
+                sealed_miniblocks: None,
+                events_sender: self.events_sender.clone(),
+            };
+            let notifier_task =
+                tokio::spawn(notifier.notify_syncing(sync_state, stop_receiver.clone()));
+            notifier_tasks.push(notifier_task);
+        }
+
         let notifier = PubSubNotifier {
             subscribers: self.active_log_subs.clone(),
             connection_pool,
             polling_interval,
+            sealed_miniblocks: self.subscribe_to_sealed_miniblocks(),
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
@@ -387,4 +677,10 @@ impl EthSubscribe {
         notifier_tasks.push(notifier_task);
         notifier_tasks
     }
+
+    fn subscribe_to_sealed_miniblocks(&self) -> Option<broadcast::Receiver<Arc<SealedMiniblock>>> {
+        self.sealed_miniblocks_sender
+            .as_ref()
+            .map(SealedMiniblocksSender::subscribe)
+    }
 }
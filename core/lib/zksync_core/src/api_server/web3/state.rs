@@ -4,7 +4,7 @@ use std::{
     future::Future,
     sync::{
         atomic::{AtomicU32, Ordering},
-        Arc,
+        Arc, RwLock,
     },
     time::{Duration, Instant},
 };
@@ -12,7 +12,7 @@ use std::{
 use tokio::sync::Mutex;
 use vise::GaugeGuard;
 use zksync_config::configs::{api::Web3JsonRpcConfig, chain::NetworkConfig, ContractsConfig};
-use zksync_dal::ConnectionPool;
+use zksync_dal::{pruning_dal::PruningInfo, ConnectionPool, StorageProcessor};
 use zksync_types::{
     api::{self, BlockId, BlockNumber, GetLogsFilter},
     block::unpack_block_upgrade_info,
@@ -38,6 +38,7 @@ use crate::{
             resolve_block, TypedFilter,
         },
     },
+    state_keeper::MempoolGuard,
     sync_layer::SyncState,
 };
 
@@ -163,6 +164,69 @@ impl SealedMiniblockNumber {
     }
 }
 
+/// Information about the first block and L1 batch available in the storage, which may be greater than 0
+/// if the node prunes historical data.
+///
+/// The information is cached for [`Self::CACHE_TTL`]; the pruner must wait longer than that
+/// between marking data as pruned and removing it from the storage.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BlockStartInfo {
+    cached_pruning_info: Arc<RwLock<Option<(Instant, PruningInfo)>>>,
+}
+
+impl BlockStartInfo {
+    pub(crate) const CACHE_TTL: Duration = Duration::from_secs(10);
+
+    async fn pruning_info(&self, storage: &mut StorageProcessor<'_>) -> sqlx::Result<PruningInfo> {
+        let cached_info = *self.cached_pruning_info.read().unwrap();
+        if let Some((updated_at, pruning_info)) = cached_info {
+            if updated_at.elapsed() < Self::CACHE_TTL {
+                return Ok(pruning_info);
+            }
+        }
+
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        *self.cached_pruning_info.write().unwrap() = Some((Instant::now(), pruning_info));
+        Ok(pruning_info)
+    }
+
+    /// Returns an error if data for the specified miniblock is pruned.
+    pub async fn ensure_miniblock_not_pruned(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        number: MiniblockNumber,
+        method_name: &'static str,
+    ) -> Result<(), Web3Error> {
+        let first_miniblock = self
+            .pruning_info(storage)
+            .await
+            .map_err(|err| internal_error(method_name, err))?
+            .first_available_miniblock();
+        if number < first_miniblock {
+            return Err(Web3Error::PrunedBlock(number, first_miniblock));
+        }
+        Ok(())
+    }
+
+    /// Returns an error if data for the specified L1 batch is pruned.
+    pub async fn ensure_l1_batch_not_pruned(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        number: L1BatchNumber,
+        method_name: &'static str,
+    ) -> Result<(), Web3Error> {
+        let first_l1_batch = self
+            .pruning_info(storage)
+            .await
+            .map_err(|err| internal_error(method_name, err))?
+            .first_available_l1_batch();
+        if number < first_l1_batch {
+            return Err(Web3Error::PrunedL1Batch(number, first_l1_batch));
+        }
+        Ok(())
+    }
+}
+
 /// Holder for the data required for the API to be functional.
 #[derive(Debug)]
 pub struct RpcState<E> {
@@ -171,8 +235,11 @@ pub struct RpcState<E> {
     pub tree_api: Option<TreeApiHttpClient>,
     pub tx_sender: TxSender<E>,
     pub sync_state: Option<SyncState>,
+    /// Mempool of the state keeper running in the same process, if any.
+    pub mempool: Option<MempoolGuard>,
     pub(super) api_config: InternalApiConfig,
     pub(super) last_sealed_miniblock: SealedMiniblockNumber,
+    pub(crate) start_info: BlockStartInfo,
     // The flag that enables redirect of eth get logs implementation to
     // implementation with virtual block translation to miniblocks
     pub logs_translator_enabled: bool,
@@ -189,8 +256,10 @@ impl<E> Clone for RpcState<E> {
             tx_sender: self.tx_sender.clone(),
             tree_api: self.tree_api.clone(),
             sync_state: self.sync_state.clone(),
+            mempool: self.mempool.clone(),
             api_config: self.api_config.clone(),
             last_sealed_miniblock: self.last_sealed_miniblock.clone(),
+            start_info: self.start_info.clone(),
             logs_translator_enabled: self.logs_translator_enabled,
         }
     }
@@ -207,6 +276,43 @@ impl<E> RpcState<E> {
         ))
     }
 
+    /// Resolves the specified block ID to a miniblock number, returning an error if the block is not found
+    /// or its data is pruned.
+    pub(crate) async fn resolve_block(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block: BlockId,
+        method_name: &'static str,
+    ) -> Result<MiniblockNumber, Web3Error> {
+        let block_number = resolve_block(connection, block, method_name).await?;
+        self.start_info
+            .ensure_miniblock_not_pruned(connection, block_number, method_name)
+            .await?;
+        Ok(block_number)
+    }
+
+    /// Resolves [`BlockArgs`] for the specified block ID, returning an error if the block is not found
+    /// or its data is pruned.
+    pub(crate) async fn resolve_block_args(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block: BlockId,
+        method_name: &'static str,
+    ) -> Result<BlockArgs, Web3Error> {
+        let block_args = BlockArgs::new(connection, block)
+            .await
+            .map_err(|err| internal_error(method_name, err))?
+            .ok_or(Web3Error::NoBlock)?;
+        self.start_info
+            .ensure_miniblock_not_pruned(
+                connection,
+                block_args.resolved_block_number(),
+                method_name,
+            )
+            .await?;
+        Ok(block_args)
+    }
+
     pub fn u64_to_block_number(n: U64) -> MiniblockNumber {
         if n.as_u64() > u32::MAX as u64 {
             MiniblockNumber(u32::MAX)
@@ -248,6 +354,14 @@ impl<E> RpcState<E> {
     ) -> Result<(MiniblockNumber, MiniblockNumber), Web3Error> {
         let from_block = self.resolve_filter_block_number(filter.from_block).await?;
         let to_block = self.resolve_filter_block_number(filter.to_block).await?;
+        let mut conn = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        self.start_info
+            .ensure_miniblock_not_pruned(&mut conn, from_block, "resolve_filter_block_range")
+            .await?;
         Ok((from_block, to_block))
     }
 
@@ -317,7 +431,9 @@ impl<E> RpcState<E> {
                 .access_storage_tagged("api")
                 .await
                 .unwrap();
-            let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
+            let block_number = self
+                .resolve_block(&mut connection, block_id, METHOD_NAME)
+                .await?;
             let address_historical_nonce = connection
                 .storage_web3_dal()
                 .get_address_historical_nonce(from, block_number)
@@ -413,6 +529,13 @@ impl<E> RpcState<E> {
         if to_miniblock_number < from_miniblock_number {
             return Ok(vec![]);
         }
+        self.start_info
+            .ensure_miniblock_not_pruned(
+                &mut conn,
+                MiniblockNumber(from_miniblock_number),
+                METHOD_NAME,
+            )
+            .await?;
 
         let block_filter = Filter {
             from_block: Some(from_miniblock_number.into()),
@@ -1,15 +1,20 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::NonZeroU32,
+    sync::Arc,
+    time::Instant,
+};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_config::configs::{
     api::Web3JsonRpcConfig,
-    chain::{NetworkConfig, StateKeeperConfig},
+    chain::{MempoolConfig, NetworkConfig, StateKeeperConfig},
     ContractsConfig,
 };
 use zksync_contracts::BaseSystemContractsHashes;
-use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool};
+use zksync_dal::{pruning_dal::PruneType, transactions_dal::L2TxSubmissionResult, ConnectionPool};
 use zksync_health_check::CheckHealth;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
@@ -18,7 +23,7 @@ use zksync_types::{
 };
 use zksync_web3_decl::{
     jsonrpsee::{core::Error as RpcError, http_client::HttpClient, types::error::ErrorCode},
-    namespaces::{EthNamespaceClient, ZksNamespaceClient},
+    namespaces::{EthNamespaceClient, TxPoolNamespaceClient, ZksNamespaceClient},
     types::FilterChanges,
 };
 
@@ -81,31 +86,54 @@ pub(crate) async fn spawn_http_server(
     pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
-    spawn_server(ApiTransportLabel::Http, network_config, pool, stop_receiver)
-        .await
-        .0
+    spawn_server(
+        ApiTransportLabel::Http,
+        network_config,
+        pool,
+        None,
+        None,
+        stop_receiver,
+    )
+    .await
+    .0
 }
 
 async fn spawn_ws_server(
     network_config: &NetworkConfig,
     pool: ConnectionPool,
+    sealed_miniblocks_sender: Option<SealedMiniblocksSender>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
-    spawn_server(ApiTransportLabel::Ws, network_config, pool, stop_receiver).await
+    spawn_server(
+        ApiTransportLabel::Ws,
+        network_config,
+        pool,
+        sealed_miniblocks_sender,
+        None,
+        stop_receiver,
+    )
+    .await
 }
 
 async fn spawn_server(
     transport: ApiTransportLabel,
     network_config: &NetworkConfig,
     pool: ConnectionPool,
+    sealed_miniblocks_sender: Option<SealedMiniblocksSender>,
+    client_limits: Option<ClientLimits>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     let contracts_config = ContractsConfig::for_tests();
     let web3_config = Web3JsonRpcConfig::for_tests();
     let state_keeper_config = StateKeeperConfig::for_tests();
     let api_config = InternalApiConfig::new(network_config, &web3_config, &contracts_config);
-    let tx_sender_config =
-        TxSenderConfig::new(&state_keeper_config, &web3_config, api_config.l2_chain_id);
+    let mempool_config = MempoolConfig::for_tests();
+    let tx_sender_config = TxSenderConfig::new(
+        &state_keeper_config,
+        &web3_config,
+        &mempool_config,
+        api_config.l2_chain_id,
+    );
 
     let storage_caches = PostgresStorageCaches::new(1, 1);
     let gas_adjuster = Arc::new(MockL1GasPriceProvider(1));
@@ -121,18 +149,30 @@ async fn spawn_server(
     .await;
     let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
-    let server_builder = match transport {
+    let mut server_builder = match transport {
         ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
         ApiTransportLabel::Ws => ApiBuilder::jsonrpc_backend(api_config, pool)
             .ws(0)
             .with_polling_interval(POLL_INTERVAL)
             .with_subscriptions_limit(100),
     };
+    if let Some(sender) = sealed_miniblocks_sender {
+        server_builder = server_builder.with_sealed_miniblocks(sender);
+    }
+    if let Some(client_limits) = client_limits {
+        server_builder = server_builder.with_client_limits(client_limits);
+    }
     let server_handles = server_builder
         .with_threads(1)
         .with_tx_sender(tx_sender, vm_barrier)
         .with_pub_sub_events(pub_sub_events_sender)
-        .enable_api_namespaces(Namespace::DEFAULT.to_vec())
+        .enable_api_namespaces(
+            Namespace::DEFAULT
+                .iter()
+                .cloned()
+                .chain([Namespace::TxPool])
+                .collect(),
+        )
         .build(stop_receiver)
         .await
         .expect("Failed spawning JSON-RPC server");
@@ -494,3 +534,134 @@ impl HttpTest for LogFilterChangesWithBlockBoundaries {
 async fn log_filter_changes_with_block_boundaries() {
     test_http_server(LogFilterChangesWithBlockBoundaries).await;
 }
+
+#[test]
+fn parsing_namespaces() {
+    assert_eq!("eth".parse::<Namespace>().unwrap(), Namespace::Eth);
+    assert_eq!("txpool".parse::<Namespace>().unwrap(), Namespace::TxPool);
+    "unknown".parse::<Namespace>().unwrap_err();
+}
+
+#[derive(Debug)]
+struct TxPoolWithoutMempool;
+
+#[async_trait]
+impl HttpTest for TxPoolWithoutMempool {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let err = client.status().await.unwrap_err();
+        assert_matches!(err, RpcError::Call(err) if err.code() == 6);
+        let err = client.content_from(Address::zero()).await.unwrap_err();
+        assert_matches!(err, RpcError::Call(err) if err.code() == 6);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn txpool_without_mempool() {
+    test_http_server(TxPoolWithoutMempool).await;
+}
+
+#[derive(Debug)]
+struct PrunedBlocks;
+
+#[async_trait]
+impl HttpTest for PrunedBlocks {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        store_block(pool).await?;
+        let mut storage = pool.access_storage().await?;
+        storage
+            .pruning_dal()
+            .insert_pruning_log(L1BatchNumber(0), MiniblockNumber(0), PruneType::Soft)
+            .await?;
+        drop(storage);
+
+        let err = client
+            .get_block_by_number(api::BlockNumber::Number(0.into()), false)
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+                && err.message().contains("pruned")
+        );
+        let err = client
+            .get_balance(
+                Address::zero(),
+                Some(api::BlockIdVariant::BlockNumber(api::BlockNumber::Number(
+                    0.into(),
+                ))),
+            )
+            .await
+            .unwrap_err();
+        assert_matches!(err, RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code());
+        let err = client
+            .get_l1_batch_details(L1BatchNumber(0))
+            .await
+            .unwrap_err();
+        assert_matches!(err, RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code());
+
+        let block = client
+            .get_block_by_number(api::BlockNumber::Number(1.into()), false)
+            .await?
+            .context("no miniblock #1")?;
+        assert_eq!(block.number, U64::from(1));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn pruned_blocks() {
+    test_http_server(PrunedBlocks).await;
+}
+
+/// Sends an `eth_chainId` request to the server from the specified local IP address.
+async fn send_chain_id_request(local_ip: IpAddr, server_addr: SocketAddr) -> serde_json::Value {
+    let mut connector = hyper::client::HttpConnector::new();
+    connector.set_local_address(Some(local_ip));
+    let client = hyper::Client::builder().build::<_, hyper::Body>(connector);
+    let request = hyper::Request::post(format!("http://{server_addr}/"))
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(r#"{"jsonrpc":"2.0","id":1,"method":"eth_chainId"}"#.into())
+        .unwrap();
+    let response = client.request(request).await.unwrap();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn client_limits_are_applied_per_peer_address() {
+    let pool = ConnectionPool::test_pool().await;
+    let network_config = NetworkConfig::for_tests();
+    let client_limits = ClientLimits {
+        requests_per_minute: NonZeroU32::new(1),
+        ..ClientLimits::default()
+    };
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let (server_handles, _) = spawn_server(
+        ApiTransportLabel::Http,
+        &network_config,
+        pool,
+        None,
+        Some(client_limits),
+        stop_receiver,
+    )
+    .await;
+    server_handles.wait_until_ready().await;
+    let server_addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), server_handles.local_addr.port());
+
+    let first_ip = IpAddr::from([127, 0, 0, 1]);
+    let response = send_chain_id_request(first_ip, server_addr).await;
+    assert!(response["result"].is_string(), "{response}");
+    let response = send_chain_id_request(first_ip, server_addr).await;
+    assert_eq!(response["error"]["code"], 429, "{response}");
+
+    // A client with another peer address has a separate budget.
+    let second_ip = IpAddr::from([127, 0, 0, 2]);
+    let response = send_chain_id_request(second_ip, server_addr).await;
+    assert!(response["result"].is_string(), "{response}");
+    let response = send_chain_id_request(second_ip, server_addr).await;
+    assert_eq!(response["error"]["code"], 429, "{response}");
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+}
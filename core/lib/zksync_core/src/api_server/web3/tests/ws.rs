@@ -1,10 +1,12 @@
 //! WS-related tests.
 
 use async_trait::async_trait;
-use tokio::sync::watch;
+use tokio::sync::{broadcast, watch};
 use zksync_config::configs::chain::NetworkConfig;
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, L1BatchNumber, H256, U64};
+use zksync_types::{
+    api, tx::IncludedTxLocation, Address, L1BatchNumber, MiniblockNumber, VmEvent, H256, U64,
+};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::client::{Subscription, SubscriptionClientT},
@@ -12,11 +14,11 @@ use zksync_web3_decl::{
         ws_client::{WsClient, WsClientBuilder},
     },
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
-    types::{BlockHeader, PubSubFilter},
+    types::{BlockHeader, PendingTransactionsOptions, PubSubFilter},
 };
 
 use super::*;
-use crate::api_server::web3::metrics::SubscriptionType;
+use crate::{api_server::web3::metrics::SubscriptionType, state_keeper::SealedMiniblock};
 
 #[allow(clippy::needless_pass_by_ref_mut)] // false positive
 async fn wait_for_subscription(
@@ -64,6 +66,11 @@ async fn wait_for_notifier(
 
 #[async_trait]
 trait WsTest {
+    /// Sender of miniblocks pushed to the server. If not specified, the server polls Postgres.
+    fn sealed_miniblocks_sender(&self) -> Option<SealedMiniblocksSender> {
+        None
+    }
+
     async fn test(
         &self,
         client: &WsClient,
@@ -88,8 +95,13 @@ async fn test_ws_server(test: impl WsTest) {
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
-    let (server_handles, pub_sub_events) =
-        spawn_ws_server(&network_config, pool.clone(), stop_receiver).await;
+    let (server_handles, pub_sub_events) = spawn_ws_server(
+        &network_config,
+        pool.clone(),
+        test.sealed_miniblocks_sender(),
+        stop_receiver,
+    )
+    .await;
     server_handles.wait_until_ready().await;
 
     let client = WsClientBuilder::default()
@@ -185,6 +197,226 @@ async fn basic_subscriptions() {
     test_ws_server(BasicSubscriptions).await;
 }
 
+#[derive(Debug)]
+struct FullPendingTransactionsSubscriptions;
+
+#[async_trait]
+impl WsTest for FullPendingTransactionsSubscriptions {
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifier(&mut pub_sub_events, SubscriptionType::Txs).await;
+
+        let options = PendingTransactionsOptions {
+            full_transactions: true,
+        };
+        let params = rpc_params!["newPendingTransactions", options];
+        let mut txs_subscription = client
+            .subscribe::<api::Transaction, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Txs).await;
+        // Geth-compatible way to request full transactions.
+        let params = rpc_params!["newPendingTransactions", true];
+        let mut geth_txs_subscription = client
+            .subscribe::<api::Transaction, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Txs).await;
+
+        let (_, new_tx_hash) = store_block(pool).await?;
+
+        for subscription in [&mut txs_subscription, &mut geth_txs_subscription] {
+            let received_tx = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+                .await
+                .context("Timed out waiting for new tx")?
+                .context("Pending txs subscription terminated")??;
+            assert_eq!(received_tx.hash, new_tx_hash);
+            assert_eq!(received_tx.block_number, None);
+        }
+        txs_subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn full_pending_transactions_subscriptions() {
+    test_ws_server(FullPendingTransactionsSubscriptions).await;
+}
+
+#[derive(Debug)]
+struct SyncingSubscription;
+
+#[async_trait]
+impl WsTest for SyncingSubscription {
+    async fn test(
+        &self,
+        client: &WsClient,
+        _pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let params = rpc_params!["syncing"];
+        let mut syncing_subscription = client
+            .subscribe::<bool, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Syncing).await;
+
+        // The server has no sync state, so it's always considered synced.
+        let is_syncing = tokio::time::timeout(TEST_TIMEOUT, syncing_subscription.next())
+            .await
+            .context("Timed out waiting for syncing status")?
+            .context("Syncing subscription terminated")??;
+        assert!(!is_syncing);
+        syncing_subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn syncing_subscription() {
+    test_ws_server(SyncingSubscription).await;
+}
+
+#[derive(Debug)]
+struct PushedMiniblockSubscriptions(SealedMiniblocksSender);
+
+#[async_trait]
+impl WsTest for PushedMiniblockSubscriptions {
+    fn sealed_miniblocks_sender(&self) -> Option<SealedMiniblocksSender> {
+        Some(self.0.clone())
+    }
+
+    async fn test(
+        &self,
+        client: &WsClient,
+        _pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let params = rpc_params!["newHeads"];
+        let mut blocks_subscription = client
+            .subscribe::<BlockHeader, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Blocks).await;
+        let params = rpc_params!["logs"];
+        let mut logs_subscription = client
+            .subscribe::<api::Log, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Logs).await;
+
+        // The miniblock is not persisted, so subscribers can only learn about it from the pushed data.
+        let tx_location = IncludedTxLocation {
+            tx_hash: H256::repeat_byte(1),
+            tx_index_in_miniblock: 0,
+            tx_initiator_address: Address::repeat_byte(2),
+        };
+        let events: Vec<_> = (0..2_u32)
+            .map(|i| VmEvent {
+                location: (L1BatchNumber(1), 0),
+                address: Address::repeat_byte(23),
+                indexed_topics: vec![H256::repeat_byte(42)],
+                value: i.to_le_bytes().to_vec(),
+            })
+            .collect();
+        let miniblock = SealedMiniblock {
+            number: MiniblockNumber(1),
+            hash: H256::repeat_byte(0x11),
+            timestamp: 1,
+            events: vec![(tx_location, events.clone())],
+        };
+        self.0.send(Arc::new(miniblock)).ok();
+
+        let received_block_header = tokio::time::timeout(TEST_TIMEOUT, blocks_subscription.next())
+            .await
+            .context("Timed out waiting for new block header")?
+            .context("New blocks subscription terminated")??;
+        assert_eq!(received_block_header.number, Some(1.into()));
+        assert_eq!(received_block_header.hash, Some(H256::repeat_byte(0x11)));
+        assert_eq!(received_block_header.timestamp, 1.into());
+
+        let logs = collect_logs(&mut logs_subscription, 2).await?;
+        for (i, log) in logs.iter().enumerate() {
+            assert_eq!(log.log_index, Some(i.into()));
+            assert_eq!(log.transaction_hash, Some(tx_location.tx_hash));
+            assert_eq!(log.block_number, Some(1.into()));
+            assert_eq!(log.block_hash, Some(H256::repeat_byte(0x11)));
+        }
+        let events: Vec<_> = events.iter().collect();
+        assert_logs_match(&logs, &events);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn pushed_miniblock_subscriptions() {
+    let (sealed_miniblocks_sender, _) = broadcast::channel(16);
+    test_ws_server(PushedMiniblockSubscriptions(sealed_miniblocks_sender)).await;
+}
+
+#[derive(Debug)]
+struct NotPushedMiniblockSubscriptions(SealedMiniblocksSender);
+
+#[async_trait]
+impl WsTest for NotPushedMiniblockSubscriptions {
+    fn sealed_miniblocks_sender(&self) -> Option<SealedMiniblocksSender> {
+        Some(self.0.clone())
+    }
+
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let params = rpc_params!["newHeads"];
+        let mut blocks_subscription = client
+            .subscribe::<BlockHeader, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Blocks).await;
+        let params = rpc_params!["logs"];
+        let mut logs_subscription = client
+            .subscribe::<api::Log, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Logs).await;
+
+        // Emulate a fictive miniblock, which is persisted, but is not pushed by the miniblock sealer.
+        let mut storage = pool.access_storage().await?;
+        let (_, events) = store_events(&mut storage, 1, 0).await?;
+        drop(storage);
+
+        let received_block_header = tokio::time::timeout(TEST_TIMEOUT, blocks_subscription.next())
+            .await
+            .context("Timed out waiting for new block header")?
+            .context("New blocks subscription terminated")??;
+        assert_eq!(received_block_header.number, Some(1.into()));
+        let logs = collect_logs(&mut logs_subscription, events.len()).await?;
+        let events: Vec<_> = events.iter().collect();
+        assert_logs_match(&logs, &events);
+
+        // Subsequent pushed miniblocks are still used.
+        let miniblock = SealedMiniblock {
+            number: MiniblockNumber(2),
+            hash: H256::repeat_byte(0x22),
+            timestamp: 2,
+            events: vec![],
+        };
+        self.0.send(Arc::new(miniblock)).ok();
+        let received_block_header = tokio::time::timeout(TEST_TIMEOUT, blocks_subscription.next())
+            .await
+            .context("Timed out waiting for new block header")?
+            .context("New blocks subscription terminated")??;
+        assert_eq!(received_block_header.number, Some(2.into()));
+        assert_eq!(received_block_header.hash, Some(H256::repeat_byte(0x22)));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn not_pushed_miniblock_subscriptions() {
+    let (sealed_miniblocks_sender, _) = broadcast::channel(16);
+    test_ws_server(NotPushedMiniblockSubscriptions(sealed_miniblocks_sender)).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptions;
 
@@ -3,12 +3,16 @@ use std::time::Duration;
 use zksync_contracts::PRE_BOOJUM_COMMIT_FUNCTION;
 use zksync_dal::ConnectionPool;
 use zksync_types::{
+    commitment::L1BatchWithMetadata,
     web3::{error, ethabi, transports::Http, types::TransactionId, Web3},
     L1BatchNumber,
 };
 
 use crate::metrics::{CheckerComponent, EN_METRICS};
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug)]
 pub struct ConsistencyChecker {
     // ABI of the zkSync contract
@@ -17,6 +21,8 @@ pub struct ConsistencyChecker {
     max_batches_to_recheck: u32,
     web3: Web3<Http>,
     db: ConnectionPool,
+    // Whether L1 batch pubdata is published to a data availability layer instead of L1 calldata
+    validium_mode: bool,
 }
 
 const SLEEP_DELAY: Duration = Duration::from_secs(5);
@@ -30,9 +36,17 @@ impl ConsistencyChecker {
             contract,
             max_batches_to_recheck,
             db,
+            validium_mode: false,
         }
     }
 
+    /// Configures the checker for the validium mode, in which commit transactions carry data availability
+    /// inclusion data instead of L1 batch pubdata.
+    pub fn with_validium_mode(mut self, validium_mode: bool) -> Self {
+        self.validium_mode = validium_mode;
+        self
+    }
+
     async fn check_commitments(&self, batch_number: L1BatchNumber) -> Result<bool, error::Error> {
         let mut storage = self.db.access_storage().await.unwrap();
 
@@ -48,7 +62,7 @@ impl ConsistencyChecker {
             .unwrap_or_else(|| panic!("Commit tx not found for L1 batch #{}", batch_number))
             as u32;
 
-        let block_metadata = storage
+        let mut block_metadata = storage
             .blocks_dal()
             .get_l1_batch_with_metadata(storage_l1_batch)
             .await
@@ -60,6 +74,13 @@ impl ConsistencyChecker {
                 )
             });
 
+        // Inclusion data is only available locally on the main node.
+        block_metadata.da_inclusion_data = storage
+            .data_availability_dal()
+            .get_l1_batch_da_inclusion_data(batch_number)
+            .await
+            .unwrap();
+
         let commit_tx_hash = storage
             .eth_sender_dal()
             .get_confirmed_tx_hash_by_eth_tx_id(commit_tx_id)
@@ -127,7 +148,11 @@ impl ConsistencyChecker {
         };
         let commitment = &commitments[batch_number.0 as usize - first_batch_number];
 
-        Ok(commitment == &block_metadata.l1_commit_data())
+        Ok(is_commitment_consistent(
+            commitment,
+            &block_metadata,
+            self.validium_mode,
+        ))
     }
 
     async fn last_committed_batch(&self) -> L1BatchNumber {
@@ -205,3 +230,28 @@ impl ConsistencyChecker {
         Ok(())
     }
 }
+
+/// Checks whether the L1 batch commitment published on L1 matches the one computed locally. In the validium mode,
+/// the pubdata field (the last field of the commitment) is not checked unless the data availability inclusion data
+/// for the batch is known locally.
+fn is_commitment_consistent(
+    l1_commitment: &ethabi::Token,
+    l1_batch: &L1BatchWithMetadata,
+    validium_mode: bool,
+) -> bool {
+    let local_commitment = l1_batch.l1_commit_data();
+    let check_pubdata = !validium_mode
+        || l1_batch.da_inclusion_data.is_some()
+        || l1_batch.header.protocol_version.unwrap().is_pre_boojum();
+    if check_pubdata {
+        return *l1_commitment == local_commitment;
+    }
+
+    match (l1_commitment, local_commitment) {
+        (ethabi::Token::Tuple(l1_fields), ethabi::Token::Tuple(local_fields)) => {
+            l1_fields.len() == local_fields.len()
+                && l1_fields[..l1_fields.len() - 1] == local_fields[..local_fields.len() - 1]
+        }
+        _ => false,
+    }
+}
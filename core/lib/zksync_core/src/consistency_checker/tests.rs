@@ -0,0 +1,59 @@
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_types::{block::L1BatchHeader, Address, ProtocolVersionId};
+
+use super::*;
+use crate::state_keeper::tests::create_l1_batch_metadata;
+
+fn create_l1_batch_with_metadata(number: u32) -> L1BatchWithMetadata {
+    let mut header = L1BatchHeader::new(
+        L1BatchNumber(number),
+        number.into(),
+        Address::default(),
+        BaseSystemContractsHashes::default(),
+        ProtocolVersionId::latest(),
+    );
+    header.l2_to_l1_messages.push(vec![1, 2, 3]);
+    L1BatchWithMetadata::new(header, create_l1_batch_metadata(number), Default::default())
+}
+
+#[test]
+fn checking_rollup_commitment() {
+    let l1_batch = create_l1_batch_with_metadata(1);
+    let l1_commitment = l1_batch.l1_commit_data();
+    assert!(is_commitment_consistent(&l1_commitment, &l1_batch, false));
+    assert!(is_commitment_consistent(&l1_commitment, &l1_batch, true));
+
+    let other_l1_batch = create_l1_batch_with_metadata(2);
+    assert!(!is_commitment_consistent(
+        &l1_commitment,
+        &other_l1_batch,
+        false
+    ));
+}
+
+#[test]
+fn checking_validium_commitment() {
+    let mut l1_batch = create_l1_batch_with_metadata(1);
+    l1_batch.da_inclusion_data = Some(vec![0xff; 32]);
+    let l1_commitment = l1_batch.l1_commit_data();
+
+    // The main node knows the inclusion data.
+    assert!(is_commitment_consistent(&l1_commitment, &l1_batch, true));
+    assert!(is_commitment_consistent(&l1_commitment, &l1_batch, false));
+
+    // External nodes don't know the inclusion data.
+    l1_batch.da_inclusion_data = None;
+    assert!(is_commitment_consistent(&l1_commitment, &l1_batch, true));
+    assert!(!is_commitment_consistent(&l1_commitment, &l1_batch, false));
+
+    // Other fields are still checked.
+    let other_l1_batch = create_l1_batch_with_metadata(2);
+    assert!(!is_commitment_consistent(
+        &l1_commitment,
+        &other_l1_batch,
+        true
+    ));
+
+    l1_batch.da_inclusion_data = Some(vec![0xee; 32]);
+    assert!(!is_commitment_consistent(&l1_commitment, &l1_batch, true));
+}
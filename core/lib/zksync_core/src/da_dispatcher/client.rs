@@ -0,0 +1,33 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use zksync_types::L1BatchNumber;
+
+/// Response of a data availability layer to dispatching a blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DispatchResponse {
+    /// Identifier of the blob on the data availability layer, used to query its inclusion.
+    pub blob_id: String,
+}
+
+/// Data proving the inclusion of a blob on a data availability layer. This data is passed
+/// to L1 in the commit transaction instead of the pubdata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionData {
+    pub data: Vec<u8>,
+}
+
+/// Client of a data availability layer that stores L1 batch pubdata in the validium mode.
+#[async_trait]
+pub trait DataAvailabilityClient: 'static + fmt::Debug + Send + Sync {
+    /// Dispatches the pubdata of the specified L1 batch to the data availability layer.
+    async fn dispatch_blob(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        pubdata: Vec<u8>,
+    ) -> anyhow::Result<DispatchResponse>;
+
+    /// Returns the inclusion data for a previously dispatched blob, or `None` if the blob
+    /// is not included on the data availability layer yet.
+    async fn get_inclusion_data(&self, blob_id: &str) -> anyhow::Result<Option<InclusionData>>;
+}
@@ -0,0 +1,61 @@
+use std::{io, path::PathBuf};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_types::{web3::signing::keccak256, L1BatchNumber};
+
+use super::client::{DataAvailabilityClient, DispatchResponse, InclusionData};
+
+/// Data availability client storing blobs as files in a local directory. Blobs are considered
+/// included as soon as they are written; the inclusion data is the hash of the blob contents.
+/// Intended for local setups and tests.
+#[derive(Debug, Clone)]
+pub struct FileDataAvailabilityClient {
+    dir: PathBuf,
+}
+
+impl FileDataAvailabilityClient {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn blob_path(&self, blob_id: &str) -> PathBuf {
+        self.dir.join(format!("{blob_id}.bin"))
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for FileDataAvailabilityClient {
+    async fn dispatch_blob(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        pubdata: Vec<u8>,
+    ) -> anyhow::Result<DispatchResponse> {
+        let blob_id = format!("l1_batch_{}", l1_batch_number.0);
+        let dir = self.dir.clone();
+        let path = self.blob_path(&blob_id);
+        tokio::task::spawn_blocking(move || {
+            std::fs::create_dir_all(&dir)?;
+            std::fs::write(&path, pubdata)
+        })
+        .await
+        .context("panicked writing blob")?
+        .with_context(|| format!("failed writing blob `{blob_id}`"))?;
+        Ok(DispatchResponse { blob_id })
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> anyhow::Result<Option<InclusionData>> {
+        let path = self.blob_path(blob_id);
+        let contents = tokio::task::spawn_blocking(move || match std::fs::read(path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        })
+        .await
+        .context("panicked reading blob")?
+        .with_context(|| format!("failed reading blob `{blob_id}`"))?;
+        Ok(contents.map(|contents| InclusionData {
+            data: keccak256(&contents).to_vec(),
+        }))
+    }
+}
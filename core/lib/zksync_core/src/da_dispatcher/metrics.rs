@@ -0,0 +1,26 @@
+//! Metrics for the data availability dispatcher.
+
+use std::time::Duration;
+
+use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_da_dispatcher")]
+pub(super) struct DataAvailabilityDispatcherMetrics {
+    /// Number of the last L1 batch dispatched to the data availability layer.
+    pub last_dispatched_l1_batch: Gauge<u64>,
+    /// Number of the last L1 batch whose inclusion on the data availability layer was confirmed.
+    pub last_included_l1_batch: Gauge<u64>,
+    /// Size of dispatched blobs.
+    #[metrics(buckets = Buckets::exponential(1_024.0..=16.0 * 1_024.0 * 1_024.0, 4.0), unit = Unit::Bytes)]
+    pub blob_size: Histogram<usize>,
+    /// Latency of dispatching a single blob.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub blob_dispatch_latency: Histogram<Duration>,
+    /// Time between dispatching a blob and confirming its inclusion.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub inclusion_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<DataAvailabilityDispatcherMetrics> = vise::Global::new();
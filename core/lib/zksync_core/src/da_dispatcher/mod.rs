@@ -0,0 +1,164 @@
+//! Data availability dispatcher that sends L1 batch pubdata to a data availability layer in the validium mode.
+
+use anyhow::Context as _;
+use chrono::Utc;
+use tokio::sync::watch;
+use zksync_config::configs::DADispatcherConfig;
+use zksync_dal::ConnectionPool;
+
+use self::metrics::METRICS;
+pub use self::{
+    client::{DataAvailabilityClient, DispatchResponse, InclusionData},
+    file_client::FileDataAvailabilityClient,
+};
+
+mod client;
+mod file_client;
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Dispatches the pubdata of L1 batches with metadata to a data availability layer and polls the layer
+/// for the inclusion of dispatched blobs. Inclusion data is persisted per L1 batch; `Aggregator` waits for it
+/// before committing the batch on L1 and passes it in the commit transaction instead of the pubdata.
+#[derive(Debug)]
+pub struct DataAvailabilityDispatcher {
+    connection_pool: ConnectionPool,
+    config: DADispatcherConfig,
+    client: Box<dyn DataAvailabilityClient>,
+}
+
+impl DataAvailabilityDispatcher {
+    pub fn new(
+        connection_pool: ConnectionPool,
+        config: DADispatcherConfig,
+        client: Box<dyn DataAvailabilityClient>,
+    ) -> Self {
+        Self {
+            connection_pool,
+            config,
+            client,
+        }
+    }
+
+    /// Dispatches pubdata of L1 batches that are ready for dispatch.
+    async fn dispatch(&self) -> anyhow::Result<()> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("da_dispatcher")
+            .await?;
+        let batches = storage
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(self.config.max_rows_to_dispatch as usize)
+            .await
+            .context("get_ready_for_da_dispatch_l1_batches()")?;
+
+        for l1_batch_number in batches {
+            let Some(batch) = storage
+                .blocks_dal()
+                .get_l1_batch_metadata(l1_batch_number)
+                .await
+                .with_context(|| format!("get_l1_batch_metadata({l1_batch_number})"))?
+            else {
+                // The batch may have been reverted in the meantime.
+                break;
+            };
+            let pubdata = batch.construct_pubdata();
+            let pubdata_len = pubdata.len();
+
+            let latency = METRICS.blob_dispatch_latency.start();
+            let response = self
+                .client
+                .dispatch_blob(l1_batch_number, pubdata)
+                .await
+                .with_context(|| {
+                    format!("failed dispatching pubdata for L1 batch #{l1_batch_number}")
+                })?;
+            let latency = latency.observe();
+
+            storage
+                .data_availability_dal()
+                .insert_l1_batch_da(l1_batch_number, &response.blob_id, Utc::now().naive_utc())
+                .await
+                .with_context(|| format!("insert_l1_batch_da({l1_batch_number})"))?;
+
+            METRICS.blob_size.observe(pubdata_len);
+            METRICS
+                .last_dispatched_l1_batch
+                .set(l1_batch_number.0.into());
+            tracing::info!(
+                "Dispatched pubdata for L1 batch #{l1_batch_number} ({pubdata_len} bytes) in {latency:?}, \
+                 blob ID: {}",
+                response.blob_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Polls the data availability layer for the inclusion of dispatched blobs. Blobs are checked in the order
+    /// of L1 batches, so that the inclusion of a batch is confirmed only after the inclusion of all previous batches.
+    async fn poll_for_inclusion(&self) -> anyhow::Result<()> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("da_dispatcher")
+            .await?;
+        loop {
+            let Some(blob) = storage
+                .data_availability_dal()
+                .get_first_da_blob_awaiting_inclusion()
+                .await
+                .context("get_first_da_blob_awaiting_inclusion()")?
+            else {
+                return Ok(());
+            };
+            let Some(inclusion_data) = self
+                .client
+                .get_inclusion_data(&blob.blob_id)
+                .await
+                .with_context(|| {
+                    format!("failed getting inclusion data for blob `{}`", blob.blob_id)
+                })?
+            else {
+                return Ok(());
+            };
+
+            storage
+                .data_availability_dal()
+                .save_l1_batch_inclusion_data(blob.l1_batch_number, &inclusion_data.data)
+                .await
+                .with_context(|| {
+                    format!("save_l1_batch_inclusion_data({})", blob.l1_batch_number)
+                })?;
+
+            let inclusion_latency = (Utc::now().naive_utc() - blob.sent_at)
+                .to_std()
+                .unwrap_or_default();
+            METRICS.inclusion_latency.observe(inclusion_latency);
+            METRICS
+                .last_included_l1_batch
+                .set(blob.l1_batch_number.0.into());
+            tracing::info!(
+                "Confirmed inclusion of pubdata for L1 batch #{} (blob ID: {}) after {inclusion_latency:?}",
+                blob.l1_batch_number,
+                blob.blob_id
+            );
+        }
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            // Errors of the data availability layer are transient, so they are logged rather than propagated.
+            if let Err(err) = self.dispatch().await {
+                tracing::warn!("Failed dispatching pubdata: {err:#}");
+            }
+            if let Err(err) = self.poll_for_inclusion().await {
+                tracing::warn!("Failed polling for pubdata inclusion: {err:#}");
+            }
+            tokio::time::timeout(self.config.polling_interval(), stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, data availability dispatcher is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_types::{
+    block::{BlockGasCount, L1BatchHeader},
+    web3::signing::keccak256,
+    Address, L1BatchNumber, L2ChainId, ProtocolVersionId, H256,
+};
+
+use super::*;
+use crate::{
+    genesis::{ensure_genesis_state, GenesisParams},
+    state_keeper::tests::create_l1_batch_metadata,
+};
+
+fn test_config() -> DADispatcherConfig {
+    DADispatcherConfig {
+        polling_interval_ms: 10,
+        max_rows_to_dispatch: 10,
+        file_client_path: None,
+    }
+}
+
+/// Stores L1 batches with numbers `1..=count` with metadata.
+async fn prepare_storage(pool: &ConnectionPool, count: u32) {
+    let mut storage = pool.access_storage().await.unwrap();
+    ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    for number in 1..=count {
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            number.into(),
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.is_finished = true;
+        storage
+            .blocks_dal()
+            .insert_l1_batch(&header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_metadata(
+                L1BatchNumber(number),
+                &create_l1_batch_metadata(number),
+                H256::zero(),
+                false,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn dispatching_and_inclusion_workflow() {
+    let pool = ConnectionPool::test_pool().await;
+    prepare_storage(&pool, 2).await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let client = FileDataAvailabilityClient::new(temp_dir.path());
+    let dispatcher = DataAvailabilityDispatcher::new(pool.clone(), test_config(), Box::new(client));
+
+    dispatcher.dispatch().await.unwrap();
+    let mut storage = pool.access_storage().await.unwrap();
+    let ready_batches = storage
+        .data_availability_dal()
+        .get_ready_for_da_dispatch_l1_batches(10)
+        .await
+        .unwrap();
+    assert!(ready_batches.is_empty(), "{ready_batches:?}");
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap()
+        .expect("no dispatched blobs");
+    assert_eq!(blob.l1_batch_number, L1BatchNumber(1));
+    assert_eq!(blob.inclusion_data, None);
+
+    dispatcher.poll_for_inclusion().await.unwrap();
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap();
+    assert_eq!(blob, None);
+
+    for number in 1..=2 {
+        let l1_batch_number = L1BatchNumber(number);
+        let batch = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap();
+        let inclusion_data = storage
+            .data_availability_dal()
+            .get_l1_batch_da_inclusion_data(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no inclusion data");
+        assert_eq!(inclusion_data, keccak256(&batch.construct_pubdata()));
+    }
+}
+
+#[tokio::test]
+async fn inclusion_is_not_confirmed_for_missing_blobs() {
+    let pool = ConnectionPool::test_pool().await;
+    prepare_storage(&pool, 1).await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let client = FileDataAvailabilityClient::new(temp_dir.path());
+    let dispatcher = DataAvailabilityDispatcher::new(pool.clone(), test_config(), Box::new(client));
+
+    dispatcher.dispatch().await.unwrap();
+    // Emulate a blob not yet included on the data availability layer.
+    std::fs::remove_file(temp_dir.path().join("l1_batch_1.bin")).unwrap();
+    dispatcher.poll_for_inclusion().await.unwrap();
+
+    let mut storage = pool.access_storage().await.unwrap();
+    let inclusion_data = storage
+        .data_availability_dal()
+        .get_l1_batch_da_inclusion_data(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(inclusion_data, None);
+}
@@ -0,0 +1,36 @@
+//! Metrics for the Postgres pruner.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "prune_type", rename_all = "snake_case")]
+pub(super) enum MetricPruneType {
+    Soft,
+    Hard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "table", rename_all = "snake_case")]
+pub(super) enum PrunedTable {
+    Transactions,
+    Events,
+    L2ToL1Logs,
+    StorageLogs,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "db_pruner")]
+pub(super) struct DbPrunerMetrics {
+    /// Latency of a single pruning iteration.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub pruning_chunk_duration: Family<MetricPruneType, Histogram<Duration>>,
+    /// Number of the last pruned L1 batch.
+    pub last_pruned_l1_batch: Family<MetricPruneType, Gauge<u64>>,
+    /// Total number of rows removed from the storage.
+    pub deleted_rows: Family<PrunedTable, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<DbPrunerMetrics> = vise::Global::new();
@@ -0,0 +1,243 @@
+//! Postgres pruning component that removes historical data for old L1 batches.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{pruning_dal::PruneType, ConnectionPool, StorageProcessor};
+use zksync_types::{L1BatchNumber, MiniblockNumber};
+use zksync_utils::time::seconds_since_epoch;
+
+use self::metrics::{MetricPruneType, PrunedTable, METRICS};
+use crate::api_server::web3::state::BlockStartInfo;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Configuration of [`DbPruner`].
+#[derive(Debug, Clone)]
+pub struct DbPrunerConfig {
+    /// Minimum age of an L1 batch for it to be pruned.
+    pub data_retention: Duration,
+    /// Maximum number of L1 batches pruned in a single iteration.
+    pub pruned_batch_chunk_size: u32,
+    /// Delay between soft pruning (i.e., marking data as pruned) and the actual data removal.
+    /// Should be greater than [`Self::MIN_REMOVAL_DELAY`] so that no pruned data is served.
+    pub removal_delay: Duration,
+    /// Delay between pruning iterations if there is nothing to prune.
+    pub poll_interval: Duration,
+}
+
+impl DbPrunerConfig {
+    /// Minimum [`Self::removal_delay`], equal to the TTL of the first available block cache in the API server.
+    pub const MIN_REMOVAL_DELAY: Duration = BlockStartInfo::CACHE_TTL;
+}
+
+/// Postgres pruner. Removes transactions, events, L2-to-L1 logs and storage logs not needed to restore
+/// the latest state for L1 batches that are older than the configured retention window and are executed on L1.
+/// Miniblock and L1 batch headers are retained.
+///
+/// Pruning is performed in 2 phases. On the first (soft) phase, a chunk of L1 batches is marked as pruned,
+/// so that its data is no longer served by the API. After [`DbPrunerConfig::removal_delay`], the data is removed
+/// from the storage (hard pruning).
+#[derive(Debug)]
+pub struct DbPruner {
+    config: DbPrunerConfig,
+    connection_pool: ConnectionPool,
+}
+
+impl DbPruner {
+    pub fn new(config: DbPrunerConfig, connection_pool: ConnectionPool) -> Self {
+        Self {
+            config,
+            connection_pool,
+        }
+    }
+
+    /// Returns the last L1 batch that can be pruned at this point.
+    async fn last_prunable_l1_batch(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let Some(last_executed_l1_batch) = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .context("failed getting last L1 batch executed on L1")?
+        else {
+            return Ok(None);
+        };
+        let last_l1_batch_with_metadata = storage
+            .blocks_dal()
+            .get_last_l1_batch_number_with_metadata()
+            .await?;
+        let max_timestamp =
+            seconds_since_epoch().saturating_sub(self.config.data_retention.as_secs());
+        let Some(last_old_l1_batch) = storage
+            .blocks_dal()
+            .get_last_l1_batch_number_with_timestamp_before(max_timestamp)
+            .await
+            .context("failed getting last L1 batch older than retention window")?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(
+            last_executed_l1_batch
+                .min(last_l1_batch_with_metadata)
+                .min(last_old_l1_batch),
+        ))
+    }
+
+    /// Marks the next chunk of L1 batches as pruned. Returns `false` if there is nothing to prune.
+    async fn soft_prune(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<bool> {
+        let latency = METRICS.pruning_chunk_duration[&MetricPruneType::Soft].start();
+        let mut transaction = storage.start_transaction().await?;
+
+        let pruning_info = transaction.pruning_dal().get_pruning_info().await?;
+        let next_l1_batch = pruning_info.first_available_l1_batch();
+        let Some(last_prunable_l1_batch) = self.last_prunable_l1_batch(&mut transaction).await?
+        else {
+            return Ok(false);
+        };
+        if next_l1_batch > last_prunable_l1_batch {
+            return Ok(false);
+        }
+
+        let last_l1_batch_to_prune = last_prunable_l1_batch
+            .min(next_l1_batch + self.config.pruned_batch_chunk_size.saturating_sub(1));
+        let (_, last_miniblock_to_prune) = transaction
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(last_l1_batch_to_prune)
+            .await?
+            .with_context(|| format!("L1 batch #{last_l1_batch_to_prune} has no miniblocks"))?;
+        transaction
+            .pruning_dal()
+            .insert_pruning_log(
+                last_l1_batch_to_prune,
+                last_miniblock_to_prune,
+                PruneType::Soft,
+            )
+            .await?;
+        transaction.commit().await?;
+
+        let latency = latency.observe();
+        METRICS.last_pruned_l1_batch[&MetricPruneType::Soft].set(last_l1_batch_to_prune.0.into());
+        tracing::info!(
+            "Soft pruned L1 batches #{next_l1_batch}..=#{last_l1_batch_to_prune} \
+             (up to miniblock #{last_miniblock_to_prune}) in {latency:?}"
+        );
+        Ok(true)
+    }
+
+    /// Removes data for all soft-pruned L1 batches from the storage.
+    async fn hard_prune(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let latency = METRICS.pruning_chunk_duration[&MetricPruneType::Hard].start();
+        let mut transaction = storage.start_transaction().await?;
+
+        let pruning_info = transaction.pruning_dal().get_pruning_info().await?;
+        let (Some(last_soft_pruned_l1_batch), Some(last_soft_pruned_miniblock)) = (
+            pruning_info.last_soft_pruned_l1_batch,
+            pruning_info.last_soft_pruned_miniblock,
+        ) else {
+            return Ok(());
+        };
+        if pruning_info.last_hard_pruned_l1_batch == Some(last_soft_pruned_l1_batch) {
+            return Ok(());
+        }
+        let first_miniblock = pruning_info
+            .last_hard_pruned_miniblock
+            .map_or(MiniblockNumber(0), |number| number + 1);
+        let miniblocks = first_miniblock..=last_soft_pruned_miniblock;
+
+        let deleted_transactions = transaction
+            .transactions_dal()
+            .prune_transactions(miniblocks.clone())
+            .await?;
+        let deleted_events = transaction
+            .events_dal()
+            .prune_events(miniblocks.clone())
+            .await?;
+        let deleted_l2_to_l1_logs = transaction
+            .events_dal()
+            .prune_l2_to_l1_logs(miniblocks.clone())
+            .await?;
+        let deleted_storage_logs = transaction
+            .storage_logs_dal()
+            .prune_storage_logs(miniblocks.clone())
+            .await?;
+        transaction
+            .pruning_dal()
+            .insert_pruning_log(
+                last_soft_pruned_l1_batch,
+                last_soft_pruned_miniblock,
+                PruneType::Hard,
+            )
+            .await?;
+        transaction.commit().await?;
+
+        let latency = latency.observe();
+        METRICS.deleted_rows[&PrunedTable::Transactions].inc_by(deleted_transactions);
+        METRICS.deleted_rows[&PrunedTable::Events].inc_by(deleted_events);
+        METRICS.deleted_rows[&PrunedTable::L2ToL1Logs].inc_by(deleted_l2_to_l1_logs);
+        METRICS.deleted_rows[&PrunedTable::StorageLogs].inc_by(deleted_storage_logs);
+        METRICS.last_pruned_l1_batch[&MetricPruneType::Hard]
+            .set(last_soft_pruned_l1_batch.0.into());
+        tracing::info!(
+            "Hard pruned miniblocks {miniblocks:?} in {latency:?}: removed {deleted_transactions} transactions, \
+             {deleted_events} events, {deleted_l2_to_l1_logs} L2-to-L1 logs and {deleted_storage_logs} storage logs"
+        );
+        Ok(())
+    }
+
+    /// Runs a single pruning iteration. Returns `false` if there was nothing to prune.
+    async fn run_single_iteration(
+        &self,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<bool> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("db_pruner")
+            .await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        // If the node was restarted after soft pruning, the hard pruning phase is finished first.
+        if pruning_info.last_soft_pruned_l1_batch == pruning_info.last_hard_pruned_l1_batch
+            && !self.soft_prune(&mut storage).await?
+        {
+            return Ok(false);
+        }
+        // Do not hold the connection while waiting.
+        drop(storage);
+
+        // `Ok(_)` means that the stop signal was received before the delay has elapsed.
+        if tokio::time::timeout(self.config.removal_delay, stop_receiver.changed())
+            .await
+            .is_ok()
+        {
+            return Ok(true);
+        }
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("db_pruner")
+            .await?;
+        self.hard_prune(&mut storage).await?;
+        Ok(true)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            let pruned = self
+                .run_single_iteration(&mut stop_receiver)
+                .await
+                .context("failed pruning Postgres data")?;
+            if !pruned {
+                tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .ok();
+            }
+        }
+        tracing::info!("Stop signal received, DB pruner is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_dal::pruning_dal::PruningInfo;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    api,
+    block::{BlockGasCount, L1BatchHeader, MiniblockHeader},
+    tx::{tx_execution_info::TxExecutionStatus, IncludedTxLocation, TransactionExecutionResult},
+    AccountTreeId, Address, L2ChainId, ProtocolVersionId, StorageKey, StorageLog, VmEvent, H256,
+};
+
+use super::*;
+use crate::{
+    genesis::{ensure_genesis_state, GenesisParams},
+    state_keeper::tests::{create_l1_batch_metadata, create_l2_transaction},
+};
+
+fn test_config(data_retention: Duration) -> DbPrunerConfig {
+    DbPrunerConfig {
+        data_retention,
+        pruned_batch_chunk_size: 2,
+        removal_delay: Duration::ZERO,
+        poll_interval: Duration::from_millis(10),
+    }
+}
+
+fn test_storage_key() -> StorageKey {
+    StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero())
+}
+
+/// Stores L1 batches with numbers `1..=count` and the specified timestamp. Each L1 batch consists of a single miniblock
+/// with a transaction, an event and a storage log overwriting the same storage slot. L1 batches `1..=executed_count`
+/// are marked as executed on L1.
+async fn prepare_storage(
+    pool: &ConnectionPool,
+    count: u32,
+    executed_count: u32,
+    timestamp: u64,
+) -> Vec<H256> {
+    let mut storage = pool.access_storage().await.unwrap();
+    if storage.blocks_dal().is_genesis_needed().await.unwrap() {
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+    }
+
+    let mut tx_hashes = vec![];
+    for number in 1..=count {
+        let mut l1_batch_header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            timestamp,
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        l1_batch_header.is_finished = true;
+        let miniblock_number = MiniblockNumber(number);
+        let miniblock_header = MiniblockHeader {
+            number: miniblock_number,
+            timestamp,
+            hash: H256::from_low_u64_be(number.into()),
+            l1_tx_count: 0,
+            l2_tx_count: 1,
+            base_fee_per_gas: 100,
+            l1_gas_price: 100,
+            l2_fair_gas_price: 100,
+            base_system_contracts_hashes: BaseSystemContractsHashes::default(),
+            protocol_version: Some(ProtocolVersionId::latest()),
+            virtual_blocks: 1,
+        };
+        storage
+            .blocks_dal()
+            .insert_miniblock(&miniblock_header)
+            .await
+            .unwrap();
+
+        let tx = create_l2_transaction(10, 100);
+        let tx_hash = tx.hash();
+        tx_hashes.push(tx_hash);
+        let tx_result = TransactionExecutionResult {
+            transaction: tx.into(),
+            hash: tx_hash,
+            execution_info: Default::default(),
+            execution_status: TxExecutionStatus::Success,
+            refunded_gas: 0,
+            operator_suggested_refund: 0,
+            compressed_bytecodes: vec![],
+            call_traces: vec![],
+            revert_reason: None,
+        };
+        storage
+            .transactions_dal()
+            .mark_txs_as_executed_in_miniblock(miniblock_number, &[tx_result], 100.into())
+            .await;
+
+        let tx_location = IncludedTxLocation {
+            tx_hash,
+            tx_index_in_miniblock: 0,
+            tx_initiator_address: Address::repeat_byte(2),
+        };
+        let event = VmEvent {
+            location: (L1BatchNumber(number), 0),
+            address: Address::repeat_byte(23),
+            indexed_topics: vec![],
+            value: vec![],
+        };
+        storage
+            .events_dal()
+            .save_events(miniblock_number, &[(tx_location, vec![&event])])
+            .await;
+        let storage_log =
+            StorageLog::new_write_log(test_storage_key(), H256::from_low_u64_be(number.into()));
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(miniblock_number, &[(tx_hash, vec![storage_log])])
+            .await;
+
+        storage
+            .blocks_dal()
+            .insert_l1_batch(&l1_batch_header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_metadata(
+                L1BatchNumber(number),
+                &create_l1_batch_metadata(number),
+                H256::zero(),
+                false,
+            )
+            .await
+            .unwrap();
+        if number <= executed_count {
+            storage
+                .eth_sender_dal()
+                .insert_bogus_confirmed_eth_tx(
+                    L1BatchNumber(number),
+                    AggregatedActionType::Execute,
+                    H256::from_low_u64_be(number.into()),
+                    chrono::Utc::now(),
+                )
+                .await
+                .unwrap();
+        }
+    }
+    tx_hashes
+}
+
+/// Counts storage logs for non-genesis miniblocks.
+async fn storage_log_count(storage: &mut StorageProcessor<'_>, miniblock_count: u32) -> u64 {
+    let mut count = 0;
+    for number in 1..=miniblock_count {
+        count += storage
+            .storage_logs_dal()
+            .count_miniblock_storage_logs(MiniblockNumber(number))
+            .await
+            .unwrap();
+    }
+    count
+}
+
+async fn transaction_exists(storage: &mut StorageProcessor<'_>, tx_hash: H256) -> bool {
+    storage
+        .transactions_web3_dal()
+        .get_transaction(api::TransactionId::Hash(tx_hash), L2ChainId::default())
+        .await
+        .unwrap()
+        .is_some()
+}
+
+#[tokio::test]
+async fn pruning_workflow() {
+    let pool = ConnectionPool::test_pool().await;
+    let tx_hashes = prepare_storage(&pool, 5, 4, 1).await;
+    let pruner = DbPruner::new(test_config(Duration::ZERO), pool.clone());
+    let mut storage = pool.access_storage().await.unwrap();
+
+    assert!(pruner.soft_prune(&mut storage).await.unwrap());
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info,
+        PruningInfo {
+            last_soft_pruned_l1_batch: Some(L1BatchNumber(1)),
+            last_soft_pruned_miniblock: Some(MiniblockNumber(1)),
+            last_hard_pruned_l1_batch: None,
+            last_hard_pruned_miniblock: None,
+        }
+    );
+    assert_eq!(pruning_info.first_available_miniblock(), MiniblockNumber(2));
+    // Soft pruning doesn't remove data.
+    assert!(transaction_exists(&mut storage, tx_hashes[0]).await);
+
+    pruner.hard_prune(&mut storage).await.unwrap();
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_hard_pruned_l1_batch,
+        Some(L1BatchNumber(1))
+    );
+    assert_eq!(
+        pruning_info.last_hard_pruned_miniblock,
+        Some(MiniblockNumber(1))
+    );
+    assert!(!transaction_exists(&mut storage, tx_hashes[0]).await);
+    let events = storage
+        .events_web3_dal()
+        .get_all_logs(MiniblockNumber(0))
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 4);
+    assert!(events
+        .iter()
+        .all(|log| log.block_number.unwrap().as_u32() > 1));
+    // The latest storage log in the pruned range is retained.
+    assert_eq!(storage_log_count(&mut storage, 5).await, 5);
+
+    assert!(pruner.soft_prune(&mut storage).await.unwrap());
+    pruner.hard_prune(&mut storage).await.unwrap();
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_hard_pruned_l1_batch,
+        Some(L1BatchNumber(3))
+    );
+    // Only the log for miniblock #3 is retained from the pruned range.
+    assert_eq!(storage_log_count(&mut storage, 5).await, 3);
+    let value = storage
+        .storage_web3_dal()
+        .get_historical_value_unchecked(&test_storage_key(), MiniblockNumber(3))
+        .await
+        .unwrap();
+    assert_eq!(value, H256::from_low_u64_be(3));
+
+    assert!(pruner.soft_prune(&mut storage).await.unwrap());
+    pruner.hard_prune(&mut storage).await.unwrap();
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_hard_pruned_l1_batch,
+        Some(L1BatchNumber(4))
+    );
+
+    // L1 batch #5 is not executed on L1 yet.
+    assert!(!pruner.soft_prune(&mut storage).await.unwrap());
+    assert!(transaction_exists(&mut storage, tx_hashes[4]).await);
+}
+
+#[tokio::test]
+async fn pruning_respects_data_retention() {
+    let pool = ConnectionPool::test_pool().await;
+    prepare_storage(&pool, 3, 3, seconds_since_epoch()).await;
+    let pruner = DbPruner::new(test_config(Duration::from_secs(3_600)), pool.clone());
+    let mut storage = pool.access_storage().await.unwrap();
+
+    // Only the genesis L1 batch is old enough to be pruned.
+    assert!(pruner.soft_prune(&mut storage).await.unwrap());
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_soft_pruned_l1_batch,
+        Some(L1BatchNumber(0))
+    );
+    assert!(!pruner.soft_prune(&mut storage).await.unwrap());
+}
+
+#[tokio::test]
+async fn pruner_stops_on_signal() {
+    let pool = ConnectionPool::test_pool().await;
+    prepare_storage(&pool, 3, 3, 1).await;
+    let pruner = DbPruner::new(test_config(Duration::ZERO), pool.clone());
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let pruner_task = tokio::spawn(pruner.run(stop_receiver));
+
+    let started_at = std::time::Instant::now();
+    loop {
+        assert!(started_at.elapsed() < Duration::from_secs(10), "timed out");
+        let mut storage = pool.access_storage().await.unwrap();
+        let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+        if pruning_info.last_hard_pruned_l1_batch == Some(L1BatchNumber(3)) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    stop_sender.send_replace(true);
+    pruner_task.await.unwrap().unwrap();
+}
@@ -1,4 +1,8 @@
-use zksync_config::configs::eth_sender::{ProofLoadingMode, ProofSendingMode, SenderConfig};
+use std::collections::HashMap;
+
+use zksync_config::configs::eth_sender::{
+    ProofLoadingMode, ProofSendingMode, PubdataSendingMode, SenderConfig,
+};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::StorageProcessor;
 use zksync_object_store::ObjectStore;
@@ -11,7 +15,7 @@ use zksync_types::{
     commitment::L1BatchWithMetadata,
     helpers::unix_timestamp_ms,
     protocol_version::L1VerifierConfig,
-    L1BatchNumber, ProtocolVersionId,
+    Address, L1BatchNumber, ProtocolVersionId,
 };
 
 use super::publish_criterion::{
@@ -26,6 +30,9 @@ pub struct Aggregator {
     execute_criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
     config: SenderConfig,
     blob_store: Box<dyn ObjectStore>,
+    /// Operator accounts dedicated to specific types of aggregated operations. Operations of other types
+    /// are sent from the main operator account.
+    custom_operators: HashMap<AggregatedActionType, Address>,
 }
 
 impl Aggregator {
@@ -85,9 +92,62 @@ impl Aggregator {
             ],
             config,
             blob_store,
+            custom_operators: HashMap::new(),
         }
     }
 
+    /// Marks operations of the specified type as sent from a dedicated operator account.
+    pub fn with_custom_operator(
+        mut self,
+        action_type: AggregatedActionType,
+        operator_address: Address,
+    ) -> Self {
+        self.custom_operators.insert(action_type, operator_address);
+        self
+    }
+
+    /// Checks whether an operation must wait for the preceding operation on the same L1 batches
+    /// to be confirmed on L1. This is the case if the operations are sent from different operator accounts;
+    /// otherwise, the nonce order guarantees that the operations are mined in order.
+    fn requires_confirmed_predecessor(&self, action_type: AggregatedActionType) -> bool {
+        let predecessor = match action_type {
+            AggregatedActionType::Commit => return false,
+            AggregatedActionType::PublishProofOnchain => AggregatedActionType::Commit,
+            AggregatedActionType::Execute => AggregatedActionType::PublishProofOnchain,
+        };
+        self.custom_operators.get(&action_type) != self.custom_operators.get(&predecessor)
+    }
+
+    /// Retains only the L1 batches for which an operation of the specified type can be sent; see
+    /// [`Self::requires_confirmed_predecessor()`]. A sent operation with a different operator account
+    /// could otherwise be mined before the preceding operation, which would make it revert.
+    async fn retain_l1_batches_with_confirmed_predecessor(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        action_type: AggregatedActionType,
+        l1_batches: &mut Vec<L1BatchWithMetadata>,
+    ) {
+        if !self.requires_confirmed_predecessor(action_type) {
+            return;
+        }
+        let last_confirmed_l1_batch = if action_type == AggregatedActionType::Execute {
+            storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_proven_on_eth()
+                .await
+                .unwrap()
+        } else {
+            storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_committed_on_eth()
+                .await
+                .unwrap()
+        };
+        l1_batches.retain(|l1_batch| {
+            last_confirmed_l1_batch.map_or(false, |number| l1_batch.header.number <= number)
+        });
+    }
+
     pub async fn get_next_ready_operation(
         &mut self,
         storage: &mut StorageProcessor<'_>,
@@ -144,11 +204,17 @@ impl Aggregator {
             .config
             .l1_batch_min_age_before_execute_seconds
             .map(|age| unix_timestamp_ms() - age * 1_000);
-        let ready_for_execute_batches = storage
+        let mut ready_for_execute_batches = storage
             .blocks_dal()
             .get_ready_for_execute_l1_batches(limit, max_l1_batch_timestamp_millis)
             .await
             .unwrap();
+        self.retain_l1_batches_with_confirmed_predecessor(
+            storage,
+            AggregatedActionType::Execute,
+            &mut ready_for_execute_batches,
+        )
+        .await;
         let l1_batches = extract_ready_subrange(
             storage,
             &mut self.execute_criteria,
@@ -174,7 +240,7 @@ impl Aggregator {
             .await
             .unwrap()?;
 
-        let ready_for_commit_l1_batches = if protocol_version_id.is_pre_boojum() {
+        let mut ready_for_commit_l1_batches = if protocol_version_id.is_pre_boojum() {
             blocks_dal
                 .pre_boojum_get_ready_for_commit_l1_batches(
                     limit,
@@ -207,6 +273,33 @@ impl Aggregator {
                 }
             });
 
+        if self.config.pubdata_sending_mode == PubdataSendingMode::DataAvailability {
+            // In the validium mode, only L1 batches with pubdata confirmed on the data availability layer
+            // can be committed; the commit transaction carries the inclusion data instead of the pubdata.
+            let (Some(first_batch), Some(last_batch)) = (
+                ready_for_commit_l1_batches.first(),
+                ready_for_commit_l1_batches.last(),
+            ) else {
+                return None;
+            };
+            let mut inclusion_data = storage
+                .data_availability_dal()
+                .get_l1_batches_da_inclusion_data(
+                    first_batch.header.number..=last_batch.header.number,
+                )
+                .await
+                .unwrap();
+            let mut ready_count = 0;
+            for batch in &mut ready_for_commit_l1_batches {
+                let Some(data) = inclusion_data.remove(&batch.header.number) else {
+                    break;
+                };
+                batch.da_inclusion_data = Some(data);
+                ready_count += 1;
+            }
+            ready_for_commit_l1_batches.truncate(ready_count);
+        }
+
         let batches = extract_ready_subrange(
             storage,
             &mut self.commit_criteria,
@@ -227,6 +320,7 @@ impl Aggregator {
         l1_verifier_config: L1VerifierConfig,
         proof_loading_mode: &ProofLoadingMode,
         blob_store: &dyn ObjectStore,
+        require_confirmed_commit: bool,
     ) -> Option<L1BatchProofOperation> {
         let previous_proven_batch_number = storage
             .blocks_dal()
@@ -241,6 +335,16 @@ impl Aggregator {
             .get_eth_commit_tx_id(batch_to_prove)
             .await
             .unwrap()?;
+        if require_confirmed_commit {
+            let last_committed_batch = storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_committed_on_eth()
+                .await
+                .unwrap()?;
+            if last_committed_batch < batch_to_prove {
+                return None;
+            }
+        }
 
         if let Some(version_id) = storage
             .blocks_dal()
@@ -309,9 +413,15 @@ impl Aggregator {
     async fn prepare_dummy_proof_operation(
         &mut self,
         storage: &mut StorageProcessor<'_>,
-        ready_for_proof_l1_batches: Vec<L1BatchWithMetadata>,
+        mut ready_for_proof_l1_batches: Vec<L1BatchWithMetadata>,
         last_sealed_l1_batch: L1BatchNumber,
     ) -> Option<L1BatchProofOperation> {
+        self.retain_l1_batches_with_confirmed_predecessor(
+            storage,
+            AggregatedActionType::PublishProofOnchain,
+            &mut ready_for_proof_l1_batches,
+        )
+        .await;
         let batches = extract_ready_subrange(
             storage,
             &mut self.proof_criteria,
@@ -343,6 +453,8 @@ impl Aggregator {
         last_sealed_l1_batch: L1BatchNumber,
         l1_verifier_config: L1VerifierConfig,
     ) -> Option<L1BatchProofOperation> {
+        let require_confirmed_commit =
+            self.requires_confirmed_predecessor(AggregatedActionType::PublishProofOnchain);
         match self.config.proof_sending_mode {
             ProofSendingMode::OnlyRealProofs => {
                 Self::load_real_proof_operation(
@@ -351,6 +463,7 @@ impl Aggregator {
                     l1_verifier_config,
                     &self.config.proof_loading_mode,
                     &*self.blob_store,
+                    require_confirmed_commit,
                 )
                 .await
             }
@@ -377,6 +490,7 @@ impl Aggregator {
                     l1_verifier_config,
                     &self.config.proof_loading_mode,
                     &*self.blob_store,
+                    require_confirmed_commit,
                 )
                 .await
                 {
@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{collections::HashMap, convert::TryInto};
 
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
@@ -6,7 +6,7 @@ use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_eth_client::BoundEthInterface;
 use zksync_types::{
-    aggregated_operations::AggregatedOperation,
+    aggregated_operations::{AggregatedActionType, AggregatedOperation},
     contracts::{Multicall3Call, Multicall3Result},
     eth_sender::EthTx,
     ethabi::{Contract, Token},
@@ -47,6 +47,9 @@ pub struct EthTxAggregator {
     pub(super) main_zksync_contract_address: Address,
     functions: ZkSyncFunctions,
     base_nonce: u64,
+    /// Operator accounts dedicated to specific types of aggregated operations, together with their base nonces.
+    /// Operations of other types are sent from the main operator account.
+    custom_operators: HashMap<AggregatedActionType, (Address, u64)>,
 }
 
 impl EthTxAggregator {
@@ -67,9 +70,26 @@ impl EthTxAggregator {
             main_zksync_contract_address,
             functions,
             base_nonce,
+            custom_operators: HashMap::new(),
         }
     }
 
+    /// Sends aggregated operations of the specified type from a dedicated operator account,
+    /// which has its own nonce stream independent of the main operator account.
+    pub fn with_custom_operator(
+        mut self,
+        action_type: AggregatedActionType,
+        operator_address: Address,
+        base_nonce: u64,
+    ) -> Self {
+        self.custom_operators
+            .insert(action_type, (operator_address, base_nonce));
+        self.aggregator = self
+            .aggregator
+            .with_custom_operator(action_type, operator_address);
+        self
+    }
+
     pub async fn run<E: BoundEthInterface>(
         mut self,
         pool: ConnectionPool,
@@ -489,11 +509,16 @@ impl EthTxAggregator {
         aggregated_op: &AggregatedOperation,
         contracts_are_pre_boojum: bool,
     ) -> Result<EthTx, ETHSenderError> {
+        let op_type = aggregated_op.get_action_type();
+        let (from_address, base_nonce) = match self.custom_operators.get(&op_type) {
+            Some(&(address, base_nonce)) => (Some(address), base_nonce),
+            None => (None, self.base_nonce),
+        };
+
         let mut transaction = storage.start_transaction().await.unwrap();
-        let nonce = self.get_next_nonce(&mut transaction).await?;
+        let nonce = Self::get_next_nonce(&mut transaction, from_address, base_nonce).await?;
         let calldata = self.encode_aggregated_op(aggregated_op, contracts_are_pre_boojum);
         let l1_batch_number_range = aggregated_op.l1_batch_range();
-        let op_type = aggregated_op.get_action_type();
 
         let predicted_gas_for_batches = transaction
             .blocks_dal()
@@ -510,6 +535,7 @@ impl EthTxAggregator {
                 op_type,
                 self.timelock_contract_address,
                 eth_tx_predicted_gas,
+                from_address,
             )
             .await
             .unwrap();
@@ -524,17 +550,18 @@ impl EthTxAggregator {
     }
 
     async fn get_next_nonce(
-        &self,
         storage: &mut StorageProcessor<'_>,
+        from_address: Option<Address>,
+        base_nonce: u64,
     ) -> Result<u64, ETHSenderError> {
         let db_nonce = storage
             .eth_sender_dal()
-            .get_next_nonce()
+            .get_next_nonce(from_address)
             .await
             .unwrap()
             .unwrap_or(0);
         // Between server starts we can execute some txs using operator account or remove some txs from the database
         // At the start we have to consider this fact and get the max nonce.
-        Ok(db_nonce.max(self.base_nonce))
+        Ok(db_nonce.max(base_nonce))
     }
 }
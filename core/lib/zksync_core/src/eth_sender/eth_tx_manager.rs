@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
@@ -15,7 +15,7 @@ use zksync_types::{
         error::Error as Web3Error,
         types::{BlockId, BlockNumber},
     },
-    L1BlockNumber, Nonce, H256, U256,
+    Address, L1BlockNumber, Nonce, H256, U256,
 };
 use zksync_utils::time::seconds_since_epoch;
 
@@ -46,12 +46,19 @@ pub(super) struct L1BlockNumbers {
 /// Based on eth_tx queue the component generates new attempt with the minimum possible fee,
 /// save it to the database, and send it to Ethereum.
 /// Based on eth_tx_history queue the component can mark txs as stuck and create the new attempt
-/// with higher gas price
+/// with higher gas price.
+/// Each operator account (the main one and ones dedicated to specific types of aggregated operations)
+/// has its own nonce stream, so transactions from different accounts are monitored and resent independently.
 #[derive(Debug)]
 pub struct EthTxManager<E, G> {
     ethereum_gateway: E,
+    /// Gateways for operator accounts dedicated to specific types of aggregated operations,
+    /// keyed by the account address.
+    custom_gateways: HashMap<Address, E>,
     config: SenderConfig,
     gas_adjuster: Arc<G>,
+    /// Numbers of in-flight transactions for each operator account; used to report the total number.
+    inflight_tx_counts: HashMap<Option<Address>, usize>,
 }
 
 impl<E, G> EthTxManager<E, G>
@@ -62,11 +69,41 @@ where
     pub fn new(config: SenderConfig, gas_adjuster: Arc<G>, ethereum_gateway: E) -> Self {
         Self {
             ethereum_gateway,
+            custom_gateways: HashMap::new(),
             config,
             gas_adjuster,
+            inflight_tx_counts: HashMap::new(),
         }
     }
 
+    /// Adds a gateway for an operator account dedicated to specific types of aggregated operations.
+    pub fn with_custom_operator(mut self, ethereum_gateway: E) -> Self {
+        self.custom_gateways
+            .insert(ethereum_gateway.sender_account(), ethereum_gateway);
+        self
+    }
+
+    /// Returns addresses of all managed operator accounts. `None` corresponds to the main operator account.
+    fn operator_addresses(&self) -> Vec<Option<Address>> {
+        let custom_addresses = self.custom_gateways.keys().copied().map(Some);
+        std::iter::once(None).chain(custom_addresses).collect()
+    }
+
+    fn gateway(&self, operator_address: Option<Address>) -> &E {
+        match operator_address {
+            None => &self.ethereum_gateway,
+            Some(address) => self
+                .custom_gateways
+                .get(&address)
+                .unwrap_or_else(|| panic!("No gateway for operator account {address:?}")),
+        }
+    }
+
+    fn operator_label(&self, operator_address: Option<Address>) -> String {
+        let address = operator_address.unwrap_or_else(|| self.ethereum_gateway.sender_account());
+        format!("{address:?}")
+    }
+
     async fn get_tx_status(
         &self,
         tx_hash: H256,
@@ -258,17 +295,17 @@ where
 
     async fn get_operator_nonce(
         &self,
+        operator_address: Option<Address>,
         block_numbers: L1BlockNumbers,
     ) -> Result<OperatorNonce, ETHSenderError> {
-        let finalized = self
-            .ethereum_gateway
+        let gateway = self.gateway(operator_address);
+        let finalized = gateway
             .nonce_at(block_numbers.finalized.0.into(), "eth_tx_manager")
             .await?
             .as_u32()
             .into();
 
-        let latest = self
-            .ethereum_gateway
+        let latest = gateway
             .nonce_at(block_numbers.latest.0.into(), "eth_tx_manager")
             .await?
             .as_u32()
@@ -304,22 +341,49 @@ where
         Ok(L1BlockNumbers { finalized, latest })
     }
 
-    // Monitors the inflight transactions, marks mined ones as confirmed,
+    async fn update_operator_balance(
+        &self,
+        operator_address: Option<Address>,
+    ) -> Result<(), ETHSenderError> {
+        let balance = self
+            .gateway(operator_address)
+            .sender_eth_balance("eth_tx_manager")
+            .await?;
+        let balance_gwei = balance / U256::exp10(9);
+        METRICS.operator_balance_gwei[&self.operator_label(operator_address)]
+            .set(balance_gwei.low_u64());
+        Ok(())
+    }
+
+    // Monitors the inflight transactions of the specified operator account, marks mined ones as confirmed,
     // returns the one that has to be resent (if there is one).
     pub(super) async fn monitor_inflight_transactions(
         &mut self,
         storage: &mut StorageProcessor<'_>,
+        operator_address: Option<Address>,
         l1_block_numbers: L1BlockNumbers,
     ) -> Result<Option<(EthTx, u32)>, ETHSenderError> {
         METRICS
             .last_known_l1_block
             .set(l1_block_numbers.latest.0.into());
-        let operator_nonce = self.get_operator_nonce(l1_block_numbers).await?;
-        let inflight_txs = storage.eth_sender_dal().get_inflight_txs().await.unwrap();
-        METRICS.number_of_inflight_txs.set(inflight_txs.len());
+        let operator_nonce = self
+            .get_operator_nonce(operator_address, l1_block_numbers)
+            .await?;
+        let inflight_txs = storage
+            .eth_sender_dal()
+            .get_inflight_txs(operator_address)
+            .await
+            .unwrap();
+        METRICS.number_of_inflight_txs_by_operator[&self.operator_label(operator_address)]
+            .set(inflight_txs.len());
+        self.inflight_tx_counts
+            .insert(operator_address, inflight_txs.len());
+        METRICS
+            .number_of_inflight_txs
+            .set(self.inflight_tx_counts.values().sum());
 
         tracing::trace!(
-            "Going through not confirmed txs. \
+            "Going through not confirmed txs of operator {operator_address:?}. \
              Block numbers: latest {}, finalized {}, \
              operator's nonce: latest {}, finalized {}",
             l1_block_numbers.latest,
@@ -387,7 +451,7 @@ where
         base_fee_per_gas: u64,
         priority_fee_per_gas: u64,
     ) -> SignedCallResult {
-        self.ethereum_gateway
+        self.gateway(tx.from_addr)
             .sign_prepared_tx_for_addr(
                 tx.raw_tx.clone(),
                 tx.contract_address,
@@ -548,11 +612,37 @@ where
         METRICS.l1_blocks_waited_in_mempool[&tx_type_label].observe(waited_blocks.into());
     }
 
+    /// Checks that all operator accounts with unconfirmed transactions are configured. Otherwise, transactions
+    /// of a removed operator account would never be monitored.
+    pub(super) async fn ensure_operators_are_configured(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<()> {
+        let configured_operators = self.operator_addresses();
+        let operators = storage
+            .eth_sender_dal()
+            .get_operators_with_unconfirmed_txs()
+            .await
+            .context("get_operators_with_unconfirmed_txs()")?;
+        for operator in operators {
+            anyhow::ensure!(
+                configured_operators.contains(&operator),
+                "There are unconfirmed L1 transactions sent from operator account {operator:?}, which is not configured; \
+                 its private key must be provided until all its transactions are confirmed"
+            );
+        }
+        Ok(())
+    }
+
     pub async fn run(
         mut self,
         pool: ConnectionPool,
         stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
+        {
+            let mut storage = pool.access_storage_tagged("eth_sender").await.unwrap();
+            self.ensure_operators_are_configured(&mut storage).await?;
+        }
         {
             let l1_block_numbers = self
                 .get_l1_block_numbers()
@@ -562,9 +652,10 @@ where
             self.send_unsent_txs(&mut storage, l1_block_numbers).await;
         }
 
-        // It's mandatory to set last_known_l1_block to zero, otherwise the first iteration
-        // will never check inflight txs status
-        let mut last_known_l1_block = L1BlockNumber(0);
+        // Last known L1 blocks are tracked separately for each operator account, so that
+        // an error for one account doesn't delay processing other accounts. It's mandatory
+        // to start from zero blocks, otherwise the first iteration will never check inflight txs status
+        let mut last_known_l1_blocks = HashMap::new();
         loop {
             let mut storage = pool.access_storage_tagged("eth_sender").await.unwrap();
 
@@ -573,13 +664,13 @@ where
                 break;
             }
 
-            match self.loop_iteration(&mut storage, last_known_l1_block).await {
-                Ok(block) => last_known_l1_block = block,
-                Err(e) => {
-                    // Web3 API request failures can cause this,
-                    // and anything more important is already properly reported.
-                    tracing::warn!("eth_sender error {:?}", e);
-                }
+            if let Err(e) = self
+                .loop_iteration(&mut storage, &mut last_known_l1_blocks)
+                .await
+            {
+                // Web3 API request failures can cause this,
+                // and anything more important is already properly reported.
+                tracing::warn!("eth_sender error {:?}", e);
             }
 
             tokio::time::sleep(self.config.tx_poll_period()).await;
@@ -590,11 +681,12 @@ where
     async fn send_new_eth_txs(
         &mut self,
         storage: &mut StorageProcessor<'_>,
+        operator_address: Option<Address>,
         current_block: L1BlockNumber,
     ) {
         let number_inflight_txs = storage
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(operator_address)
             .await
             .unwrap()
             .len();
@@ -607,7 +699,7 @@ where
             // Get the new eth tx and create history item for them
             let new_eth_tx = storage
                 .eth_sender_dal()
-                .get_new_eth_txs(number_of_available_slots_for_eth_txs)
+                .get_new_eth_txs(number_of_available_slots_for_eth_txs, operator_address)
                 .await
                 .unwrap();
 
@@ -617,15 +709,47 @@ where
         }
     }
 
-    #[tracing::instrument(skip(self, storage))]
+    #[tracing::instrument(skip(self, storage, last_known_l1_blocks))]
     async fn loop_iteration(
         &mut self,
         storage: &mut StorageProcessor<'_>,
-        previous_block: L1BlockNumber,
-    ) -> Result<L1BlockNumber, ETHSenderError> {
+        last_known_l1_blocks: &mut HashMap<Option<Address>, L1BlockNumber>,
+    ) -> Result<(), ETHSenderError> {
         let l1_block_numbers = self.get_l1_block_numbers().await?;
 
-        self.send_new_eth_txs(storage, l1_block_numbers.latest)
+        for operator_address in self.operator_addresses() {
+            let previous_block = last_known_l1_blocks
+                .get(&operator_address)
+                .copied()
+                .unwrap_or(L1BlockNumber(0));
+            let result = self
+                .operator_loop_iteration(
+                    storage,
+                    operator_address,
+                    l1_block_numbers,
+                    previous_block,
+                )
+                .await;
+            match result {
+                Ok(block) => {
+                    last_known_l1_blocks.insert(operator_address, block);
+                }
+                Err(err) => {
+                    tracing::warn!("eth_sender error for operator {operator_address:?}: {err:?}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn operator_loop_iteration(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        operator_address: Option<Address>,
+        l1_block_numbers: L1BlockNumbers,
+        previous_block: L1BlockNumber,
+    ) -> Result<L1BlockNumber, ETHSenderError> {
+        self.send_new_eth_txs(storage, operator_address, l1_block_numbers.latest)
             .await;
 
         if l1_block_numbers.latest <= previous_block {
@@ -633,8 +757,12 @@ where
             return Ok(previous_block);
         }
 
+        if let Err(err) = self.update_operator_balance(operator_address).await {
+            tracing::warn!("Failed getting balance of operator {operator_address:?}: {err:?}");
+        }
+
         if let Some((tx, sent_at_block)) = self
-            .monitor_inflight_transactions(storage, l1_block_numbers)
+            .monitor_inflight_transactions(storage, operator_address, l1_block_numbers)
             .await?
         {
             // New gas price depends on the time this tx spent in mempool.
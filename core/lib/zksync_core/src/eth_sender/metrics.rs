@@ -2,7 +2,10 @@
 
 use std::{fmt, time::Duration};
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics,
+};
 use zksync_dal::StorageProcessor;
 use zksync_types::{aggregated_operations::AggregatedActionType, eth_sender::EthTx};
 use zksync_utils::time::seconds_since_epoch;
@@ -77,8 +80,14 @@ pub(super) struct EthSenderMetrics {
     pub used_priority_fee_per_gas: Histogram<u64>,
     /// Last L1 block observed by the Ethereum sender.
     pub last_known_l1_block: Gauge<u64>,
-    /// Number of in-flight txs produced by the Ethereum sender.
+    /// Total number of in-flight txs produced by the Ethereum sender.
     pub number_of_inflight_txs: Gauge<usize>,
+    /// Number of in-flight txs produced by the Ethereum sender, grouped by the operator account.
+    #[metrics(labels = ["operator"])]
+    pub number_of_inflight_txs_by_operator: LabeledFamily<String, Gauge<usize>>,
+    /// Balance of the operator account in gwei.
+    #[metrics(labels = ["operator"])]
+    pub operator_balance_gwei: LabeledFamily<String, Gauge<u64>>,
     #[metrics(buckets = GAS_BUCKETS)]
     pub l1_gas_used: Family<ActionTypeLabel, Histogram<f64>>,
     #[metrics(buckets = Buckets::LATENCIES)]
@@ -3,7 +3,7 @@ use std::sync::{atomic::Ordering, Arc};
 use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
 use zksync_config::{
-    configs::eth_sender::{ProofSendingMode, SenderConfig},
+    configs::eth_sender::{ProofSendingMode, PubdataSendingMode, SenderConfig},
     ContractsConfig, ETHSenderConfig, GasAdjusterConfig,
 };
 use zksync_contracts::BaseSystemContractsHashes;
@@ -12,14 +12,15 @@ use zksync_eth_client::{clients::mock::MockEthereum, EthInterface};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{
     aggregated_operations::{
-        AggregatedOperation, L1BatchCommitOperation, L1BatchExecuteOperation, L1BatchProofOperation,
+        AggregatedActionType, AggregatedOperation, L1BatchCommitOperation, L1BatchExecuteOperation,
+        L1BatchProofOperation,
     },
     block::L1BatchHeader,
     commitment::{L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata},
     ethabi::Token,
     helpers::unix_timestamp_ms,
     web3::contract::Error,
-    Address, L1BatchNumber, L1BlockNumber, ProtocolVersionId, H256,
+    Address, L1BatchNumber, L1BlockNumber, Nonce, ProtocolVersionId, H256,
 };
 
 use crate::{
@@ -44,6 +45,7 @@ static DUMMY_OPERATION: Lazy<AggregatedOperation> = Lazy::new(|| {
             ),
             metadata: default_l1_batch_metadata(),
             factory_deps: Vec::new(),
+            da_inclusion_data: None,
         }],
     })
 });
@@ -181,7 +183,7 @@ async fn confirm_many() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -198,6 +200,7 @@ async fn confirm_many() -> anyhow::Result<()> {
         .manager
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
+            None,
             tester.get_block_numbers().await,
         )
         .await?;
@@ -208,7 +211,7 @@ async fn confirm_many() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -258,7 +261,7 @@ async fn resend_each_block() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -279,6 +282,7 @@ async fn resend_each_block() -> anyhow::Result<()> {
         .manager
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
+            None,
             block_numbers,
         )
         .await?
@@ -301,7 +305,7 @@ async fn resend_each_block() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -349,7 +353,7 @@ async fn dont_resend_already_mined() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -365,6 +369,7 @@ async fn dont_resend_already_mined() -> anyhow::Result<()> {
         .manager
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
+            None,
             tester.get_block_numbers().await,
         )
         .await?;
@@ -375,7 +380,7 @@ async fn dont_resend_already_mined() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -436,6 +441,7 @@ async fn three_scenarios() -> anyhow::Result<()> {
         .manager
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
+            None,
             tester.get_block_numbers().await,
         )
         .await?
@@ -447,7 +453,7 @@ async fn three_scenarios() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -496,12 +502,176 @@ async fn failed_eth_tx() {
         .manager
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
+            None,
             tester.get_block_numbers().await,
         )
         .await
         .unwrap();
 }
 
+// Tests that operations sent from a dedicated operator account use a nonce stream independent of the main operator.
+#[tokio::test]
+async fn custom_operator_uses_separate_nonces() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let tester = EthSenderTester::new(connection_pool, vec![10; 100], false).await;
+    let custom_operator = Address::repeat_byte(0x22);
+    let aggregator =
+        tester
+            .aggregator
+            .with_custom_operator(AggregatedActionType::Execute, custom_operator, 5);
+
+    let mut storage = tester.conn.access_storage().await.unwrap();
+    for expected_nonce in [5, 6] {
+        let tx = aggregator
+            .save_eth_tx(&mut storage, &DUMMY_OPERATION, true)
+            .await
+            .unwrap();
+        assert_eq!(tx.nonce, Nonce(expected_nonce));
+        assert_eq!(tx.from_addr, Some(custom_operator));
+    }
+
+    let mut eth_sender_dal = storage.eth_sender_dal();
+    let custom_txs = eth_sender_dal
+        .get_new_eth_txs(10, Some(custom_operator))
+        .await
+        .unwrap();
+    assert_eq!(custom_txs.len(), 2);
+    let main_txs = eth_sender_dal.get_new_eth_txs(10, None).await.unwrap();
+    assert!(main_txs.is_empty());
+    let next_main_nonce = eth_sender_dal.get_next_nonce(None).await.unwrap();
+    assert_eq!(next_main_nonce, None);
+}
+
+// Tests that the manager refuses to start if there are unconfirmed transactions from an operator account that is not configured.
+#[tokio::test]
+async fn removed_custom_operator_with_unconfirmed_txs() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let tester = EthSenderTester::new(connection_pool, vec![10; 100], false).await;
+    let aggregator = tester.aggregator.with_custom_operator(
+        AggregatedActionType::Execute,
+        Address::repeat_byte(0x22),
+        0,
+    );
+    let mut storage = tester.conn.access_storage().await.unwrap();
+    aggregator
+        .save_eth_tx(&mut storage, &DUMMY_OPERATION, true)
+        .await
+        .unwrap();
+
+    let err = tester
+        .manager
+        .ensure_operators_are_configured(&mut storage)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not configured"), "{err}");
+}
+
+// Tests that operations sent from a dedicated operator account wait for the preceding operation to be confirmed on L1,
+// so that they cannot be mined before it.
+#[tokio::test]
+async fn custom_prove_operator_waits_for_confirmed_commit() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester = EthSenderTester::new(connection_pool.clone(), vec![100; 100], false).await;
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+
+    let aggregator_config = SenderConfig {
+        aggregated_proof_sizes: vec![1],
+        proof_sending_mode: ProofSendingMode::SkipEveryProof,
+        ..ETHSenderConfig::for_tests().sender
+    };
+    let mut aggregator = Aggregator::new(
+        aggregator_config,
+        ObjectStoreFactory::mock().create_store().await,
+    )
+    .with_custom_operator(
+        AggregatedActionType::PublishProofOnchain,
+        Address::repeat_byte(0x22),
+    );
+
+    let commit_tx_hash =
+        commit_l1_batch(&mut tester, genesis_l1_batch, first_l1_batch, false).await;
+    let operation = get_next_ready_operation(&mut aggregator, &connection_pool).await;
+    assert!(operation.is_none(), "{operation:?}");
+
+    confirm_tx(&mut tester, commit_tx_hash).await;
+    let operation = get_next_ready_operation(&mut aggregator, &connection_pool).await;
+    assert_matches!(
+        operation,
+        Some(AggregatedOperation::PublishProofOnchain(op)) if op.l1_batches.len() == 1
+    );
+}
+
+// Tests that in the validium mode, L1 batches are committed only after their pubdata inclusion is confirmed
+// on the data availability layer.
+#[tokio::test]
+async fn commit_waits_for_da_inclusion() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let tester = EthSenderTester::new(connection_pool.clone(), vec![100; 100], false).await;
+    insert_genesis_protocol_version(&tester).await;
+    insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    insert_l1_batch(&tester, L1BatchNumber(1)).await;
+
+    let aggregator_config = SenderConfig {
+        pubdata_sending_mode: PubdataSendingMode::DataAvailability,
+        ..ETHSenderConfig::for_tests().sender
+    };
+    let mut aggregator = Aggregator::new(
+        aggregator_config,
+        ObjectStoreFactory::mock().create_store().await,
+    );
+
+    let operation = get_next_ready_operation(&mut aggregator, &connection_pool).await;
+    assert!(operation.is_none(), "{operation:?}");
+
+    // Dispatched, but not yet included pubdata is not enough.
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    storage
+        .data_availability_dal()
+        .insert_l1_batch_da(L1BatchNumber(1), "blob", chrono::Utc::now().naive_utc())
+        .await
+        .unwrap();
+    let operation = get_next_ready_operation(&mut aggregator, &connection_pool).await;
+    assert!(operation.is_none(), "{operation:?}");
+
+    let inclusion_data = vec![0xff; 32];
+    storage
+        .data_availability_dal()
+        .save_l1_batch_inclusion_data(L1BatchNumber(1), &inclusion_data)
+        .await
+        .unwrap();
+    let operation = get_next_ready_operation(&mut aggregator, &connection_pool).await;
+    let operation = match operation {
+        Some(AggregatedOperation::Commit(operation)) => operation,
+        other => panic!("Unexpected operation: {other:?}"),
+    };
+    assert_eq!(operation.l1_batches.len(), 1);
+    assert_eq!(operation.l1_batches[0].header.number, L1BatchNumber(1));
+    assert_eq!(
+        operation.l1_batches[0].da_inclusion_data,
+        Some(inclusion_data)
+    );
+}
+
+async fn get_next_ready_operation(
+    aggregator: &mut Aggregator,
+    pool: &ConnectionPool,
+) -> Option<AggregatedOperation> {
+    let mut storage = pool.access_storage().await.unwrap();
+    let mut prover_storage = pool.access_storage().await.unwrap();
+    aggregator
+        .get_next_ready_operation(
+            &mut storage,
+            &mut prover_storage,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+            Default::default(),
+        )
+        .await
+}
+
 fn default_l1_batch_metadata() -> L1BatchMetadata {
     L1BatchMetadata {
         root_hash: Default::default(),
@@ -531,6 +701,7 @@ fn l1_batch_with_metadata(header: L1BatchHeader) -> L1BatchWithMetadata {
         header,
         metadata: default_l1_batch_metadata(),
         factory_deps: vec![],
+        da_inclusion_data: None,
     }
 }
 
@@ -986,6 +1157,7 @@ async fn confirm_tx(tester: &mut EthSenderTester, hash: H256) {
         .manager
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
+            None,
             tester.get_block_numbers().await,
         )
         .await
@@ -0,0 +1,290 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use vise::{Counter, LabeledFamily, Metrics};
+use zksync_dal::ConnectionPool;
+use zksync_object_store::{Bucket, ObjectStore, StoredObject};
+use zksync_prover_utils::periodic_job::PeriodicJob;
+use zksync_types::{witness_block_state::WitnessBlockState, L1BatchNumber};
+
+/// Maximum number of jobs whose artifacts are removed at once.
+const CLEANING_CHUNK_SIZE: u32 = 1_000;
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_prover_artifacts_cleaner")]
+struct ProverArtifactsCleanerMetrics {
+    /// Number of objects removed from the object store.
+    #[metrics(labels = ["bucket"])]
+    removed_objects: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<ProverArtifactsCleanerMetrics> = vise::Global::new();
+
+/// Removes prover artifacts from the object store for L1 batches that are already proven on L1.
+/// Only [`Bucket::ProverJobsFri`] and [`Bucket::WitnessInput`] buckets are supported.
+///
+/// Artifacts to remove are taken from the blob URLs recorded in the prover DB tables; removed blobs
+/// are marked with the `is_blob_cleaned` flag, so each blob is processed only once.
+#[derive(Debug)]
+pub struct ProverArtifactsCleaner {
+    pool: ConnectionPool,
+    prover_pool: ConnectionPool,
+    blob_store: Box<dyn ObjectStore>,
+    buckets: Vec<Bucket>,
+    cleaning_interval_ms: u64,
+}
+
+impl ProverArtifactsCleaner {
+    pub fn new(
+        cleaning_interval_ms: u64,
+        pool: ConnectionPool,
+        prover_pool: ConnectionPool,
+        blob_store: Box<dyn ObjectStore>,
+        buckets: Vec<Bucket>,
+    ) -> Self {
+        Self {
+            pool,
+            prover_pool,
+            blob_store,
+            buckets,
+            cleaning_interval_ms,
+        }
+    }
+
+    /// Removes artifacts for L1 batches up to and including `last_proven_l1_batch` from the `bucket`.
+    /// Returns the number of removed objects.
+    async fn clean_bucket(
+        &self,
+        bucket: Bucket,
+        last_proven_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<usize> {
+        let mut removed_count = 0;
+        loop {
+            let chunk_removed_count = match bucket {
+                Bucket::ProverJobsFri => self.clean_prover_jobs(last_proven_l1_batch).await?,
+                Bucket::WitnessInput => self.clean_witness_inputs(last_proven_l1_batch).await?,
+                _ => anyhow::bail!("cleaning bucket {bucket} is not supported"),
+            };
+            let Some(chunk_removed_count) = chunk_removed_count else {
+                return Ok(removed_count);
+            };
+            removed_count += chunk_removed_count;
+        }
+    }
+
+    /// Removes circuits for the next chunk of prover jobs. Returns the number of removed objects,
+    /// or `None` if there are no more jobs to process.
+    async fn clean_prover_jobs(
+        &self,
+        last_proven_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<Option<usize>> {
+        let mut storage = self.prover_pool.access_storage().await?;
+        let jobs = storage
+            .fri_prover_jobs_dal()
+            .get_circuit_blob_urls_to_be_cleaned(last_proven_l1_batch, CLEANING_CHUNK_SIZE)
+            .await
+            .context("get_circuit_blob_urls_to_be_cleaned()")?;
+        if jobs.is_empty() {
+            return Ok(None);
+        }
+
+        let (ids, keys): (Vec<_>, Vec<_>) = jobs.into_iter().unzip();
+        self.blob_store
+            .remove_many_raw(Bucket::ProverJobsFri, &keys)
+            .await
+            .context("failed removing prover job circuits")?;
+        storage
+            .fri_prover_jobs_dal()
+            .mark_circuit_blobs_as_cleaned(&ids)
+            .await
+            .context("mark_circuit_blobs_as_cleaned()")?;
+        Ok(Some(keys.len()))
+    }
+
+    /// Removes witness inputs for the next chunk of L1 batches. Returns the number of removed objects,
+    /// or `None` if there are no more L1 batches to process.
+    async fn clean_witness_inputs(
+        &self,
+        last_proven_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<Option<usize>> {
+        let mut storage = self.prover_pool.access_storage().await?;
+        let inputs = storage
+            .fri_witness_generator_dal()
+            .get_witness_input_blob_urls_to_be_cleaned(last_proven_l1_batch, CLEANING_CHUNK_SIZE)
+            .await
+            .context("get_witness_input_blob_urls_to_be_cleaned()")?;
+        if inputs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut l1_batch_numbers = Vec::with_capacity(inputs.len());
+        let mut keys = Vec::with_capacity(inputs.len() * 2);
+        for (l1_batch_number, merkle_paths_key) in inputs {
+            l1_batch_numbers.push(l1_batch_number);
+            keys.extend(merkle_paths_key);
+            keys.push(WitnessBlockState::encode_key(l1_batch_number));
+        }
+        self.blob_store
+            .remove_many_raw(Bucket::WitnessInput, &keys)
+            .await
+            .context("failed removing witness inputs")?;
+        storage
+            .fri_witness_generator_dal()
+            .mark_witness_input_blobs_as_cleaned(&l1_batch_numbers)
+            .await
+            .context("mark_witness_input_blobs_as_cleaned()")?;
+        Ok(Some(keys.len()))
+    }
+}
+
+/// Invoked periodically to remove artifacts of already proven L1 batches from the object store.
+#[async_trait]
+impl PeriodicJob for ProverArtifactsCleaner {
+    const SERVICE_NAME: &'static str = "ProverArtifactsCleaner";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        let last_proven_l1_batch = self
+            .pool
+            .access_storage()
+            .await?
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_proven_on_eth()")?;
+        let Some(last_proven_l1_batch) = last_proven_l1_batch else {
+            return Ok(());
+        };
+
+        for &bucket in &self.buckets {
+            let removed_count = self.clean_bucket(bucket, last_proven_l1_batch).await?;
+            tracing::info!(
+                "Removed {removed_count} objects for L1 batches <= {last_proven_l1_batch} from bucket {bucket}"
+            );
+            METRICS.removed_objects[&bucket.as_str()].inc_by(removed_count as u64);
+        }
+        Ok(())
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.cleaning_interval_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::ObjectStoreFactory;
+    use zksync_types::{
+        block::L1BatchHeader,
+        proofs::AggregationRound,
+        protocol_version::{FriProtocolVersionId, L1VerifierConfig},
+        L2ChainId, ProtocolVersionId,
+    };
+
+    use super::*;
+    use crate::genesis::{ensure_genesis_state, GenesisParams};
+
+    #[tokio::test]
+    async fn cleaning_buckets() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+        let header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            1,
+            Default::default(),
+            Default::default(),
+            ProtocolVersionId::latest(),
+        );
+        storage
+            .blocks_dal()
+            .insert_l1_batch(&header, &[], Default::default(), &[], &[])
+            .await
+            .unwrap();
+        storage
+            .fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                FriProtocolVersionId::latest(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+
+        let blob_store = ObjectStoreFactory::mock().create_store().await;
+        for number in [L1BatchNumber(0), L1BatchNumber(1)] {
+            let circuit_key = format!("{number}_0_1_BasicCircuits_0.bin");
+            let merkle_paths_key = format!("merkel_tree_paths_{number}.bin");
+            let block_state_key = WitnessBlockState::encode_key(number);
+            for (bucket, key) in [
+                (Bucket::ProverJobsFri, &circuit_key),
+                (Bucket::WitnessInput, &merkle_paths_key),
+                (Bucket::WitnessInput, &block_state_key),
+            ] {
+                blob_store.put_raw(bucket, key, vec![0]).await.unwrap();
+            }
+
+            storage
+                .fri_prover_jobs_dal()
+                .insert_prover_job(
+                    number,
+                    1,
+                    0,
+                    0,
+                    AggregationRound::BasicCircuits,
+                    &circuit_key,
+                    false,
+                    FriProtocolVersionId::latest(),
+                )
+                .await;
+            storage
+                .fri_witness_generator_dal()
+                .save_witness_inputs(number, &merkle_paths_key, FriProtocolVersionId::latest())
+                .await;
+        }
+        drop(storage);
+
+        let cleaner = ProverArtifactsCleaner::new(
+            1_000,
+            pool.clone(),
+            pool,
+            blob_store,
+            vec![Bucket::ProverJobsFri, Bucket::WitnessInput],
+        );
+        let removed_count = cleaner
+            .clean_bucket(Bucket::ProverJobsFri, L1BatchNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 1);
+        let removed_count = cleaner
+            .clean_bucket(Bucket::WitnessInput, L1BatchNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 2);
+
+        let page = cleaner
+            .blob_store
+            .list_raw(Bucket::ProverJobsFri, "", None)
+            .await
+            .unwrap();
+        assert_eq!(page.keys, ["1_0_1_BasicCircuits_0.bin"]);
+        let page = cleaner
+            .blob_store
+            .list_raw(Bucket::WitnessInput, "", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            page.keys,
+            [
+                "merkel_tree_paths_1.bin",
+                "witness_block_state_for_l1_batch_1.bin"
+            ]
+        );
+
+        // Blobs are marked as cleaned, so they are not processed again.
+        let removed_count = cleaner
+            .clean_bucket(Bucket::ProverJobsFri, L1BatchNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 0);
+    }
+}
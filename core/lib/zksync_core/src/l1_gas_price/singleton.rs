@@ -5,8 +5,8 @@ use tokio::{
     sync::{watch, OnceCell},
     task::JoinHandle,
 };
-use zksync_config::GasAdjusterConfig;
-use zksync_eth_client::clients::http::QueryClient;
+use zksync_config::{ETHClientConfig, GasAdjusterConfig};
+use zksync_eth_client::clients::http::MultiQueryClient;
 
 use crate::l1_gas_price::{BoundedGasAdjuster, GasAdjuster};
 
@@ -14,9 +14,9 @@ use crate::l1_gas_price::{BoundedGasAdjuster, GasAdjuster};
 /// This is needed only for running the server.
 #[derive(Debug)]
 pub struct GasAdjusterSingleton {
-    web3_url: String,
+    eth_client_config: ETHClientConfig,
     gas_adjuster_config: GasAdjusterConfig,
-    singleton: OnceCell<Result<Arc<GasAdjuster<QueryClient>>, Error>>,
+    singleton: OnceCell<Result<Arc<GasAdjuster<MultiQueryClient>>, Error>>,
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -30,20 +30,20 @@ impl From<anyhow::Error> for Error {
 }
 
 impl GasAdjusterSingleton {
-    pub fn new(web3_url: String, gas_adjuster_config: GasAdjusterConfig) -> Self {
+    pub fn new(eth_client_config: ETHClientConfig, gas_adjuster_config: GasAdjusterConfig) -> Self {
         Self {
-            web3_url,
+            eth_client_config,
             gas_adjuster_config,
             singleton: OnceCell::new(),
         }
     }
 
-    pub async fn get_or_init(&mut self) -> Result<Arc<GasAdjuster<QueryClient>>, Error> {
+    pub async fn get_or_init(&mut self) -> Result<Arc<GasAdjuster<MultiQueryClient>>, Error> {
         let adjuster = self
             .singleton
             .get_or_init(|| async {
-                let query_client =
-                    QueryClient::new(&self.web3_url).context("QueryClient::new()")?;
+                let query_client = MultiQueryClient::from_config(&self.eth_client_config)
+                    .context("MultiQueryClient::from_config()")?;
                 let adjuster = GasAdjuster::new(query_client.clone(), self.gas_adjuster_config)
                     .await
                     .context("GasAdjuster::new()")?;
@@ -55,7 +55,7 @@ impl GasAdjusterSingleton {
 
     pub async fn get_or_init_bounded(
         &mut self,
-    ) -> anyhow::Result<Arc<BoundedGasAdjuster<GasAdjuster<QueryClient>>>> {
+    ) -> anyhow::Result<Arc<BoundedGasAdjuster<GasAdjuster<MultiQueryClient>>>> {
         let adjuster = self.get_or_init().await.context("get_or_init()")?;
         Ok(Arc::new(BoundedGasAdjuster::new(
             self.gas_adjuster_config.max_l1_gas_price(),
@@ -6,7 +6,10 @@ use anyhow::Context as _;
 use futures::channel::oneshot;
 use prometheus_exporter::PrometheusExporterConfig;
 use temp_config_store::TempConfigStore;
-use tokio::{sync::watch, task::JoinHandle};
+use tokio::{
+    sync::{broadcast, watch},
+    task::JoinHandle,
+};
 use zksync_circuit_breaker::{
     l1_txs::FailedL1TransactionChecker, replication_lag::ReplicationLagChecker, CircuitBreaker,
     CircuitBreakerChecker, CircuitBreakerError,
@@ -20,21 +23,23 @@ use zksync_config::{
         },
         contracts::ProverAtGenesis,
         database::MerkleTreeMode,
+        eth_sender::PubdataSendingMode,
     },
-    ApiConfig, ContractsConfig, DBConfig, ETHSenderConfig, PostgresConfig,
+    ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, PostgresConfig,
 };
 use zksync_contracts::{governance_contract, BaseSystemContracts};
 use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
 use zksync_eth_client::{
-    clients::http::{PKSigningClient, QueryClient},
+    clients::http::{MultiQueryClient, PKSigningClient, QueryClient},
     BoundEthInterface, EthInterface,
 };
 use zksync_health_check::{CheckHealth, HealthStatus, ReactiveHealthCheck};
-use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreFactory};
 use zksync_prover_utils::periodic_job::PeriodicJob;
 use zksync_queued_job_processor::JobProcessor;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     protocol_version::{L1VerifierConfig, VerifierParams},
     system_contracts::get_system_smart_contracts,
     L2ChainId, PackedEthSignature, ProtocolVersionId,
@@ -50,6 +55,7 @@ use crate::{
         web3::{state::InternalApiConfig, ApiServerHandles, Namespace},
     },
     basic_witness_input_producer::BasicWitnessInputProducer,
+    da_dispatcher::{DataAvailabilityDispatcher, FileDataAvailabilityClient},
     data_fetchers::run_data_fetchers,
     eth_sender::{Aggregator, EthTxAggregator, EthTxManager},
     eth_watch::start_eth_watch,
@@ -63,6 +69,7 @@ use crate::{
         fri_witness_generator_jobs_retry_manager::FriWitnessGeneratorJobRetryManager,
         fri_witness_generator_queue_monitor::FriWitnessGeneratorStatsReporter,
         gpu_prover_queue_monitor::GpuProverQueueMonitor,
+        prover_artifacts_cleaner::ProverArtifactsCleaner,
         prover_job_retry_manager::ProverJobRetryManager, prover_queue_monitor::ProverStatsReporter,
         waiting_to_queued_fri_witness_job_mover::WaitingToQueuedFriWitnessJobMover,
     },
@@ -71,7 +78,10 @@ use crate::{
         MetadataCalculator, MetadataCalculatorConfig, MetadataCalculatorModeConfig,
     },
     metrics::{InitStage, APP_METRICS},
-    state_keeper::{create_state_keeper, MempoolFetcher, MempoolGuard, MiniblockSealer},
+    state_keeper::{
+        create_state_keeper, types::account_limits, MempoolFetcher, MempoolGuard, MiniblockSealer,
+        SealedMiniblocksSender,
+    },
 };
 
 pub mod api_server;
@@ -79,7 +89,9 @@ pub mod basic_witness_input_producer;
 pub mod block_reverter;
 mod consensus;
 pub mod consistency_checker;
+pub mod da_dispatcher;
 pub mod data_fetchers;
+pub mod db_pruner;
 pub mod eth_sender;
 pub mod eth_watch;
 pub mod gas_tracker;
@@ -90,6 +102,7 @@ pub mod metadata_calculator;
 mod metrics;
 pub mod proof_data_handler;
 pub mod reorg_detector;
+pub mod snapshot_recovery;
 pub mod state_keeper;
 pub mod sync_layer;
 pub mod temp_config_store;
@@ -240,6 +253,8 @@ pub enum Component {
     Housekeeper,
     /// Component for exposing APIs to prover for providing proof generation data and accepting proofs.
     ProofDataHandler,
+    /// Dispatches L1 batch pubdata to a data availability layer in the validium mode.
+    DADispatcher,
 }
 
 #[derive(Debug)]
@@ -280,6 +295,7 @@ impl FromStr for Components {
             "eth_tx_aggregator" => Ok(Components(vec![Component::EthTxAggregator])),
             "eth_tx_manager" => Ok(Components(vec![Component::EthTxManager])),
             "proof_data_handler" => Ok(Components(vec![Component::ProofDataHandler])),
+            "da_dispatcher" => Ok(Components(vec![Component::DADispatcher])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -336,10 +352,11 @@ pub async fn initialize_components(
         panic!("Circuit breaker triggered: {}", err);
     });
 
-    let query_client = QueryClient::new(&eth_client_config.web3_url).unwrap();
+    let query_client = MultiQueryClient::from_config(&eth_client_config)
+        .context("MultiQueryClient::from_config()")?;
     let gas_adjuster_config = configs.gas_adjuster_config.context("gas_adjuster_config")?;
     let mut gas_adjuster =
-        GasAdjusterSingleton::new(eth_client_config.web3_url.clone(), gas_adjuster_config);
+        GasAdjusterSingleton::new(eth_client_config.clone(), gas_adjuster_config);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (cb_sender, cb_receiver) = oneshot::channel();
@@ -367,6 +384,25 @@ pub async fn initialize_components(
         tokio::spawn(circuit_breaker_checker.run(cb_sender, stop_receiver.clone())),
     ];
 
+    // The mempool is shared by the state keeper and the API servers (the latter use it for mempool introspection),
+    // so it's created before both of them.
+    let mempool = if components.contains(&Component::StateKeeper) {
+        let state_keeper_config = configs
+            .state_keeper_config
+            .as_ref()
+            .context("state_keeper_config")?;
+        let mempool_config = configs.mempool_config.as_ref().context("mempool_config")?;
+        Some(build_mempool(&connection_pool, state_keeper_config, mempool_config).await)
+    } else {
+        None
+    };
+    // Miniblocks sealed by the state keeper are pushed to WebSocket API subscribers. Subscribers lagging
+    // behind by more than the channel capacity fall back to polling Postgres.
+    const SEALED_MINIBLOCKS_CHANNEL_CAPACITY: usize = 128;
+    let sealed_miniblocks_sender = mempool
+        .as_ref()
+        .map(|_| broadcast::channel(SEALED_MINIBLOCKS_CHANNEL_CAPACITY).0);
+
     if components.contains(&Component::WsApi)
         || components.contains(&Component::HttpApi)
         || components.contains(&Component::ContractVerificationApi)
@@ -378,9 +414,11 @@ pub async fn initialize_components(
             .clone()
             .context("state_keeper_config")?;
         let network_config = configs.network_config.clone().context("network_config")?;
+        let mempool_config = configs.mempool_config.clone().context("mempool_config")?;
         let tx_sender_config = TxSenderConfig::new(
             &state_keeper_config,
             &api_config.web3_json_rpc,
+            &mempool_config,
             network_config.zksync_network_id,
         );
         let internal_api_config = InternalApiConfig::new(
@@ -420,6 +458,7 @@ pub async fn initialize_components(
                 state_keeper_config.save_call_traces,
                 components.contains(&Component::ApiTranslator),
                 storage_caches.clone().unwrap(),
+                mempool.clone(),
             )
             .await
             .context("run_http_api")?;
@@ -459,6 +498,8 @@ pub async fn initialize_components(
                 stop_receiver.clone(),
                 storage_caches,
                 components.contains(&Component::ApiTranslator),
+                mempool.clone(),
+                sealed_miniblocks_sender.clone(),
             )
             .await
             .context("run_ws_api")?;
@@ -494,7 +535,7 @@ pub async fn initialize_components(
         .context("object_store_config")?;
     let store_factory = ObjectStoreFactory::new(object_store_config);
 
-    if components.contains(&Component::StateKeeper) {
+    if let Some(mempool) = mempool {
         let started_at = Instant::now();
         tracing::info!("initializing State Keeper");
         let bounded_gas_adjuster = gas_adjuster
@@ -512,6 +553,8 @@ pub async fn initialize_components(
             &configs.network_config.clone().context("network_config")?,
             &db_config,
             &configs.mempool_config.clone().context("mempool_config")?,
+            mempool,
+            sealed_miniblocks_sender,
             bounded_gas_adjuster,
             store_factory.create_store().await,
             stop_receiver.clone(),
@@ -570,10 +613,17 @@ pub async fn initialize_components(
             .eth_sender_config
             .clone()
             .context("eth_sender_config")?;
+        // Otherwise, L1 batches would never be committed since their pubdata is never dispatched.
+        anyhow::ensure!(
+            eth_sender.sender.pubdata_sending_mode != PubdataSendingMode::DataAvailability
+                || components.contains(&Component::DADispatcher),
+            "`da_dispatcher` component must be run together with `eth_tx_aggregator` if pubdata is sent \
+             to a data availability layer"
+        );
         let eth_client =
             PKSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config);
         let nonce = eth_client.pending_nonce("eth_sender").await.unwrap();
-        let eth_tx_aggregator_actor = EthTxAggregator::new(
+        let mut eth_tx_aggregator_actor = EthTxAggregator::new(
             eth_sender.sender.clone(),
             Aggregator::new(
                 eth_sender.sender.clone(),
@@ -584,6 +634,19 @@ pub async fn initialize_components(
             main_zksync_contract_address,
             nonce.as_u64(),
         );
+        for (action_type, operator_client) in
+            custom_operator_clients(&eth_sender, &contracts_config, &eth_client_config)
+                .context("custom_operator_clients()")?
+        {
+            let operator_address = operator_client.sender_account();
+            let nonce = operator_client.pending_nonce("eth_sender").await.unwrap();
+            tracing::info!("Using operator {operator_address:?} for {action_type} operations");
+            eth_tx_aggregator_actor = eth_tx_aggregator_actor.with_custom_operator(
+                action_type,
+                operator_address,
+                nonce.as_u64(),
+            );
+        }
         task_futures.push(tokio::spawn(eth_tx_aggregator_actor.run(
             eth_sender_pool,
             eth_sender_prover_pool,
@@ -608,7 +671,10 @@ pub async fn initialize_components(
             .context("eth_sender_config")?;
         let eth_client =
             PKSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config);
-        let eth_tx_manager_actor = EthTxManager::new(
+        let custom_operator_clients =
+            custom_operator_clients(&eth_sender, &contracts_config, &eth_client_config)
+                .context("custom_operator_clients()")?;
+        let mut eth_tx_manager_actor = EthTxManager::new(
             eth_sender.sender,
             gas_adjuster
                 .get_or_init()
@@ -616,6 +682,9 @@ pub async fn initialize_components(
                 .context("gas_adjuster.get_or_init()")?,
             eth_client,
         );
+        for (_, operator_client) in custom_operator_clients {
+            eth_tx_manager_actor = eth_tx_manager_actor.with_custom_operator(operator_client);
+        }
         task_futures.extend([tokio::spawn(
             eth_tx_manager_actor.run(eth_manager_pool, stop_receiver.clone()),
         )]);
@@ -690,6 +759,23 @@ pub async fn initialize_components(
         )));
     }
 
+    if components.contains(&Component::DADispatcher) {
+        let da_dispatcher_config = configs
+            .da_dispatcher_config
+            .clone()
+            .context("da_dispatcher_config")?;
+        let file_client_path = da_dispatcher_config
+            .file_client_path
+            .clone()
+            .context("data availability client is not configured")?;
+        let da_dispatcher = DataAvailabilityDispatcher::new(
+            connection_pool.clone(),
+            da_dispatcher_config,
+            Box::new(FileDataAvailabilityClient::new(file_client_path)),
+        );
+        task_futures.push(tokio::spawn(da_dispatcher.run(stop_receiver.clone())));
+    }
+
     // Run healthcheck server for all components.
     healthchecks.push(Box::new(ConnectionPoolHealthCheck::new(
         replica_connection_pool,
@@ -717,6 +803,8 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
     network_config: &NetworkConfig,
     db_config: &DBConfig,
     mempool_config: &MempoolConfig,
+    mempool: MempoolGuard,
+    sealed_miniblocks_sender: Option<SealedMiniblocksSender>,
     gas_adjuster: Arc<E>,
     object_store: Box<dyn ObjectStore>,
     stop_receiver: watch::Receiver<bool>,
@@ -727,15 +815,6 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         .build()
         .await
         .context("failed to build state_keeper_pool")?;
-    let next_priority_id = state_keeper_pool
-        .access_storage()
-        .await
-        .unwrap()
-        .transactions_dal()
-        .next_priority_id()
-        .await;
-    let mempool = MempoolGuard::new(next_priority_id, mempool_config.capacity);
-    mempool.register_metrics();
 
     let miniblock_sealer_pool = pool_builder
         .build()
@@ -745,6 +824,11 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         miniblock_sealer_pool,
         state_keeper_config.miniblock_seal_queue_capacity,
     );
+    let miniblock_sealer = if let Some(sender) = sealed_miniblocks_sender {
+        miniblock_sealer.with_sealed_miniblocks_sender(sender)
+    } else {
+        miniblock_sealer
+    };
     task_futures.push(tokio::spawn(miniblock_sealer.run()));
 
     let state_keeper = create_state_keeper(
@@ -779,6 +863,28 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
     Ok(())
 }
 
+async fn build_mempool(
+    pool: &ConnectionPool,
+    state_keeper_config: &StateKeeperConfig,
+    mempool_config: &MempoolConfig,
+) -> MempoolGuard {
+    let next_priority_id = pool
+        .access_storage()
+        .await
+        .unwrap()
+        .transactions_dal()
+        .next_priority_id()
+        .await;
+    let mempool = MempoolGuard::new(
+        next_priority_id,
+        mempool_config.capacity,
+        state_keeper_config.mempool_ordering,
+        account_limits(mempool_config),
+    );
+    mempool.register_metrics();
+    mempool
+}
+
 async fn add_trees_to_task_futures(
     configs: &TempConfigStore,
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
@@ -1046,9 +1152,84 @@ async fn add_house_keeper_to_task_futures(
         prover_connection_pool.clone(),
     );
     task_futures.push(tokio::spawn(fri_proof_compressor_retry_manager.run()));
+
+    if let Some(cleaning_interval_ms) = house_keeper_config.prover_artifacts_cleaning_interval_ms {
+        let object_store_config = configs
+            .object_store_config
+            .clone()
+            .context("object_store_config")?;
+        let prover_object_store_config = configs
+            .prover_object_store_config
+            .clone()
+            .context("prover_object_store_config")?;
+        let witness_inputs_cleaner = ProverArtifactsCleaner::new(
+            cleaning_interval_ms,
+            connection_pool.clone(),
+            prover_connection_pool.clone(),
+            ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await,
+            vec![Bucket::WitnessInput],
+        );
+        task_futures.push(tokio::spawn(witness_inputs_cleaner.run()));
+
+        let prover_jobs_cleaner = ProverArtifactsCleaner::new(
+            cleaning_interval_ms,
+            connection_pool.clone(),
+            prover_connection_pool.clone(),
+            ObjectStoreFactory::new(prover_object_store_config)
+                .create_store()
+                .await,
+            vec![Bucket::ProverJobsFri],
+        );
+        task_futures.push(tokio::spawn(prover_jobs_cleaner.run()));
+    }
     Ok(())
 }
 
+/// Creates L1 clients for operator accounts dedicated to specific types of aggregated operations.
+fn custom_operator_clients(
+    eth_sender: &ETHSenderConfig,
+    contracts_config: &ContractsConfig,
+    eth_client_config: &ETHClientConfig,
+) -> anyhow::Result<Vec<(AggregatedActionType, PKSigningClient)>> {
+    let sender = &eth_sender.sender;
+    let main_private_key = sender.private_key();
+    let private_keys = [
+        (
+            AggregatedActionType::Commit,
+            sender.commit_operator_private_key(),
+        ),
+        (
+            AggregatedActionType::PublishProofOnchain,
+            sender.prove_operator_private_key(),
+        ),
+        (
+            AggregatedActionType::Execute,
+            sender.execute_operator_private_key(),
+        ),
+    ];
+    let mut clients = vec![];
+    for (action_type, private_key) in private_keys {
+        let Some(private_key) = private_key else {
+            continue;
+        };
+        // Otherwise, the same account would be used with 2 independent nonce streams.
+        anyhow::ensure!(
+            Some(private_key) != main_private_key,
+            "Dedicated operator account for {action_type} operations must differ from the main operator account"
+        );
+        let client = PKSigningClient::from_config_with_private_key(
+            eth_sender,
+            contracts_config,
+            eth_client_config,
+            private_key,
+        );
+        clients.push((action_type, client));
+    }
+    Ok(clients)
+}
+
 fn build_storage_caches(
     configs: &TempConfigStore,
     replica_connection_pool: &ConnectionPool,
@@ -1121,6 +1302,7 @@ async fn run_http_api<G: L1GasPriceProvider + Send + Sync + 'static>(
     with_debug_namespace: bool,
     with_logs_request_translator_enabled: bool,
     storage_caches: PostgresStorageCaches,
+    mempool: Option<MempoolGuard>,
 ) -> anyhow::Result<ApiServerHandles> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1133,11 +1315,17 @@ async fn run_http_api<G: L1GasPriceProvider + Send + Sync + 'static>(
     )
     .await;
 
-    let mut namespaces = Namespace::DEFAULT.to_vec();
+    let mut default_namespaces = Namespace::DEFAULT.to_vec();
     if with_debug_namespace {
-        namespaces.push(Namespace::Debug)
+        default_namespaces.push(Namespace::Debug)
     }
-    namespaces.push(Namespace::Snapshots);
+    default_namespaces.push(Namespace::Snapshots);
+    let namespaces = api_namespaces(
+        &api_config.web3_json_rpc,
+        default_namespaces,
+        with_debug_namespace,
+        mempool.is_some(),
+    )?;
 
     let last_miniblock_pool = ConnectionPool::singleton(postgres_config.replica_url()?)
         .build()
@@ -1153,11 +1341,15 @@ async fn run_http_api<G: L1GasPriceProvider + Send + Sync + 'static>(
             .with_tree_api(api_config.web3_json_rpc.tree_api_url())
             .with_batch_request_size_limit(api_config.web3_json_rpc.max_batch_request_size())
             .with_response_body_size_limit(api_config.web3_json_rpc.max_response_body_size())
+            .with_client_limits((&api_config.web3_json_rpc).into())
             .with_tx_sender(tx_sender, vm_barrier)
             .enable_api_namespaces(namespaces);
     if with_logs_request_translator_enabled {
         api_builder = api_builder.enable_request_translator();
     }
+    if let Some(mempool) = mempool {
+        api_builder = api_builder.with_mempool(mempool);
+    }
     api_builder.build(stop_receiver).await
 }
 
@@ -1174,6 +1366,8 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
     stop_receiver: watch::Receiver<bool>,
     storage_caches: PostgresStorageCaches,
     with_logs_request_translator_enabled: bool,
+    mempool: Option<MempoolGuard>,
+    sealed_miniblocks_sender: Option<SealedMiniblocksSender>,
 ) -> anyhow::Result<ApiServerHandles> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1190,8 +1384,14 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
         .await
         .context("failed to build last_miniblock_pool")?;
 
-    let mut namespaces = Namespace::DEFAULT.to_vec();
-    namespaces.push(Namespace::Snapshots);
+    let mut default_namespaces = Namespace::DEFAULT.to_vec();
+    default_namespaces.push(Namespace::Snapshots);
+    let namespaces = api_namespaces(
+        &api_config.web3_json_rpc,
+        default_namespaces,
+        state_keeper_config.save_call_traces,
+        mempool.is_some(),
+    )?;
 
     let mut api_builder =
         web3::ApiBuilder::jsonrpc_backend(internal_api.clone(), replica_connection_pool)
@@ -1215,9 +1415,49 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
     if with_logs_request_translator_enabled {
         api_builder = api_builder.enable_request_translator();
     }
+    if let Some(mempool) = mempool {
+        api_builder = api_builder.with_mempool(mempool);
+    }
+    if let Some(sender) = sealed_miniblocks_sender {
+        api_builder = api_builder.with_sealed_miniblocks(sender);
+    }
     api_builder.build(stop_receiver.clone()).await
 }
 
+/// Returns API namespaces specified in the config, or `default_namespaces` if the config doesn't specify them.
+/// Configured namespaces that cannot be served are skipped: `debug` requires call traces to be saved
+/// by the state keeper, and `txpool` requires the state keeper mempool in the same process.
+fn api_namespaces(
+    config: &Web3JsonRpcConfig,
+    default_namespaces: Vec<Namespace>,
+    with_debug_namespace: bool,
+    with_mempool: bool,
+) -> anyhow::Result<Vec<Namespace>> {
+    let Some(names) = &config.api_namespaces else {
+        return Ok(default_namespaces);
+    };
+
+    let mut namespaces = Vec::with_capacity(names.len());
+    for name in names {
+        let namespace: Namespace = name
+            .parse()
+            .with_context(|| format!("invalid API namespace `{name}`"))?;
+        let is_supported = match namespace {
+            Namespace::Debug => with_debug_namespace,
+            Namespace::TxPool => with_mempool,
+            _ => true,
+        };
+        if is_supported {
+            namespaces.push(namespace);
+        } else {
+            tracing::warn!(
+                "API namespace `{name}` is not supported with the current node configuration; skipping it"
+            );
+        }
+    }
+    Ok(namespaces)
+}
+
 async fn circuit_breakers_for_components(
     components: &[Component],
     postgres_config: &PostgresConfig,
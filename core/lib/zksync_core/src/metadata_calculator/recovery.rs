@@ -421,8 +421,24 @@ impl AsyncTreeRecovery {
     }
 }
 
-async fn snapshot_l1_batch(_pool: &ConnectionPool) -> anyhow::Result<Option<L1BatchNumber>> {
-    Ok(None) // FIXME: implement real logic
+/// Returns the L1 batch of the snapshot that Postgres was recovered from, or `None` if Postgres
+/// wasn't recovered from a snapshot.
+async fn snapshot_l1_batch(pool: &ConnectionPool) -> anyhow::Result<Option<L1BatchNumber>> {
+    let mut storage = pool.access_storage().await?;
+    let snapshot_recovery = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .context("failed getting snapshot recovery status")?;
+    let Some(snapshot_recovery) = snapshot_recovery else {
+        return Ok(None);
+    };
+    anyhow::ensure!(
+        snapshot_recovery.is_finished(),
+        "Postgres snapshot recovery for L1 batch #{} is not finished",
+        snapshot_recovery.l1_batch_number
+    );
+    Ok(Some(snapshot_recovery.l1_batch_number))
 }
 
 #[cfg(test)]
@@ -0,0 +1,644 @@
+//! Authentication and rate limiting for the proof data handler API.
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use axum::{
+    body::{Body, Bytes},
+    extract::{ConnectInfo, FromRequest, State},
+    http::{HeaderMap, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use governor::{clock::DefaultClock, state::keyed::DefaultKeyedStateStore, Quota, RateLimiter};
+use zksync_config::configs::{
+    proof_data_handler::ProofDataHandlerAuthMode, ProofDataHandlerConfig,
+};
+use zksync_types::prover_server_api::auth::{
+    verify_request_signature, NONCE_HEADER, PROVER_ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+
+/// Maximum length of a nonce in HMAC-signed requests.
+const MAX_NONCE_LEN: usize = 64;
+
+/// Identity of the prover that has sent a request. `None` if authentication is disabled.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ProverIdentity(pub Option<String>);
+
+impl ProverIdentity {
+    pub fn prover_id(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+}
+
+/// Key used for rate limiting. Before verifying credentials, requests are limited by the client IP address
+/// (so that credential guessing is limited as well); authenticated requests are additionally limited per prover.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum RateLimitKey {
+    /// Authenticated prover.
+    Prover(String),
+    /// IP address of the client. Used for authenticated requests as well if authentication is disabled.
+    Ip(IpAddr),
+    /// Client with an unknown IP address.
+    Unknown,
+}
+
+impl fmt::Display for RateLimitKey {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Prover(prover_id) => write!(formatter, "prover `{prover_id}`"),
+            Self::Ip(ip) => write!(formatter, "IP address {ip}"),
+            Self::Unknown => formatter.write_str("unknown client"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AuthError {
+    #[error("missing or malformed `{0}` header")]
+    MalformedHeader(&'static str),
+    #[error("unknown prover `{0}`")]
+    UnknownProver(String),
+    #[error("invalid credentials for prover `{0}`")]
+    InvalidCredentials(String),
+    #[error("request timestamp is outside the allowed window")]
+    StaleTimestamp,
+    #[error("nonce `{1}` was already used by prover `{0}`")]
+    ReplayedRequest(String, String),
+    #[error("failed reading request body")]
+    Body,
+    #[error("rate limit exceeded for {0}")]
+    RateLimited(RateLimitKey),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        tracing::warn!("Rejected proof data handler request: {self}");
+        let status_code = match &self {
+            Self::Body => StatusCode::BAD_REQUEST,
+            Self::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::UNAUTHORIZED,
+        };
+        (status_code, self.to_string()).into_response()
+    }
+}
+
+type ProverRateLimiter =
+    RateLimiter<RateLimitKey, DefaultKeyedStateStore<RateLimitKey>, DefaultClock>;
+
+/// Nonces of accepted HMAC-signed requests. A nonce is remembered until the timestamp of the request
+/// leaves the allowed window, after which the request would be rejected anyway.
+#[derive(Debug, Default)]
+struct UsedNonces {
+    nonces: HashSet<(String, String)>,
+    /// Nonces ordered by their expiration time (seconds since Unix epoch).
+    expirations: BTreeSet<(u64, (String, String))>,
+}
+
+impl UsedNonces {
+    /// Records a nonce used by the prover. Returns `false` if the nonce was already used.
+    fn insert(&mut self, prover_id: &str, nonce: &str, expires_at: u64, now: u64) -> bool {
+        while let Some((expires_at, _)) = self.expirations.first() {
+            if *expires_at >= now {
+                break;
+            }
+            let (_, key) = self.expirations.pop_first().unwrap();
+            self.nonces.remove(&key);
+        }
+
+        let key = (prover_id.to_owned(), nonce.to_owned());
+        if !self.nonces.insert(key.clone()) {
+            return false;
+        }
+        self.expirations.insert((expires_at, key));
+        true
+    }
+}
+
+/// Authenticates provers according to the configured [`ProofDataHandlerAuthMode`] and applies
+/// rate limits.
+pub(crate) struct ProverAuthenticator {
+    mode: ProofDataHandlerAuthMode,
+    credentials: HashMap<String, String>,
+    max_signature_age: Duration,
+    used_nonces: Mutex<UsedNonces>,
+    rate_limiter: Option<ProverRateLimiter>,
+    ip_rate_limiter: Option<ProverRateLimiter>,
+}
+
+impl fmt::Debug for ProverAuthenticator {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Prover secrets must not be logged.
+        let mut prover_ids: Vec<_> = self.credentials.keys().collect();
+        prover_ids.sort_unstable();
+        formatter
+            .debug_struct("ProverAuthenticator")
+            .field("mode", &self.mode)
+            .field("prover_ids", &prover_ids)
+            .field("max_signature_age", &self.max_signature_age)
+            .field("is_rate_limited", &self.rate_limiter.is_some())
+            .field("is_ip_rate_limited", &self.ip_rate_limiter.is_some())
+            .finish()
+    }
+}
+
+impl ProverAuthenticator {
+    pub fn new(config: &ProofDataHandlerConfig) -> anyhow::Result<Self> {
+        let credentials = config
+            .prover_credentials()
+            .context("invalid prover credentials")?;
+        if config.auth_mode != ProofDataHandlerAuthMode::None {
+            anyhow::ensure!(
+                !credentials.is_empty(),
+                "no prover credentials are provided for auth mode {:?}",
+                config.auth_mode
+            );
+        }
+        let rate_limiter = config
+            .rate_limit_requests_per_minute
+            .map(|limit| RateLimiter::keyed(Quota::per_minute(limit)));
+        let ip_rate_limiter = config
+            .ip_rate_limit_requests_per_minute
+            .map(|limit| RateLimiter::keyed(Quota::per_minute(limit)));
+
+        Ok(Self {
+            mode: config.auth_mode,
+            credentials,
+            max_signature_age: config.max_signature_age(),
+            used_nonces: Mutex::default(),
+            rate_limiter,
+            ip_rate_limiter,
+        })
+    }
+
+    fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        path: &str,
+        body: &[u8],
+        now: Duration,
+    ) -> Result<ProverIdentity, AuthError> {
+        if self.mode == ProofDataHandlerAuthMode::None {
+            return Ok(ProverIdentity(None));
+        }
+
+        let prover_id = header_str(headers, PROVER_ID_HEADER)?;
+        let secret = self
+            .credentials
+            .get(prover_id)
+            .ok_or_else(|| AuthError::UnknownProver(prover_id.to_owned()))?;
+        let is_valid = match self.mode {
+            ProofDataHandlerAuthMode::None => unreachable!(),
+            ProofDataHandlerAuthMode::ApiKey => {
+                let api_key = header_str(headers, "authorization")?
+                    .strip_prefix("Bearer ")
+                    .ok_or(AuthError::MalformedHeader("authorization"))?;
+                constant_time_eq(api_key.as_bytes(), secret.as_bytes())
+            }
+            ProofDataHandlerAuthMode::Hmac => {
+                let timestamp: u64 = header_str(headers, TIMESTAMP_HEADER)?
+                    .parse()
+                    .map_err(|_| AuthError::MalformedHeader(TIMESTAMP_HEADER))?;
+                let timestamp = Duration::from_secs(timestamp);
+                let age = if timestamp > now {
+                    timestamp - now
+                } else {
+                    now - timestamp
+                };
+                if age > self.max_signature_age {
+                    return Err(AuthError::StaleTimestamp);
+                }
+                let nonce = header_str(headers, NONCE_HEADER)?;
+                if nonce.is_empty() || nonce.len() > MAX_NONCE_LEN {
+                    return Err(AuthError::MalformedHeader(NONCE_HEADER));
+                }
+                let signature = header_str(headers, SIGNATURE_HEADER)?;
+                let is_valid = verify_request_signature(
+                    secret,
+                    timestamp.as_secs(),
+                    nonce,
+                    path,
+                    body,
+                    signature,
+                );
+                // Only nonces of authentic requests are recorded, so that they cannot be exhausted by third parties.
+                if is_valid {
+                    let expires_at = (timestamp + self.max_signature_age).as_secs();
+                    let mut used_nonces = self.used_nonces.lock().unwrap();
+                    if !used_nonces.insert(prover_id, nonce, expires_at, now.as_secs()) {
+                        return Err(AuthError::ReplayedRequest(
+                            prover_id.to_owned(),
+                            nonce.to_owned(),
+                        ));
+                    }
+                }
+                is_valid
+            }
+        };
+
+        if is_valid {
+            Ok(ProverIdentity(Some(prover_id.to_owned())))
+        } else {
+            Err(AuthError::InvalidCredentials(prover_id.to_owned()))
+        }
+    }
+
+    fn check_key(
+        rate_limiter: Option<&ProverRateLimiter>,
+        key: RateLimitKey,
+    ) -> Result<(), AuthError> {
+        let Some(rate_limiter) = rate_limiter else {
+            return Ok(());
+        };
+        rate_limiter
+            .check_key(&key)
+            .map_err(|_| AuthError::RateLimited(key))
+    }
+
+    /// Checks the rate limit for a request before authenticating it.
+    fn check_ip_rate_limit(&self, peer_ip: Option<IpAddr>) -> Result<(), AuthError> {
+        let key = peer_ip.map_or(RateLimitKey::Unknown, RateLimitKey::Ip);
+        Self::check_key(self.ip_rate_limiter.as_ref(), key)
+    }
+
+    /// Checks the rate limit for an authenticated request.
+    fn check_rate_limit(
+        &self,
+        identity: &ProverIdentity,
+        peer_ip: Option<IpAddr>,
+    ) -> Result<(), AuthError> {
+        let key = match identity.prover_id() {
+            Some(prover_id) => RateLimitKey::Prover(prover_id.to_owned()),
+            None => peer_ip.map_or(RateLimitKey::Unknown, RateLimitKey::Ip),
+        };
+        Self::check_key(self.rate_limiter.as_ref(), key)
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, AuthError> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(AuthError::MalformedHeader(name))
+}
+
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len()
+        && lhs
+            .iter()
+            .zip(rhs)
+            .fold(0_u8, |acc, (&lhs, &rhs)| acc | (lhs ^ rhs))
+            == 0
+}
+
+/// Axum middleware authenticating and rate-limiting requests. On success, the [`ProverIdentity`]
+/// is added to request extensions.
+pub(crate) async fn authenticate(
+    State(authenticator): State<Arc<ProverAuthenticator>>,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Result<Response, AuthError> {
+    let (mut parts, body) = request.into_parts();
+    let peer_ip = parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    authenticator.check_ip_rate_limit(peer_ip)?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("invalid system time");
+    let (identity, body) = if authenticator.mode == ProofDataHandlerAuthMode::Hmac {
+        // The signature covers the request body, so we need to buffer it.
+        let body = Bytes::from_request(Request::new(body), &())
+            .await
+            .map_err(|_| AuthError::Body)?;
+        let identity = authenticator.authenticate(&parts.headers, parts.uri.path(), &body, now)?;
+        (identity, Body::from(body))
+    } else {
+        let identity = authenticator.authenticate(&parts.headers, parts.uri.path(), &[], now)?;
+        (identity, body)
+    };
+
+    authenticator.check_rate_limit(&identity, peer_ip)?;
+
+    parts.extensions.insert(identity);
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use axum::http::{HeaderName, HeaderValue};
+    use zksync_config::configs::proof_data_handler::ProtocolVersionLoadingMode;
+    use zksync_types::prover_server_api::auth::sign_request;
+
+    use super::*;
+
+    const NOW: Duration = Duration::from_secs(1_700_000_000);
+
+    fn mock_config(auth_mode: ProofDataHandlerAuthMode) -> ProofDataHandlerConfig {
+        ProofDataHandlerConfig {
+            http_port: 3320,
+            proof_generation_timeout_in_secs: 18000,
+            protocol_version_loading_mode: ProtocolVersionLoadingMode::FromEnvVar,
+            fri_protocol_version_id: 2,
+            auth_mode,
+            prover_credentials: vec![
+                "prover-1:secret-1".to_owned(),
+                "prover-2:secret-2".to_owned(),
+            ],
+            rate_limit_requests_per_minute: None,
+            ip_rate_limit_requests_per_minute: None,
+            max_signature_age_secs: 300,
+        }
+    }
+
+    fn headers(entries: &[(&'static str, &str)]) -> HeaderMap {
+        entries
+            .iter()
+            .map(|&(name, value)| {
+                let name = HeaderName::from_static(name);
+                (name, HeaderValue::from_str(value).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn authentication_is_skipped_if_disabled() {
+        let authenticator =
+            ProverAuthenticator::new(&mock_config(ProofDataHandlerAuthMode::None)).unwrap();
+        let identity = authenticator
+            .authenticate(&HeaderMap::new(), "/proof_generation_data", &[], NOW)
+            .unwrap();
+        assert_eq!(identity, ProverIdentity(None));
+    }
+
+    #[test]
+    fn authentication_requires_credentials() {
+        let mut config = mock_config(ProofDataHandlerAuthMode::ApiKey);
+        config.prover_credentials.clear();
+        ProverAuthenticator::new(&config).unwrap_err();
+    }
+
+    #[test]
+    fn api_key_authentication() {
+        let authenticator =
+            ProverAuthenticator::new(&mock_config(ProofDataHandlerAuthMode::ApiKey)).unwrap();
+        let path = "/proof_generation_data";
+
+        let valid_headers = headers(&[
+            (PROVER_ID_HEADER, "prover-1"),
+            ("authorization", "Bearer secret-1"),
+        ]);
+        let identity = authenticator
+            .authenticate(&valid_headers, path, &[], NOW)
+            .unwrap();
+        assert_eq!(identity.prover_id(), Some("prover-1"));
+
+        let wrong_key_headers = headers(&[
+            (PROVER_ID_HEADER, "prover-1"),
+            ("authorization", "Bearer secret-2"),
+        ]);
+        let err = authenticator
+            .authenticate(&wrong_key_headers, path, &[], NOW)
+            .unwrap_err();
+        assert!(matches!(err, AuthError::InvalidCredentials(_)), "{err:?}");
+
+        let unknown_prover_headers = headers(&[
+            (PROVER_ID_HEADER, "prover-3"),
+            ("authorization", "Bearer secret-1"),
+        ]);
+        let err = authenticator
+            .authenticate(&unknown_prover_headers, path, &[], NOW)
+            .unwrap_err();
+        assert!(matches!(err, AuthError::UnknownProver(_)), "{err:?}");
+
+        let missing_key_headers = headers(&[(PROVER_ID_HEADER, "prover-1")]);
+        let err = authenticator
+            .authenticate(&missing_key_headers, path, &[], NOW)
+            .unwrap_err();
+        assert!(matches!(err, AuthError::MalformedHeader(_)), "{err:?}");
+    }
+
+    fn hmac_headers(
+        prover_id: &str,
+        secret: &str,
+        nonce: &str,
+        path: &str,
+        body: &[u8],
+    ) -> HeaderMap {
+        headers_at(NOW, prover_id, secret, nonce, path, body)
+    }
+
+    fn headers_at(
+        now: Duration,
+        prover_id: &str,
+        secret: &str,
+        nonce: &str,
+        path: &str,
+        body: &[u8],
+    ) -> HeaderMap {
+        let timestamp = now.as_secs().to_string();
+        let signature = sign_request(secret, now.as_secs(), nonce, path, body);
+        headers(&[
+            (PROVER_ID_HEADER, prover_id),
+            (TIMESTAMP_HEADER, &timestamp),
+            (NONCE_HEADER, nonce),
+            (SIGNATURE_HEADER, &signature),
+        ])
+    }
+
+    #[test]
+    fn hmac_authentication() {
+        let authenticator =
+            ProverAuthenticator::new(&mock_config(ProofDataHandlerAuthMode::Hmac)).unwrap();
+        let path = "/submit_proof/1";
+        let body = br#""SkippedProofGeneration""#;
+
+        let valid_headers = hmac_headers("prover-2", "secret-2", "0", path, body);
+        let identity = authenticator
+            .authenticate(&valid_headers, path, body, NOW)
+            .unwrap();
+        assert_eq!(identity.prover_id(), Some("prover-2"));
+        // Small clock skew is allowed.
+        let valid_headers = hmac_headers("prover-2", "secret-2", "1", path, body);
+        authenticator
+            .authenticate(&valid_headers, path, body, NOW + Duration::from_secs(10))
+            .unwrap();
+
+        let valid_headers = hmac_headers("prover-2", "secret-2", "2", path, body);
+        let err = authenticator
+            .authenticate(&valid_headers, path, b"{}", NOW)
+            .unwrap_err();
+        assert!(matches!(err, AuthError::InvalidCredentials(_)), "{err:?}");
+        let err = authenticator
+            .authenticate(&valid_headers, "/submit_proof/2", body, NOW)
+            .unwrap_err();
+        assert!(matches!(err, AuthError::InvalidCredentials(_)), "{err:?}");
+        let err = authenticator
+            .authenticate(&valid_headers, path, body, NOW + Duration::from_secs(3_600))
+            .unwrap_err();
+        assert!(matches!(err, AuthError::StaleTimestamp), "{err:?}");
+
+        let mut wrong_prover_headers = valid_headers.clone();
+        wrong_prover_headers.insert(PROVER_ID_HEADER, HeaderValue::from_static("prover-1"));
+        let err = authenticator
+            .authenticate(&wrong_prover_headers, path, body, NOW)
+            .unwrap_err();
+        assert!(matches!(err, AuthError::InvalidCredentials(_)), "{err:?}");
+
+        let mut wrong_nonce_headers = valid_headers.clone();
+        wrong_nonce_headers.insert(NONCE_HEADER, HeaderValue::from_static("3"));
+        let err = authenticator
+            .authenticate(&wrong_nonce_headers, path, body, NOW)
+            .unwrap_err();
+        assert!(matches!(err, AuthError::InvalidCredentials(_)), "{err:?}");
+
+        let mut missing_nonce_headers = valid_headers;
+        missing_nonce_headers.remove(NONCE_HEADER);
+        let err = authenticator
+            .authenticate(&missing_nonce_headers, path, body, NOW)
+            .unwrap_err();
+        assert!(
+            matches!(err, AuthError::MalformedHeader(NONCE_HEADER)),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn replayed_hmac_requests_are_rejected() {
+        let authenticator =
+            ProverAuthenticator::new(&mock_config(ProofDataHandlerAuthMode::Hmac)).unwrap();
+        let path = "/proof_generation_data";
+        let body = b"{}";
+
+        let headers = hmac_headers("prover-1", "secret-1", "nonce", path, body);
+        authenticator
+            .authenticate(&headers, path, body, NOW)
+            .unwrap();
+        let err = authenticator
+            .authenticate(&headers, path, body, NOW + Duration::from_secs(1))
+            .unwrap_err();
+        assert!(
+            matches!(&err, AuthError::ReplayedRequest(id, nonce) if id == "prover-1" && nonce == "nonce"),
+            "{err:?}"
+        );
+
+        // Nonces are tracked per prover.
+        let other_prover_headers = hmac_headers("prover-2", "secret-2", "nonce", path, body);
+        authenticator
+            .authenticate(&other_prover_headers, path, body, NOW)
+            .unwrap();
+        // Requests with invalid signatures don't consume nonces.
+        let forged_headers = hmac_headers("prover-1", "secret-2", "other-nonce", path, body);
+        authenticator
+            .authenticate(&forged_headers, path, body, NOW)
+            .unwrap_err();
+        let headers = hmac_headers("prover-1", "secret-1", "other-nonce", path, body);
+        authenticator
+            .authenticate(&headers, path, body, NOW)
+            .unwrap();
+
+        // Nonces are forgotten once the request timestamp leaves the allowed window.
+        let later = NOW + Duration::from_secs(301);
+        let later_headers = headers_at(later, "prover-1", "secret-1", "third-nonce", path, body);
+        authenticator
+            .authenticate(&later_headers, path, body, later)
+            .unwrap();
+        assert_eq!(authenticator.used_nonces.lock().unwrap().nonces.len(), 1);
+    }
+
+    #[test]
+    fn requests_are_rate_limited_per_ip_before_authentication() {
+        let mut config = mock_config(ProofDataHandlerAuthMode::ApiKey);
+        config.ip_rate_limit_requests_per_minute = NonZeroU32::new(1);
+        let authenticator = ProverAuthenticator::new(&config).unwrap();
+        let peer_ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        authenticator.check_ip_rate_limit(Some(peer_ip)).unwrap();
+        let err = authenticator
+            .check_ip_rate_limit(Some(peer_ip))
+            .unwrap_err();
+        assert!(
+            matches!(err, AuthError::RateLimited(RateLimitKey::Ip(ip)) if ip == peer_ip),
+            "{err:?}"
+        );
+
+        let other_ip: IpAddr = "10.0.0.2".parse().unwrap();
+        authenticator.check_ip_rate_limit(Some(other_ip)).unwrap();
+        // Prover limits are not configured.
+        let identity = ProverIdentity(Some("prover-1".to_owned()));
+        for _ in 0..10 {
+            authenticator
+                .check_rate_limit(&identity, Some(peer_ip))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn authenticated_requests_are_rate_limited_per_prover() {
+        let mut config = mock_config(ProofDataHandlerAuthMode::ApiKey);
+        config.rate_limit_requests_per_minute = NonZeroU32::new(2);
+        let authenticator = ProverAuthenticator::new(&config).unwrap();
+        let peer_ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let first_prover = ProverIdentity(Some("prover-1".to_owned()));
+        let second_prover = ProverIdentity(Some("prover-2".to_owned()));
+
+        authenticator
+            .check_rate_limit(&first_prover, Some(peer_ip))
+            .unwrap();
+        authenticator
+            .check_rate_limit(&first_prover, Some(peer_ip))
+            .unwrap();
+        let err = authenticator
+            .check_rate_limit(&first_prover, Some(peer_ip))
+            .unwrap_err();
+        assert!(
+            matches!(&err, AuthError::RateLimited(RateLimitKey::Prover(id)) if id == "prover-1"),
+            "{err:?}"
+        );
+        // Provers sharing an IP address have separate budgets.
+        authenticator
+            .check_rate_limit(&second_prover, Some(peer_ip))
+            .unwrap();
+        // IP limits are not configured.
+        authenticator.check_ip_rate_limit(Some(peer_ip)).unwrap();
+    }
+
+    #[test]
+    fn authenticated_requests_are_rate_limited_per_ip_without_authentication() {
+        let mut config = mock_config(ProofDataHandlerAuthMode::None);
+        config.rate_limit_requests_per_minute = NonZeroU32::new(1);
+        let authenticator = ProverAuthenticator::new(&config).unwrap();
+        let peer_ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let identity = ProverIdentity(None);
+
+        authenticator
+            .check_rate_limit(&identity, Some(peer_ip))
+            .unwrap();
+        let err = authenticator
+            .check_rate_limit(&identity, Some(peer_ip))
+            .unwrap_err();
+        assert!(
+            matches!(err, AuthError::RateLimited(RateLimitKey::Ip(ip)) if ip == peer_ip),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn debug_output_does_not_contain_secrets() {
+        let authenticator =
+            ProverAuthenticator::new(&mock_config(ProofDataHandlerAuthMode::Hmac)).unwrap();
+        let debug_output = format!("{authenticator:?}");
+        assert!(debug_output.contains("prover-1"), "{debug_output}");
+        assert!(!debug_output.contains("secret"), "{debug_output}");
+    }
+}
@@ -1,7 +1,7 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 use anyhow::Context as _;
-use axum::{extract::Path, routing::post, Json, Router};
+use axum::{extract::Path, middleware, routing::post, Extension, Json, Router};
 use tokio::sync::watch;
 use zksync_config::{
     configs::{proof_data_handler::ProtocolVersionLoadingMode, ProofDataHandlerConfig},
@@ -15,8 +15,12 @@ use zksync_types::{
     H256,
 };
 
-use crate::proof_data_handler::request_processor::RequestProcessor;
+use self::{
+    auth::{ProverAuthenticator, ProverIdentity},
+    request_processor::RequestProcessor,
+};
 
+mod auth;
 mod request_processor;
 
 fn fri_l1_verifier_config(contracts_config: &ContractsConfig) -> L1VerifierConfig {
@@ -40,6 +44,12 @@ pub(crate) async fn run_server(
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
     tracing::debug!("Starting proof data handler server on {bind_address}");
+    let authenticator =
+        ProverAuthenticator::new(&config).context("failed initializing prover authentication")?;
+    tracing::info!(
+        "Using {:?} authentication mode for proof data handler",
+        config.auth_mode
+    );
     let l1_verifier_config: Option<L1VerifierConfig> = match config.protocol_version_loading_mode {
         ProtocolVersionLoadingMode::FromDb => None,
         ProtocolVersionLoadingMode::FromEnvVar => Some(fri_l1_verifier_config(&contracts_config)),
@@ -53,9 +63,10 @@ pub(crate) async fn run_server(
             post(
                 // we use post method because the returned data is not idempotent,
                 // i.e we return different result on each call.
-                move |payload: Json<ProofGenerationDataRequest>| async move {
+                move |Extension(prover): Extension<ProverIdentity>,
+                      payload: Json<ProofGenerationDataRequest>| async move {
                     get_proof_gen_processor
-                        .get_proof_generation_data(payload)
+                        .get_proof_generation_data(prover, payload)
                         .await
                 },
             ),
@@ -63,16 +74,22 @@ pub(crate) async fn run_server(
         .route(
             "/submit_proof/:l1_batch_number",
             post(
-                move |l1_batch_number: Path<u32>, payload: Json<SubmitProofRequest>| async move {
+                move |Extension(prover): Extension<ProverIdentity>,
+                      l1_batch_number: Path<u32>,
+                      payload: Json<SubmitProofRequest>| async move {
                     submit_proof_processor
-                        .submit_proof(l1_batch_number, payload)
+                        .submit_proof(prover, l1_batch_number, payload)
                         .await
                 },
             ),
-        );
+        )
+        .layer(middleware::from_fn_with_state(
+            Arc::new(authenticator),
+            auth::authenticate,
+        ));
 
     axum::Server::bind(&bind_address)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(async move {
             if stop_receiver.changed().await.is_err() {
                 tracing::warn!("Stop signal sender for proof data handler server was dropped without sending a signal");
@@ -9,7 +9,7 @@ use axum::{
 use zksync_config::configs::{
     proof_data_handler::ProtocolVersionLoadingMode, ProofDataHandlerConfig,
 };
-use zksync_dal::{ConnectionPool, SqlxError};
+use zksync_dal::{proof_generation_dal::ProverApiAction, ConnectionPool, SqlxError};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_types::{
     commitment::serialize_commitments,
@@ -23,6 +23,8 @@ use zksync_types::{
 };
 use zksync_utils::u256_to_h256;
 
+use super::auth::ProverIdentity;
+
 #[derive(Clone)]
 pub(crate) struct RequestProcessor {
     blob_store: Arc<dyn ObjectStore>,
@@ -80,9 +82,14 @@ impl RequestProcessor {
 
     pub(crate) async fn get_proof_generation_data(
         &self,
+        prover: ProverIdentity,
         request: Json<ProofGenerationDataRequest>,
     ) -> Result<Json<ProofGenerationDataResponse>, RequestProcessorError> {
-        tracing::info!("Received request for proof generation data: {:?}", request);
+        tracing::info!(
+            "Received request for proof generation data from prover {:?}: {:?}",
+            prover.prover_id(),
+            request
+        );
 
         let l1_batch_number_result = self
             .pool
@@ -104,6 +111,19 @@ impl RequestProcessor {
             .await
             .map_err(RequestProcessorError::ObjectStore)?;
 
+        self.pool
+            .access_storage()
+            .await
+            .unwrap()
+            .proof_generation_dal()
+            .insert_prover_api_audit_record(
+                prover.prover_id(),
+                ProverApiAction::FetchedProofGenerationData,
+                l1_batch_number,
+            )
+            .await
+            .map_err(RequestProcessorError::Sqlx)?;
+
         let fri_protocol_version_id =
             FriProtocolVersionId::try_from(self.config.fri_protocol_version_id)
                 .expect("Invalid FRI protocol version id");
@@ -132,12 +152,17 @@ impl RequestProcessor {
 
     pub(crate) async fn submit_proof(
         &self,
+        prover: ProverIdentity,
         Path(l1_batch_number): Path<u32>,
         Json(payload): Json<SubmitProofRequest>,
     ) -> Result<Json<SubmitProofResponse>, RequestProcessorError> {
-        tracing::info!("Received proof for block number: {:?}", l1_batch_number);
+        tracing::info!(
+            "Received proof for block number {:?} from prover {:?}",
+            l1_batch_number,
+            prover.prover_id()
+        );
         let l1_batch_number = L1BatchNumber(l1_batch_number);
-        match payload {
+        let action = match payload {
             SubmitProofRequest::Proof(proof) => {
                 let blob_url = self
                     .blob_store
@@ -220,6 +245,7 @@ impl RequestProcessor {
                     .save_proof_artifacts_metadata(l1_batch_number, &blob_url)
                     .await
                     .map_err(RequestProcessorError::Sqlx)?;
+                ProverApiAction::SubmittedProof
             }
             SubmitProofRequest::SkippedProofGeneration => {
                 self.pool
@@ -230,8 +256,18 @@ impl RequestProcessor {
                     .mark_proof_generation_job_as_skipped(l1_batch_number)
                     .await
                     .map_err(RequestProcessorError::Sqlx)?;
+                ProverApiAction::SkippedProofGeneration
             }
-        }
+        };
+
+        self.pool
+            .access_storage()
+            .await
+            .unwrap()
+            .proof_generation_dal()
+            .insert_prover_api_audit_record(prover.prover_id(), action, l1_batch_number)
+            .await
+            .map_err(RequestProcessorError::Sqlx)?;
 
         Ok(Json(SubmitProofResponse::Success))
     }
@@ -0,0 +1,23 @@
+//! Metrics for the snapshot applier.
+
+use std::time::Duration;
+
+use vise::{Buckets, Gauge, Histogram, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "snapshots_applier")]
+pub(super) struct SnapshotsApplierMetrics {
+    /// Total number of storage logs chunks in the recovered snapshot.
+    pub storage_logs_chunks_count: Gauge<usize>,
+    /// Number of storage logs chunks left to process.
+    pub storage_logs_chunks_left_to_process: Gauge<usize>,
+    /// Latency of preparing Postgres for recovery (inserting headers, factory deps etc.).
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub initial_stage_duration: Histogram<Duration>,
+    /// Latency of processing a single storage logs chunk.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub storage_logs_chunk_duration: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<SnapshotsApplierMetrics> = vise::Global::new();
@@ -0,0 +1,570 @@
+//! Logic for applying application-level snapshots to Postgres storage.
+//!
+//! A snapshot produced by the snapshots creator consists of a header (served by the main node via
+//! the `snapshots` JSON-RPC namespace) and storage logs / factory deps stored in an object store. The applier
+//! loads this data into Postgres so that the node can continue syncing from the snapshot miniblock
+//! without having to re-execute the entire chain history. The Merkle tree and RocksDB state keeper cache
+//! are recovered afterwards from Postgres data by the corresponding components.
+//!
+//! If the newest snapshot is a delta one, the applier applies the entire chain of snapshots starting
+//! from the closest full snapshot. Snapshots in the chain are applied sequentially, so that storage logs
+//! from later snapshots overwrite ones from earlier snapshots.
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use futures::{stream, StreamExt, TryStreamExt};
+use multivm::vm_latest::utils::fee::derive_base_fee_and_gas_per_pubdata;
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    api::{self, en::SyncBlock},
+    block::{BlockGasCount, MiniblockHeader},
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotRecoveryStatus,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber, MiniblockNumber, ProtocolVersionId, StorageLog, H256,
+};
+use zksync_utils::bytecode::hash_bytecode;
+use zksync_web3_decl::{
+    jsonrpsee::http_client::HttpClient,
+    namespaces::{EnNamespaceClient, SnapshotsNamespaceClient, ZksNamespaceClient},
+};
+
+use self::metrics::METRICS;
+pub use self::verifier::{
+    ChunkFailure, ChunkVerificationError, SnapshotVerificationReport, SnapshotVerifier,
+};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+mod verifier;
+
+/// Main node API used by [`SnapshotsApplier`].
+#[async_trait]
+pub trait SnapshotsApplierMainNodeClient: fmt::Debug + Send + Sync {
+    async fn fetch_l2_block(&self, number: MiniblockNumber) -> anyhow::Result<Option<SyncBlock>>;
+
+    async fn fetch_l1_batch_root_hash(&self, number: L1BatchNumber)
+        -> anyhow::Result<Option<H256>>;
+
+    async fn fetch_newest_snapshot(&self) -> anyhow::Result<Option<SnapshotHeader>>;
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>>;
+
+    async fn fetch_protocol_version(
+        &self,
+        id: ProtocolVersionId,
+    ) -> anyhow::Result<Option<api::ProtocolVersion>>;
+}
+
+#[async_trait]
+impl SnapshotsApplierMainNodeClient for HttpClient {
+    async fn fetch_l2_block(&self, number: MiniblockNumber) -> anyhow::Result<Option<SyncBlock>> {
+        Ok(self.sync_l2_block(number, false).await?)
+    }
+
+    async fn fetch_l1_batch_root_hash(
+        &self,
+        number: L1BatchNumber,
+    ) -> anyhow::Result<Option<H256>> {
+        let details = self.get_l1_batch_details(number).await?;
+        Ok(details.and_then(|details| details.base.root_hash))
+    }
+
+    async fn fetch_newest_snapshot(&self) -> anyhow::Result<Option<SnapshotHeader>> {
+        let snapshots = self.get_all_snapshots().await?;
+        let Some(newest_snapshot) = snapshots.snapshots_l1_batch_numbers.into_iter().max() else {
+            return Ok(None);
+        };
+        self.fetch_snapshot(newest_snapshot).await
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>> {
+        Ok(self
+            .get_snapshot_by_l1_batch_number(l1_batch_number)
+            .await?)
+    }
+
+    async fn fetch_protocol_version(
+        &self,
+        id: ProtocolVersionId,
+    ) -> anyhow::Result<Option<api::ProtocolVersion>> {
+        Ok(self.get_protocol_version(Some(id as u16)).await?)
+    }
+}
+
+/// Outcome of [`SnapshotsApplier::load_snapshot()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotRecoveryOutcome {
+    /// Postgres already contains data not originating from a snapshot (e.g., the node was synced from genesis),
+    /// so recovery was skipped.
+    Skipped,
+    /// Snapshot for the specified L1 batch was (possibly previously) fully applied to Postgres.
+    Recovered(L1BatchNumber),
+}
+
+/// Applies the newest snapshot available on the main node to Postgres.
+///
+/// Recovery is resumable: Postgres headers, factory deps and the recovery status are inserted
+/// in a single transaction, after which storage logs chunks are processed independently and are marked
+/// as processed in the recovery status. If the applier is interrupted, it continues from the unprocessed chunks
+/// of the same snapshot on restart. For a chain of delta snapshots, the recovery status tracks chunks
+/// of all snapshots in the chain in the order of application.
+#[derive(Debug)]
+pub struct SnapshotsApplier {
+    connection_pool: ConnectionPool,
+    main_node_client: Box<dyn SnapshotsApplierMainNodeClient>,
+    blob_store: Arc<dyn ObjectStore>,
+}
+
+impl SnapshotsApplier {
+    pub fn new(
+        connection_pool: ConnectionPool,
+        main_node_client: Box<dyn SnapshotsApplierMainNodeClient>,
+        blob_store: Arc<dyn ObjectStore>,
+    ) -> Self {
+        Self {
+            connection_pool,
+            main_node_client,
+            blob_store,
+        }
+    }
+
+    /// Loads the snapshot into Postgres, or resumes previously started recovery.
+    pub async fn load_snapshot(&self) -> anyhow::Result<SnapshotRecoveryOutcome> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("snapshots_applier")
+            .await?;
+        let applied_status = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await
+            .context("failed getting snapshot recovery status")?;
+
+        let (status, chain) = match applied_status {
+            Some(status) if status.is_finished() => {
+                tracing::info!(
+                    "Snapshot for L1 batch #{} is already applied",
+                    status.l1_batch_number
+                );
+                return Ok(SnapshotRecoveryOutcome::Recovered(status.l1_batch_number));
+            }
+            Some(status) => {
+                tracing::info!(
+                    "Resuming recovery from snapshot for L1 batch #{}",
+                    status.l1_batch_number
+                );
+                let header = self
+                    .main_node_client
+                    .fetch_snapshot(status.l1_batch_number)
+                    .await?
+                    .with_context(|| {
+                        format!(
+                            "snapshot for L1 batch #{} being recovered is missing on the main node",
+                            status.l1_batch_number
+                        )
+                    })?;
+                let chain = self.fetch_snapshot_chain(header).await?;
+                let chunk_count: usize = chain
+                    .iter()
+                    .map(|snapshot| snapshot.storage_logs_chunks.len())
+                    .sum();
+                anyhow::ensure!(
+                    chunk_count == status.storage_logs_chunks_processed.len(),
+                    "number of storage logs chunks in the snapshot chain ({chunk_count}) differs from the one \
+                     in recovery status ({})",
+                    status.storage_logs_chunks_processed.len()
+                );
+                (status, chain)
+            }
+            None => {
+                if !storage.blocks_dal().is_genesis_needed().await? {
+                    tracing::info!(
+                        "Postgres contains data not originating from a snapshot; skipping snapshot recovery"
+                    );
+                    return Ok(SnapshotRecoveryOutcome::Skipped);
+                }
+
+                let header = self
+                    .main_node_client
+                    .fetch_newest_snapshot()
+                    .await?
+                    .context("main node does not have any snapshots")?;
+                tracing::info!(
+                    "Recovering from snapshot for L1 batch #{} (miniblock #{}) with {} storage logs chunks",
+                    header.l1_batch_number,
+                    header.miniblock_number,
+                    header.storage_logs_chunks.len()
+                );
+                let chain = self.fetch_snapshot_chain(header).await?;
+                let stage_latency = METRICS.initial_stage_duration.start();
+                let status = self.prepare_storage(&mut storage, &chain).await?;
+                let stage_latency = stage_latency.observe();
+                tracing::info!("Prepared Postgres for snapshot recovery in {stage_latency:?}");
+                (status, chain)
+            }
+        };
+        drop(storage);
+
+        self.recover_storage_logs(&status, &chain).await?;
+        Ok(SnapshotRecoveryOutcome::Recovered(status.l1_batch_number))
+    }
+
+    /// Fetches the chain of snapshots ending at the specified one. The chain starts from a full snapshot
+    /// and is ordered by L1 batch number; for a full snapshot, the chain consists of the snapshot itself.
+    async fn fetch_snapshot_chain(
+        &self,
+        header: SnapshotHeader,
+    ) -> anyhow::Result<Vec<SnapshotHeader>> {
+        let mut chain = vec![header];
+        while let Some(parent_l1_batch_number) = chain.last().unwrap().parent_l1_batch_number {
+            let l1_batch_number = chain.last().unwrap().l1_batch_number;
+            anyhow::ensure!(
+                parent_l1_batch_number < l1_batch_number,
+                "snapshot for L1 batch #{l1_batch_number} has parent snapshot for non-preceding \
+                 L1 batch #{parent_l1_batch_number}"
+            );
+            let parent = self
+                .main_node_client
+                .fetch_snapshot(parent_l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "parent snapshot for L1 batch #{parent_l1_batch_number} is missing on the main node"
+                    )
+                })?;
+            chain.push(parent);
+        }
+        chain.reverse();
+
+        if chain.len() > 1 {
+            tracing::info!(
+                "Snapshot for L1 batch #{} is a delta one; applying chain of {} snapshots starting from \
+                 full snapshot for L1 batch #{}",
+                chain.last().unwrap().l1_batch_number,
+                chain.len(),
+                chain[0].l1_batch_number
+            );
+        }
+        Ok(chain)
+    }
+
+    /// Inserts the snapshot miniblock and L1 batch headers, factory deps and the initial recovery status
+    /// into Postgres in a single transaction. Headers are taken from the last snapshot in the `chain`,
+    /// while factory deps are collected from all snapshots in it.
+    async fn prepare_storage(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        chain: &[SnapshotHeader],
+    ) -> anyhow::Result<SnapshotRecoveryStatus> {
+        let header = chain.last().context("snapshot chain is empty")?;
+        let l1_batch_number = header.l1_batch_number;
+        let miniblock_number = header.miniblock_number;
+        let l1_batch = &header.last_l1_batch_with_metadata;
+        for snapshot in chain {
+            let snapshot_l1_batch_number = snapshot.l1_batch_number;
+            anyhow::ensure!(
+                snapshot.last_l1_batch_with_metadata.header.number == snapshot_l1_batch_number,
+                "snapshot header is inconsistent: L1 batch #{} is provided for snapshot \
+                 for L1 batch #{snapshot_l1_batch_number}",
+                snapshot.last_l1_batch_with_metadata.header.number
+            );
+            for (i, chunk) in snapshot.storage_logs_chunks.iter().enumerate() {
+                anyhow::ensure!(
+                    chunk.chunk_id == i as u64,
+                    "storage logs chunks in header of snapshot for L1 batch #{snapshot_l1_batch_number} \
+                     are not numbered sequentially: chunk #{i} has ID {}",
+                    chunk.chunk_id
+                );
+            }
+        }
+
+        let miniblock = self
+            .main_node_client
+            .fetch_l2_block(miniblock_number)
+            .await?
+            .with_context(|| {
+                format!("miniblock #{miniblock_number} is missing on the main node")
+            })?;
+        anyhow::ensure!(
+            miniblock.l1_batch_number == l1_batch_number,
+            "miniblock #{miniblock_number} belongs to L1 batch #{} on the main node, \
+             while the snapshot is for L1 batch #{l1_batch_number}",
+            miniblock.l1_batch_number
+        );
+        let miniblock_hash = miniblock.hash.with_context(|| {
+            format!("main node returned miniblock #{miniblock_number} without hash")
+        })?;
+
+        let previous_root_hash = if l1_batch_number == L1BatchNumber(0) {
+            H256::zero()
+        } else {
+            let previous_l1_batch = l1_batch_number - 1;
+            self.main_node_client
+                .fetch_l1_batch_root_hash(previous_l1_batch)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "root hash for L1 batch #{previous_l1_batch} is missing on the main node"
+                    )
+                })?
+        };
+
+        let protocol_version_id = miniblock.protocol_version;
+        let protocol_version = self
+            .main_node_client
+            .fetch_protocol_version(protocol_version_id)
+            .await?
+            .with_context(|| {
+                format!("protocol version {protocol_version_id:?} is missing on the main node")
+            })?;
+
+        let mut factory_deps = HashMap::new();
+        for snapshot in chain {
+            let snapshot_factory_deps: SnapshotFactoryDependencies = self
+                .blob_store
+                .get(snapshot.l1_batch_number)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed fetching factory dependencies for snapshot for L1 batch #{} from the object store",
+                        snapshot.l1_batch_number
+                    )
+                })?;
+            factory_deps.extend(
+                snapshot_factory_deps
+                    .factory_deps
+                    .into_iter()
+                    .map(|dep| (hash_bytecode(&dep.bytecode), dep.bytecode)),
+            );
+        }
+
+        let (base_fee_per_gas, _) = derive_base_fee_and_gas_per_pubdata(
+            miniblock.l1_gas_price,
+            miniblock.l2_fair_gas_price,
+        );
+        let miniblock_header = MiniblockHeader {
+            number: miniblock_number,
+            timestamp: miniblock.timestamp,
+            hash: miniblock_hash,
+            // Transactions are not a part of the snapshot.
+            l1_tx_count: 0,
+            l2_tx_count: 0,
+            base_fee_per_gas,
+            l1_gas_price: miniblock.l1_gas_price,
+            l2_fair_gas_price: miniblock.l2_fair_gas_price,
+            base_system_contracts_hashes: miniblock.base_system_contracts_hashes,
+            protocol_version: Some(protocol_version_id),
+            virtual_blocks: miniblock.virtual_blocks.unwrap_or(0),
+        };
+        let chunk_count = chain
+            .iter()
+            .map(|snapshot| snapshot.storage_logs_chunks.len())
+            .sum();
+        let status = SnapshotRecoveryStatus {
+            l1_batch_number,
+            l1_batch_root_hash: l1_batch.metadata.root_hash,
+            miniblock_number,
+            miniblock_hash,
+            storage_logs_chunks_processed: vec![false; chunk_count],
+        };
+
+        let mut transaction = storage.start_transaction().await?;
+        if transaction
+            .protocol_versions_dal()
+            .get_protocol_version(protocol_version_id)
+            .await
+            .is_none()
+        {
+            transaction
+                .protocol_versions_dal()
+                .save_protocol_version(
+                    protocol_version_id,
+                    protocol_version.timestamp,
+                    protocol_version.verification_keys_hashes,
+                    protocol_version.base_system_contracts,
+                    // Verifier is not used in the external node, so we can pass an empty
+                    Default::default(),
+                    protocol_version.l2_system_upgrade_tx_hash,
+                )
+                .await;
+        }
+
+        transaction
+            .blocks_dal()
+            .insert_miniblock(&miniblock_header)
+            .await
+            .context("failed inserting snapshot miniblock header")?;
+        transaction
+            .blocks_dal()
+            .insert_l1_batch(&l1_batch.header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .context("failed inserting snapshot L1 batch header")?;
+        transaction
+            .blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(l1_batch_number)
+            .await?;
+        transaction
+            .blocks_dal()
+            .save_l1_batch_metadata(
+                l1_batch_number,
+                &l1_batch.metadata,
+                previous_root_hash,
+                protocol_version_id.is_pre_boojum(),
+            )
+            .await
+            .context("failed saving snapshot L1 batch metadata")?;
+        transaction
+            .storage_dal()
+            .insert_factory_deps(miniblock_number, &factory_deps)
+            .await;
+        transaction
+            .snapshot_recovery_dal()
+            .set_applied_snapshot_status(&status)
+            .await?;
+        transaction.commit().await?;
+        Ok(status)
+    }
+
+    /// Recovers storage logs from all snapshots in the `chain`. Snapshots are processed sequentially,
+    /// while chunks within a single snapshot are processed concurrently.
+    async fn recover_storage_logs(
+        &self,
+        status: &SnapshotRecoveryStatus,
+        chain: &[SnapshotHeader],
+    ) -> anyhow::Result<()> {
+        let chunks_left_count = status
+            .storage_logs_chunks_processed
+            .iter()
+            .filter(|&&is_processed| !is_processed)
+            .count();
+        METRICS
+            .storage_logs_chunks_count
+            .set(status.storage_logs_chunks_processed.len());
+        METRICS
+            .storage_logs_chunks_left_to_process
+            .set(chunks_left_count);
+        tracing::info!(
+            "Recovering {chunks_left_count} of {} storage logs chunks",
+            status.storage_logs_chunks_processed.len()
+        );
+
+        let concurrency_limit = self.connection_pool.max_size() as usize;
+        let mut chunk_offset = 0;
+        for snapshot in chain {
+            let chunks_left: Vec<_> = snapshot
+                .storage_logs_chunks
+                .iter()
+                .filter(|chunk| {
+                    !status.storage_logs_chunks_processed[chunk_offset + chunk.chunk_id as usize]
+                })
+                .map(|chunk| chunk.chunk_id)
+                .collect();
+            let chunk_futures = chunks_left.into_iter().map(|chunk_id| {
+                self.recover_storage_logs_chunk(status, snapshot, chunk_offset, chunk_id)
+            });
+            stream::iter(chunk_futures)
+                .buffer_unordered(concurrency_limit)
+                .try_collect::<()>()
+                .await?;
+            chunk_offset += snapshot.storage_logs_chunks.len();
+        }
+
+        tracing::info!(
+            "Finished recovering storage logs from snapshot for L1 batch #{}",
+            status.l1_batch_number
+        );
+        Ok(())
+    }
+
+    /// Recovers storage logs from a single chunk of the `snapshot`. `chunk_offset` is the total number of chunks
+    /// in the preceding snapshots of the chain; it is used to index chunks in the recovery status.
+    async fn recover_storage_logs_chunk(
+        &self,
+        status: &SnapshotRecoveryStatus,
+        snapshot: &SnapshotHeader,
+        chunk_offset: usize,
+        chunk_id: u64,
+    ) -> anyhow::Result<()> {
+        let latency = METRICS.storage_logs_chunk_duration.start();
+        let snapshot_l1_batch_number = snapshot.l1_batch_number;
+        let storage_key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk =
+            self.blob_store.get(storage_key).await.with_context(|| {
+                format!(
+                    "failed fetching storage logs chunk #{chunk_id} for snapshot for L1 batch #{snapshot_l1_batch_number}"
+                )
+            })?;
+        let storage_logs = &chunk.storage_logs;
+        let logs_for_storage: Vec<_> = storage_logs
+            .iter()
+            .map(|log| StorageLog::new_write_log(log.key, log.value))
+            .collect();
+
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("snapshots_applier")
+            .await?;
+        let mut transaction = storage.start_transaction().await?;
+        if snapshot.is_delta() {
+            // Storage logs for keys modified in the delta snapshot may have been inserted
+            // from the preceding snapshots in the chain.
+            let hashed_keys: Vec<_> = storage_logs
+                .iter()
+                .map(|log| log.key.hashed_key())
+                .collect();
+            transaction
+                .storage_logs_dal()
+                .remove_snapshot_storage_logs(status.miniblock_number, &hashed_keys)
+                .await
+                .with_context(|| {
+                    format!("failed removing overwritten storage logs for chunk #{chunk_id}")
+                })?;
+        }
+        transaction
+            .storage_logs_dal()
+            .insert_storage_logs_from_snapshot(status.miniblock_number, storage_logs)
+            .await
+            .with_context(|| format!("failed inserting storage logs from chunk #{chunk_id}"))?;
+        transaction
+            .storage_logs_dedup_dal()
+            .insert_initial_writes_from_snapshot(storage_logs)
+            .await
+            .with_context(|| format!("failed inserting initial writes from chunk #{chunk_id}"))?;
+        transaction
+            .storage_dal()
+            .apply_storage_logs(&[(H256::zero(), logs_for_storage)])
+            .await;
+        transaction
+            .snapshot_recovery_dal()
+            .mark_storage_logs_chunk_as_processed(
+                status.l1_batch_number,
+                chunk_offset as u64 + chunk_id,
+            )
+            .await?;
+        transaction.commit().await?;
+
+        METRICS.storage_logs_chunks_left_to_process.dec_by(1);
+        let latency = latency.observe();
+        tracing::info!(
+            "Recovered {} storage logs from chunk #{chunk_id} of snapshot for L1 batch #{snapshot_l1_batch_number} \
+             in {latency:?}",
+            storage_logs.len()
+        );
+        Ok(())
+    }
+}
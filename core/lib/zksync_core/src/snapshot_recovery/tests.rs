@@ -0,0 +1,577 @@
+//! Tests for the snapshot applier and verifier.
+
+use std::{collections::HashMap, slice};
+
+use assert_matches::assert_matches;
+use zksync_merkle_tree::{MerkleTree, PatchSet, TreeEntry};
+use zksync_object_store::{Bucket, ObjectStoreFactory, StoredObject};
+use zksync_types::{
+    block::L1BatchHeader,
+    commitment::{L1BatchMetadata, L1BatchWithMetadata},
+    snapshots::{
+        get_chunk_hashed_keys_range, SnapshotFactoryDependency, SnapshotStorageLog,
+        SnapshotStorageLogsChunkMetadata,
+    },
+    AccountTreeId, Address, L2ChainId, StorageKey, StorageValue,
+};
+
+use super::*;
+use crate::{
+    genesis::{ensure_genesis_state, GenesisParams},
+    state_keeper::tests::create_l1_batch_metadata,
+};
+
+const SNAPSHOT_L1_BATCH: L1BatchNumber = L1BatchNumber(5);
+const SNAPSHOT_MINIBLOCK: MiniblockNumber = MiniblockNumber(10);
+
+#[derive(Debug, Default)]
+struct MockMainNodeClient {
+    l2_blocks: HashMap<MiniblockNumber, SyncBlock>,
+    l1_batch_root_hashes: HashMap<L1BatchNumber, H256>,
+    snapshots: Vec<SnapshotHeader>,
+}
+
+#[async_trait]
+impl SnapshotsApplierMainNodeClient for MockMainNodeClient {
+    async fn fetch_l2_block(&self, number: MiniblockNumber) -> anyhow::Result<Option<SyncBlock>> {
+        Ok(self.l2_blocks.get(&number).cloned())
+    }
+
+    async fn fetch_l1_batch_root_hash(
+        &self,
+        number: L1BatchNumber,
+    ) -> anyhow::Result<Option<H256>> {
+        Ok(self.l1_batch_root_hashes.get(&number).copied())
+    }
+
+    async fn fetch_newest_snapshot(&self) -> anyhow::Result<Option<SnapshotHeader>> {
+        let newest_snapshot = self
+            .snapshots
+            .iter()
+            .max_by_key(|snapshot| snapshot.l1_batch_number);
+        Ok(newest_snapshot.cloned())
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>> {
+        let snapshot = self
+            .snapshots
+            .iter()
+            .find(|snapshot| snapshot.l1_batch_number == l1_batch_number);
+        Ok(snapshot.cloned())
+    }
+
+    async fn fetch_protocol_version(
+        &self,
+        id: ProtocolVersionId,
+    ) -> anyhow::Result<Option<api::ProtocolVersion>> {
+        Ok(Some(api::ProtocolVersion {
+            version_id: id as u16,
+            timestamp: 0,
+            verification_keys_hashes: Default::default(),
+            base_system_contracts: Default::default(),
+            l2_system_upgrade_tx_hash: None,
+        }))
+    }
+}
+
+fn mock_snapshot_miniblock() -> SyncBlock {
+    SyncBlock {
+        number: SNAPSHOT_MINIBLOCK,
+        l1_batch_number: SNAPSHOT_L1_BATCH,
+        last_in_batch: true,
+        timestamp: 100,
+        l1_gas_price: 2,
+        l2_fair_gas_price: 3,
+        base_system_contracts_hashes: Default::default(),
+        operator_address: Address::default(),
+        transactions: None,
+        virtual_blocks: Some(0),
+        hash: Some(H256::repeat_byte(1)),
+        protocol_version: ProtocolVersionId::latest(),
+        consensus: None,
+    }
+}
+
+fn mock_storage_logs(chunk_id: u64) -> Vec<SnapshotStorageLog> {
+    (0..10)
+        .map(|i| {
+            let index = chunk_id * 10 + i + 1;
+            let key = StorageKey::new(
+                AccountTreeId::new(Address::repeat_byte(chunk_id as u8 + 1)),
+                H256::from_low_u64_be(i),
+            );
+            SnapshotStorageLog {
+                key,
+                value: StorageValue::from_low_u64_be(index),
+                l1_batch_number_of_initial_write: L1BatchNumber(1),
+                enumeration_index: index,
+            }
+        })
+        .collect()
+}
+
+fn mock_snapshot_header(
+    l1_batch_number: L1BatchNumber,
+    chunk_count: u64,
+    metadata: L1BatchMetadata,
+) -> SnapshotHeader {
+    let l1_batch_header = L1BatchHeader::new(
+        l1_batch_number,
+        100,
+        Address::default(),
+        Default::default(),
+        ProtocolVersionId::latest(),
+    );
+    SnapshotHeader {
+        l1_batch_number,
+        miniblock_number: SNAPSHOT_MINIBLOCK,
+        storage_logs_chunks: (0..chunk_count)
+            .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
+                chunk_id,
+                filepath: format!("chunk_{chunk_id}"),
+            })
+            .collect(),
+        factory_deps_filepath: "factory_deps".to_owned(),
+        last_l1_batch_with_metadata: L1BatchWithMetadata {
+            header: l1_batch_header,
+            metadata,
+            factory_deps: vec![],
+            da_inclusion_data: None,
+        },
+        parent_l1_batch_number: None,
+    }
+}
+
+async fn prepare_snapshot(chunk_count: u64) -> (MockMainNodeClient, Arc<dyn ObjectStore>) {
+    let blob_store: Arc<dyn ObjectStore> = ObjectStoreFactory::mock().create_store().await.into();
+    for chunk_id in 0..chunk_count {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: SNAPSHOT_L1_BATCH,
+            chunk_id,
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: mock_storage_logs(chunk_id),
+        };
+        blob_store.put(key, &chunk).await.unwrap();
+    }
+    let factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![SnapshotFactoryDependency {
+            bytecode: vec![0; 32],
+        }],
+    };
+    blob_store
+        .put(SNAPSHOT_L1_BATCH, &factory_deps)
+        .await
+        .unwrap();
+
+    let snapshot = mock_snapshot_header(
+        SNAPSHOT_L1_BATCH,
+        chunk_count,
+        create_l1_batch_metadata(SNAPSHOT_L1_BATCH.0),
+    );
+
+    let client = MockMainNodeClient {
+        l2_blocks: HashMap::from([(SNAPSHOT_MINIBLOCK, mock_snapshot_miniblock())]),
+        l1_batch_root_hashes: HashMap::from([(SNAPSHOT_L1_BATCH - 1, H256::repeat_byte(0xff))]),
+        snapshots: vec![snapshot],
+    };
+    (client, blob_store)
+}
+
+async fn assert_snapshot_is_applied(storage: &mut StorageProcessor<'_>, chunk_count: u64) {
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .expect("no recovery status");
+    assert!(status.is_finished());
+    assert_eq!(status.l1_batch_number, SNAPSHOT_L1_BATCH);
+    assert_eq!(status.miniblock_number, SNAPSHOT_MINIBLOCK);
+    assert_eq!(status.miniblock_hash, H256::repeat_byte(1));
+
+    let miniblock_header = storage
+        .blocks_dal()
+        .get_miniblock_header(SNAPSHOT_MINIBLOCK)
+        .await
+        .unwrap()
+        .expect("no snapshot miniblock");
+    assert_eq!(miniblock_header.hash, H256::repeat_byte(1));
+    let root_hash = storage
+        .blocks_dal()
+        .get_l1_batch_state_root(SNAPSHOT_L1_BATCH)
+        .await
+        .unwrap();
+    assert_eq!(root_hash, Some(status.l1_batch_root_hash));
+
+    let bytecode_hash = hash_bytecode(&[0; 32]);
+    let factory_dep = storage.storage_dal().get_factory_dep(bytecode_hash).await;
+    assert_eq!(factory_dep, Some(vec![0; 32]));
+
+    for chunk_id in 0..chunk_count {
+        for log in mock_storage_logs(chunk_id) {
+            let value = storage.storage_dal().get_by_key(&log.key).await;
+            assert_eq!(value, Some(log.value));
+            let index = storage
+                .storage_logs_dedup_dal()
+                .get_enumeration_index_for_key(log.key)
+                .await;
+            assert_eq!(index, Some(log.enumeration_index));
+        }
+    }
+}
+
+#[tokio::test]
+async fn applying_snapshot() {
+    let pool = ConnectionPool::test_pool().await;
+    let (client, blob_store) = prepare_snapshot(3).await;
+    let applier = SnapshotsApplier::new(pool.clone(), Box::new(client), blob_store);
+
+    let outcome = applier.load_snapshot().await.unwrap();
+    assert_eq!(
+        outcome,
+        SnapshotRecoveryOutcome::Recovered(SNAPSHOT_L1_BATCH)
+    );
+    let mut storage = pool.access_storage().await.unwrap();
+    assert_snapshot_is_applied(&mut storage, 3).await;
+
+    // Repeated recovery should be a no-op.
+    let outcome = applier.load_snapshot().await.unwrap();
+    assert_eq!(
+        outcome,
+        SnapshotRecoveryOutcome::Recovered(SNAPSHOT_L1_BATCH)
+    );
+}
+
+#[tokio::test]
+async fn resuming_snapshot_recovery() {
+    let pool = ConnectionPool::test_pool().await;
+    let (client, blob_store) = prepare_snapshot(3).await;
+    let applier = SnapshotsApplier::new(pool.clone(), Box::new(client), blob_store);
+
+    // Emulate an interrupted recovery by preparing storage and processing a single chunk.
+    let mut storage = pool.access_storage().await.unwrap();
+    let header = applier
+        .main_node_client
+        .fetch_newest_snapshot()
+        .await
+        .unwrap()
+        .unwrap();
+    let status = applier
+        .prepare_storage(&mut storage, slice::from_ref(&header))
+        .await
+        .unwrap();
+    applier
+        .recover_storage_logs_chunk(&status, &header, 0, 1)
+        .await
+        .unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(status.storage_logs_chunks_processed, [false, true, false]);
+
+    let outcome = applier.load_snapshot().await.unwrap();
+    assert_eq!(
+        outcome,
+        SnapshotRecoveryOutcome::Recovered(SNAPSHOT_L1_BATCH)
+    );
+    assert_snapshot_is_applied(&mut storage, 3).await;
+}
+
+#[tokio::test]
+async fn snapshot_recovery_is_skipped_for_non_empty_storage() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let (client, blob_store) = prepare_snapshot(1).await;
+    let applier = SnapshotsApplier::new(pool.clone(), Box::new(client), blob_store);
+    let outcome = applier.load_snapshot().await.unwrap();
+    assert_eq!(outcome, SnapshotRecoveryOutcome::Skipped);
+
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(status, None);
+}
+
+#[tokio::test]
+async fn applying_delta_snapshot_chain() {
+    const PARENT_L1_BATCH: L1BatchNumber = L1BatchNumber(3);
+
+    let pool = ConnectionPool::test_pool().await;
+    let blob_store: Arc<dyn ObjectStore> = ObjectStoreFactory::mock().create_store().await.into();
+    for chunk_id in 0..2 {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: PARENT_L1_BATCH,
+            chunk_id,
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: mock_storage_logs(chunk_id),
+        };
+        blob_store.put(key, &chunk).await.unwrap();
+    }
+    // The delta snapshot overwrites a key from the parent snapshot and adds a new key.
+    let mut overwritten_log = mock_storage_logs(0)[0].clone();
+    overwritten_log.value = H256::repeat_byte(0xee);
+    let new_log = SnapshotStorageLog {
+        key: StorageKey::new(AccountTreeId::new(Address::repeat_byte(0xaa)), H256::zero()),
+        value: StorageValue::repeat_byte(0xdd),
+        l1_batch_number_of_initial_write: L1BatchNumber(4),
+        enumeration_index: 21,
+    };
+    let key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: SNAPSHOT_L1_BATCH,
+        chunk_id: 0,
+    };
+    let chunk = SnapshotStorageLogsChunk {
+        storage_logs: vec![overwritten_log.clone(), new_log.clone()],
+    };
+    blob_store.put(key, &chunk).await.unwrap();
+
+    for (l1_batch_number, bytecode) in [
+        (PARENT_L1_BATCH, vec![0; 32]),
+        (SNAPSHOT_L1_BATCH, vec![1; 32]),
+    ] {
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![SnapshotFactoryDependency { bytecode }],
+        };
+        blob_store
+            .put(l1_batch_number, &factory_deps)
+            .await
+            .unwrap();
+    }
+
+    let parent_snapshot = mock_snapshot_header(
+        PARENT_L1_BATCH,
+        2,
+        create_l1_batch_metadata(PARENT_L1_BATCH.0),
+    );
+    let mut delta_snapshot = mock_snapshot_header(
+        SNAPSHOT_L1_BATCH,
+        1,
+        create_l1_batch_metadata(SNAPSHOT_L1_BATCH.0),
+    );
+    delta_snapshot.parent_l1_batch_number = Some(PARENT_L1_BATCH);
+    let client = MockMainNodeClient {
+        l2_blocks: HashMap::from([(SNAPSHOT_MINIBLOCK, mock_snapshot_miniblock())]),
+        l1_batch_root_hashes: HashMap::from([(SNAPSHOT_L1_BATCH - 1, H256::repeat_byte(0xff))]),
+        snapshots: vec![parent_snapshot, delta_snapshot],
+    };
+    let applier = SnapshotsApplier::new(pool.clone(), Box::new(client), blob_store);
+
+    let outcome = applier.load_snapshot().await.unwrap();
+    assert_eq!(
+        outcome,
+        SnapshotRecoveryOutcome::Recovered(SNAPSHOT_L1_BATCH)
+    );
+
+    let mut storage = pool.access_storage().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .expect("no recovery status");
+    assert_eq!(status.l1_batch_number, SNAPSHOT_L1_BATCH);
+    assert_eq!(status.storage_logs_chunks_processed, [true; 3]);
+
+    let expected_logs = mock_storage_logs(0)
+        .into_iter()
+        .chain(mock_storage_logs(1))
+        .map(|log| {
+            if log.key == overwritten_log.key {
+                overwritten_log.clone()
+            } else {
+                log
+            }
+        })
+        .chain([new_log]);
+    for log in expected_logs {
+        let value = storage.storage_dal().get_by_key(&log.key).await;
+        assert_eq!(value, Some(log.value));
+        let index = storage
+            .storage_logs_dedup_dal()
+            .get_enumeration_index_for_key(log.key)
+            .await;
+        assert_eq!(index, Some(log.enumeration_index));
+    }
+    // Each key must have a single storage log at the snapshot miniblock.
+    let storage_log_count = storage
+        .storage_logs_dal()
+        .count_miniblock_storage_logs(SNAPSHOT_MINIBLOCK)
+        .await
+        .unwrap();
+    assert_eq!(storage_log_count, 21);
+
+    for bytecode in [vec![0; 32], vec![1; 32]] {
+        let factory_dep = storage
+            .storage_dal()
+            .get_factory_dep(hash_bytecode(&bytecode))
+            .await;
+        assert_eq!(factory_dep, Some(bytecode));
+    }
+}
+
+/// Prepares a snapshot with storage logs correctly split into chunks by hashed key ranges
+/// and the root hash computed using a Merkle tree.
+async fn prepare_verifiable_snapshot(
+    chunk_count: u64,
+) -> (
+    SnapshotHeader,
+    Vec<Vec<SnapshotStorageLog>>,
+    Arc<dyn ObjectStore>,
+) {
+    let storage_logs: Vec<_> = (0_u64..50)
+        .map(|i| SnapshotStorageLog {
+            key: StorageKey::new(
+                AccountTreeId::new(Address::repeat_byte(1)),
+                H256::from_low_u64_be(i),
+            ),
+            value: StorageValue::from_low_u64_be(i + 1),
+            l1_batch_number_of_initial_write: L1BatchNumber(1),
+            enumeration_index: i + 1,
+        })
+        .collect();
+    let tree_entries = storage_logs
+        .iter()
+        .map(|log| TreeEntry::new(log.key.hashed_key_u256(), log.enumeration_index, log.value))
+        .collect();
+    let root_hash = MerkleTree::new(PatchSet::default())
+        .extend(tree_entries)
+        .root_hash;
+
+    let mut chunks = vec![vec![]; chunk_count as usize];
+    for log in storage_logs {
+        let hashed_key = log.key.hashed_key();
+        let chunk_id = (0..chunk_count)
+            .find(|&id| get_chunk_hashed_keys_range(id, chunk_count).contains(&hashed_key))
+            .unwrap();
+        chunks[chunk_id as usize].push(log);
+    }
+
+    let blob_store: Arc<dyn ObjectStore> = ObjectStoreFactory::mock().create_store().await.into();
+    for (chunk_id, storage_logs) in chunks.iter().enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: SNAPSHOT_L1_BATCH,
+            chunk_id: chunk_id as u64,
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: storage_logs.clone(),
+        };
+        blob_store.put(key, &chunk).await.unwrap();
+    }
+
+    let mut metadata = create_l1_batch_metadata(SNAPSHOT_L1_BATCH.0);
+    metadata.root_hash = root_hash;
+    let header = mock_snapshot_header(SNAPSHOT_L1_BATCH, chunk_count, metadata);
+    (header, chunks, blob_store)
+}
+
+#[tokio::test]
+async fn verifying_valid_snapshot() {
+    let (header, _, blob_store) = prepare_verifiable_snapshot(4).await;
+    let report = SnapshotVerifier::new(&*blob_store)
+        .verify(&header, PatchSet::default())
+        .await
+        .unwrap();
+
+    assert!(report.is_valid(), "{report:?}");
+    assert_eq!(report.chunk_count, 4);
+    assert_eq!(report.storage_log_count, 50);
+    assert_eq!(report.computed_root_hash, Some(report.expected_root_hash));
+}
+
+#[tokio::test]
+async fn verifying_delta_snapshot_is_rejected() {
+    let (mut header, _, blob_store) = prepare_verifiable_snapshot(4).await;
+    header.parent_l1_batch_number = Some(SNAPSHOT_L1_BATCH - 1);
+    let err = SnapshotVerifier::new(&*blob_store)
+        .verify(&header, PatchSet::default())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("delta"), "{err}");
+}
+
+#[tokio::test]
+async fn verifying_snapshot_with_tampered_value() {
+    let (header, mut chunks, blob_store) = prepare_verifiable_snapshot(4).await;
+    let chunk_id = chunks.iter().position(|chunk| !chunk.is_empty()).unwrap();
+    chunks[chunk_id][0].value = H256::repeat_byte(0xff);
+    let key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: SNAPSHOT_L1_BATCH,
+        chunk_id: chunk_id as u64,
+    };
+    let chunk = SnapshotStorageLogsChunk {
+        storage_logs: chunks[chunk_id].clone(),
+    };
+    blob_store.put(key, &chunk).await.unwrap();
+
+    let report = SnapshotVerifier::new(&*blob_store)
+        .verify(&header, PatchSet::default())
+        .await
+        .unwrap();
+    assert!(!report.is_valid());
+    assert!(report.chunk_failures.is_empty());
+    assert_ne!(report.computed_root_hash, Some(report.expected_root_hash));
+}
+
+#[tokio::test]
+async fn verifying_snapshot_with_invalid_chunks() {
+    let (header, chunks, blob_store) = prepare_verifiable_snapshot(4).await;
+    // Move a log to a chunk with a non-matching key range.
+    let (src_id, dest_id) = if chunks[0].is_empty() { (1, 0) } else { (0, 1) };
+    let mut dest_logs = chunks[dest_id].clone();
+    dest_logs.push(chunks[src_id][0].clone());
+    let key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: SNAPSHOT_L1_BATCH,
+        chunk_id: dest_id as u64,
+    };
+    let chunk = SnapshotStorageLogsChunk {
+        storage_logs: dest_logs,
+    };
+    blob_store.put(key, &chunk).await.unwrap();
+    // Remove a chunk from the object store.
+    let missing_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: SNAPSHOT_L1_BATCH,
+        chunk_id: 3,
+    };
+    blob_store
+        .remove_raw(
+            Bucket::StorageSnapshot,
+            &SnapshotStorageLogsChunk::encode_key(missing_key),
+        )
+        .await
+        .unwrap();
+
+    let report = SnapshotVerifier::new(&*blob_store)
+        .verify(&header, PatchSet::default())
+        .await
+        .unwrap();
+    assert!(!report.is_valid());
+    assert_eq!(report.computed_root_hash, None);
+    let failed_chunks: Vec<_> = report
+        .chunk_failures
+        .iter()
+        .map(|failure| failure.chunk_id)
+        .collect();
+    assert_eq!(failed_chunks, [dest_id as u64, 3]);
+    assert_matches!(
+        report.chunk_failures[0].error,
+        ChunkVerificationError::KeyOutOfRange { index, .. } if index == chunks[dest_id].len()
+    );
+    assert_matches!(
+        report.chunk_failures[1].error,
+        ChunkVerificationError::Fetch(_)
+    );
+}
@@ -0,0 +1,246 @@
+//! Integrity verification for snapshots.
+//!
+//! Snapshot storage logs are downloaded from the object store and fed to a Merkle tree in the recovery mode.
+//! The root hash of the recovered tree is then compared to the root hash of the snapshot L1 batch
+//! from the snapshot header. Since the root hash commits to all storage keys, values and enumeration indices,
+//! a match authenticates the entire storage snapshot, provided that the header is obtained from a trusted source.
+
+use std::{collections::HashSet, ops};
+
+use anyhow::Context as _;
+use futures::{stream, StreamExt};
+use zksync_merkle_tree::{recovery::MerkleTreeRecovery, PruneDatabase, TreeEntry};
+use zksync_object_store::{ObjectStore, ObjectStoreError};
+use zksync_types::{
+    snapshots::{
+        get_chunk_hashed_keys_range, SnapshotHeader, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber, H256,
+};
+
+/// Default number of storage logs chunks fetched from the object store concurrently.
+const DEFAULT_CONCURRENCY: usize = 10;
+
+/// Error verifying a single storage logs chunk.
+#[derive(Debug, thiserror::Error)]
+pub enum ChunkVerificationError {
+    #[error("failed fetching chunk from the object store: {0}")]
+    Fetch(#[source] ObjectStoreError),
+    #[error("storage log #{index} has hashed key {hashed_key:?} outside the chunk key range")]
+    KeyOutOfRange { index: usize, hashed_key: H256 },
+    #[error("storage log #{index} has hashed key {hashed_key:?} duplicating an earlier log")]
+    DuplicateKey { index: usize, hashed_key: H256 },
+    #[error("storage log #{index} has zero enumeration index")]
+    ZeroEnumerationIndex { index: usize },
+    #[error(
+        "storage log #{index} has initial write in L1 batch #{l1_batch_number}, \
+         which is after the snapshot L1 batch"
+    )]
+    InitialWriteAfterSnapshot {
+        index: usize,
+        l1_batch_number: L1BatchNumber,
+    },
+}
+
+/// Verification failure for a specific storage logs chunk.
+#[derive(Debug)]
+pub struct ChunkFailure {
+    pub chunk_id: u64,
+    pub error: ChunkVerificationError,
+}
+
+/// Outcome of [`SnapshotVerifier::verify()`].
+#[derive(Debug)]
+pub struct SnapshotVerificationReport {
+    pub l1_batch_number: L1BatchNumber,
+    /// Total number of storage logs chunks in the snapshot.
+    pub chunk_count: usize,
+    /// Total number of storage logs in chunks that have passed verification.
+    pub storage_log_count: u64,
+    /// Failures for individual chunks.
+    pub chunk_failures: Vec<ChunkFailure>,
+    /// Root hash of the snapshot L1 batch according to the snapshot header.
+    pub expected_root_hash: H256,
+    /// Root hash of the tree recovered from storage logs. `None` if some chunks have failed verification,
+    /// since the recovered tree would be incomplete in this case.
+    pub computed_root_hash: Option<H256>,
+}
+
+impl SnapshotVerificationReport {
+    /// Checks whether the snapshot is valid, i.e., all chunks have passed verification
+    /// and the recovered root hash matches the expected one.
+    pub fn is_valid(&self) -> bool {
+        self.chunk_failures.is_empty() && self.computed_root_hash == Some(self.expected_root_hash)
+    }
+}
+
+/// Verifies integrity of snapshots produced by the snapshots creator.
+#[derive(Debug)]
+pub struct SnapshotVerifier<'a> {
+    blob_store: &'a dyn ObjectStore,
+    concurrency: usize,
+}
+
+impl<'a> SnapshotVerifier<'a> {
+    pub fn new(blob_store: &'a dyn ObjectStore) -> Self {
+        Self {
+            blob_store,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Sets the number of storage logs chunks fetched from the object store concurrently.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "concurrency must be positive");
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Verifies the snapshot with the specified header. The Merkle tree is recovered in the provided `db`,
+    /// which should be empty; an in-memory [`PatchSet`](zksync_merkle_tree::PatchSet) is sufficient
+    /// for small snapshots.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot header is malformed or corresponds to a delta snapshot.
+    /// Errors related to individual chunks are not propagated, but are rather collected in the returned report.
+    pub async fn verify<DB>(
+        &self,
+        header: &SnapshotHeader,
+        db: DB,
+    ) -> anyhow::Result<SnapshotVerificationReport>
+    where
+        DB: PruneDatabase + Send + 'static,
+    {
+        let l1_batch_number = header.l1_batch_number;
+        // The root hash commits to the entire storage, so it cannot be checked using storage logs
+        // changed after the parent snapshot only.
+        anyhow::ensure!(
+            !header.is_delta(),
+            "snapshot for L1 batch #{l1_batch_number} is a delta one and cannot be verified on its own"
+        );
+        let l1_batch = &header.last_l1_batch_with_metadata;
+        anyhow::ensure!(
+            l1_batch.header.number == l1_batch_number,
+            "snapshot header is inconsistent: L1 batch #{} is provided for snapshot for L1 batch #{l1_batch_number}",
+            l1_batch.header.number
+        );
+        let chunk_count = header.storage_logs_chunks.len();
+        for (i, chunk) in header.storage_logs_chunks.iter().enumerate() {
+            anyhow::ensure!(
+                chunk.chunk_id == i as u64,
+                "storage logs chunks in snapshot header are not numbered sequentially: \
+                 chunk #{i} has ID {}",
+                chunk.chunk_id
+            );
+        }
+        tracing::info!(
+            "Verifying snapshot for L1 batch #{l1_batch_number} with {chunk_count} storage logs chunks"
+        );
+
+        let mut tree = Some(MerkleTreeRecovery::new(db, l1_batch_number.0.into()));
+        let mut report = SnapshotVerificationReport {
+            l1_batch_number,
+            chunk_count,
+            storage_log_count: 0,
+            chunk_failures: vec![],
+            expected_root_hash: l1_batch.metadata.root_hash,
+            computed_root_hash: None,
+        };
+
+        let chunks = (0..chunk_count as u64).map(|chunk_id| async move {
+            let chunk = self.fetch_chunk(l1_batch_number, chunk_id).await;
+            (chunk_id, chunk)
+        });
+        let mut chunks = stream::iter(chunks).buffered(self.concurrency);
+        while let Some((chunk_id, chunk)) = chunks.next().await {
+            let key_range = get_chunk_hashed_keys_range(chunk_id, chunk_count as u64);
+            let entries =
+                chunk.and_then(|chunk| Self::check_chunk(&chunk, &key_range, l1_batch_number));
+            let entries = match entries {
+                Ok(entries) => entries,
+                Err(error) => {
+                    tracing::warn!("Storage logs chunk #{chunk_id} failed verification: {error}");
+                    report.chunk_failures.push(ChunkFailure { chunk_id, error });
+                    continue;
+                }
+            };
+            report.storage_log_count += entries.len() as u64;
+
+            // There's no point in extending the tree after a failure since the root hash
+            // will not be checked anyway.
+            if report.chunk_failures.is_empty() {
+                let mut recovery = tree.take().unwrap();
+                let recovery = tokio::task::spawn_blocking(move || {
+                    recovery.extend_random(entries);
+                    recovery
+                })
+                .await
+                .context("panicked while extending Merkle tree")?;
+                tree = Some(recovery);
+            }
+            tracing::info!("Verified storage logs chunk #{chunk_id} / {chunk_count}");
+        }
+
+        if report.chunk_failures.is_empty() {
+            let computed_root_hash = tree.unwrap().root_hash();
+            report.computed_root_hash = Some(computed_root_hash);
+            if computed_root_hash != report.expected_root_hash {
+                tracing::warn!(
+                    "Root hash of the recovered tree {computed_root_hash:?} differs from the expected {:?}",
+                    report.expected_root_hash
+                );
+            }
+        }
+        Ok(report)
+    }
+
+    async fn fetch_chunk(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        chunk_id: u64,
+    ) -> Result<SnapshotStorageLogsChunk, ChunkVerificationError> {
+        let storage_key = SnapshotStorageLogsStorageKey {
+            l1_batch_number,
+            chunk_id,
+        };
+        self.blob_store
+            .get(storage_key)
+            .await
+            .map_err(ChunkVerificationError::Fetch)
+    }
+
+    /// Performs sanity checks on the chunk and converts it to tree entries.
+    fn check_chunk(
+        chunk: &SnapshotStorageLogsChunk,
+        key_range: &ops::RangeInclusive<H256>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Vec<TreeEntry>, ChunkVerificationError> {
+        let mut hashed_keys = HashSet::with_capacity(chunk.storage_logs.len());
+        let entries = chunk.storage_logs.iter().enumerate().map(|(index, log)| {
+            let hashed_key = log.key.hashed_key();
+            if !key_range.contains(&hashed_key) {
+                return Err(ChunkVerificationError::KeyOutOfRange { index, hashed_key });
+            }
+            if !hashed_keys.insert(hashed_key) {
+                return Err(ChunkVerificationError::DuplicateKey { index, hashed_key });
+            }
+            if log.enumeration_index == 0 {
+                return Err(ChunkVerificationError::ZeroEnumerationIndex { index });
+            }
+            if log.l1_batch_number_of_initial_write > l1_batch_number {
+                return Err(ChunkVerificationError::InitialWriteAfterSnapshot {
+                    index,
+                    l1_batch_number: log.l1_batch_number_of_initial_write,
+                });
+            }
+            Ok(TreeEntry::new(
+                log.key.hashed_key_u256(),
+                log.enumeration_index,
+                log.value,
+            ))
+        });
+        entries.collect()
+    }
+}
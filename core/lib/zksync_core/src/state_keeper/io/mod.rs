@@ -1,16 +1,17 @@
 use std::{
     fmt,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
 use multivm::interface::{FinishedL1Batch, L1BatchEnv, SystemEnv};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use zksync_dal::ConnectionPool;
 use zksync_types::{
-    block::MiniblockExecutionData, protocol_version::ProtocolUpgradeTx,
+    block::MiniblockExecutionData, protocol_version::ProtocolUpgradeTx, tx::IncludedTxLocation,
     witness_block_state::WitnessBlockState, L1BatchNumber, MiniblockNumber, ProtocolVersionId,
-    Transaction,
+    Transaction, VmEvent, H256,
 };
 
 pub(crate) use self::mempool::MempoolIO;
@@ -204,6 +205,20 @@ impl MiniblockSealerHandle {
     }
 }
 
+/// Information about a miniblock broadcast by [`MiniblockSealer`] after the miniblock is persisted.
+/// Used by the WebSocket API server to notify subscribers without polling Postgres.
+#[derive(Debug)]
+pub struct SealedMiniblock {
+    pub number: MiniblockNumber,
+    pub hash: H256,
+    pub timestamp: u64,
+    /// Events emitted in the miniblock grouped by transaction, in the same order as they are stored in Postgres.
+    pub events: Vec<(IncludedTxLocation, Vec<VmEvent>)>,
+}
+
+/// Sender part of the channel broadcasting sealed miniblocks.
+pub type SealedMiniblocksSender = broadcast::Sender<Arc<SealedMiniblock>>;
+
 /// Component responsible for sealing miniblocks (i.e., storing their data to Postgres).
 #[derive(Debug)]
 pub struct MiniblockSealer {
@@ -212,6 +227,7 @@ pub struct MiniblockSealer {
     // Weak sender handle to get queue capacity stats.
     commands_sender: mpsc::WeakSender<Completable<MiniblockSealCommand>>,
     commands_receiver: mpsc::Receiver<Completable<MiniblockSealCommand>>,
+    sealed_miniblocks_sender: Option<SealedMiniblocksSender>,
 }
 
 impl MiniblockSealer {
@@ -227,6 +243,7 @@ impl MiniblockSealer {
             is_sync,
             commands_sender: commands_sender.downgrade(),
             commands_receiver,
+            sealed_miniblocks_sender: None,
         };
         let handle = MiniblockSealerHandle {
             commands_sender,
@@ -236,6 +253,13 @@ impl MiniblockSealer {
         (this, handle)
     }
 
+    /// Makes the sealer broadcast information about each sealed miniblock via the provided `sender`.
+    /// Fictive miniblocks (ones sealed together with L1 batches) are not broadcast.
+    pub fn with_sealed_miniblocks_sender(mut self, sender: SealedMiniblocksSender) -> Self {
+        self.sealed_miniblocks_sender = Some(sender);
+        self
+    }
+
     /// Seals miniblocks as they are received from the [`MiniblockSealerHandle`]. This should be run
     /// on a separate Tokio task.
     pub async fn run(mut self) -> anyhow::Result<()> {
@@ -260,6 +284,13 @@ impl MiniblockSealer {
                 .await
                 .unwrap();
             completable.command.seal(&mut conn).await;
+            if let Some(sender) = &self.sealed_miniblocks_sender {
+                // Avoid cloning miniblock data if there are no receivers.
+                if sender.receiver_count() > 0 {
+                    let sealed_miniblock = completable.command.sealed_miniblock();
+                    sender.send(Arc::new(sealed_miniblock)).ok();
+                }
+            }
             if let Some(delta) = miniblock_seal_delta {
                 MINIBLOCK_METRICS.seal_delta.observe(delta.elapsed());
             }
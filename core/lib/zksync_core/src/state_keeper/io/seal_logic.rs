@@ -34,6 +34,7 @@ use crate::{
     metrics::{BlockStage, MiniblockStage, APP_METRICS},
     state_keeper::{
         extractors,
+        io::SealedMiniblock,
         metrics::{L1BatchSealStage, MiniblockSealStage, L1_BATCH_METRICS, MINIBLOCK_METRICS},
         updates::{MiniblockSealCommand, UpdatesManager},
     },
@@ -552,6 +553,21 @@ impl MiniblockSealCommand {
         count
     }
 
+    /// Returns information about the miniblock broadcast to API servers after it is sealed.
+    pub(crate) fn sealed_miniblock(&self) -> SealedMiniblock {
+        let events = self
+            .extract_events(false)
+            .into_iter()
+            .map(|(location, events)| (location, events.into_iter().cloned().collect()))
+            .collect();
+        SealedMiniblock {
+            number: self.miniblock_number,
+            hash: self.miniblock.get_miniblock_hash(),
+            timestamp: self.miniblock.timestamp,
+            events,
+        }
+    }
+
     fn extract_events(&self, is_fictive: bool) -> Vec<(IncludedTxLocation, Vec<&VmEvent>)> {
         self.group_by_tx_location(&self.miniblock.events, is_fictive, |event| event.location.1)
     }
@@ -77,6 +77,13 @@ impl<G: L1GasPriceProvider> MempoolFetcher<G> {
             let latency = KEEPER_METRICS.mempool_sync.start();
             let mut storage = pool.access_storage_tagged("state_keeper").await.unwrap();
             let mempool_info = self.mempool.get_mempool_info();
+            for (tx_hash, err) in &mempool_info.rejected_transactions {
+                let error = format!("rejected by mempool: {err}");
+                storage
+                    .transactions_dal()
+                    .mark_tx_as_rejected(*tx_hash, &error)
+                    .await;
+            }
             let l2_tx_filter = l2_tx_filter(self.l1_gas_price_provider.as_ref(), fair_l2_gas_price);
 
             let (transactions, nonces) = storage
@@ -12,7 +12,7 @@ use zksync_system_constants::MAX_TXS_IN_BLOCK;
 use self::io::MempoolIO;
 pub use self::{
     batch_executor::{L1BatchExecutorBuilder, MainBatchExecutorBuilder},
-    io::{MiniblockSealer, MiniblockSealerHandle},
+    io::{MiniblockSealer, MiniblockSealerHandle, SealedMiniblock, SealedMiniblocksSender},
     keeper::ZkSyncStateKeeper,
 };
 pub(crate) use self::{
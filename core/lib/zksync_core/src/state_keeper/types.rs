@@ -3,19 +3,41 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
+use zksync_config::configs::chain::{MempoolConfig, MempoolOrdering};
+use zksync_mempool::{
+    AccountLimits, AccountTransactionsView, L2TxFilter, MempoolInfo, MempoolStore,
+};
 use zksync_types::{
-    block::BlockGasCount, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
+    block::BlockGasCount, l2::L2Tx, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
 };
 
 use super::metrics::StateKeeperGauges;
 
+/// Creates limits for pending transactions of a single account from the mempool configuration.
+pub(crate) fn account_limits(config: &MempoolConfig) -> AccountLimits {
+    AccountLimits {
+        max_pending_txs: config.max_pending_txs_per_account,
+        max_nonce_gap: config.max_nonce_gap,
+        replacement_fee_bump_percent: config.replacement_fee_bump_percent,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MempoolGuard(Arc<Mutex<MempoolStore>>);
 
 impl MempoolGuard {
-    pub fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
-        let store = MempoolStore::new(next_priority_id, capacity);
+    pub fn new(
+        next_priority_id: PriorityOpId,
+        capacity: u64,
+        ordering: MempoolOrdering,
+        account_limits: AccountLimits,
+    ) -> Self {
+        let ordering = match ordering {
+            MempoolOrdering::Fifo => zksync_mempool::MempoolOrdering::Fifo,
+            MempoolOrdering::PriorityFee => zksync_mempool::MempoolOrdering::PriorityFee,
+        };
+        let store = MempoolStore::new(next_priority_id, capacity, ordering)
+            .with_account_limits(account_limits);
         Self(Arc::new(Mutex::new(store)))
     }
 
@@ -54,6 +76,40 @@ impl MempoolGuard {
             .get_mempool_info()
     }
 
+    /// Returns the number of L2 transactions ready for execution and the number of L2 transactions
+    /// blocked by nonce gaps.
+    pub fn l2_transaction_counts(&self) -> (usize, usize) {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .l2_transaction_counts()
+    }
+
+    /// Returns transactions of the specified account mapped with `map_fn`. Since the mempool is locked
+    /// for the duration of the call, `map_fn` should be cheap (e.g., extract transaction hashes).
+    pub fn account_view<T>(
+        &self,
+        account: Address,
+        map_fn: impl FnMut(&L2Tx) -> T,
+    ) -> Option<AccountTransactionsView<T>> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .account_view(account, map_fn)
+    }
+
+    /// Returns transactions of all accounts mapped with `map_fn`. Since the mempool is locked
+    /// for the duration of the call, `map_fn` should be cheap (e.g., extract transaction hashes).
+    pub fn account_views<T>(
+        &self,
+        map_fn: impl FnMut(&L2Tx) -> T,
+    ) -> HashMap<Address, AccountTransactionsView<T>> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .account_views(map_fn)
+    }
+
     pub fn register_metrics(&self) {
         StateKeeperGauges::register(Arc::downgrade(&self.0));
     }
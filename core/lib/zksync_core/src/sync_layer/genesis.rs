@@ -15,6 +15,19 @@ pub async fn perform_genesis_if_needed(
     client: &dyn MainNodeClient,
 ) -> anyhow::Result<()> {
     let mut transaction = storage.start_transaction().await?;
+    if let Some(status) = transaction
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await?
+    {
+        // The node was recovered from a snapshot, so there's no genesis L1 batch in Postgres.
+        tracing::info!(
+            "Node was recovered from snapshot for L1 batch #{}; skipping genesis",
+            status.l1_batch_number
+        );
+        return Ok(());
+    }
+
     // We want to check whether the genesis is needed before we create genesis params to not
     // make the node startup slower.
     let genesis_block_hash = if transaction.blocks_dal().is_genesis_needed().await? {
@@ -7,8 +7,8 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, PrometheusConfig,
-        ProofDataHandlerConfig, ProverGroupConfig, WitnessGeneratorConfig,
+        DADispatcherConfig, FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig,
+        PrometheusConfig, ProofDataHandlerConfig, ProverGroupConfig, WitnessGeneratorConfig,
     },
     ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
     FetcherConfig, GasAdjusterConfig, ObjectStoreConfig, PostgresConfig, ProverConfigs,
@@ -36,6 +36,7 @@ pub struct TempConfigStore {
     pub prometheus_config: Option<PrometheusConfig>,
     pub proof_data_handler_config: Option<ProofDataHandlerConfig>,
     pub prover_group_config: Option<ProverGroupConfig>,
+    pub da_dispatcher_config: Option<DADispatcherConfig>,
     pub witness_generator_config: Option<WitnessGeneratorConfig>,
     pub api_config: Option<ApiConfig>,
     pub contracts_config: Option<ContractsConfig>,
@@ -47,4 +48,5 @@ pub struct TempConfigStore {
     pub gas_adjuster_config: Option<GasAdjusterConfig>,
     pub prover_configs: Option<ProverConfigs>,
     pub object_store_config: Option<ObjectStoreConfig>,
+    pub prover_object_store_config: Option<ObjectStoreConfig>,
 }
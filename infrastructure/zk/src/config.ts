@@ -28,6 +28,7 @@ const CONFIG_FILES = [
     'fri_witness_generator.toml',
     'fri_prover_group.toml',
     'proof_data_handler.toml',
+    'da_dispatcher.toml',
     'fri_witness_vector_generator.toml',
     'fri_prover_gateway.toml',
     'fri_proof_compressor.toml'
@@ -1,8 +1,8 @@
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
-use tokio::task::JoinHandle;
+use tokio::{sync::Notify, task::JoinHandle};
 use zkevm_test_harness::proof_wrapper_utils::{wrap_proof, WrapperConfig};
 use zksync_dal::ConnectionPool;
 use zksync_object_store::ObjectStore;
@@ -38,6 +38,7 @@ pub struct ProofCompressor {
     compression_mode: u8,
     verify_wrapper_proof: bool,
     max_attempts: u32,
+    new_jobs_notifier: Option<Arc<Notify>>,
 }
 
 impl ProofCompressor {
@@ -47,6 +48,7 @@ impl ProofCompressor {
         compression_mode: u8,
         verify_wrapper_proof: bool,
         max_attempts: u32,
+        new_jobs_notifier: Option<Arc<Notify>>,
     ) -> Self {
         Self {
             blob_store,
@@ -54,6 +56,7 @@ impl ProofCompressor {
             compression_mode,
             verify_wrapper_proof,
             max_attempts,
+            new_jobs_notifier,
         }
     }
 
@@ -111,6 +114,10 @@ impl JobProcessor for ProofCompressor {
     type JobArtifacts = Proof<Bn256, ZkSyncCircuit<Bn256, VmWitnessOracle<Bn256>>>;
     const SERVICE_NAME: &'static str = "ProofCompressor";
 
+    fn new_jobs_notifier(&self) -> Option<&Notify> {
+        self.new_jobs_notifier.as_deref()
+    }
+
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
         let mut conn = self.pool.access_storage().await.unwrap();
         let pod_name = get_current_pod_name();
@@ -5,7 +5,10 @@ use prometheus_exporter::PrometheusExporterConfig;
 use structopt::StructOpt;
 use tokio::sync::{oneshot, watch};
 use zksync_config::configs::{FriProofCompressorConfig, PostgresConfig};
-use zksync_dal::ConnectionPool;
+use zksync_dal::{
+    job_notifications::{JobNotificationChannel, JobNotificationsListener},
+    ConnectionPool,
+};
 use zksync_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_queued_job_processor::JobProcessor;
@@ -60,12 +63,25 @@ async fn main() -> anyhow::Result<()> {
     let blob_store = ObjectStoreFactory::new(object_store_config.0)
         .create_store()
         .await;
+    let job_notifications_listener = if postgres_config.job_notifications_enabled {
+        let channels = [JobNotificationChannel::ProofCompressionJobs];
+        let listener = JobNotificationsListener::new(&pool, &channels)
+            .await
+            .context("failed creating job notifications listener")?;
+        Some(listener)
+    } else {
+        None
+    };
+    let new_jobs_notifier = job_notifications_listener
+        .as_ref()
+        .map(JobNotificationsListener::notifier);
     let proof_compressor = ProofCompressor::new(
         blob_store,
         pool,
         config.compression_mode,
         config.verify_wrapper_proof,
         config.max_attempts,
+        new_jobs_notifier,
     );
 
     let (stop_sender, stop_receiver) = watch::channel(false);
@@ -91,10 +107,13 @@ async fn main() -> anyhow::Result<()> {
         config.prometheus_pushgateway_url,
         Duration::from_millis(config.prometheus_push_interval_ms.unwrap_or(100)),
     );
-    let tasks = vec![
-        tokio::spawn(prometheus_config.run(stop_receiver.clone())),
-        tokio::spawn(proof_compressor.run(stop_receiver, opt.number_of_iterations)),
-    ];
+    let mut tasks = vec![tokio::spawn(prometheus_config.run(stop_receiver.clone()))];
+    if let Some(listener) = job_notifications_listener {
+        tasks.push(tokio::spawn(listener.run(stop_receiver.clone())));
+    }
+    tasks.push(tokio::spawn(
+        proof_compressor.run(stop_receiver, opt.number_of_iterations),
+    ));
 
     let graceful_shutdown = None::<futures::future::Ready<()>>;
     let tasks_allowed_to_finish = true;
@@ -134,6 +134,7 @@ async fn main() -> anyhow::Result<()> {
         public_blob_store,
         pool,
         circuit_ids_for_round_to_be_proven,
+        postgres_config.job_notifications_enabled,
     )
     .await
     .context("get_prover_tasks()")?;
@@ -170,7 +171,9 @@ async fn get_prover_tasks(
     public_blob_store: Option<Box<dyn ObjectStore>>,
     pool: ConnectionPool,
     circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+    job_notifications_enabled: bool,
 ) -> anyhow::Result<Vec<JoinHandle<anyhow::Result<()>>>> {
+    use zksync_dal::job_notifications::{JobNotificationChannel, JobNotificationsListener};
     use zksync_vk_setup_data_server_fri::commitment_utils::get_cached_commitments;
 
     use crate::prover_job_processor::{load_setup_data_cache, Prover};
@@ -182,6 +185,18 @@ async fn get_prover_tasks(
         vk_commitments
     );
 
+    let mut tasks = vec![];
+    let new_jobs_notifier = if job_notifications_enabled {
+        let listener = JobNotificationsListener::new(&pool, &[JobNotificationChannel::ProverJobs])
+            .await
+            .context("failed creating job notifications listener")?;
+        let notifier = listener.notifier();
+        tasks.push(tokio::spawn(listener.run(stop_receiver.clone())));
+        Some(notifier)
+    } else {
+        None
+    };
+
     let setup_load_mode =
         load_setup_data_cache(&prover_config).context("load_setup_data_cache()")?;
     let prover = Prover::new(
@@ -192,8 +207,10 @@ async fn get_prover_tasks(
         setup_load_mode,
         circuit_ids_for_round_to_be_proven,
         vk_commitments,
+        new_jobs_notifier,
     );
-    Ok(vec![tokio::spawn(prover.run(stop_receiver, None))])
+    tasks.push(tokio::spawn(prover.run(stop_receiver, None)));
+    Ok(tasks)
 }
 
 #[cfg(feature = "gpu")]
@@ -204,6 +221,8 @@ async fn get_prover_tasks(
     public_blob_store: Option<Box<dyn ObjectStore>>,
     pool: ConnectionPool,
     circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+    // The GPU prover receives witness vectors via a socket rather than polling Postgres.
+    _job_notifications_enabled: bool,
 ) -> anyhow::Result<Vec<JoinHandle<anyhow::Result<()>>>> {
     use std::sync::Arc;
 
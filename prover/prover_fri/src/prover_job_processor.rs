@@ -1,7 +1,7 @@
 use std::{collections::HashMap, sync::Arc, time::Instant};
 
 use anyhow::Context as _;
-use tokio::task::JoinHandle;
+use tokio::{sync::Notify, task::JoinHandle};
 use zkevm_test_harness::prover_utils::{prove_base_layer_circuit, prove_recursion_layer_circuit};
 use zksync_config::configs::{fri_prover_group::FriProverGroupConfig, FriProverConfig};
 use zksync_dal::ConnectionPool;
@@ -52,6 +52,7 @@ pub struct Prover {
     // Empty means all jobs are picked.
     circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
     vk_commitments: L1VerifierConfig,
+    new_jobs_notifier: Option<Arc<Notify>>,
 }
 
 impl Prover {
@@ -64,6 +65,7 @@ impl Prover {
         setup_load_mode: SetupLoadMode,
         circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
         vk_commitments: L1VerifierConfig,
+        new_jobs_notifier: Option<Arc<Notify>>,
     ) -> Self {
         Prover {
             blob_store,
@@ -73,6 +75,7 @@ impl Prover {
             setup_load_mode,
             circuit_ids_for_round_to_be_proven,
             vk_commitments,
+            new_jobs_notifier,
         }
     }
 
@@ -196,6 +199,10 @@ impl JobProcessor for Prover {
     type JobArtifacts = ProverArtifacts;
     const SERVICE_NAME: &'static str = "FriCpuProver";
 
+    fn new_jobs_notifier(&self) -> Option<&Notify> {
+        self.new_jobs_notifier.as_deref()
+    }
+
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
         let mut storage = self.prover_connection_pool.access_storage().await.unwrap();
         let Some(prover_job) = fetch_next_circuit(
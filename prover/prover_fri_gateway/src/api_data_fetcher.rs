@@ -1,11 +1,24 @@
-use std::time::Duration;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use anyhow::Context as _;
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{
+    header::{HeaderValue, AUTHORIZATION},
+    Body, Client, Request,
+};
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::{sync::watch, time::sleep};
+use zksync_config::configs::{
+    proof_data_handler::ProofDataHandlerAuthMode, FriProverGatewayConfig,
+};
 use zksync_dal::ConnectionPool;
 use zksync_object_store::ObjectStore;
+use zksync_types::prover_server_api::auth::{
+    sign_request, NONCE_HEADER, PROVER_ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
 
 use crate::metrics::METRICS;
 
@@ -15,12 +28,78 @@ pub(crate) const PROOF_GENERATION_DATA_PATH: &str = "/proof_generation_data";
 /// The path to the API endpoint that submits the proof.
 pub(crate) const SUBMIT_PROOF_PATH: &str = "/submit_proof";
 
+/// Credentials used to authenticate requests to the proof data handler API.
+#[derive(Debug, Clone)]
+pub(crate) struct ApiCredentials {
+    mode: ProofDataHandlerAuthMode,
+    prover_id: String,
+    secret: String,
+}
+
+impl ApiCredentials {
+    /// Returns `None` if authentication is disabled.
+    pub(crate) fn from_config(config: &FriProverGatewayConfig) -> anyhow::Result<Option<Self>> {
+        if config.auth_mode == ProofDataHandlerAuthMode::None {
+            return Ok(None);
+        }
+        let prover_id = config
+            .prover_id
+            .clone()
+            .context("prover ID is required for authentication")?;
+        let secret = config
+            .prover_secret
+            .clone()
+            .context("prover secret is required for authentication")?;
+        Ok(Some(Self {
+            mode: config.auth_mode,
+            prover_id,
+            secret,
+        }))
+    }
+
+    fn authenticate(&self, request: &mut Request) {
+        let prover_id = HeaderValue::from_str(&self.prover_id).expect("invalid prover ID");
+        request.headers_mut().insert(PROVER_ID_HEADER, prover_id);
+        match self.mode {
+            ProofDataHandlerAuthMode::None => { /* requests are not authenticated */ }
+            ProofDataHandlerAuthMode::ApiKey => {
+                let api_key = HeaderValue::from_str(&format!("Bearer {}", self.secret))
+                    .expect("invalid prover secret");
+                request.headers_mut().insert(AUTHORIZATION, api_key);
+            }
+            ProofDataHandlerAuthMode::Hmac => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("invalid system time");
+                let timestamp = now.as_secs();
+                let nonce = request_nonce(now);
+                let body = request.body().and_then(Body::as_bytes).unwrap_or_default();
+                let path = request.url().path();
+                let signature = sign_request(&self.secret, timestamp, &nonce, path, body);
+                let headers = request.headers_mut();
+                headers.insert(TIMESTAMP_HEADER, HeaderValue::from(timestamp));
+                headers.insert(NONCE_HEADER, HeaderValue::from_str(&nonce).unwrap());
+                headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(&signature).unwrap());
+            }
+        }
+    }
+}
+
+/// Generates a nonce for an HMAC-signed request. Nonces only need to be unique for the prover,
+/// so the current time combined with a counter is enough.
+fn request_nonce(now: Duration) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{counter}", now.as_nanos())
+}
+
 pub(crate) struct PeriodicApiStruct {
     pub(crate) blob_store: Box<dyn ObjectStore>,
     pub(crate) pool: ConnectionPool,
     pub(crate) api_url: String,
     pub(crate) poll_duration: Duration,
     pub(crate) client: Client,
+    pub(crate) credentials: Option<ApiCredentials>,
 }
 
 impl PeriodicApiStruct {
@@ -35,10 +114,12 @@ impl PeriodicApiStruct {
     {
         tracing::info!("Sending request to {}", endpoint);
 
+        let mut request = self.client.post(endpoint).json(&request).build()?;
+        if let Some(credentials) = &self.credentials {
+            credentials.authenticate(&mut request);
+        }
         self.client
-            .post(endpoint)
-            .json(&request)
-            .send()
+            .execute(request)
             .await?
             .error_for_status()?
             .json::<Resp>()
@@ -9,7 +9,9 @@ use zksync_object_store::ObjectStoreFactory;
 use zksync_types::prover_server_api::{ProofGenerationDataRequest, SubmitProofRequest};
 use zksync_utils::wait_for_tasks::wait_for_tasks;
 
-use crate::api_data_fetcher::{PeriodicApiStruct, PROOF_GENERATION_DATA_PATH, SUBMIT_PROOF_PATH};
+use crate::api_data_fetcher::{
+    ApiCredentials, PeriodicApiStruct, PROOF_GENERATION_DATA_PATH, SUBMIT_PROOF_PATH,
+};
 
 mod api_data_fetcher;
 mod metrics;
@@ -47,6 +49,8 @@ async fn main() -> anyhow::Result<()> {
     let object_store_config =
         ProverObjectStoreConfig::from_env().context("ProverObjectStoreConfig::from_env()")?;
     let store_factory = ObjectStoreFactory::new(object_store_config.0);
+    let credentials =
+        ApiCredentials::from_config(&config).context("invalid API authentication config")?;
 
     let proof_submitter = PeriodicApiStruct {
         blob_store: store_factory.create_store().await,
@@ -54,6 +58,7 @@ async fn main() -> anyhow::Result<()> {
         api_url: format!("{}{SUBMIT_PROOF_PATH}", config.api_url),
         poll_duration: config.api_poll_duration(),
         client: Client::new(),
+        credentials: credentials.clone(),
     };
     let proof_gen_data_fetcher = PeriodicApiStruct {
         blob_store: store_factory.create_store().await,
@@ -61,6 +66,7 @@ async fn main() -> anyhow::Result<()> {
         api_url: format!("{}{PROOF_GENERATION_DATA_PATH}", config.api_url),
         poll_duration: config.api_poll_duration(),
         client: Client::new(),
+        credentials,
     };
 
     let (stop_sender, stop_receiver) = watch::channel(false);
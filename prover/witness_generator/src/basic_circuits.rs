@@ -12,6 +12,7 @@ use multivm::vm_latest::{
 };
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
 use zkevm_test_harness::{
     geometry_config::get_geometry_config,
     toolset::GeometryConfig,
@@ -92,6 +93,7 @@ pub struct BasicWitnessGenerator {
     connection_pool: ConnectionPool,
     prover_connection_pool: ConnectionPool,
     protocol_versions: Vec<FriProtocolVersionId>,
+    new_jobs_notifier: Option<Arc<Notify>>,
 }
 
 impl BasicWitnessGenerator {
@@ -102,6 +104,7 @@ impl BasicWitnessGenerator {
         connection_pool: ConnectionPool,
         prover_connection_pool: ConnectionPool,
         protocol_versions: Vec<FriProtocolVersionId>,
+        new_jobs_notifier: Option<Arc<Notify>>,
     ) -> Self {
         Self {
             config: Arc::new(config),
@@ -110,6 +113,7 @@ impl BasicWitnessGenerator {
             connection_pool,
             prover_connection_pool,
             protocol_versions,
+            new_jobs_notifier,
         }
     }
 
@@ -184,6 +188,10 @@ impl JobProcessor for BasicWitnessGenerator {
 
     const SERVICE_NAME: &'static str = "fri_basic_circuit_witness_generator";
 
+    fn new_jobs_notifier(&self) -> Option<&Notify> {
+        self.new_jobs_notifier.as_deref()
+    }
+
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
         let mut prover_connection = self.prover_connection_pool.access_storage().await.unwrap();
         let last_l1_batch_to_process = self.config.last_l1_batch_to_process();
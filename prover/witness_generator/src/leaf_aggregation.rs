@@ -1,7 +1,8 @@
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
+use tokio::sync::Notify;
 use zkevm_test_harness::witness::recursive_aggregation::{
     compute_leaf_params, create_leaf_witnesses,
 };
@@ -75,6 +76,7 @@ pub struct LeafAggregationWitnessGenerator {
     object_store: Box<dyn ObjectStore>,
     prover_connection_pool: ConnectionPool,
     protocol_versions: Vec<FriProtocolVersionId>,
+    new_jobs_notifier: Option<Arc<Notify>>,
 }
 
 impl LeafAggregationWitnessGenerator {
@@ -83,12 +85,14 @@ impl LeafAggregationWitnessGenerator {
         store_factory: &ObjectStoreFactory,
         prover_connection_pool: ConnectionPool,
         protocol_versions: Vec<FriProtocolVersionId>,
+        new_jobs_notifier: Option<Arc<Notify>>,
     ) -> Self {
         Self {
             config,
             object_store: store_factory.create_store().await,
             prover_connection_pool,
             protocol_versions,
+            new_jobs_notifier,
         }
     }
 
@@ -114,6 +118,10 @@ impl JobProcessor for LeafAggregationWitnessGenerator {
 
     const SERVICE_NAME: &'static str = "fri_leaf_aggregation_witness_generator";
 
+    fn new_jobs_notifier(&self) -> Option<&Notify> {
+        self.new_jobs_notifier.as_deref()
+    }
+
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
         let mut prover_connection = self.prover_connection_pool.access_storage().await.unwrap();
         let pod_name = get_current_pod_name();
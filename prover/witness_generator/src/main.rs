@@ -10,7 +10,10 @@ use zksync_config::{
     configs::{FriWitnessGeneratorConfig, PostgresConfig, PrometheusConfig},
     ObjectStoreConfig,
 };
-use zksync_dal::ConnectionPool;
+use zksync_dal::{
+    job_notifications::{JobNotificationChannel, JobNotificationsListener},
+    ConnectionPool,
+};
 use zksync_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_utils::get_stop_signal_receiver;
@@ -53,6 +56,16 @@ struct Opt {
     all_rounds: bool,
 }
 
+/// Returns the Postgres channel notifying about queued jobs for the specified aggregation round.
+fn job_notification_channel(round: AggregationRound) -> JobNotificationChannel {
+    match round {
+        AggregationRound::BasicCircuits => JobNotificationChannel::BasicWitnessInputs,
+        AggregationRound::LeafAggregation => JobNotificationChannel::LeafAggregationJobs,
+        AggregationRound::NodeAggregation => JobNotificationChannel::NodeAggregationJobs,
+        AggregationRound::Scheduler => JobNotificationChannel::SchedulerJobs,
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
@@ -165,6 +178,18 @@ async fn main() -> anyhow::Result<()> {
         };
         let prometheus_task = prometheus_config.run(stop_receiver.clone());
 
+        let new_jobs_notifier = if postgres_config.job_notifications_enabled {
+            let channel = job_notification_channel(*round);
+            let listener = JobNotificationsListener::new(&prover_connection_pool, &[channel])
+                .await
+                .context("failed creating job notifications listener")?;
+            let notifier = listener.notifier();
+            tasks.push(tokio::spawn(listener.run(stop_receiver.clone())));
+            Some(notifier)
+        } else {
+            None
+        };
+
         let witness_generator_task = match round {
             AggregationRound::BasicCircuits => {
                 let public_blob_store = match config.shall_save_to_public_bucket {
@@ -185,6 +210,7 @@ async fn main() -> anyhow::Result<()> {
                     connection_pool.clone(),
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
+                    new_jobs_notifier,
                 )
                 .await;
                 generator.run(stop_receiver.clone(), opt.batch_size)
@@ -195,6 +221,7 @@ async fn main() -> anyhow::Result<()> {
                     &store_factory,
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
+                    new_jobs_notifier,
                 )
                 .await;
                 generator.run(stop_receiver.clone(), opt.batch_size)
@@ -205,6 +232,7 @@ async fn main() -> anyhow::Result<()> {
                     &store_factory,
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
+                    new_jobs_notifier,
                 )
                 .await;
                 generator.run(stop_receiver.clone(), opt.batch_size)
@@ -215,6 +243,7 @@ async fn main() -> anyhow::Result<()> {
                     &store_factory,
                     prover_connection_pool.clone(),
                     protocol_versions.clone(),
+                    new_jobs_notifier,
                 )
                 .await;
                 generator.run(stop_receiver.clone(), opt.batch_size)
@@ -1,7 +1,8 @@
-use std::{convert::TryInto, time::Instant};
+use std::{convert::TryInto, sync::Arc, time::Instant};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
+use tokio::sync::Notify;
 use zksync_config::configs::FriWitnessGeneratorConfig;
 use zksync_dal::ConnectionPool;
 use zksync_object_store::{FriCircuitKey, ObjectStore, ObjectStoreFactory};
@@ -54,6 +55,7 @@ pub struct SchedulerWitnessGenerator {
     object_store: Box<dyn ObjectStore>,
     prover_connection_pool: ConnectionPool,
     protocol_versions: Vec<FriProtocolVersionId>,
+    new_jobs_notifier: Option<Arc<Notify>>,
 }
 
 impl SchedulerWitnessGenerator {
@@ -62,12 +64,14 @@ impl SchedulerWitnessGenerator {
         store_factory: &ObjectStoreFactory,
         prover_connection_pool: ConnectionPool,
         protocol_versions: Vec<FriProtocolVersionId>,
+        new_jobs_notifier: Option<Arc<Notify>>,
     ) -> Self {
         Self {
             config,
             object_store: store_factory.create_store().await,
             prover_connection_pool,
             protocol_versions,
+            new_jobs_notifier,
         }
     }
 
@@ -116,6 +120,10 @@ impl JobProcessor for SchedulerWitnessGenerator {
 
     const SERVICE_NAME: &'static str = "fri_scheduler_witness_generator";
 
+    fn new_jobs_notifier(&self) -> Option<&Notify> {
+        self.new_jobs_notifier.as_deref()
+    }
+
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
         let mut prover_connection = self.prover_connection_pool.access_storage().await.unwrap();
         let pod_name = get_current_pod_name();
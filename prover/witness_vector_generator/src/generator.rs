@@ -1,8 +1,11 @@
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
-use tokio::{task::JoinHandle, time::sleep};
+use tokio::{sync::Notify, task::JoinHandle, time::sleep};
 use zksync_config::configs::FriWitnessVectorGeneratorConfig;
 use zksync_dal::ConnectionPool;
 use zksync_object_store::ObjectStore;
@@ -31,6 +34,7 @@ pub struct WitnessVectorGenerator {
     config: FriWitnessVectorGeneratorConfig,
     vk_commitments: L1VerifierConfig,
     max_attempts: u32,
+    new_jobs_notifier: Option<Arc<Notify>>,
 }
 
 impl WitnessVectorGenerator {
@@ -42,6 +46,7 @@ impl WitnessVectorGenerator {
         config: FriWitnessVectorGeneratorConfig,
         vk_commitments: L1VerifierConfig,
         max_attempts: u32,
+        new_jobs_notifier: Option<Arc<Notify>>,
     ) -> Self {
         Self {
             blob_store,
@@ -51,6 +56,7 @@ impl WitnessVectorGenerator {
             config,
             vk_commitments,
             max_attempts,
+            new_jobs_notifier,
         }
     }
 
@@ -79,6 +85,10 @@ impl JobProcessor for WitnessVectorGenerator {
     type JobArtifacts = WitnessVectorArtifacts;
     const SERVICE_NAME: &'static str = "WitnessVectorGenerator";
 
+    fn new_jobs_notifier(&self) -> Option<&Notify> {
+        self.new_jobs_notifier.as_deref()
+    }
+
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
         let mut storage = self.pool.access_storage().await.unwrap();
         let Some(job) = fetch_next_circuit(
@@ -8,7 +8,10 @@ use zksync_config::configs::{
     fri_prover_group::FriProverGroupConfig, FriProverConfig, FriWitnessVectorGeneratorConfig,
     PostgresConfig, ProverGroupConfig,
 };
-use zksync_dal::ConnectionPool;
+use zksync_dal::{
+    job_notifications::{JobNotificationChannel, JobNotificationsListener},
+    ConnectionPool,
+};
 use zksync_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_fri_utils::get_all_circuit_id_round_tuples_for;
@@ -81,6 +84,17 @@ async fn main() -> anyhow::Result<()> {
     let zone = get_zone(&prover_group_config).await.context("get_zone()")?;
     let vk_commitments = get_cached_commitments();
     let fri_prover_config = FriProverConfig::from_env().context("FriProverConfig::from_env()")?;
+    let job_notifications_listener = if postgres_config.job_notifications_enabled {
+        let listener = JobNotificationsListener::new(&pool, &[JobNotificationChannel::ProverJobs])
+            .await
+            .context("failed creating job notifications listener")?;
+        Some(listener)
+    } else {
+        None
+    };
+    let new_jobs_notifier = job_notifications_listener
+        .as_ref()
+        .map(JobNotificationsListener::notifier);
     let witness_vector_generator = WitnessVectorGenerator::new(
         blob_store,
         pool,
@@ -89,6 +103,7 @@ async fn main() -> anyhow::Result<()> {
         config,
         vk_commitments,
         fri_prover_config.max_attempts,
+        new_jobs_notifier,
     );
 
     let (stop_sender, stop_receiver) = watch::channel(false);
@@ -104,10 +119,13 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Starting witness vector generation for group: {} with circuits: {:?} in zone: {} with vk_commitments: {:?}", specialized_group_id, circuit_ids_for_round_to_be_proven, zone, vk_commitments);
 
-    let tasks = vec![
-        tokio::spawn(exporter_config.run(stop_receiver.clone())),
-        tokio::spawn(witness_vector_generator.run(stop_receiver, opt.number_of_iterations)),
-    ];
+    let mut tasks = vec![tokio::spawn(exporter_config.run(stop_receiver.clone()))];
+    if let Some(listener) = job_notifications_listener {
+        tasks.push(tokio::spawn(listener.run(stop_receiver.clone())));
+    }
+    tasks.push(tokio::spawn(
+        witness_vector_generator.run(stop_receiver, opt.number_of_iterations),
+    ));
 
     let graceful_shutdown = None::<futures::future::Ready<()>>;
     let tasks_allowed_to_finish = false;
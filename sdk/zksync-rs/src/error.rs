@@ -39,6 +39,9 @@ pub enum ClientError {
     #[error("Provided function arguments are incorrect")]
     IncorrectInput,
 
+    #[error("Withdrawal was not found in transaction {0:?}")]
+    WithdrawalNotFound(zksync_types::H256),
+
     #[error("Other")]
     Other,
 }
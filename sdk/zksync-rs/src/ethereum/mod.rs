@@ -11,7 +11,7 @@ use zksync_eth_client::{
 };
 use zksync_eth_signer::EthereumSigner;
 use zksync_types::{
-    api::BridgeAddresses,
+    api::{self, BridgeAddresses},
     l1::L1Tx,
     network::Network,
     web3::{
@@ -20,8 +20,10 @@ use zksync_types::{
         transports::Http,
         types::{TransactionReceipt, H160, H256, U256},
     },
-    Address, L1ChainId, L1TxCommonData, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE,
+    Address, L1BatchNumber, L1ChainId, L1TxCommonData, L1_MESSENGER_ADDRESS, L2_ETH_TOKEN_ADDRESS,
+    REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE,
 };
+use zksync_utils::h256_to_account_address;
 use zksync_web3_decl::namespaces::{EthNamespaceClient, ZksNamespaceClient};
 
 use crate::{
@@ -40,6 +42,67 @@ const RAW_ERC20_DEPOSIT_GAS_LIMIT: &str = include_str!("DepositERC20GasLimit.jso
 // as an optimal one. In the future, it will be estimated.
 const L1_TO_L2_GAS_PER_PUBDATA: u32 = 800;
 
+/// Returns the signature of the `L1MessageSent` event emitted by the L1 messenger system contract.
+fn l1_message_sent_signature() -> H256 {
+    ethabi::long_signature(
+        "L1MessageSent",
+        &[
+            ethabi::ParamType::Address,
+            ethabi::ParamType::FixedBytes(32),
+            ethabi::ParamType::Bytes,
+        ],
+    )
+}
+
+/// L2->L1 message sent by a withdrawal transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawalMessage {
+    /// Sender of the message: `L2_ETH_TOKEN_ADDRESS` for ETH withdrawals, or the L2 bridge address.
+    pub sender: Address,
+    /// Message contents.
+    pub message: Vec<u8>,
+    /// Index of the corresponding L2->L1 log among logs emitted by the transaction.
+    pub log_index: usize,
+}
+
+impl WithdrawalMessage {
+    /// Finds the first withdrawal message in the L2 transaction receipt.
+    pub fn from_receipt(receipt: &api::TransactionReceipt) -> Option<Self> {
+        let signature = l1_message_sent_signature();
+        let event = receipt.logs.iter().find(|log| {
+            log.address == L1_MESSENGER_ADDRESS && log.topics.first() == Some(&signature)
+        })?;
+        let sender_topic = *event.topics.get(1)?;
+        let mut tokens = ethabi::decode(&[ethabi::ParamType::Bytes], &event.data.0).ok()?;
+        let message = tokens.pop()?.into_bytes()?;
+        let log_index = receipt
+            .l2_to_l1_logs
+            .iter()
+            .position(|log| log.sender == L1_MESSENGER_ADDRESS && log.key == sender_topic)?;
+
+        Some(Self {
+            sender: h256_to_account_address(&sender_topic),
+            message,
+            log_index,
+        })
+    }
+}
+
+/// Parameters of the L1 call finalizing a withdrawal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinalizeWithdrawalParams {
+    /// Withdrawal message sent by the L2 transaction.
+    pub message: WithdrawalMessage,
+    /// Number of the L1 batch containing the withdrawal transaction.
+    pub l1_batch_number: L1BatchNumber,
+    /// Index of the L2->L1 message in the batch.
+    pub l2_message_index: u32,
+    /// Index of the withdrawal transaction in the batch.
+    pub l2_tx_number_in_batch: u16,
+    /// Merkle proof of the message inclusion.
+    pub proof: Vec<H256>,
+}
+
 /// Returns `ethabi::Contract` object for zkSync smart contract.
 pub fn zksync_contract() -> ethabi::Contract {
     load_contract(ZKSYNC_INTERFACE)
@@ -66,6 +129,7 @@ pub struct EthereumProvider<S: EthereumSigner> {
     erc20_abi: ethabi::Contract,
     l1_bridge_abi: ethabi::Contract,
     confirmation_timeout: Duration,
+    execution_timeout: Duration,
     polling_interval: Duration,
 }
 
@@ -118,6 +182,7 @@ impl<S: EthereumSigner> EthereumProvider<S> {
             erc20_abi,
             l1_bridge_abi,
             confirmation_timeout: Duration::from_secs(10),
+            execution_timeout: Duration::from_secs(24 * 60 * 60),
             polling_interval: Duration::from_secs(1),
         })
     }
@@ -575,10 +640,216 @@ impl<S: EthereumSigner> EthereumProvider<S> {
         self.confirmation_timeout = timeout;
     }
 
+    /// Sets the timeout to wait for an L1 batch with a withdrawal to be executed on L1 when finalizing the withdrawal.
+    /// By default it is set to 1 day, since batches may be executed with a delay.
+    pub fn set_execution_timeout(&mut self, timeout: Duration) {
+        self.execution_timeout = timeout;
+    }
+
     pub fn set_polling_interval(&mut self, polling_interval: Duration) {
         self.polling_interval = polling_interval;
     }
 
+    /// Returns the L1 bridge for the withdrawal message sender, or `None` for ETH withdrawals,
+    /// which are finalized by the zkSync contract.
+    fn l1_bridge_for_withdrawal(&self, sender: Address) -> Result<Option<Address>, ClientError> {
+        let bridges = &self.default_bridges;
+        if sender == L2_ETH_TOKEN_ADDRESS {
+            Ok(None)
+        } else if sender == bridges.l2_erc20_default_bridge {
+            Ok(Some(bridges.l1_erc20_default_bridge))
+        } else if Some(sender) == bridges.l2_weth_bridge && bridges.l1_weth_bridge.is_some() {
+            Ok(bridges.l1_weth_bridge)
+        } else {
+            Err(ClientError::IncorrectAddress)
+        }
+    }
+
+    /// Returns parameters required to finalize the withdrawal, or `None` if the withdrawal transaction
+    /// is not included into an L1 batch yet.
+    pub async fn finalize_withdrawal_params<P>(
+        &self,
+        provider: &P,
+        withdrawal_hash: H256,
+    ) -> Result<Option<FinalizeWithdrawalParams>, ClientError>
+    where
+        P: EthNamespaceClient + ZksNamespaceClient + Sync,
+    {
+        let receipt = provider
+            .get_transaction_receipt(withdrawal_hash)
+            .await?
+            .ok_or(ClientError::WithdrawalNotFound(withdrawal_hash))?;
+        let (Some(l1_batch_number), Some(l1_batch_tx_index)) =
+            (receipt.l1_batch_number, receipt.l1_batch_tx_index)
+        else {
+            return Ok(None);
+        };
+        let message = WithdrawalMessage::from_receipt(&receipt)
+            .ok_or(ClientError::WithdrawalNotFound(withdrawal_hash))?;
+        let Some(proof) = provider
+            .get_l2_to_l1_log_proof(withdrawal_hash, Some(message.log_index))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let l2_tx_number_in_batch = u16::try_from(l1_batch_tx_index.as_u64()).map_err(|_| {
+            ClientError::MalformedResponse("Transaction index in batch overflow".to_owned())
+        })?;
+        Ok(Some(FinalizeWithdrawalParams {
+            message,
+            l1_batch_number: L1BatchNumber(l1_batch_number.as_u32()),
+            l2_message_index: proof.id,
+            l2_tx_number_in_batch,
+            proof: proof.proof,
+        }))
+    }
+
+    /// Waits until the L1 batch is executed on L1.
+    async fn wait_for_batch_execution<P>(
+        &self,
+        provider: &P,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), ClientError>
+    where
+        P: ZksNamespaceClient + Sync,
+    {
+        let mut poller = tokio::time::interval(self.polling_interval);
+        let start = Instant::now();
+        loop {
+            let details = provider.get_l1_batch_details(l1_batch_number).await?;
+            if details.map_or(false, |details| details.base.executed_at.is_some()) {
+                return Ok(());
+            }
+
+            if start.elapsed() > self.execution_timeout {
+                return Err(ClientError::OperationTimeout);
+            }
+            poller.tick().await;
+        }
+    }
+
+    /// Finalizes a withdrawal started on L2 by calling the zkSync contract (for ETH withdrawals)
+    /// or the corresponding L1 bridge. Waits until the L1 batch with the withdrawal is executed on L1.
+    pub async fn finalize_withdrawal<P>(
+        &self,
+        provider: &P,
+        withdrawal_hash: H256,
+    ) -> Result<H256, ClientError>
+    where
+        P: EthNamespaceClient + ZksNamespaceClient + Sync,
+    {
+        let mut poller = tokio::time::interval(self.polling_interval);
+        let start = Instant::now();
+        let params = loop {
+            if let Some(params) = self
+                .finalize_withdrawal_params(provider, withdrawal_hash)
+                .await?
+            {
+                break params;
+            }
+
+            if start.elapsed() > self.execution_timeout {
+                return Err(ClientError::OperationTimeout);
+            }
+            poller.tick().await;
+        };
+        self.wait_for_batch_execution(provider, params.l1_batch_number)
+            .await?;
+
+        let bridge = self.l1_bridge_for_withdrawal(params.message.sender)?;
+        let (contract_address, function) = match bridge {
+            Some(bridge) => (bridge, self.l1_bridge_abi.function("finalizeWithdrawal")),
+            None => (
+                self.contract_address(),
+                self.eth_client.contract().function("finalizeEthWithdrawal"),
+            ),
+        };
+        let function = function.expect("failed to get function parameters");
+        let proof = params
+            .proof
+            .iter()
+            .map(|hash| ethabi::Token::FixedBytes(hash.as_bytes().to_vec()))
+            .collect();
+        let data = function
+            .encode_input(&[
+                ethabi::Token::Uint(params.l1_batch_number.0.into()),
+                ethabi::Token::Uint(params.l2_message_index.into()),
+                ethabi::Token::Uint(params.l2_tx_number_in_batch.into()),
+                ethabi::Token::Bytes(params.message.message),
+                ethabi::Token::Array(proof),
+            ])
+            .expect("failed to encode parameters");
+
+        let signed_tx = self
+            .eth_client
+            .sign_prepared_tx_for_addr(
+                data,
+                contract_address,
+                Options {
+                    gas: Some(300_000.into()),
+                    ..Default::default()
+                },
+                "provider",
+            )
+            .await
+            .map_err(|_| ClientError::IncorrectCredentials)?;
+        self.eth_client
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))
+    }
+
+    /// Checks whether the withdrawal is finalized on L1. Returns `false` if the withdrawal transaction
+    /// is not included into an L1 batch yet.
+    pub async fn is_withdrawal_finalized<P>(
+        &self,
+        provider: &P,
+        withdrawal_hash: H256,
+    ) -> Result<bool, ClientError>
+    where
+        P: EthNamespaceClient + ZksNamespaceClient + Sync,
+    {
+        let Some(params) = self
+            .finalize_withdrawal_params(provider, withdrawal_hash)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let args = (
+            U256::from(params.l1_batch_number.0),
+            U256::from(params.l2_message_index),
+        );
+        let is_finalized = match self.l1_bridge_for_withdrawal(params.message.sender)? {
+            Some(bridge) => {
+                self.eth_client
+                    .call_contract_function(
+                        "isWithdrawalFinalized",
+                        args,
+                        None,
+                        Options::default(),
+                        None,
+                        bridge,
+                        self.l1_bridge_abi.clone(),
+                    )
+                    .await
+            }
+            None => {
+                self.eth_client
+                    .call_main_contract_function(
+                        "isEthWithdrawalFinalized",
+                        args,
+                        None,
+                        Options::default(),
+                        None,
+                    )
+                    .await
+            }
+        };
+        is_finalized.map_err(|err| ClientError::NetworkError(err.to_string()))
+    }
+
     /// Waits until the transaction is confirmed by the Ethereum blockchain.
     pub async fn wait_for_tx(&self, tx_hash: H256) -> Result<TransactionReceipt, ClientError> {
         let mut poller = tokio::time::interval(self.polling_interval);
@@ -624,3 +895,74 @@ impl PriorityOpHolder for TransactionReceipt {
             .find_map(|op| L1Tx::try_from(op.clone()).ok().map(|tx| tx.common_data))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{web3::types::Bytes, U64};
+    use zksync_utils::address_to_h256;
+
+    use super::*;
+
+    fn l2_to_l1_log(sender: Address, key: H256) -> api::L2ToL1Log {
+        api::L2ToL1Log {
+            block_hash: None,
+            block_number: U64::zero(),
+            l1_batch_number: None,
+            log_index: U256::zero(),
+            transaction_index: U64::zero(),
+            transaction_hash: H256::zero(),
+            transaction_log_index: U256::zero(),
+            tx_index_in_l1_batch: None,
+            shard_id: U64::zero(),
+            is_service: false,
+            sender,
+            key,
+            value: H256::zero(),
+        }
+    }
+
+    #[test]
+    fn finding_withdrawal_message_in_receipt() {
+        let sender_topic = address_to_h256(&L2_ETH_TOKEN_ADDRESS);
+        let message = b"withdrawal".to_vec();
+        let event = api::Log {
+            address: L1_MESSENGER_ADDRESS,
+            topics: vec![
+                l1_message_sent_signature(),
+                sender_topic,
+                H256::repeat_byte(1),
+            ],
+            data: Bytes(ethabi::encode(&[ethabi::Token::Bytes(message.clone())])),
+            block_hash: None,
+            block_number: None,
+            l1_batch_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let mut receipt = api::TransactionReceipt {
+            logs: vec![event],
+            l2_to_l1_logs: vec![
+                l2_to_l1_log(Address::repeat_byte(1), H256::zero()),
+                l2_to_l1_log(L1_MESSENGER_ADDRESS, sender_topic),
+            ],
+            ..api::TransactionReceipt::default()
+        };
+
+        let withdrawal = WithdrawalMessage::from_receipt(&receipt).unwrap();
+        assert_eq!(
+            withdrawal,
+            WithdrawalMessage {
+                sender: L2_ETH_TOKEN_ADDRESS,
+                message,
+                log_index: 1,
+            }
+        );
+
+        receipt.logs[0].topics[0] = H256::zero();
+        assert!(WithdrawalMessage::from_receipt(&receipt).is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use zksync_eth_signer::EthereumSigner;
 use zksync_types::{
-    l2::L2Tx, transaction_request::PaymasterParams, Execute, Nonce, CONTRACT_DEPLOYER_ADDRESS, U256,
+    l2::L2Tx, transaction_request::PaymasterParams, utils::deployed_address_create2, web3::ethabi,
+    Address, Nonce, CONTRACT_DEPLOYER_ADDRESS, H256, U256,
 };
 use zksync_utils::bytecode::hash_bytecode;
 
@@ -9,6 +10,48 @@ use crate::{
     EthNamespaceClient, ZksNamespaceClient,
 };
 
+/// Version of the account abstraction protocol used for accounts deployed via `createAccount` / `create2Account`
+/// (corresponds to `IContractDeployer.AccountAbstractionVersion.Version1`).
+const ACCOUNT_ABSTRACTION_VERSION: u8 = 1;
+
+/// Encodes the calldata for the `ContractDeployer` system contract.
+///
+/// Depending on whether `salt` is provided and whether the deployed contract is an account, this invokes
+/// `create`, `create2`, `createAccount` or `create2Account` method.
+fn encode_deploy_calldata(
+    salt: Option<H256>,
+    is_account: bool,
+    bytecode_hash: H256,
+    constructor_input: Vec<u8>,
+) -> Vec<u8> {
+    // TODO (SMA-1608): Do not implement the ABI manually, introduce ABI files with an update script similarly to
+    //  how it's done for L1 part of SDK.
+    let function_name = match (salt.is_some(), is_account) {
+        (false, false) => "create",
+        (true, false) => "create2",
+        (false, true) => "createAccount",
+        (true, true) => "create2Account",
+    };
+    let mut params = vec![
+        ethabi::ParamType::FixedBytes(32),
+        ethabi::ParamType::FixedBytes(32),
+        ethabi::ParamType::Bytes,
+    ];
+    let mut tokens = vec![
+        ethabi::Token::FixedBytes(salt.unwrap_or_default().as_bytes().to_vec()),
+        ethabi::Token::FixedBytes(bytecode_hash.as_bytes().to_vec()),
+        ethabi::Token::Bytes(constructor_input),
+    ];
+    if is_account {
+        params.push(ethabi::ParamType::Uint(8));
+        tokens.push(ethabi::Token::Uint(ACCOUNT_ABSTRACTION_VERSION.into()));
+    }
+
+    let mut calldata = ethabi::short_signature(function_name, &params).to_vec();
+    calldata.append(&mut ethabi::encode(&tokens));
+    calldata
+}
+
 pub struct DeployContractBuilder<'a, S: EthereumSigner, P> {
     wallet: &'a Wallet<S, P>,
     bytecode: Option<Vec<u8>>,
@@ -18,6 +61,8 @@ pub struct DeployContractBuilder<'a, S: EthereumSigner, P> {
     value: Option<U256>,
     factory_deps: Option<Vec<Vec<u8>>>,
     paymaster_params: Option<PaymasterParams>,
+    salt: Option<H256>,
+    is_account: bool,
 }
 
 impl<'a, S, P> DeployContractBuilder<'a, S, P>
@@ -36,6 +81,8 @@ where
             value: None,
             factory_deps: None,
             paymaster_params: None,
+            salt: None,
+            is_account: false,
         }
     }
 
@@ -61,10 +108,10 @@ where
 
         let main_contract_hash = hash_bytecode(&bytecode);
         let execute_calldata =
-            Execute::encode_deploy_params_create(Default::default(), main_contract_hash, calldata);
+            encode_deploy_calldata(self.salt, self.is_account, main_contract_hash, calldata);
 
         let mut factory_deps = self.factory_deps.unwrap_or_default();
-        factory_deps.push(bytecode.clone());
+        factory_deps.push(bytecode);
 
         self.wallet
             .signer
@@ -73,7 +120,7 @@ where
                 execute_calldata,
                 fee,
                 nonce,
-                Some(vec![bytecode.clone()]),
+                Some(factory_deps),
                 paymaster_params,
             )
             .await
@@ -126,6 +173,40 @@ where
         self
     }
 
+    /// Deploys the contract using CREATE2 with the provided salt. The address of the deployed contract
+    /// can be obtained in advance via [`Self::create2_address()`].
+    pub fn create2(mut self, salt: H256) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    /// Deploys the contract as a smart-contract account (i.e., using `createAccount` or `create2Account`
+    /// methods of the `ContractDeployer`).
+    pub fn account(mut self) -> Self {
+        self.is_account = true;
+        self
+    }
+
+    /// Returns the address the contract will be deployed to. Requires the salt to be set via [`Self::create2()`],
+    /// since addresses of contracts deployed via CREATE depend on the deployment nonce of the wallet.
+    pub fn create2_address(&self) -> Result<Address, ClientError> {
+        let salt = self
+            .salt
+            .ok_or_else(|| ClientError::MissingRequiredField("salt".into()))?;
+        let bytecode = self
+            .bytecode
+            .as_ref()
+            .ok_or_else(|| ClientError::MissingRequiredField("bytecode".into()))?;
+        let calldata = self.calldata.as_deref().unwrap_or_default();
+
+        Ok(deployed_address_create2(
+            self.wallet.address(),
+            salt,
+            hash_bytecode(bytecode),
+            calldata,
+        ))
+    }
+
     pub async fn estimate_fee(
         &self,
         paymaster_params: Option<PaymasterParams>,
@@ -145,7 +226,7 @@ where
         factory_deps.push(bytecode);
         let l2_tx = L2Tx::new(
             CONTRACT_DEPLOYER_ADDRESS,
-            Execute::encode_deploy_params_create(Default::default(), main_contract_hash, calldata),
+            encode_deploy_calldata(self.salt, self.is_account, main_contract_hash, calldata),
             Nonce(0),
             Default::default(),
             self.wallet.address(),
@@ -160,3 +241,42 @@ where
             .map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deploy_calldata_for_create_matches_execute_encoding() {
+        let bytecode_hash = H256::repeat_byte(1);
+        let calldata = encode_deploy_calldata(None, false, bytecode_hash, vec![1, 2, 3]);
+        let expected = zksync_types::Execute::encode_deploy_params_create(
+            H256::zero(),
+            bytecode_hash,
+            vec![1, 2, 3],
+        );
+        assert_eq!(calldata, expected);
+    }
+
+    #[test]
+    fn deploy_calldata_selectors() {
+        let bytecode_hash = H256::repeat_byte(1);
+        let salt = Some(H256::repeat_byte(2));
+        let create2_calldata = encode_deploy_calldata(salt, false, bytecode_hash, vec![]);
+        // `create2(bytes32,bytes32,bytes)`
+        assert_eq!(create2_calldata[..4], [0x3c, 0xda, 0x33, 0x51]);
+        assert_eq!(create2_calldata[4..36], *salt.unwrap().as_bytes());
+
+        let account_calldata = encode_deploy_calldata(None, true, bytecode_hash, vec![]);
+        // `createAccount(bytes32,bytes32,bytes,uint8)`
+        assert_eq!(account_calldata[..4], [0xec, 0xf9, 0x5b, 0x8a]);
+        assert_eq!(
+            U256::from_big_endian(&account_calldata[100..132]),
+            ACCOUNT_ABSTRACTION_VERSION.into()
+        );
+
+        let create2_account_calldata = encode_deploy_calldata(salt, true, bytecode_hash, vec![]);
+        // `create2Account(bytes32,bytes32,bytes,uint8)`
+        assert_eq!(create2_account_calldata[..4], [0x5d, 0x38, 0x27, 0x00]);
+    }
+}
@@ -11,6 +11,7 @@ use zksync_types::{
 pub use self::{
     deploy_contract::DeployContractBuilder,
     execute_contract::ExecuteContractBuilder,
+    multicall::{BatchedCall, MulticallBuilder},
     transfer::{create_transfer_calldata, TransferBuilder},
     withdraw::WithdrawBuilder,
 };
@@ -18,6 +19,7 @@ use crate::{error::ClientError, EthNamespaceClient};
 
 mod deploy_contract;
 mod execute_contract;
+mod multicall;
 mod transfer;
 mod withdraw;
 
@@ -0,0 +1,239 @@
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::{
+    fee::Fee, l2::L2Tx, transaction_request::PaymasterParams, web3::ethabi, Address, Nonce, U256,
+};
+
+use crate::{
+    error::ClientError,
+    operations::{ExecuteContractBuilder, SyncTransactionHandle},
+    wallet::Wallet,
+    EthNamespaceClient, ZksNamespaceClient,
+};
+
+/// Single call batched by [`MulticallBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchedCall {
+    /// Address of the called contract.
+    pub target: Address,
+    /// Calldata of the call.
+    pub calldata: Vec<u8>,
+    /// Value sent with the call.
+    pub value: U256,
+    /// If set, the call is allowed to fail without reverting the entire batch.
+    pub allow_failure: bool,
+}
+
+impl BatchedCall {
+    /// Creates a call without value that must succeed.
+    pub fn new(target: Address, calldata: Vec<u8>) -> Self {
+        Self {
+            target,
+            calldata,
+            value: U256::zero(),
+            allow_failure: false,
+        }
+    }
+
+    /// Sets the value sent with the call.
+    pub fn value(mut self, value: U256) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Allows the call to fail without reverting the entire batch.
+    pub fn allow_failure(mut self) -> Self {
+        self.allow_failure = true;
+        self
+    }
+}
+
+/// Encodes calldata for the `aggregate3Value((address,bool,uint256,bytes)[])` method of a `Multicall3` contract.
+fn encode_multicall_calldata(calls: &[BatchedCall]) -> Vec<u8> {
+    // TODO (SMA-1608): Do not implement the ABI manually, introduce ABI files with an update script similarly to
+    //  how it's done for L1 part of SDK.
+    let call_param = ethabi::ParamType::Tuple(vec![
+        ethabi::ParamType::Address,
+        ethabi::ParamType::Bool,
+        ethabi::ParamType::Uint(256),
+        ethabi::ParamType::Bytes,
+    ]);
+    let params = [ethabi::ParamType::Array(Box::new(call_param))];
+    let calls = calls
+        .iter()
+        .map(|call| {
+            ethabi::Token::Tuple(vec![
+                ethabi::Token::Address(call.target),
+                ethabi::Token::Bool(call.allow_failure),
+                ethabi::Token::Uint(call.value),
+                ethabi::Token::Bytes(call.calldata.clone()),
+            ])
+        })
+        .collect();
+
+    let mut calldata = ethabi::short_signature("aggregate3Value", &params).to_vec();
+    calldata.append(&mut ethabi::encode(&[ethabi::Token::Array(calls)]));
+    calldata
+}
+
+/// Builder for a transaction executing multiple calls atomically via a `Multicall3`-compatible contract.
+///
+/// Calls are executed with the multicall contract as `msg.sender`, so this is only suitable for calls
+/// that do not rely on the sender being the wallet address.
+pub struct MulticallBuilder<'a, S: EthereumSigner, P> {
+    wallet: &'a Wallet<S, P>,
+    multicall_address: Option<Address>,
+    calls: Vec<BatchedCall>,
+    fee: Option<Fee>,
+    nonce: Option<Nonce>,
+    paymaster_params: Option<PaymasterParams>,
+}
+
+impl<'a, S, P> MulticallBuilder<'a, S, P>
+where
+    S: EthereumSigner,
+    P: ZksNamespaceClient + EthNamespaceClient + Sync,
+{
+    /// Initializes a multicall transaction building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            multicall_address: None,
+            calls: vec![],
+            fee: None,
+            nonce: None,
+            paymaster_params: None,
+        }
+    }
+
+    fn get_execute_builder(&self) -> Result<ExecuteContractBuilder<'_, S, P>, ClientError> {
+        let multicall_address = self
+            .multicall_address
+            .ok_or_else(|| ClientError::MissingRequiredField("multicall_address".into()))?;
+        if self.calls.is_empty() {
+            return Err(ClientError::MissingRequiredField("calls".into()));
+        }
+
+        let value = self
+            .calls
+            .iter()
+            .fold(U256::zero(), |acc, call| acc + call.value);
+        let paymaster_params = self.paymaster_params.clone().unwrap_or_default();
+
+        let mut builder = ExecuteContractBuilder::new(self.wallet)
+            .contract_address(multicall_address)
+            .calldata(encode_multicall_calldata(&self.calls))
+            .value(value)
+            .paymaster_params(paymaster_params);
+
+        if let Some(fee) = self.fee.clone() {
+            builder = builder.fee(fee);
+        }
+        if let Some(nonce) = self.nonce {
+            builder = builder.nonce(nonce);
+        }
+
+        Ok(builder)
+    }
+
+    /// Directly returns the signed multicall transaction for the subsequent usage.
+    pub async fn tx(self) -> Result<L2Tx, ClientError> {
+        let builder = self.get_execute_builder()?;
+        builder.tx().await
+    }
+
+    /// Sends the transaction, returning the handle for its awaiting.
+    pub async fn send(self) -> Result<SyncTransactionHandle<'a, P>, ClientError> {
+        let wallet = self.wallet;
+        let tx = self.tx().await?;
+
+        wallet.send_transaction(tx).await
+    }
+
+    /// Sets the address of the `Multicall3`-compatible contract.
+    pub fn multicall_address(mut self, address: Address) -> Self {
+        self.multicall_address = Some(address);
+        self
+    }
+
+    /// Adds a call without value that must succeed to the batch.
+    pub fn call(self, target: Address, calldata: Vec<u8>) -> Self {
+        self.batched_call(BatchedCall::new(target, calldata))
+    }
+
+    /// Adds an arbitrary call to the batch.
+    pub fn batched_call(mut self, call: BatchedCall) -> Self {
+        self.calls.push(call);
+        self
+    }
+
+    /// Set the fee amount.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee(mut self, fee: Fee) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Sets the transaction nonce.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the paymaster parameters.
+    pub fn paymaster_params(mut self, paymaster_params: PaymasterParams) -> Self {
+        self.paymaster_params = Some(paymaster_params);
+        self
+    }
+
+    pub async fn estimate_fee(
+        &self,
+        paymaster_params: Option<PaymasterParams>,
+    ) -> Result<Fee, ClientError> {
+        let builder = self.get_execute_builder()?;
+        builder.estimate_fee(paymaster_params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multicall_calldata() {
+        let calls = [
+            BatchedCall::new(Address::repeat_byte(1), vec![1, 2, 3, 4]),
+            BatchedCall::new(Address::repeat_byte(2), vec![])
+                .value(100.into())
+                .allow_failure(),
+        ];
+        let calldata = encode_multicall_calldata(&calls);
+        // `aggregate3Value((address,bool,uint256,bytes)[])`
+        assert_eq!(calldata[..4], [0x17, 0x4d, 0xea, 0x71]);
+
+        let call_param = ethabi::ParamType::Tuple(vec![
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Bool,
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Bytes,
+        ]);
+        let tokens = ethabi::decode(
+            &[ethabi::ParamType::Array(Box::new(call_param))],
+            &calldata[4..],
+        )
+        .unwrap();
+        let [ethabi::Token::Array(decoded_calls)] = tokens.as_slice() else {
+            panic!("unexpected tokens: {tokens:?}");
+        };
+        assert_eq!(decoded_calls.len(), 2);
+        assert_eq!(
+            decoded_calls[1],
+            ethabi::Token::Tuple(vec![
+                ethabi::Token::Address(Address::repeat_byte(2)),
+                ethabi::Token::Bool(true),
+                ethabi::Token::Uint(100.into()),
+                ethabi::Token::Bytes(vec![]),
+            ])
+        );
+    }
+}
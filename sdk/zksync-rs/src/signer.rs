@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use zksync_eth_signer::{error::SignerError, EthereumSigner};
 use zksync_types::{
@@ -12,11 +12,21 @@ fn signing_failed_error(err: impl ToString) -> SignerError {
     SignerError::SigningFailed(err.to_string())
 }
 
+/// Encodes signatures for smart-contract accounts that do not use the default ECDSA signature validation.
+///
+/// The encoder receives the EIP-712 signature of the transaction produced by the Ethereum signer
+/// and returns the signature in the format expected by the account's `validateTransaction` method
+/// (e.g., with additional data appended, or with signatures of several owners concatenated).
+pub trait AccountSignatureEncoder: Debug + Send + Sync {
+    fn encode_signature(&self, signature: &PackedEthSignature) -> Vec<u8>;
+}
+
 #[derive(Debug)]
 pub struct Signer<S: EthereumSigner> {
     pub(crate) eth_signer: S,
     pub(crate) address: Address,
     pub(crate) chain_id: L2ChainId,
+    pub(crate) signature_encoder: Option<Arc<dyn AccountSignatureEncoder>>,
 }
 
 impl<S: EthereumSigner> Signer<S> {
@@ -25,7 +35,45 @@ impl<S: EthereumSigner> Signer<S> {
             eth_signer,
             address,
             chain_id,
+            signature_encoder: None,
+        }
+    }
+
+    /// Creates a signer for a smart-contract account. Transactions are initiated by the `account` address,
+    /// and their signatures are produced by the Ethereum signer and then encoded using `signature_encoder`.
+    pub fn for_account(
+        eth_signer: S,
+        account: Address,
+        chain_id: L2ChainId,
+        signature_encoder: Arc<dyn AccountSignatureEncoder>,
+    ) -> Self {
+        Self {
+            eth_signer,
+            address: account,
+            chain_id,
+            signature_encoder: Some(signature_encoder),
+        }
+    }
+
+    /// Returns the custom signature to be included into the transaction, or `None` if the signer
+    /// uses the default ECDSA signature.
+    pub(crate) fn custom_signature(&self, signature: &PackedEthSignature) -> Option<Vec<u8>> {
+        self.signature_encoder
+            .as_ref()
+            .map(|encoder| encoder.encode_signature(signature))
+    }
+
+    /// Signs the transaction and sets the (possibly custom) signature.
+    async fn sign_and_set_signature(&self, transaction: &mut L2Tx) -> Result<(), SignerError> {
+        let signature = self
+            .sign_transaction(transaction)
+            .await
+            .map_err(signing_failed_error)?;
+        match self.custom_signature(&signature) {
+            Some(custom_signature) => transaction.set_raw_signature(custom_signature),
+            None => transaction.set_signature(signature),
         }
+        Ok(())
     }
 
     pub async fn sign_transaction(
@@ -55,17 +103,12 @@ impl<S: EthereumSigner> Signer<S> {
                 Default::default(),
                 nonce,
                 fee,
-                self.eth_signer.get_address().await?,
+                self.address,
                 amount,
                 None,
-                Default::default(),
+                paymaster_params,
             );
-
-            let signature = self
-                .sign_transaction(&transfer)
-                .await
-                .map_err(signing_failed_error)?;
-            transfer.set_signature(signature);
+            self.sign_and_set_signature(&mut transfer).await?;
 
             return Ok(transfer);
         }
@@ -77,17 +120,12 @@ impl<S: EthereumSigner> Signer<S> {
             data,
             nonce,
             fee,
-            self.eth_signer.get_address().await?,
+            self.address,
             U256::zero(),
             None,
             paymaster_params,
         );
-
-        let signature = self
-            .sign_transaction(&transfer)
-            .await
-            .map_err(signing_failed_error)?;
-        transfer.set_signature(signature);
+        self.sign_and_set_signature(&mut transfer).await?;
 
         Ok(transfer)
     }
@@ -126,17 +164,12 @@ impl<S: EthereumSigner> Signer<S> {
             calldata,
             nonce,
             fee,
-            self.eth_signer.get_address().await?,
+            self.address,
             U256::zero(),
             factory_deps,
             paymaster_params,
         );
-
-        let signature = self
-            .sign_transaction(&execute_contract)
-            .await
-            .map_err(signing_failed_error)?;
-        execute_contract.set_signature(signature);
+        self.sign_and_set_signature(&mut execute_contract).await?;
 
         Ok(execute_contract)
     }
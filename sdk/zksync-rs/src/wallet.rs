@@ -130,11 +130,22 @@ where
         DeployContractBuilder::new(self)
     }
 
+    /// Initializes `DeployContract` transaction sending for a smart-contract account
+    /// (i.e., using the `createAccount` method of the `ContractDeployer`).
+    pub fn start_deploy_account(&self) -> DeployContractBuilder<'_, S, P> {
+        DeployContractBuilder::new(self).account()
+    }
+
     /// Initializes `ExecuteContract` transaction sending.
     pub fn start_execute_contract(&self) -> ExecuteContractBuilder<'_, S, P> {
         ExecuteContractBuilder::new(self)
     }
 
+    /// Initializes a transaction batching multiple calls via a `Multicall3`-compatible contract.
+    pub fn start_multicall(&self) -> MulticallBuilder<'_, S, P> {
+        MulticallBuilder::new(self)
+    }
+
     /// Submits an L2 transaction.
     pub async fn send_transaction(
         &self,
@@ -142,7 +153,7 @@ where
     ) -> Result<SyncTransactionHandle<'_, P>, ClientError> {
         // Since we sign the transaction with the Ethereum signature later on,
         // we might want to get rid of the signature and the initiator left from `L2Tx`.
-        let transaction_request: TransactionRequest = {
+        let mut transaction_request: TransactionRequest = {
             let mut req: TransactionRequest = tx.into();
             if let Some(meta) = req.eip712_meta.as_mut() {
                 meta.custom_signature = None;
@@ -156,6 +167,10 @@ where
             .eth_signer
             .sign_typed_data(&domain, &transaction_request)
             .await?;
+        let custom_signature = self.signer.custom_signature(&signature);
+        if let Some(meta) = transaction_request.eip712_meta.as_mut() {
+            meta.custom_signature = custom_signature;
+        }
 
         let encoded_tx = transaction_request.get_signed_bytes(&signature, self.signer.chain_id);
         let bytes = Bytes(encoded_tx);